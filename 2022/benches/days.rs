@@ -0,0 +1,26 @@
+//! Wall clock times of parsing and both parts of every day registered with `#[aoc]`, measured
+//! with criterion, see [`bench_days`].
+//!
+//! Run with `cargo bench --bench days`, criterion compares against the previous run and keeps its
+//! reports in `target/criterion`. Afterwards the mean times of every day are also written to
+//! `target/criterion/days-2022.json` and drawn as a bar chart, see [`export_days`]. `aoc bench`
+//! runs this to save baselines and compare against them.
+
+use aoc2022::YEAR;
+use aoc_core::bench::{bench_days, export_days};
+use criterion::{criterion_group, Criterion};
+
+fn all_days(c: &mut Criterion) {
+    bench_days(c, YEAR);
+}
+
+criterion_group!(days, all_days);
+
+// `criterion_main!` with the export after the benchmarks
+fn main() {
+    days();
+    Criterion::default().configure_from_args().final_summary();
+    if let Err(e) = export_days(YEAR) {
+        eprintln!("{e:#}");
+    }
+}
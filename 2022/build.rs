@@ -0,0 +1,99 @@
+//! Turns the examples in `fixtures/dayXX.toml` into rstest cases, included with `crate::fixtures!`.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn ident(name: &str) -> String {
+    let ident: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{ident}")
+    } else {
+        ident
+    }
+}
+
+fn answer(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn generate(path: &Path) -> String {
+    let toml = fs::read_to_string(path).expect("Readable fixture");
+    let table: toml::Table = toml
+        .parse()
+        .unwrap_or_else(|e| panic!("Invalid fixture {}: {e}", path.display()));
+    let examples = table
+        .get("example")
+        .and_then(toml::Value::as_array)
+        .unwrap_or_else(|| panic!("{} has no [[example]]", path.display()));
+
+    let mut code = String::new();
+    for part in ["part1", "part2"] {
+        let mut cases = String::new();
+        for example in examples {
+            let name = example["name"].as_str().expect("Examples need a name");
+            let input = example["input"].as_str().expect("Examples need an input");
+            let Some(expected) = example.get(part) else {
+                continue;
+            };
+
+            if let Some(notes) = example.get("notes").and_then(toml::Value::as_str) {
+                for line in notes.lines() {
+                    writeln!(cases, "// {line}").unwrap();
+                }
+            }
+            writeln!(
+                cases,
+                "#[case::{}({input:?}, {:?})]",
+                ident(name),
+                answer(expected)
+            )
+            .unwrap();
+        }
+
+        if !cases.is_empty() {
+            writeln!(
+                code,
+                "#[rstest::rstest]
+{cases}fn fixtures_{part}(#[case] input: &str, #[case] expected: &str) {{
+    assert_eq!(fixture_{part}(input), expected);
+}}
+"
+            )
+            .unwrap();
+        }
+    }
+
+    code
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=fixtures");
+
+    let out = PathBuf::from(std::env::var_os("OUT_DIR").unwrap()).join("fixtures");
+    fs::create_dir_all(&out).unwrap();
+
+    let Ok(fixtures) = fs::read_dir("fixtures") else {
+        return;
+    };
+    for entry in fixtures {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|e| e == "toml") {
+            let day = path.file_stem().unwrap();
+            fs::write(out.join(day).with_extension("rs"), generate(&path)).unwrap();
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use anyhow::{anyhow, Context, Result};
+use aoc_core::answer::Answer;
+use aoc_core::compress;
+use aoc_core::registry::Solution;
+use aoc_core::solver::format_answers;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Where community made oversized inputs are expected, e.g. `input/big/day09-1M.txt`.
+pub const BIG_INPUT_DIR: &str = "input/big";
+
+/// All big inputs for `day`: the file itself, or the `dayNN*` files in a directory.
+///
+/// # Errors
+/// If the path does not exist or has no inputs for the day.
+pub fn big_inputs(day: u32, path: &Path) -> Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_owned()]);
+    }
+
+    let prefix = format!("day{day:02}");
+    let mut inputs: Vec<PathBuf> = fs::read_dir(path)
+        .with_context(|| format!("Could not read {}", path.display()))?
+        .filter_map(|e| Some(e.ok()?.path()))
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(&prefix))
+        })
+        .collect();
+    inputs.sort();
+
+    if inputs.is_empty() {
+        Err(anyhow!("No {prefix} inputs in {}", path.display()))
+    } else {
+        Ok(inputs)
+    }
+}
+
+/// Reads `--big [<path>]`, without a path the default directory is used.
+#[must_use]
+pub fn big_from_args(args: impl IntoIterator<Item = String>) -> Option<PathBuf> {
+    let mut args = args.into_iter().skip_while(|a| a != "--big");
+    args.next()?;
+
+    Some(
+        args.next()
+            .filter(|path| !path.starts_with("--"))
+            .map_or_else(|| BIG_INPUT_DIR.into(), PathBuf::from),
+    )
+}
+
+/// Solves every input with every implementation, printing the timings.
+///
+/// # Errors
+/// If an input can not be read, or the implementations fail or disagree.
+pub fn run_big(inputs: &[PathBuf], implementations: &[&Solution]) -> Result<()> {
+    for path in inputs {
+        let input = compress::read(path)?;
+        println!("{} ({} bytes)", path.display(), input.len());
+
+        let mut agreed: Option<(&str, Vec<(String, Answer)>)> = None;
+        for solution in implementations {
+            let start = Instant::now();
+            let answers = solution.solve(&input, &[]);
+            let time = start.elapsed();
+            let answers = match answers {
+                Ok(answers) => answers,
+                Err(err) => {
+                    println!("  {:<16} {time:>12.3?}  failed: {err}", solution.name);
+                    return Err(err).with_context(|| format!("{} failed", solution.name));
+                }
+            };
+            let shown = format_answers(&answers).trim_end().replace('\n', ", ");
+            println!("  {:<16} {time:>12.3?}  {shown}", solution.name);
+
+            match &agreed {
+                Some((first, expected)) if *expected != answers => {
+                    return Err(anyhow!("{first} and {} disagree", solution.name));
+                }
+                Some(_) => {}
+                None => agreed = Some((solution.name, answers)),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn args() {
+        let args = |a: &[&str]| big_from_args(a.iter().map(|&s| s.to_owned()));
+
+        assert_eq!(args(&["--impl", "bits"]), None);
+        assert_eq!(args(&["--big"]), Some(BIG_INPUT_DIR.into()));
+        assert_eq!(
+            args(&["--big", "--impl", "bits"]),
+            Some(BIG_INPUT_DIR.into())
+        );
+        assert_eq!(args(&["--big", "x.txt"]), Some("x.txt".into()));
+    }
+
+    #[test]
+    fn finds_inputs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        for name in ["day09-1M.txt", "day09-10M.txt", "day15.txt"] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+
+        let found = big_inputs(9, dir).unwrap();
+        assert_eq!(
+            found,
+            vec![dir.join("day09-10M.txt"), dir.join("day09-1M.txt")]
+        );
+        assert_eq!(big_inputs(15, &dir.join("day15.txt")).unwrap().len(), 1);
+        big_inputs(3, dir).unwrap_err();
+    }
+}
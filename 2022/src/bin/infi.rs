@@ -1,19 +1,30 @@
 #![warn(clippy::pedantic)]
 
 use anyhow::{anyhow, Context, Result};
+use aoc2022::YEAR;
+use aoc_core::animation::Animation;
+use aoc_core::coord::{Coord, Dir8};
+use aoc_core::crypt;
+use aoc_core::fetch::InputSource;
+use aoc_core::parts::{parts_from_args, table, Parts};
+use aoc_core::screen::{self, Screen};
+use aoc_core::verify;
+use std::cell::RefCell;
 use std::collections::BTreeSet;
-use std::fs::read_to_string;
+use std::path::Path;
+use std::rc::Rc;
 use std::str::FromStr;
 
-type Input = Vec<Inst>;
+/// Also read compressed or decrypted from `input/infi.txt.enc`, like the inputs of the days.
+const INPUT: &str = "input/infi.txt";
 
-type Coord = (i32, i32);
+type Input = Vec<Inst>;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum Inst {
     Turn(i16),
-    Walk(i32),
-    Jump(i32),
+    Walk(isize),
+    Jump(isize),
 }
 
 impl FromStr for Inst {
@@ -34,71 +45,17 @@ impl FromStr for Inst {
     }
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum Dir {
-    North = 0,
-    NorthEast = 1,
-    East = 2,
-    SouthEast = 3,
-    South = 4,
-    SouthWest = 5,
-    West = 6,
-    NorthWest = 7,
-}
-
-impl Dir {
-    fn turn(self, deg: i16) -> Self {
-        assert_eq!(deg % 45, 0);
-
-        let new = (self as i16) + deg / 45;
-        let new = new.rem_euclid(8);
-
-        assert!((0..=7).contains(&new));
-
-        Self::from_int(u8::try_from(new).unwrap()).unwrap()
-    }
-
-    fn jump(self, oud: Coord, lang: i32) -> Coord {
-        let (d_x, d_y) = match self {
-            Dir::North => (0, 1),
-            Dir::NorthEast => (1, 1),
-            Dir::East => (1, 0),
-            Dir::SouthEast => (1, -1),
-            Dir::South => (0, -1),
-            Dir::SouthWest => (-1, -1),
-            Dir::West => (-1, 0),
-            Dir::NorthWest => (-1, 1),
-        };
-
-        (lang * d_x + oud.0, lang * d_y + oud.1)
-    }
-
-    fn from_int(dir: u8) -> Option<Self> {
-        match dir {
-            0 => Some(Self::North),
-            1 => Some(Self::NorthEast),
-            2 => Some(Self::East),
-            3 => Some(Self::SouthEast),
-            4 => Some(Self::South),
-            5 => Some(Self::SouthWest),
-            6 => Some(Self::West),
-            7 => Some(Self::NorthWest),
-            _ => None,
-        }
-    }
-}
-
 #[derive(Debug, Copy, Clone)]
 struct KerstMens {
     pos: Coord,
-    dir: Dir,
+    dir: Dir8,
 }
 
 impl Default for KerstMens {
     fn default() -> Self {
         Self {
-            pos: (0, 0),
-            dir: Dir::North,
+            pos: Coord::ORIGIN,
+            dir: Dir8::Up,
         }
     }
 }
@@ -106,8 +63,11 @@ impl Default for KerstMens {
 impl KerstMens {
     fn exec(&mut self, inst: Inst) {
         match inst {
-            Inst::Turn(deg) => self.dir = self.dir.turn(deg),
-            Inst::Jump(lang) | Inst::Walk(lang) => self.pos = self.dir.jump(self.pos, lang),
+            Inst::Turn(deg) => {
+                assert_eq!(deg % 45, 0);
+                self.dir = self.dir.turn((deg / 45).into());
+            }
+            Inst::Jump(lang) | Inst::Walk(lang) => self.pos = self.pos.step_n(self.dir, lang),
         }
     }
 }
@@ -116,16 +76,60 @@ fn parse_input(input: &str) -> Result<Input> {
     input.lines().map(str::parse).collect()
 }
 
-fn part1(input: &Input) -> i32 {
+fn part1(input: &Input) -> usize {
     let mut km = KerstMens::default();
     for inst in input {
         km.exec(*inst);
     }
 
-    km.pos.0.abs() + km.pos.1.abs()
+    km.pos.manhattan(Coord::ORIGIN)
 }
 
-fn part2(input: &Input) -> String {
+/// The top left and bottom right corner of the walked traces.
+fn bounds(traces: &BTreeSet<Coord>) -> (Coord, Coord) {
+    let min_x = traces.iter().map(|c| c.x).min().unwrap();
+    let max_x = traces.iter().map(|c| c.x).max().unwrap();
+    let min_y = traces.iter().map(|c| c.y).min().unwrap();
+    let max_y = traces.iter().map(|c| c.y).max().unwrap();
+    (Coord::new(min_x, min_y), Coord::new(max_x, max_y))
+}
+
+/// The walked traces, with the current position of the Kerstman marked, for the animation.
+fn render(traces: &BTreeSet<Coord>, pos: Coord) -> String {
+    let (min, max) = bounds(traces);
+
+    let mut buff = String::new();
+    for y in min.y..=max.y {
+        for x in min.x..=max.x {
+            let c = if pos == Coord::new(x, y) {
+                '@'
+            } else if traces.contains(&Coord::new(x, y)) {
+                '█'
+            } else {
+                ' '
+            };
+
+            buff.push(c);
+        }
+        buff.push('\n');
+    }
+
+    buff
+}
+
+/// The walked traces as pixels, to draw them in any density.
+fn map(traces: &BTreeSet<Coord>) -> Screen {
+    let (min, max) = bounds(traces);
+    let size = |from: isize, to: isize| usize::try_from(to - from + 1).unwrap();
+    let mut screen = Screen::new(size(min.x, max.x), size(min.y, max.y));
+    for c in traces {
+        let offset = |from: isize, to: isize| usize::try_from(to - from).unwrap();
+        screen.set(offset(min.x, c.x), offset(min.y, c.y), true);
+    }
+    screen
+}
+
+fn part2(input: &Input, anim: &mut Animation) -> Screen {
     let mut traces = BTreeSet::new();
     let mut km = KerstMens::default();
     traces.insert(km.pos);
@@ -140,41 +144,47 @@ fn part2(input: &Input) -> String {
                 for _ in 0..stapps {
                     km.exec(Inst::Walk(1));
                     traces.insert(km.pos);
+                    anim.frame(|| render(&traces, km.pos));
                 }
             }
         }
     }
 
-    let min = traces.first().unwrap();
-    let max = traces.last().unwrap();
-
-    let mut buff = String::new();
-    for y in min.1..=max.1 {
-        for x in min.0..=max.0 {
-            let c = if traces.contains(&(x, (max.1 - y))) {
-                '█'
-            } else {
-                ' '
-            };
-
-            buff.push(c);
-        }
-        buff.push('\n');
-    }
-
-    buff
+    map(&traces)
 }
 
 fn main() -> Result<()> {
-    let input = read_to_string("input/infi.txt").unwrap();
-    let input = parse_input(&input)?;
-
-    let part1 = part1(&input);
-    println!("Part 1: {part1}");
-
-    let part2 = part2(&input);
-    println!("Part 2:\n{part2}");
-
+    let verify = verify::requested(std::env::args().skip(1))?;
+    // Not a day of Advent of Code, so there is nothing to download
+    let source = match InputSource::from_args(YEAR, 0, std::env::args().skip(1)) {
+        InputSource::Default { .. } => {
+            crypt::decrypt_missing(Path::new(INPUT))?;
+            InputSource::File(INPUT.into())
+        }
+        source => source,
+    };
+    let input = parse_input(&source.read()?)?;
+    screen::init_from_args(std::env::args().skip(1))?;
+
+    let animation = Animation::from_args(std::env::args().skip(1))?;
+    let animation = Rc::new(RefCell::new(animation));
+    let walked = Rc::clone(&animation);
+    let parts = Parts::two(
+        |i: &Input| Ok(part1(i)),
+        move |i: &Input| Ok(part2(i, &mut walked.borrow_mut())),
+    );
+    let results = parts.run(&input, &parts_from_args(std::env::args().skip(1)))?;
+    print!("{}", table(&results));
+    animation.borrow_mut().finish()?;
+
+    if verify {
+        let answers = results
+            .into_iter()
+            .map(|r| Ok((r.name, r.answer?)))
+            .collect::<Result<Vec<_>>>()?;
+        // Known as day 0 in `answers.toml`, it has no account of its own
+        verify::check(&verify::answers_file(None), 0, &answers)?;
+    }
     Ok(())
 }
 
@@ -194,13 +204,6 @@ loop 2",
         .unwrap();
 
         assert_eq!(part1(&input), 12);
-        assert_eq!(
-            part2(&input),
-            "          █
-         █ 
-███████ █  
-"
-            .to_owned()
-        );
+        insta::assert_snapshot!(part2(&input, &mut Animation::default()));
     }
 }
@@ -0,0 +1,92 @@
+use crate::YEAR;
+use anyhow::{anyhow, Result};
+use aoc_core::answer::Answer;
+use aoc_core::aoc;
+use aoc_core::dump;
+use aoc_core::parse::{blocks, lines_to};
+use aoc_core::solver::Solver;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Elf {
+    cals: Vec<usize>,
+}
+
+impl Elf {
+    fn from_input(input: &str) -> Result<Self> {
+        Ok(Self {
+            cals: lines_to(input)?,
+        })
+    }
+
+    fn total_cal(&self) -> usize {
+        self.cals.iter().sum()
+    }
+}
+
+/// # Errors
+/// If the input is malformed.
+pub fn parse(input: &str) -> Result<Vec<Elf>> {
+    blocks(input).map(Elf::from_input).collect()
+}
+
+pub fn part1(elfs: &[Elf]) -> Option<usize> {
+    aoc_nostd::day01::part1(elfs.iter().map(Elf::total_cal))
+}
+
+pub fn part2(elfs: &[Elf]) -> Option<usize> {
+    aoc_nostd::day01::part2(elfs.iter().map(Elf::total_cal))
+}
+
+/// The example of the puzzle text.
+pub const EXAMPLE: &str = include_str!("../examples/day01.txt");
+
+#[aoc(year = 2022, day = 1)]
+pub struct Day01;
+
+impl Solver for Day01 {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 1;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Vec<Elf>;
+
+    fn parse(input: &str) -> Result<Vec<Elf>> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<Elf>) -> Result<Answer> {
+        Ok(part1(input).ok_or_else(|| anyhow!("no elfs!"))?.into())
+    }
+
+    fn part2(input: &Vec<Elf>) -> Result<Answer> {
+        Ok(part2(input)
+            .ok_or_else(|| anyhow!("not enough elfs!"))?
+            .into())
+    }
+
+    fn dump(input: &Vec<Elf>) -> Result<String> {
+        dump::json(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let elfs = parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            elfs[0],
+            Elf {
+                cals: vec![1000, 2000, 3000]
+            }
+        );
+        assert_eq!(elfs[4], Elf { cals: vec![10000] });
+
+        assert_eq!(part1(&elfs), Some(24_000));
+        assert_eq!(part2(&elfs), Some(45_000));
+    }
+}
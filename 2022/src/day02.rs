@@ -0,0 +1,96 @@
+use crate::YEAR;
+use anyhow::{anyhow, Result};
+use aoc_core::answer::Answer;
+use aoc_core::aoc;
+use aoc_core::dump;
+use aoc_core::solver::Solver;
+pub use aoc_nostd::day02::{FromXyz, Outcome, Rps};
+
+/// Both readings of the guide, XYZ as shapes and XYZ as outcomes
+pub type Input = (Vec<(Rps, Rps)>, Vec<(Rps, Outcome)>);
+
+fn parse_line<T: FromXyz>(line: &str) -> Result<(Rps, T)> {
+    let (a, b) = line
+        .split_once(' ')
+        .ok_or_else(|| anyhow!("Weird line: {line}"))?;
+
+    Ok((
+        Rps::from_abc(a).ok_or_else(|| anyhow!("Unexpected {a} where ABC was expected"))?,
+        T::from_xyz(b).ok_or_else(|| anyhow!("Unexpected {b} where XYZ was expected"))?,
+    ))
+}
+
+/// # Errors
+/// If the input is malformed.
+pub fn parse<T: FromXyz>(input: &str) -> Result<Vec<(Rps, T)>> {
+    input.trim().lines().map(parse_line).collect()
+}
+
+#[must_use]
+pub fn part1(games: &[(Rps, Rps)]) -> u32 {
+    aoc_nostd::day02::part1(games.iter().copied())
+}
+
+#[must_use]
+pub fn part2(games: &[(Rps, Outcome)]) -> u32 {
+    aoc_nostd::day02::part2(games.iter().copied())
+}
+
+/// The example of the puzzle text.
+pub const EXAMPLE: &str = include_str!("../examples/day02.txt");
+
+#[aoc(year = 2022, day = 2)]
+pub struct Day02;
+
+impl Solver for Day02 {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 2;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok((parse(input)?, parse(input)?))
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(part1(&input.0).into())
+    }
+
+    fn part2(input: &Input) -> Result<Answer> {
+        Ok(part2(&input.1).into())
+    }
+
+    fn dump(input: &Input) -> Result<String> {
+        dump::json(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::iproduct;
+
+    #[test]
+    fn game_logic_is_consistent() {
+        for (a, b) in iproduct!(Rps::ALL, Rps::ALL) {
+            assert_eq!(a.play(b), b.play(a).inverse());
+        }
+    }
+
+    #[test]
+    fn example1() {
+        let games = parse(EXAMPLE).unwrap();
+        let score = part1(&games);
+
+        assert_eq!(score, 15);
+    }
+
+    #[test]
+    fn example2() {
+        let games = parse(EXAMPLE).unwrap();
+        let score = part2(&games);
+
+        assert_eq!(score, 12);
+    }
+}
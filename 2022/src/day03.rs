@@ -0,0 +1,363 @@
+use crate::YEAR;
+use anyhow::{anyhow, Result};
+use aoc_core::answer::Answer;
+use aoc_core::aoc;
+use aoc_core::dump;
+use aoc_core::solver::Solver;
+use arbitrary::{Arbitrary, Unstructured};
+use itertools::chain;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Hash, Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Serialize, Deserialize)]
+struct Item(u8);
+
+impl TryFrom<u8> for Item {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> std::result::Result<Self, Self::Error> {
+        match value {
+            c @ b'a'..=b'z' => Ok(Self(c - b'a' + 1)),
+            c @ b'A'..=b'Z' => Ok(Self(c - b'A' + 27)),
+            other => Err(anyhow!("Unexpected item: {other}")),
+        }
+    }
+}
+
+impl From<Item> for char {
+    fn from(item: Item) -> Self {
+        match item.0 {
+            p @ 1..=26 => char::from(b'a' + p - 1),
+            p => char::from(b'A' + p - 27),
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for Item {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self(u.int_in_range(1..=52)?))
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Rucksack(HashSet<Item>, HashSet<Item>);
+
+impl FromStr for Rucksack {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if !s.is_ascii() {
+            return Err(anyhow!("Input {s:?} is not ASCII"));
+        }
+
+        if !s.len().is_multiple_of(2) {
+            return Err(anyhow!("Input length of {s:?} is not even"));
+        }
+
+        let (a, b) = s.split_at(s.len() / 2);
+
+        let parse_side =
+            |side: &str| -> Result<HashSet<_>> { side.bytes().map(Item::try_from).collect() };
+        Ok(Self(parse_side(a)?, parse_side(b)?))
+    }
+}
+
+/// Both compartments get the same number of items, like in the puzzle.
+impl<'a> Arbitrary<'a> for Rucksack {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let len = u.int_in_range(0..=24)?;
+        let mut compartment = || {
+            (0..len)
+                .map(|_| Item::arbitrary(u))
+                .collect::<arbitrary::Result<HashSet<_>>>()
+        };
+        Ok(Self(compartment()?, compartment()?))
+    }
+}
+
+/// The items of each compartment in order, the last one repeated until both are equally long.
+impl Display for Rucksack {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut halves = [&self.0, &self.1].map(|items| {
+            let mut items: Vec<_> = items.iter().copied().collect();
+            items.sort();
+            items
+        });
+        let len = halves.iter().map(Vec::len).max().unwrap_or_default();
+        for half in &mut halves {
+            if let Some(&last) = half.last() {
+                half.resize(len, last);
+            }
+        }
+
+        for item in halves.into_iter().flatten() {
+            write!(f, "{}", char::from(item))?;
+        }
+        Ok(())
+    }
+}
+
+impl Rucksack {
+    fn diff(&self) -> Vec<Item> {
+        self.0.intersection(&self.1).copied().collect()
+    }
+
+    fn all(&self) -> HashSet<Item> {
+        chain!(&self.0, &self.1).copied().collect()
+    }
+}
+
+/// # Errors
+/// If the input is malformed.
+pub fn parse(input: &str) -> Result<Vec<Rucksack>> {
+    input.lines().map(str::parse).collect()
+}
+
+/// # Errors
+/// If there is no answer for this input.
+pub fn part1<'a>(input: impl Iterator<Item = &'a Rucksack>) -> Result<u32> {
+    input
+        .map(|r| {
+            let diff = r.diff();
+            if diff.len() != 1 {
+                return Err(anyhow!("Diff contains not exactly one item: {diff:?}"));
+            }
+            Ok(u32::from(diff[0].0))
+        })
+        .sum()
+}
+
+/// # Errors
+/// If there is no answer for this input.
+pub fn part2(input: &[Rucksack]) -> Result<u32> {
+    input
+        .chunks_exact(3)
+        .map(|group| {
+            let ab: HashSet<_> = group[0]
+                .all()
+                .intersection(&group[1].all())
+                .copied()
+                .collect();
+            let item: Vec<_> = ab.intersection(&group[2].all()).copied().collect();
+
+            if item.len() != 1 {
+                return Err(anyhow!("Group contains not exactly one badge: {item:?}"));
+            }
+
+            Ok(u32::from(item[0].0))
+        })
+        .sum()
+}
+
+/// Every compartment as a bit set of priorities, the logic is in [`aoc_nostd::day03`].
+pub(crate) mod bits {
+    use anyhow::Result;
+    pub use aoc_nostd::day03::{priority, Backpack};
+
+    pub fn parse(input: &str) -> Result<Vec<Backpack>> {
+        #[cfg(feature = "simd")]
+        let backpacks = aoc_nostd::day03::parse_with(input, crate::simd::priority_mask);
+        #[cfg(not(feature = "simd"))]
+        let backpacks = aoc_nostd::day03::parse(input);
+
+        Ok(backpacks?)
+    }
+
+    pub fn part1(bp: &[Backpack]) -> Result<u32> {
+        Ok(aoc_nostd::day03::part1(bp)?)
+    }
+
+    pub fn part2(bp: &[Backpack]) -> Result<u32> {
+        Ok(aoc_nostd::day03::part2(bp)?)
+    }
+}
+
+/// Searches the strings for common items, no bit tricks.
+pub mod reference {
+    use super::bits::priority;
+
+    fn common(first: &str, others: &[&str]) -> u32 {
+        let item = first
+            .bytes()
+            .find(|&c| others.iter().all(|o| o.contains(char::from(c))))
+            .expect("No common item");
+        u32::from(priority(item).expect("Not an item"))
+    }
+
+    #[must_use]
+    pub fn part1(input: &str) -> u32 {
+        input
+            .lines()
+            .map(|l| {
+                let (a, b) = l.split_at(l.len() / 2);
+                common(a, &[b])
+            })
+            .sum()
+    }
+
+    #[must_use]
+    pub fn part2(input: &str) -> u32 {
+        let lines: Vec<_> = input.lines().collect();
+        lines
+            .chunks_exact(3)
+            .map(|group| common(group[0], &group[1..]))
+            .sum()
+    }
+}
+
+/// The example of the puzzle text.
+pub const EXAMPLE: &str = include_str!("../examples/day03.txt");
+
+#[aoc(year = 2022, day = 3)]
+pub struct Day03;
+
+impl Solver for Day03 {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 3;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Vec<Rucksack>;
+
+    fn parse(input: &str) -> Result<Vec<Rucksack>> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<Rucksack>) -> Result<Answer> {
+        Ok(part1(input.iter())?.into())
+    }
+
+    fn part2(input: &Vec<Rucksack>) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+
+    fn dump(input: &Vec<Rucksack>) -> Result<String> {
+        dump::json(input)
+    }
+}
+
+/// The same day with bit sets, checked against [`Day03`] with `aoc run --day 3 --cross-check`.
+#[aoc(year = 2022, day = 3, name = "bits")]
+pub struct Day03Bits;
+
+impl Solver for Day03Bits {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 3;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Vec<bits::Backpack>;
+
+    fn parse(input: &str) -> Result<Vec<bits::Backpack>> {
+        bits::parse(input)
+    }
+
+    fn part1(input: &Vec<bits::Backpack>) -> Result<Answer> {
+        Ok(bits::part1(input)?.into())
+    }
+
+    fn part2(input: &Vec<bits::Backpack>) -> Result<Answer> {
+        Ok(bits::part2(input)?.into())
+    }
+
+    fn dump(input: &Vec<bits::Backpack>) -> Result<String> {
+        dump::json(input)
+    }
+}
+
+/// The deliberately simple [`reference`], checked against [`Day03`] with `aoc run --cross-check`
+/// and used by `aoc minimize --wrong-answer`.
+#[aoc(year = 2022, day = 3, name = "reference")]
+pub struct Day03Reference;
+
+impl Solver for Day03Reference {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 3;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<String> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &String) -> Result<Answer> {
+        Ok(reference::part1(input).into())
+    }
+
+    fn part2(input: &String) -> Result<Answer> {
+        Ok(reference::part2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen;
+    use crate::reference::Rng;
+    use aoc_core::registry::{implementations, MAIN, REFERENCE};
+    use proptest::prelude::*;
+
+    #[test]
+    fn example1() {
+        let input = parse(EXAMPLE).unwrap();
+
+        assert_eq!(input[0].diff(), vec![Item::try_from(b'p').unwrap()]);
+
+        assert_eq!(part1(input.iter()).unwrap(), 157);
+        assert_eq!(part2(&input).unwrap(), 70);
+    }
+
+    #[test]
+    fn implementations_agree() {
+        let implementations = implementations(YEAR, 3);
+        let names: Vec<_> = implementations.iter().map(|s| s.name).collect();
+        assert_eq!(names, [MAIN, "bits", REFERENCE]);
+
+        let mut rng = Rng::new(3);
+        for _ in 0..20 {
+            let input = gen::day03(&mut rng, 5);
+            let expected = Day03Reference::parse(&input).unwrap();
+            let expected = [
+                (
+                    "Part 1".to_owned(),
+                    Day03Reference::part1(&expected).unwrap(),
+                ),
+                (
+                    "Part 2".to_owned(),
+                    Day03Reference::part2(&expected).unwrap(),
+                ),
+            ];
+            for implementation in &implementations {
+                assert_eq!(
+                    implementation.solve(&input, &[]).unwrap(),
+                    expected,
+                    "{input}"
+                );
+            }
+        }
+    }
+
+    fn text(rucksacks: &[Rucksack]) -> String {
+        let lines: Vec<_> = rucksacks.iter().map(Rucksack::to_string).collect();
+        lines.join("\n")
+    }
+
+    proptest! {
+        #[test]
+        fn rucksack_roundtrip(bytes: Vec<u8>) {
+            let rucksack = Rucksack::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+            prop_assert_eq!(rucksack.to_string().parse::<Rucksack>().unwrap(), rucksack);
+        }
+
+        #[test]
+        fn sets_and_bits_agree(bytes: Vec<u8>) {
+            let rucksacks = Vec::<Rucksack>::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+            let input = text(&rucksacks);
+
+            let (sets, bits) = (parse(&input).unwrap(), bits::parse(&input).unwrap());
+            prop_assert_eq!(part1(sets.iter()).ok(), bits::part1(&bits).ok());
+            prop_assert_eq!(part2(&sets).ok(), bits::part2(&bits).ok());
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::YEAR;
+use anyhow::Result;
+use aoc_core::answer::Answer;
+use aoc_core::aoc;
+use aoc_core::dump;
+use aoc_core::parse::At;
+use aoc_core::solver::Solver;
+use std::ops::RangeInclusive;
+
+pub type Group = (RangeInclusive<u32>, RangeInclusive<u32>);
+
+fn parse_range(input: &str) -> Result<RangeInclusive<u32>> {
+    let (from, to) = input
+        .split_once('-')
+        .at(input, || format!("Not a range like 2-4: {input:?}"))?;
+    let bound = |n: &str| n.parse().at(n, || format!("Invalid section: {n:?}"));
+    Ok(bound(from)?..=bound(to)?)
+}
+
+/// # Errors
+/// If a line is not two ranges like `2-4,6-8`.
+pub fn parse(input: &str) -> Result<Vec<Group>> {
+    input
+        .lines()
+        .map(|l| {
+            let (a, b) = l
+                .split_once(',')
+                .at(l, || format!("Not two ranges like 2-4,6-8: {l:?}"))?;
+            Ok((parse_range(a)?, parse_range(b)?))
+        })
+        .collect()
+}
+
+fn overlap_fully(g: &Group) -> bool {
+    let contains = |a: &RangeInclusive<u32>, b: &RangeInclusive<u32>| -> bool {
+        a.start() <= b.start() && a.end() >= b.end()
+    };
+
+    contains(&g.0, &g.1) || contains(&g.1, &g.0)
+}
+
+fn overlap_atall(g: &Group) -> bool {
+    let contains = |a: &RangeInclusive<u32>, b: &RangeInclusive<u32>| -> bool {
+        a.start() <= b.start() && b.end() <= a.start()
+            || b.end() >= a.start() && b.start() <= a.start()
+    };
+
+    contains(&g.0, &g.1) || contains(&g.1, &g.0)
+}
+
+#[must_use]
+pub fn part1(g: &[Group]) -> usize {
+    g.iter().filter(|&g| overlap_fully(g)).count()
+}
+
+#[must_use]
+pub fn part2(g: &[Group]) -> usize {
+    g.iter().filter(|&g| overlap_atall(g)).count()
+}
+
+/// The example of the puzzle text.
+pub const EXAMPLE: &str = include_str!("../examples/day04.txt");
+
+#[aoc(year = 2022, day = 4)]
+pub struct Day04;
+
+impl Solver for Day04 {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 4;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Vec<Group>;
+
+    fn parse(input: &str) -> Result<Vec<Group>> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<Group>) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Vec<Group>) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+
+    fn dump(input: &Vec<Group>) -> Result<String> {
+        dump::json(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::error::AocError;
+
+    #[test]
+    fn example() {
+        let groups = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&groups), 2);
+        assert_eq!(part2(&groups), 4);
+    }
+
+    #[test]
+    fn broken_lines() {
+        for broken in ["2-4", "2-4,6", "2-4,6-x", "2:4,6-8"] {
+            let input = format!("1-2,3-4\n{broken}\n");
+            let err = AocError::parse(4, &input, &parse(&input).unwrap_err());
+            assert!(
+                matches!(err, AocError::ParseError { line: Some(2), .. }),
+                "{broken}: {err:?}"
+            );
+        }
+    }
+}
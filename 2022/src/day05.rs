@@ -0,0 +1,358 @@
+use crate::YEAR;
+use anyhow::{anyhow, Context, Result};
+use aoc_core::animation::Animation;
+use aoc_core::answer::Answer;
+use aoc_core::aoc;
+use aoc_core::dump;
+use aoc_core::error::AocError;
+use aoc_core::invariant::Invariants;
+use aoc_core::parse::{lines_to, At, Span};
+use aoc_core::solver::Solver;
+use arbitrary::Arbitrary;
+use regex::Regex;
+use scan_fmt::scan_fmt;
+use serde::{Deserialize, Serialize};
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Arbitrary, Serialize, Deserialize)]
+pub struct Move {
+    amount: usize,
+    from: usize,
+    to: usize,
+}
+
+impl FromStr for Move {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (amount, from, to) = scan_fmt!(s, "move {d} from {d} to {d}", usize, usize, usize)
+            .with_context(|| format!("While parsing {s}"))?;
+        Ok(Self { amount, from, to })
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "move {} from {} to {}", self.amount, self.from, self.to)
+    }
+}
+
+pub type Stack = Vec<char>;
+pub type Input = (Vec<Stack>, Vec<Move>);
+
+fn parse_stack_line(line: &str) -> impl Iterator<Item = Option<&str>> {
+    static LINE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(
+            r"(?x)
+                \s? # Leading space (missing for first group)
+                (?:
+                    (?:\[(?P<box>.+?)\]) # Box
+                    |
+                    (?:\s\s\s) # Air
+                )",
+        )
+        .expect("Dev did not make an error when writing regex...")
+    });
+
+    LINE_REGEX
+        .captures_iter(line)
+        .map(|cap| cap.name("box").map(|cap| cap.as_str()))
+}
+
+fn parse_stacks(input: &str) -> Result<Vec<Stack>> {
+    let max_height = input.lines().count();
+    let mut lines = input.lines().rev();
+    let indecies = lines.next().context("Stacks were empty")?;
+    let mut stacks: Vec<Stack> = indecies
+        .split_whitespace()
+        .enumerate()
+        .map(|(pos, label)| {
+            let idx = usize::from_str(label)
+                .at(label, || format!("Stack index {label:?} is not an int"))?;
+
+            if pos + 1 != idx {
+                return Err(anyhow!(Span::new(label, "Indecies do not match 1,2,3,..")));
+            }
+
+            Ok(Vec::with_capacity(max_height))
+        })
+        .collect::<Result<_>>()?;
+
+    stacks.insert(0, vec![]); // Add an empty stack in the front to avoid idx to col calculations
+    let max_len = stacks.len();
+
+    for l in lines {
+        for (col, b) in parse_stack_line(l)
+            .enumerate()
+            .filter_map(|(col, b)| b.map(|b| (col, b)))
+        {
+            let idx = col + 1;
+            let stack = stacks.get_mut(idx).at(b, || {
+                format!("Unexpected number of columns of boxes... expected max {max_len} got {col}")
+            })?;
+
+            if b.chars().count() != 1 {
+                return Err(anyhow!(Span::new(
+                    b,
+                    format!("Box contains not exactly one item: {b:?}")
+                )));
+            }
+
+            stack.push(b.chars().next().unwrap());
+        }
+    }
+
+    Ok(stacks)
+}
+
+/// # Errors
+/// If the input is malformed.
+pub fn parse(input: &str) -> Result<Input> {
+    let (stacks, moves) = input
+        .split_once("\n\n")
+        .context("Input is missing segment seperator...")?;
+
+    Ok((parse_stacks(stacks)?, lines_to(moves)?))
+}
+
+fn get_both<T>(sli: &mut [T], a: usize, b: usize) -> (&mut T, &mut T) {
+    assert_ne!(a, b);
+
+    if a < b {
+        let (front, back) = sli.split_at_mut(b);
+        (&mut front[a], &mut back[0])
+    } else {
+        let (front, back) = sli.split_at_mut(a);
+        (&mut back[0], &mut front[b])
+    }
+}
+
+fn box_count(stacks: &[Stack]) -> usize {
+    stacks.iter().map(Vec::len).sum()
+}
+
+/// The stacks drawn like in the input, the first (empty) one left out.
+fn render_stacks(stacks: &[Stack]) -> String {
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut frame = String::new();
+    for level in (0..height).rev() {
+        let line: Vec<_> = stacks[1..]
+            .iter()
+            .map(|s| s.get(level).map_or("   ".to_owned(), |b| format!("[{b}]")))
+            .collect();
+        frame.push_str(line.join(" ").trim_end());
+        frame.push('\n');
+    }
+
+    let indices: Vec<_> = (1..stacks.len()).map(|idx| format!(" {idx} ")).collect();
+    frame.push_str(&indices.join(" "));
+    frame.push('\n');
+    frame
+}
+
+/// The boxes on top of every stack after moving them with the crane, one frame per move.
+///
+/// # Errors
+/// [`AocError::NoSolution`] if a move does not go between two stacks or takes more boxes than
+/// there are.
+pub fn apply_moves(
+    (stacks, moves): &Input,
+    pickup_multiple: bool,
+    anim: &mut Animation,
+) -> Result<String, AocError> {
+    let mut stacks = stacks.clone();
+
+    let boxes = box_count(&stacks);
+    let invariants = Invariants::new().register("boxes are conserved", move |s: &[Stack]| {
+        match box_count(s) {
+            count if count == boxes => Ok(()),
+            count => Err(format!("started with {boxes} boxes, now there are {count}")),
+        }
+    });
+
+    for m in moves {
+        // Stack 0 is only there for the indices
+        if m.from == m.to || m.from.min(m.to) == 0 || m.from.max(m.to) >= stacks.len() {
+            return Err(AocError::NoSolution(format!(
+                "\"{m}\" does not go between two stacks"
+            )));
+        }
+        let (from, to) = get_both(&mut stacks, m.from, m.to);
+        let start = from.len().checked_sub(m.amount).ok_or_else(|| {
+            AocError::NoSolution(format!("\"{m}\" takes more than the {} boxes", from.len()))
+        })?;
+        if pickup_multiple {
+            to.extend(from.drain(start..));
+        } else {
+            to.extend(from.drain(start..).rev());
+        }
+
+        invariants.check(&stacks);
+        anim.frame(|| render_stacks(&stacks));
+    }
+
+    Ok(stacks.iter().filter_map(|s| s.last()).collect())
+}
+
+/// # Errors
+/// If a move is impossible, see [`apply_moves`].
+pub fn part1(input: &Input) -> Result<Answer, AocError> {
+    Ok(apply_moves(input, false, &mut Animation::default())?.into())
+}
+
+/// # Errors
+/// If a move is impossible, see [`apply_moves`].
+pub fn part2(input: &Input) -> Result<Answer, AocError> {
+    Ok(apply_moves(input, true, &mut Animation::default())?.into())
+}
+
+/// The example of the puzzle text.
+pub const EXAMPLE: &str = include_str!("../examples/day05.txt");
+
+#[aoc(year = 2022, day = 5)]
+pub struct Day05;
+
+impl Solver for Day05 {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 5;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(part1(input)?)
+    }
+
+    fn part2(input: &Input) -> Result<Answer> {
+        Ok(part2(input)?)
+    }
+
+    fn dump(input: &Input) -> Result<String> {
+        dump::json(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn regex() {
+        let test = "[A] [B]     [C]";
+        assert_eq!(
+            parse_stack_line(test).collect::<Vec<_>>(),
+            vec![Some("A"), Some("B"), None, Some("C")]
+        );
+    }
+
+    #[test]
+    fn regex_emoji() {
+        let test = "[🏳️‍🌈] [👨‍👩‍👦‍👦]     [C]";
+        assert_eq!(
+            parse_stack_line(test).collect::<Vec<_>>(),
+            vec![Some("🏳️‍🌈"), Some("👨‍👩‍👦‍👦"), None, Some("C")]
+        );
+    }
+
+    #[test]
+    fn parsing() {
+        let (stacks, moves) = parse(EXAMPLE).unwrap();
+
+        assert_eq!(stacks[1], "ZN".chars().collect::<Vec<_>>());
+        assert_eq!(stacks[2], "MCD".chars().collect::<Vec<_>>());
+        assert_eq!(stacks[3], "P".chars().collect::<Vec<_>>());
+
+        assert_eq!(
+            moves[0],
+            Move {
+                amount: 1,
+                from: 2,
+                to: 1,
+            }
+        );
+        assert_eq!(
+            moves[2],
+            Move {
+                amount: 2,
+                from: 2,
+                to: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input), Ok(Answer::from("CMZ")));
+        assert_eq!(part2(&input), Ok(Answer::from("MCD")));
+    }
+
+    #[test]
+    fn impossible_moves() {
+        let (stacks, _) = parse(EXAMPLE).unwrap();
+        for m in [
+            "move 4 from 1 to 2",
+            "move 1 from 4 to 1",
+            "move 1 from 2 to 2",
+        ] {
+            let input = (stacks.clone(), vec![m.parse().unwrap()]);
+            assert!(matches!(part1(&input), Err(AocError::NoSolution(_))), "{m}");
+        }
+    }
+
+    #[test]
+    fn windows_line_endings() {
+        let crlf = format!("\u{feff}{}\r\n", EXAMPLE.replace('\n', "\r\n"));
+        assert_eq!(
+            aoc_core::solver::solve::<Day05>(&crlf, &[]).unwrap(),
+            aoc_core::solver::solve::<Day05>(EXAMPLE, &[]).unwrap()
+        );
+    }
+
+    #[test]
+    fn drawing() {
+        let (stacks, _) = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            render_stacks(&stacks),
+            "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3 \n"
+        );
+    }
+
+    #[test]
+    fn double_mut() {
+        let mut t = vec![0, 1, 2, 3, 4, 5, 6, 7];
+        assert_eq!(get_both(&mut t, 0, 1), (&mut 0, &mut 1));
+        assert_eq!(get_both(&mut t, 3, 7), (&mut 3, &mut 7));
+        assert_eq!(get_both(&mut t, 7, 3), (&mut 7, &mut 3));
+    }
+
+    fn moves() -> impl Strategy<Value = Move> {
+        (any::<usize>(), any::<usize>(), any::<usize>()).prop_map(|(amount, from, to)| Move {
+            amount,
+            from,
+            to,
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn move_roundtrip(m in moves()) {
+            prop_assert_eq!(m.to_string().parse::<Move>().unwrap(), m);
+        }
+
+        #[test]
+        fn move_near_miss(s in "move -?[0-9x]{0,22} from ?[0-9]{0,3} to [0-9 ]{0,3}") {
+            if let Ok(m) = s.parse::<Move>() {
+                prop_assert_eq!(m.to_string().parse::<Move>().unwrap(), m);
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::YEAR;
+use anyhow::{Context, Result};
+use aoc_core::answer::Answer;
+use aoc_core::aoc;
+use aoc_core::dump;
+use aoc_core::solver::Solver;
+
+pub type Input = Vec<u8>;
+
+fn pos_after_n_uniq(g: &Input, n: usize) -> Result<usize> {
+    #[cfg(feature = "simd")]
+    let pos = crate::simd::first_distinct(g, n);
+    #[cfg(not(feature = "simd"))]
+    let pos = aoc_nostd::day06::first_distinct(g, n);
+
+    pos.map(|pos| pos + n).context("No unique sequence found")
+}
+
+#[must_use]
+pub fn parse(input: &str) -> Input {
+    input.as_bytes().to_vec()
+}
+
+/// # Errors
+/// If there is no answer for this input.
+pub fn part1(g: &Input) -> Result<usize> {
+    pos_after_n_uniq(g, 4)
+}
+
+/// # Errors
+/// If there is no answer for this input.
+pub fn part2(g: &Input) -> Result<usize> {
+    pos_after_n_uniq(g, 14)
+}
+
+/// The example of the puzzle text.
+pub const EXAMPLE: &str = include_str!("../examples/day06.txt");
+
+#[aoc(year = 2022, day = 6)]
+pub struct Day06;
+
+impl Solver for Day06 {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 6;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok(parse(input))
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Input) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+
+    fn dump(input: &Input) -> Result<String> {
+        dump::json(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_part1(input: &str) -> String {
+        part1(&Vec::from(input)).unwrap().to_string()
+    }
+
+    fn fixture_part2(input: &str) -> String {
+        part2(&Vec::from(input)).unwrap().to_string()
+    }
+
+    crate::fixtures!("day06");
+}
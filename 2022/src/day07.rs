@@ -0,0 +1,327 @@
+use crate::YEAR;
+use anyhow::{anyhow, Context, Result};
+use aoc_core::answer::Answer;
+use aoc_core::aoc;
+use aoc_core::dot::Graph;
+use aoc_core::dump;
+use aoc_core::solver::Solver;
+use compact_str::CompactString;
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Line {
+    Cmd(Cmd),
+    LsOutput(LsOutput),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Cmd {
+    CdRoot,
+    CdParent,
+    CdDir(CompactString),
+    Ls,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum LsOutput {
+    DirEntry(CompactString),
+    FileEntry(usize, CompactString),
+}
+
+impl FromStr for Line {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match s.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["$", "cd", "/"] => Self::Cmd(Cmd::CdRoot),
+            ["$", "cd", ".."] => Self::Cmd(Cmd::CdParent),
+            ["$", "cd", dir] => Self::Cmd(Cmd::CdDir((*dir).into())),
+            ["$", "ls"] => Self::Cmd(Cmd::Ls),
+            ["dir", dir] => Self::LsOutput(LsOutput::DirEntry((*dir).into())),
+            [size, name] => Self::LsOutput(LsOutput::FileEntry(size.parse()?, (*name).into())),
+            _ => return Err(anyhow!("Can't parse line: {s:?}")),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+enum LineState {
+    #[default]
+    WaitingForLs,
+    InLsOutput,
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+enum BeenHere {
+    Yep,
+    Nope,
+}
+
+pub type Input = Vec<Line>;
+
+/// # Errors
+/// If the input is malformed.
+pub fn parse(input: &str) -> Result<Input> {
+    input.lines().map(str::parse).collect()
+}
+
+fn update_pwd<'a>(pwd: &mut Vec<&'a str>, line_state: &mut LineState, cmd: &'a Cmd) -> Result<()> {
+    *line_state = LineState::WaitingForLs;
+    match cmd {
+        Cmd::CdRoot => pwd.clear(),
+        Cmd::CdParent => {
+            pwd.pop().ok_or_else(|| anyhow!("Did a `cd ..` in `/`"))?;
+        }
+        Cmd::CdDir(dir) => pwd.push(dir),
+        Cmd::Ls => {
+            *line_state = LineState::InLsOutput;
+        }
+    }
+
+    Ok(())
+}
+
+/// # Errors
+/// If there is no answer for this input.
+pub fn part1(g: &Input) -> Result<usize> {
+    let sizes = dir_sizes(g)?;
+
+    Ok(sizes.values().filter(|&&s| s <= 100_000).sum())
+}
+
+fn dir_sizes(g: &Input) -> Result<HashMap<Vec<&str>, usize>> {
+    let mut pwd = vec![];
+    let mut state = LineState::default();
+    let mut dirs: HashMap<Vec<&str>, BeenHere> = HashMap::new();
+    let mut sizes: HashMap<Vec<&str>, usize> = HashMap::new();
+
+    for line in g {
+        let ls_line = match (line, state) {
+            (Line::Cmd(cmd), _) => {
+                update_pwd(&mut pwd, &mut state, cmd)?;
+                continue;
+            }
+            (Line::LsOutput(_), LineState::WaitingForLs) => {
+                return Err(anyhow!("Ls output where cmd was expected!"));
+            }
+            (Line::LsOutput(outp), LineState::InLsOutput) => outp,
+        };
+        dirs.entry_ref(pwd.as_slice()).insert(BeenHere::Yep);
+
+        match ls_line {
+            LsOutput::DirEntry(dir) => {
+                pwd.push(dir);
+                dirs.entry_ref(pwd.as_slice()).or_insert(BeenHere::Nope);
+                pwd.pop().unwrap();
+            }
+            LsOutput::FileEntry(size, _path) => {
+                for len in 0..=pwd.len() {
+                    let entry = sizes.entry_ref(&pwd[..len]).or_insert(0);
+                    *entry += size;
+                }
+            }
+        }
+    }
+
+    if dirs.values().any(|visit| *visit == BeenHere::Nope) {
+        return Err(anyhow!("Missed a dir"));
+    }
+
+    Ok(sizes)
+}
+
+/// # Errors
+/// If there is no answer for this input.
+pub fn part2(g: &Input) -> Result<usize> {
+    let sizes = dir_sizes(g)?;
+
+    let used = sizes.get(&vec![]).copied().unwrap_or(0);
+    let free_space = 70_000_000_usize
+        .checked_sub(used)
+        .with_context(|| format!("{used} bytes do not fit on the disk"))?;
+    let min_size = 30_000_000_usize.saturating_sub(free_space);
+
+    sizes
+        .iter()
+        .map(|(_, s)| *s)
+        .filter(|&s| s >= min_size)
+        .min()
+        .with_context(|| "no dir with enough size")
+}
+
+/// Only plain names, so a weird input can not write outside of the target directory.
+fn checked_name(name: &str) -> Result<&str> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+        Err(anyhow!("Refusing to create {name:?}"))
+    } else {
+        Ok(name)
+    }
+}
+
+/// Recreates the browsed directory tree below `root`, with files truncated to their size (sparse
+/// on most filesystems), so `du` and friends can check the sizes.
+///
+/// # Errors
+/// If a name would leave `root` or the files can not be written.
+pub fn materialize(g: &Input, root: &Path) -> Result<()> {
+    let mut pwd = vec![];
+    let mut state = LineState::default();
+
+    let dir_path = |pwd: &[&str]| -> PathBuf { pwd.iter().fold(root.to_owned(), |p, d| p.join(d)) };
+
+    for line in g {
+        match line {
+            Line::Cmd(cmd) => {
+                if let Cmd::CdDir(dir) = cmd {
+                    checked_name(dir)?;
+                }
+                update_pwd(&mut pwd, &mut state, cmd)?;
+            }
+            Line::LsOutput(LsOutput::DirEntry(dir)) => {
+                fs::create_dir_all(dir_path(&pwd).join(checked_name(dir)?))?;
+            }
+            Line::LsOutput(LsOutput::FileEntry(size, name)) => {
+                let dir = dir_path(&pwd);
+                fs::create_dir_all(&dir)?;
+                File::create(dir.join(checked_name(name)?))?.set_len(u64::try_from(*size)?)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads `--materialize [<dir>]`, by default into a temporary directory.
+pub fn materialize_from_args(args: impl Iterator<Item = String>) -> Option<PathBuf> {
+    let mut args = args.skip_while(|a| a != "--materialize");
+    args.next()?;
+
+    Some(
+        args.next()
+            .map_or_else(|| std::env::temp_dir().join("aoc-day07"), PathBuf::from),
+    )
+}
+
+/// The browsed directory tree for `--dot`, directories with their total size and files with
+/// theirs.
+///
+/// # Errors
+/// If the commands do not make sense.
+pub fn tree(g: &Input) -> Result<Graph> {
+    let sizes = dir_sizes(g)?;
+    let id = |pwd: &[&str]| format!("/{}", pwd.join("/"));
+    let mut pwd = vec![];
+    let mut state = LineState::default();
+    let mut graph = Graph::new("day07");
+    graph.node("/", format!("/\n{}", sizes.get(&vec![]).unwrap_or(&0)));
+
+    for line in g {
+        match line {
+            Line::Cmd(cmd) => update_pwd(&mut pwd, &mut state, cmd)?,
+            Line::LsOutput(LsOutput::DirEntry(dir)) => {
+                let parent = id(&pwd);
+                pwd.push(dir);
+                let size = sizes.get(&pwd).unwrap_or(&0);
+                graph.node(id(&pwd), format!("{dir}/\n{size}"));
+                graph.edge(parent, id(&pwd), None);
+                pwd.pop();
+            }
+            Line::LsOutput(LsOutput::FileEntry(size, name)) => {
+                let file = format!("{}/{name}", id(&pwd).trim_end_matches('/'));
+                graph.node(&file, format!("{name}\n{size}"));
+                graph.edge(id(&pwd), file, None);
+            }
+        }
+    }
+
+    Ok(graph)
+}
+
+/// The example of the puzzle text.
+pub const EXAMPLE: &str = include_str!("../examples/day07.txt");
+
+#[aoc(year = 2022, day = 7)]
+pub struct Day07;
+
+impl Solver for Day07 {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 7;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Input) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+
+    fn dump(input: &Input) -> Result<String> {
+        dump::json(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), 95_437);
+        assert_eq!(part2(&input).unwrap(), 24_933_642);
+    }
+
+    fn du(path: &Path) -> u64 {
+        if path.is_dir() {
+            fs::read_dir(path)
+                .unwrap()
+                .map(|e| du(&e.unwrap().path()))
+                .sum()
+        } else {
+            fs::metadata(path).unwrap().len()
+        }
+    }
+
+    #[test]
+    fn materialized() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        materialize(&parse(EXAMPLE).unwrap(), root).unwrap();
+
+        assert_eq!(du(root), 48_381_165);
+        assert_eq!(du(&root.join("a")), 94_853);
+        assert_eq!(du(&root.join("a/e")), 584);
+        assert_eq!(fs::metadata(root.join("d/d.log")).unwrap().len(), 8_033_020);
+    }
+
+    #[test]
+    fn dot_tree() {
+        let dot = tree(&parse(EXAMPLE).unwrap()).unwrap().to_string();
+        assert!(dot.contains(r#""/a/e" [label="e/\n584"];"#), "{dot}");
+        assert!(dot.contains(r#""/a" -> "/a/e";"#), "{dot}");
+        assert!(
+            dot.contains(r#""/b.txt" [label="b.txt\n14848514"];"#),
+            "{dot}"
+        );
+    }
+
+    #[test]
+    fn no_escape() {
+        let input = parse("$ cd /\n$ ls\n1 ..\n").unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        materialize(&input, root).unwrap_err();
+    }
+}
@@ -0,0 +1,211 @@
+use crate::YEAR;
+use anyhow::{Context, Result};
+use aoc_core::answer::Answer;
+use aoc_core::aoc;
+use aoc_core::coord::{Coord, Dir4};
+use aoc_core::dump;
+use aoc_core::image::{self, mix, Image};
+use aoc_core::screen::{self, Screen};
+use aoc_core::solver::Solver;
+use itertools::izip;
+use ndarray::{par_azip, Array1, Array2, ArrayView2, Axis};
+use std::str::FromStr;
+
+pub type Input = Array2<u8>;
+
+/// # Errors
+/// If the input is malformed.
+pub fn parse(input: &str) -> Result<Input> {
+    let len = input.lines().next().context("No lines")?.chars().count();
+
+    let mat: Result<Vec<u8>> = input
+        .lines()
+        .flat_map(|l| {
+            l.chars()
+                .map(|dig| Ok(u8::from_str(dig.to_string().as_str())?))
+        })
+        .collect();
+
+    let mat = mat?;
+
+    Array2::from_shape_vec((len, len), mat).context("weird shape")
+}
+
+fn seeable_from_up(map: ArrayView2<u8>) -> Array2<bool> {
+    let mut max_height: Option<Array1<u8>> = None;
+    let mut seeable = Array2::from_elem(map.raw_dim(), false);
+
+    for (heights, mut seeable) in izip!(map.rows(), seeable.rows_mut()) {
+        match max_height.as_mut() {
+            None => {
+                max_height = Some(heights.to_owned());
+                seeable.fill(true);
+            }
+            Some(max_height) => {
+                // Rows are contiguous when sweeping up or down, but not left or right
+                #[cfg(feature = "simd")]
+                if let (Some(tallest), Some(heights), Some(seeable)) = (
+                    max_height.as_slice_mut(),
+                    heights.as_slice(),
+                    seeable.as_slice_mut(),
+                ) {
+                    crate::simd::sweep_row(tallest, heights, seeable);
+                    continue;
+                }
+                par_azip!((m in max_height, s in &mut seeable, h in &heights) {
+                    *s = h > m;
+                    *m =  u8::max(*m, *h);
+                });
+            }
+        }
+    }
+    seeable
+}
+
+/// Which trees can be seen from outside the grid.
+fn visible(map: ArrayView2<u8>) -> Array2<bool> {
+    // Up
+    let mut up = seeable_from_up(map);
+
+    // Down
+    let mut g2 = map;
+    g2.invert_axis(Axis(0));
+    let mut down = seeable_from_up(g2);
+    down.invert_axis(Axis(0));
+
+    // Left
+    let mut rot = map;
+    rot.swap_axes(0, 1);
+    let mut left = seeable_from_up(rot);
+    left.swap_axes(0, 1);
+
+    // Right
+    let mut rot2 = rot;
+    rot2.invert_axis(Axis(0));
+    let mut right = seeable_from_up(rot2);
+    right.invert_axis(Axis(0));
+    right.swap_axes(0, 1);
+
+    par_azip!((a in &mut up, b in &down, c in &left, d in &right) *a |= b | c | d);
+
+    up
+}
+
+#[must_use]
+pub fn part1(map: ArrayView2<u8>) -> usize {
+    visible(map).iter().filter(|e| **e).count()
+}
+
+/// The trees that can be seen from outside for `--visible`, as an image with the visible ones
+/// green and the others gray, brighter the taller they are. Without images only the visible ones
+/// are drawn.
+#[must_use]
+pub fn visibility_map(map: ArrayView2<u8>) -> String {
+    let visible = visible(map);
+    let (rows, cols) = visible.dim();
+    let image = Image::from_fn(cols, rows, |x, y| {
+        let t = f64::from(map[(y, x)]) / 9.0;
+        if visible[(y, x)] {
+            mix([20, 80, 20], [130, 240, 90], t)
+        } else {
+            mix([30, 30, 30], [110, 110, 110], t)
+        }
+    });
+
+    image::show(&image.scaled(4), || {
+        let mut screen = Screen::new(cols, rows);
+        for ((y, x), &seen) in visible.indexed_iter() {
+            screen.set(x, y, seen);
+        }
+        screen.draw(screen::density())
+    })
+}
+
+/// The neighbour of `(row, col)` in `dir`, if it is still on the map.
+fn step(map: ArrayView2<u8>, (row, col): (usize, usize), dir: Dir4) -> Option<(usize, usize)> {
+    let next = Coord::new(isize::try_from(col).ok()?, isize::try_from(row).ok()?).step(dir);
+    let pos = (usize::try_from(next.y).ok()?, usize::try_from(next.x).ok()?);
+    map.get(pos).map(|_| pos)
+}
+
+fn see_trees(map: ArrayView2<u8>, mut pos: (usize, usize), dir: Dir4) -> usize {
+    let mut can_see = 0;
+    let own_height = map[pos];
+    loop {
+        pos = match step(map, pos, dir) {
+            None => break,
+            Some(p) => p,
+        };
+
+        can_see += 1;
+
+        if map[pos] >= own_height {
+            break;
+        }
+    }
+
+    can_see
+}
+
+/// # Errors
+/// If there is no answer for this input.
+pub fn part2(heights: ArrayView2<u8>) -> Result<usize> {
+    let mut score = Array2::<usize>::from_elem(heights.raw_dim(), 1);
+
+    par_azip!((index (i, j), s in &mut score) {
+        for dir in Dir4::ALL {
+            *s *= see_trees(heights, (i, j), dir);
+        }
+    });
+
+    score.iter().max().copied().context("No elements")
+}
+
+/// The example of the puzzle text.
+pub const EXAMPLE: &str = include_str!("../examples/day08.txt");
+
+#[aoc(year = 2022, day = 8)]
+pub struct Day08;
+
+impl Solver for Day08 {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 8;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(part1(input.view()).into())
+    }
+
+    fn part2(input: &Input) -> Result<Answer> {
+        Ok(part2(input.view())?.into())
+    }
+
+    fn dump(input: &Input) -> Result<String> {
+        dump::json(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(input.view()), 21);
+        assert_eq!(part2(input.view()).unwrap(), 8);
+    }
+
+    #[test]
+    fn visible_without_images() {
+        let map = visibility_map(parse(EXAMPLE).unwrap().view());
+        assert_eq!(map.matches('█').count(), 21);
+        assert_eq!(map.lines().next(), Some("█████"));
+    }
+}
@@ -0,0 +1,247 @@
+use crate::YEAR;
+use anyhow::{anyhow, Context, Result};
+use aoc_core::animation::Animation;
+use aoc_core::answer::Answer;
+use aoc_core::aoc;
+use aoc_core::coord::{Coord, Dir4};
+use aoc_core::dump;
+use aoc_core::invariant::Invariants;
+use aoc_core::point::{Point, Point2};
+use aoc_core::record::Recorder;
+use aoc_core::screen::Screen;
+use aoc_core::solver::Solver;
+use aoc_core::sparse_grid::SparseGrid;
+use serde::Serialize;
+
+pub type Input = Vec<(Dir4, usize)>;
+
+fn parse_dir(s: &str) -> Result<Dir4> {
+    Ok(match s {
+        "U" => Dir4::Up,
+        "D" => Dir4::Down,
+        "L" => Dir4::Left,
+        "R" => Dir4::Right,
+        _ => return Err(anyhow!("Illegal move dir {s:?}")),
+    })
+}
+
+/// # Errors
+/// If the input is malformed.
+pub fn parse(input: &str) -> Result<Input> {
+    input
+        .lines()
+        .map(|l| {
+            let (dir, steps) = l.split_once(char::is_whitespace).context("Invalid line")?;
+            Ok((parse_dir(dir)?, steps.parse()?))
+        })
+        .collect()
+}
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize)]
+struct State {
+    head: Coord,
+    tail: Coord,
+}
+
+impl State {
+    fn move_head(&mut self, dir: Dir4) {
+        self.head = self.head.step(dir);
+        self.update_tail();
+    }
+
+    fn update_tail(&mut self) {
+        // Better readable this way
+        #[allow(clippy::unnested_or_patterns, clippy::manual_range_patterns)]
+        match (self.head.y - self.tail.y, self.head.x - self.tail.x) {
+            (-1 | 0 | 1, -1 | 0 | 1) => (),
+            (0, 2) => self.tail.x += 1,
+            (2, 0) => self.tail.y += 1,
+            (0, -2) => self.tail.x -= 1,
+            (-2, 0) => self.tail.y -= 1,
+
+            (1, 2) | (2, 1) | (2, 2) => {
+                self.tail.y += 1;
+                self.tail.x += 1;
+            }
+            (-1, 2) | (-2, 1) | (-2, 2) => {
+                self.tail.y -= 1;
+                self.tail.x += 1;
+            }
+            (1, -2) | (2, -1) | (2, -2) => {
+                self.tail.y += 1;
+                self.tail.x -= 1;
+            }
+            (-1, -2) | (-2, -1) | (-2, -2) => {
+                self.tail.y -= 1;
+                self.tail.x -= 1;
+            }
+            other => unreachable!("Trying to move {other:?}"),
+        }
+    }
+}
+
+fn rope_invariants() -> Invariants<[State]> {
+    Invariants::new().register("knots stay connected", |rope: &[State]| {
+        for (idx, knot) in rope.iter().enumerate() {
+            let dist = knot.head.chebyshev(knot.tail);
+            if dist > 1 {
+                return Err(format!("knot {} is {dist} away from its leader", idx + 1));
+            }
+
+            if let Some(next) = rope.get(idx + 1) {
+                if next.head != knot.tail {
+                    return Err(format!("knot {} is not attached to its leader", idx + 2));
+                }
+            }
+        }
+
+        Ok(())
+    })
+}
+
+fn point(c: Coord) -> Point2 {
+    Point([c.x as i64, c.y as i64])
+}
+
+/// Every position the tail of a rope with two knots visited.
+fn trail(g: &Input) -> SparseGrid<()> {
+    let mut state = State::default();
+    let mut visited = SparseGrid::new();
+    let invariants = rope_invariants();
+
+    for (d, cnt) in g.iter().copied() {
+        for _ in 0..cnt {
+            state.move_head(d);
+            invariants.check(std::slice::from_ref(&state));
+            visited.insert(point(state.tail), ());
+        }
+    }
+
+    visited
+}
+
+#[must_use]
+pub fn part1(g: &Input) -> usize {
+    trail(g).len()
+}
+
+/// The part of the plane around the head of the rope, with the knots numbered and the positions
+/// the tail visited marked.
+fn render_rope(rope: &[State], visited: &SparseGrid<()>) -> String {
+    const WIDTH: isize = 40;
+    const HEIGHT: isize = 20;
+
+    let center = rope[0].head;
+    let mut frame = String::new();
+    for y in center.y - HEIGHT / 2..center.y + HEIGHT / 2 {
+        for x in center.x - WIDTH / 2..center.x + WIDTH / 2 {
+            let c = Coord::new(x, y);
+            let knot = rope.iter().zip(b'1'..).find(|(k, _)| k.tail == c);
+            frame.push(match knot {
+                _ if rope[0].head == c => 'H',
+                Some((_, digit)) => char::from(digit),
+                None if visited.contains(point(c)) => '#',
+                None => '.',
+            });
+        }
+        frame.push('\n');
+    }
+    frame
+}
+
+/// Every position the tail of a rope with ten knots visited.
+fn long_trail(g: &Input, rec: &mut Recorder, anim: &mut Animation) -> SparseGrid<()> {
+    let mut states = [State::default(); 9];
+    let mut visited = SparseGrid::new();
+    let invariants = rope_invariants();
+
+    for (d, cnt) in g.iter().copied() {
+        for _ in 0..cnt {
+            states[0].move_head(d);
+            for i in 1..states.len() {
+                states[i].head = states[i - 1].tail;
+                states[i].update_tail();
+            }
+            invariants.check(&states);
+            rec.record(&states);
+            visited.insert(point(states[8].tail), ());
+            anim.frame(|| render_rope(&states, &visited));
+        }
+    }
+
+    visited
+}
+
+pub fn part2(g: &Input, rec: &mut Recorder, anim: &mut Animation) -> usize {
+    long_trail(g, rec, anim).len()
+}
+
+/// The positions of part 2 as a picture, for `--visited`.
+#[must_use]
+pub fn visited(g: &Input) -> Screen {
+    long_trail(g, &mut Recorder::default(), &mut Animation::default()).render(|()| true)
+}
+
+/// The example of the puzzle text.
+pub const EXAMPLE: &str = include_str!("../examples/day09.txt");
+
+#[aoc(year = 2022, day = 9)]
+pub struct Day09;
+
+impl Solver for Day09 {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 9;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Input) -> Result<Answer> {
+        Ok(part2(input, &mut Recorder::default(), &mut Animation::default()).into())
+    }
+
+    fn dump(input: &Input) -> Result<String> {
+        dump::json(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_part1(input: &str) -> String {
+        part1(&parse(input).unwrap()).to_string()
+    }
+
+    fn fixture_part2(input: &str) -> String {
+        part2(
+            &parse(input).unwrap(),
+            &mut Recorder::default(),
+            &mut Animation::default(),
+        )
+        .to_string()
+    }
+
+    crate::fixtures!("day09");
+
+    #[test]
+    fn trail_picture() {
+        let input = parse("R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n").unwrap();
+        assert_eq!(
+            trail(&input).render(|()| true).render('#', '.'),
+            "..##.
+...##
+.####
+....#
+####.
+"
+        );
+    }
+}
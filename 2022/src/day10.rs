@@ -0,0 +1,356 @@
+use crate::YEAR;
+use anyhow::{anyhow, ensure, Result};
+use aoc_core::answer::Answer;
+use aoc_core::aoc;
+use aoc_core::dump;
+use aoc_core::record::Recorder;
+use aoc_core::screen::Screen;
+use aoc_core::solver::Solver;
+use aoc_core::vm::{self, decode_program, Machine, Peripheral};
+use arbitrary::Arbitrary;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+pub type Input = Vec<Instruction>;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub enum Instruction {
+    Nop,
+    AddX(i64),
+}
+
+impl FromStr for Instruction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut tokens = s.split_whitespace();
+        match (tokens.next(), tokens.next()) {
+            (Some("noop"), None) => Ok(Self::Nop),
+            (Some("addx"), Some(val)) => Ok(Self::AddX(val.parse()?)),
+            _ => Err(anyhow!("Invalid line: {s:?}")),
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Nop => write!(f, "noop"),
+            Self::AddX(val) => write!(f, "addx {val}"),
+        }
+    }
+}
+
+/// # Errors
+/// If the input is malformed.
+pub fn parse(input: &str) -> Result<Input> {
+    decode_program(input)
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct Cpu {
+    x: i64,
+}
+
+impl Default for Cpu {
+    fn default() -> Self {
+        Self { x: 1 }
+    }
+}
+
+impl vm::Instruction for Instruction {
+    type Registers = Cpu;
+
+    fn decode(line: &str) -> Result<Self> {
+        line.parse()
+    }
+
+    fn cycles(&self) -> u8 {
+        match self {
+            Instruction::Nop => 1,
+            Instruction::AddX(_) => 2,
+        }
+    }
+
+    fn execute(&self, regs: &mut Cpu) {
+        match self {
+            Instruction::Nop => {}
+            Instruction::AddX(val) => regs.x += val,
+        }
+    }
+}
+
+/// Draws one pixel per cycle, lit if the sprite around X covers it.
+#[derive(Debug)]
+struct Crt {
+    screen: Screen,
+}
+
+impl Default for Crt {
+    fn default() -> Self {
+        Self {
+            screen: Screen::new(40, 6),
+        }
+    }
+}
+
+impl Peripheral<Cpu> for Crt {
+    fn tick(&mut self, cycle: usize, regs: &Cpu) {
+        let (x, y) = (cycle % 40, cycle / 40);
+        let col = i64::try_from(x).unwrap();
+        self.screen.set(x, y, (regs.x - col).abs() <= 1);
+    }
+}
+
+fn run_for(prog: &Input, steps: usize) -> Result<Vec<i64>> {
+    let mut m = Machine::new(prog.clone());
+
+    (0..steps)
+        .map(|_| {
+            m.step()?;
+            Ok(m.regs.x)
+        })
+        .collect()
+}
+
+/// # Errors
+/// If there is no answer for this input.
+///
+/// # Panics
+/// If the program stops before cycle 220.
+pub fn part1(prog: &Input) -> Result<i64> {
+    let vals = run_for(prog, 221)?;
+
+    Ok([20, 60, 100, 140, 180, 220]
+        .into_iter()
+        .map(|idx| i64::try_from(idx).unwrap() * vals[idx - 2])
+        .sum())
+}
+
+/// # Errors
+/// If there is no answer for this input.
+pub fn part2(input: &Input, rec: &mut Recorder) -> Result<Answer> {
+    let mut m = Machine::new(input.clone());
+    let mut crt = Crt::default();
+
+    for _ in 0..40 * 6 {
+        m.step_with(&mut crt)?;
+        rec.record(&m);
+    }
+
+    Ok(Answer::from(crt.screen).read_letters())
+}
+
+/// Straight from the puzzle text, without the machine: expand the program into the value of X
+/// during every cycle.
+pub mod reference {
+    use super::{Input, Instruction};
+    use aoc_core::screen::Screen;
+
+    #[must_use]
+    pub fn x_during_cycles(prog: &Input) -> Vec<i64> {
+        let mut x = 1;
+        let mut during = vec![];
+        for inst in prog {
+            match inst {
+                Instruction::Nop => during.push(x),
+                Instruction::AddX(val) => {
+                    during.extend([x, x]);
+                    x += val;
+                }
+            }
+        }
+        during
+    }
+
+    /// # Panics
+    /// If the program stops before cycle 220.
+    #[must_use]
+    pub fn part1(prog: &Input) -> i64 {
+        let during = x_during_cycles(prog);
+        [20, 60, 100, 140, 180, 220]
+            .into_iter()
+            .map(|cycle: i64| cycle * during[usize::try_from(cycle).unwrap() - 1])
+            .sum()
+    }
+
+    /// # Panics
+    /// If the program stops before cycle 240.
+    #[must_use]
+    pub fn part2(prog: &Input) -> Screen {
+        let during = x_during_cycles(prog);
+        let mut screen = Screen::new(40, 6);
+        for (y, row) in during[..240].chunks(40).enumerate() {
+            for (col, x) in (0..).zip(row) {
+                screen.set(usize::try_from(col).unwrap(), y, (x - col).abs() <= 1);
+            }
+        }
+        screen
+    }
+}
+
+/// The example of the puzzle text.
+pub const EXAMPLE: &str = include_str!("../examples/day10.txt");
+
+#[aoc(year = 2022, day = 10)]
+pub struct Day10;
+
+impl Solver for Day10 {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 10;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Input) -> Result<Answer> {
+        part2(input, &mut Recorder::default())
+    }
+
+    fn dump(input: &Input) -> Result<String> {
+        dump::json(input)
+    }
+}
+
+/// The deliberately simple [`reference`], checked against [`Day10`] with `aoc run --cross-check`
+/// and used by `aoc minimize --wrong-answer`.
+#[aoc(year = 2022, day = 10, name = "reference")]
+pub struct Day10Reference;
+
+impl Solver for Day10Reference {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 10;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Input> {
+        let prog = parse(input)?;
+        let cycles = reference::x_during_cycles(&prog).len();
+        ensure!(
+            cycles >= 240,
+            "The program stops after {cycles} cycles, before 240"
+        );
+        Ok(prog)
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(reference::part1(input).into())
+    }
+
+    fn part2(input: &Input) -> Result<Answer> {
+        Ok(Answer::from(reference::part2(input)).read_letters())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reference::Rng;
+    use proptest::prelude::*;
+
+    #[test]
+    fn small_exmaple() {
+        let inp = parse(
+            "noop
+addx 3
+addx -5
+",
+        )
+        .unwrap();
+
+        let mut m = Machine::new(inp.clone());
+
+        assert_eq!(m.regs.x, 1);
+        m.step().unwrap(); // 1
+
+        assert_eq!(m.regs.x, 1);
+        m.step().unwrap(); // 2
+
+        assert_eq!(m.regs.x, 1);
+        m.step().unwrap(); // 3
+
+        assert_eq!(m.regs.x, 4);
+        m.step().unwrap(); // 4
+
+        assert_eq!(m.regs.x, 4);
+        m.step().unwrap(); // 5
+
+        assert_eq!(m.regs.x, -1);
+        m.step().unwrap_err(); // 6 // done!
+
+        let vals = run_for(&inp, 5).unwrap();
+        assert_eq!(vals, vec![1, 1, 4, 4, -1]);
+    }
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+
+        let vals = run_for(&input, 220).unwrap();
+        assert_eq!(vals[20 - 2], 21);
+        assert_eq!(vals[220 - 2], 18);
+
+        assert_eq!(
+            [20, 60, 100, 140, 180, 220]
+                .into_iter()
+                .map(|idx| i64::try_from(idx).unwrap() * vals[idx - 2])
+                .collect::<Vec<_>>(),
+            vec![420, 1140, 1800, 2940, 2880, 3960]
+        );
+
+        assert_eq!(part1(&input).unwrap(), 13140);
+        insta::assert_snapshot!(part2(&input, &mut Recorder::default()).unwrap());
+    }
+
+    #[test]
+    fn matches_reference() {
+        let example = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&example).unwrap(), reference::part1(&example));
+
+        let mut rng = Rng::new(10);
+        for _ in 0..20 {
+            // Enough cycles for part 2, no matter the mix of instructions
+            let prog: Input = (0..240)
+                .map(|_| match rng.below(3) {
+                    0 => Instruction::Nop,
+                    _ => Instruction::AddX(i64::try_from(rng.below(21)).unwrap() - 10),
+                })
+                .collect();
+
+            assert_eq!(part1(&prog).unwrap(), reference::part1(&prog));
+            assert_eq!(
+                part2(&prog, &mut Recorder::default()).unwrap(),
+                Answer::from(reference::part2(&prog)).read_letters()
+            );
+        }
+    }
+    fn instructions() -> impl Strategy<Value = Instruction> {
+        prop_oneof![
+            Just(Instruction::Nop),
+            any::<i64>().prop_map(Instruction::AddX)
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn instruction_roundtrip(inst in instructions()) {
+            prop_assert_eq!(inst.to_string().parse::<Instruction>().unwrap(), inst);
+        }
+
+        #[test]
+        fn instruction_near_miss(s in "(noop|addx|add|nop)( ?[-+]?[0-9x]{0,20}){0,2} ?") {
+            if let Ok(inst) = s.parse::<Instruction>() {
+                prop_assert_eq!(inst.to_string().parse::<Instruction>().unwrap(), inst);
+            }
+        }
+    }
+}
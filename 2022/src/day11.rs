@@ -0,0 +1,620 @@
+use crate::YEAR;
+use anyhow::{anyhow, Context, Result};
+use aoc_core::answer::Answer;
+use aoc_core::aoc;
+use aoc_core::checkpoint::Checkpoint;
+use aoc_core::dot::Graph;
+use aoc_core::dump;
+use aoc_core::error::AocError;
+use aoc_core::explain::{Explain, Silent};
+use aoc_core::expr::{BinOp, Expr};
+use aoc_core::invariant::Invariants;
+use aoc_core::parse::{blocks, At, Span};
+use aoc_core::record::Recorder;
+use aoc_core::solver::Solver;
+use arbitrary::{Arbitrary, Unstructured};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::fmt::{Display, Formatter};
+use std::mem;
+use std::str::FromStr;
+use tracing::{debug, instrument, trace_span};
+
+pub type Input = Vec<RefCell<Monkey>>;
+
+pub type Item = u64;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+enum Operation {
+    Mul(Item),
+    Add(Item),
+    Square,
+}
+
+impl Operation {
+    fn apply(self, old: Item) -> Item {
+        match self {
+            Operation::Mul(x) => old * x,
+            Operation::Add(x) => old + x,
+            Operation::Square => old * old,
+        }
+    }
+}
+
+fn constant(e: &Expr) -> Result<Item> {
+    Ok(Item::try_from(e.eval(&|_| None)?)?)
+}
+
+impl FromStr for Operation {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let expr: Expr = s
+            .trim()
+            .strip_prefix("new =")
+            .with_context(|| format!("Invalid operation: {s:?}"))?
+            .parse()?;
+
+        let old = |e: &Expr| matches!(e, Expr::Var(v) if v == "old");
+        match &expr {
+            Expr::Bin(BinOp::Mul, a, b) if old(a) && old(b) => Ok(Self::Square),
+            Expr::Bin(BinOp::Mul, a, b) if old(a) => Ok(Self::Mul(constant(b)?)),
+            Expr::Bin(BinOp::Add, a, b) if old(a) => Ok(Self::Add(constant(b)?)),
+            _ => Err(anyhow!("Unsupported operation: {expr}")),
+        }
+    }
+}
+
+/// Constants fit an `i64`, which is what the expression parser reads.
+impl<'a> Arbitrary<'a> for Operation {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let constant = |u: &mut Unstructured<'a>| u.int_in_range(0..=Item::MAX >> 1);
+        Ok(match u.int_in_range(0..=2)? {
+            0 => Self::Mul(constant(u)?),
+            1 => Self::Add(constant(u)?),
+            _ => Self::Square,
+        })
+    }
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mul(x) => write!(f, "new = old * {x}"),
+            Self::Add(x) => write!(f, "new = old + {x}"),
+            Self::Square => write!(f, "new = old * old"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Monkey {
+    items: Vec<Item>,
+    op: Operation,
+    divides_by: Item,
+    on_true_throw_to: usize,
+    on_false_throw_to: usize,
+    inspect_count: usize,
+}
+
+impl FromStr for Monkey {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let monkey_to_short = || format!("Monkey to short: {s:?}");
+        let mut lines = s.lines();
+
+        let monkey_id = lines
+            .next()
+            .context("can not parse empty string to monkey")?;
+        if !(monkey_id.starts_with("Monkey ") && monkey_id.ends_with(':')) {
+            return Err(anyhow!(Span::new(
+                monkey_id,
+                format!("invalid monkey introduction: {monkey_id:?}")
+            )));
+        }
+
+        let start_items = lines.next().at(s, monkey_to_short)?;
+        let items = start_items
+            .strip_prefix("  Starting items: ")
+            .at(start_items, || "unexpected start of starting_items")?
+            .split_terminator(',')
+            .map(|item| item.trim().parse().at(item, || "Invalid item"))
+            .collect::<Result<Vec<Item>>>()?;
+
+        let op_line = lines.next().at(s, monkey_to_short)?;
+        let op = op_line
+            .strip_prefix("  Operation: ")
+            .at(op_line, || "monkey missing op")?
+            .trim()
+            .parse()
+            .at(op_line, || "Invalid operation")?;
+
+        let test_line = lines.next().at(s, monkey_to_short)?;
+        let divides_by = test_line
+            .strip_prefix("  Test: divisible by ")
+            .at(test_line, || "monkey missing test")?
+            .trim()
+            .parse()
+            .at(test_line, || "Invalid divisor")?;
+        if divides_by == 0 {
+            return Err(anyhow!(Span::new(test_line, "Divisor must not be zero")));
+        }
+
+        let true_line = lines.next().at(s, monkey_to_short)?;
+        let on_true_throw_to = true_line
+            .strip_prefix("    If true: throw to monkey ")
+            .at(true_line, || "monkey missing on true")?
+            .trim()
+            .parse()
+            .at(true_line, || "Invalid monkey")?;
+
+        let false_line = lines.next().at(s, monkey_to_short)?;
+        let on_false_throw_to = false_line
+            .strip_prefix("    If false: throw to monkey ")
+            .at(false_line, || "monkey missing on false")?
+            .trim()
+            .parse()
+            .at(false_line, || "Invalid monkey")?;
+
+        if let Some(extra) = lines.next() {
+            return Err(anyhow!(Span::new(
+                extra,
+                format!("Monkey contains extra data: {extra:?}")
+            )));
+        }
+
+        Ok(Self {
+            items,
+            op,
+            divides_by,
+            on_true_throw_to,
+            on_false_throw_to,
+            inspect_count: 0,
+        })
+    }
+}
+
+/// A monkey as it is read from the notes, with a divisor and without inspections yet. The monkeys
+/// it throws to may not exist.
+impl<'a> Arbitrary<'a> for Monkey {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            items: u.arbitrary()?,
+            op: u.arbitrary()?,
+            divides_by: u.int_in_range(1..=Item::MAX)?,
+            on_true_throw_to: u.arbitrary()?,
+            on_false_throw_to: u.arbitrary()?,
+            inspect_count: 0,
+        })
+    }
+}
+
+/// Everything below the `Monkey <n>:` line, monkeys do not know their number.
+impl Display for Monkey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let items: Vec<_> = self.items.iter().map(Item::to_string).collect();
+        writeln!(f, "  Starting items: {}", items.join(", "))?;
+        writeln!(f, "  Operation: {}", self.op)?;
+        writeln!(f, "  Test: divisible by {}", self.divides_by)?;
+        writeln!(f, "    If true: throw to monkey {}", self.on_true_throw_to)?;
+        write!(
+            f,
+            "    If false: throw to monkey {}",
+            self.on_false_throw_to
+        )
+    }
+}
+
+/// Keeps the worry levels small without changing which monkey they are thrown to. The least
+/// common multiple and not the product, so many monkeys with the same divisors do not overflow.
+fn find_mod(monkeys: &[RefCell<Monkey>]) -> Item {
+    monkeys
+        .iter()
+        .map(|m| m.borrow().divides_by)
+        .fold(1, num::integer::lcm)
+}
+
+impl Operation {
+    fn describe(self) -> String {
+        match self {
+            Operation::Mul(x) => format!("multiplied by {x}"),
+            Operation::Add(x) => format!("increases by {x}"),
+            Operation::Square => "multiplied by itself".into(),
+        }
+    }
+}
+
+fn round(monkeys: &mut [RefCell<Monkey>], relief: bool, modulo: Item, ex: &mut impl Explain) {
+    let monkeys = &*monkeys;
+
+    for (id, monkey) in monkeys.iter().enumerate() {
+        let mut monkey = monkey.borrow_mut();
+        let items = mem::take(&mut monkey.items);
+        ex.section(|| format!("Monkey {id}"));
+        for mut item in items {
+            // Monkey starts inspecting
+            ex.step(|| format!("Monkey inspects an item with a worry level of {item}."));
+            item = monkey.op.apply(item);
+            monkey.inspect_count += 1;
+            ex.step(|| format!("Worry level is {} to {item}.", monkey.op.describe()));
+
+            if relief {
+                // Be relieved
+                item /= 3;
+                ex.step(|| {
+                    format!("Monkey gets bored with item. Worry level is divided by 3 to {item}.")
+                });
+            }
+
+            // Reduce numbers
+            item %= modulo;
+
+            // Perform test
+            let divisible = item % monkey.divides_by == 0;
+            let throw_to = if divisible {
+                monkey.on_true_throw_to
+            } else {
+                monkey.on_false_throw_to
+            };
+            ex.step(|| {
+                let not = if divisible { "" } else { "not " };
+                format!(
+                    "Current worry level is {not}divisible by {}.",
+                    monkey.divides_by
+                )
+            });
+
+            // Throw item
+            ex.step(|| format!("Item with worry level {item} is thrown to monkey {throw_to}."));
+            monkeys[throw_to].borrow_mut().items.push(item);
+        }
+        ex.end();
+    }
+}
+
+fn monkey_invariants(modulo: Item) -> Invariants<[RefCell<Monkey>]> {
+    Invariants::new().register(
+        "items stay below modulo",
+        move |monkeys: &[RefCell<Monkey>]| {
+            for (id, monkey) in monkeys.iter().enumerate() {
+                if let Some(item) = monkey.borrow().items.iter().find(|&&i| i >= modulo) {
+                    return Err(format!("monkey {id} holds {item} >= {modulo}"));
+                }
+            }
+
+            Ok(())
+        },
+    )
+}
+
+/// # Errors
+/// If the input is malformed, or a monkey throws to itself or to a monkey that does not exist.
+#[instrument(level = "debug", skip_all)]
+pub fn parse(input: &str) -> Result<Input> {
+    let blocks: Vec<_> = blocks(input).collect();
+    let monkeys = blocks
+        .iter()
+        .map(|m| Ok(RefCell::new(m.parse()?)))
+        .collect::<Result<Input>>()?;
+
+    for (id, (monkey, block)) in monkeys.iter().zip(&blocks).enumerate() {
+        let monkey = monkey.borrow();
+        for to in [monkey.on_true_throw_to, monkey.on_false_throw_to] {
+            let problem = if to == id {
+                "itself"
+            } else if to >= monkeys.len() {
+                "a monkey that does not exist"
+            } else {
+                continue;
+            };
+            return Err(anyhow!(Span::new(
+                block,
+                format!("Monkey {id} throws to {problem}: monkey {to}")
+            )));
+        }
+    }
+
+    debug!(monkeys = monkeys.len(), "Parsed");
+    Ok(monkeys)
+}
+
+/// The inspections of the two most active monkeys multiplied.
+fn monkey_business(monkeys: &[RefCell<Monkey>]) -> Result<usize, AocError> {
+    let mut inspections: Vec<_> = monkeys.iter().map(|m| m.borrow().inspect_count).collect();
+    inspections.sort_by_key(|cnt| Reverse(*cnt));
+    match inspections[..] {
+        [first, second, ..] => Ok(first * second),
+        _ => Err(AocError::NoSolution(format!(
+            "Monkey business needs two monkeys, there are {}",
+            monkeys.len()
+        ))),
+    }
+}
+
+/// # Errors
+/// [`AocError::NoSolution`] if there are fewer than two monkeys.
+pub fn part1(input: &Input, ex: &mut impl Explain) -> Result<usize, AocError> {
+    let mut input = input.clone();
+    let modulo = find_mod(&input);
+    let invariants = monkey_invariants(modulo);
+    for r in 1..=20 {
+        let _round = trace_span!("round", n = r).entered();
+        ex.section(|| format!("Round {r}"));
+        round(&mut input, true, modulo, ex);
+        ex.end();
+        invariants.check(&input);
+    }
+
+    monkey_business(&input)
+}
+
+/// Saves the monkeys and the number of rounds played to `checkpoint` now and then and continues
+/// from there if it has them.
+///
+/// # Errors
+/// If the checkpoint can not be read or written, or there are fewer than two monkeys.
+pub fn part2(input: &Input, rec: &mut Recorder, checkpoint: &mut Checkpoint) -> Result<usize> {
+    let (played, mut input): (u32, Input) = checkpoint.resume()?.unwrap_or((0, input.clone()));
+    let modulo = find_mod(&input);
+    let invariants = monkey_invariants(modulo);
+
+    debug!(
+        modulo,
+        played, "Worry levels are kept below the product of the divisors"
+    );
+
+    for r in played + 1..=10_000 {
+        let _round = trace_span!("round", n = r).entered();
+        round(&mut input, false, modulo, &mut Silent);
+        invariants.check(&input);
+        rec.record(&input);
+        checkpoint.tick(&(r, &input))?;
+    }
+
+    Ok(monkey_business(&input)?)
+}
+
+/// Which monkey throws to which for `--dot`, with their operation and test.
+#[must_use]
+pub fn throw_graph(monkeys: &[RefCell<Monkey>]) -> Graph {
+    let mut graph = Graph::new("day11");
+    for (id, monkey) in monkeys.iter().enumerate() {
+        let monkey = monkey.borrow();
+        let test = format!("divisible by {}", monkey.divides_by);
+        graph.node(id, format!("Monkey {id}\n{}\n{test}", monkey.op));
+        graph.edge(id, monkey.on_true_throw_to, Some("true"));
+        graph.edge(id, monkey.on_false_throw_to, Some("false"));
+    }
+    graph
+}
+
+/// The example of the puzzle text.
+pub const EXAMPLE: &str = include_str!("../examples/day11.txt");
+
+#[aoc(year = 2022, day = 11)]
+pub struct Day11;
+
+impl Solver for Day11 {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 11;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(part1(input, &mut Silent)?.into())
+    }
+
+    fn part2(input: &Input) -> Result<Answer> {
+        Ok(part2(input, &mut Recorder::default(), &mut Checkpoint::default())?.into())
+    }
+
+    fn dump(input: &Input) -> Result<String> {
+        dump::json(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::explain::{Format, Narrator};
+    use aoc_core::parse::report;
+    use proptest::prelude::*;
+    use std::time::Duration;
+
+    #[test]
+    fn parsing() {
+        let input = parse(EXAMPLE).unwrap();
+        let m = input.first().map(|m| m.borrow().clone()).unwrap();
+        assert_eq!(
+            m,
+            Monkey {
+                items: vec![79, 98],
+                op: Operation::Mul(19),
+                divides_by: 23,
+                on_true_throw_to: 2,
+                on_false_throw_to: 3,
+                inspect_count: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn dot_throws() {
+        let graph = throw_graph(&parse(EXAMPLE).unwrap());
+        assert_eq!((graph.node_count(), graph.edge_count()), (4, 8));
+        let dot = graph.to_string();
+        assert!(dot.contains(r#""0" [label="Monkey 0\nnew = old * 19\ndivisible by 23"];"#));
+        assert!(dot.contains(r#""0" -> "2" [label="true"];"#), "{dot}");
+    }
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input, &mut Silent).unwrap(), 10_605);
+        let part2 = part2(&input, &mut Recorder::default(), &mut Checkpoint::default());
+        assert_eq!(part2.unwrap(), 2_713_310_158);
+    }
+
+    #[test]
+    fn resumes_part2() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("day11.checkpoint");
+        let input = parse(EXAMPLE).unwrap();
+        let mut halfway = input.clone();
+        let modulo = find_mod(&halfway);
+        for _ in 0..5_000 {
+            round(&mut halfway, false, modulo, &mut Silent);
+        }
+        let mut checkpoint = Checkpoint::new(&path, Duration::from_hours(1));
+        checkpoint.save(&(5_000, &halfway)).unwrap();
+
+        let part2 = part2(&input, &mut Recorder::default(), &mut checkpoint);
+        checkpoint.done().unwrap();
+        assert_eq!(part2.unwrap(), 2_713_310_158);
+    }
+
+    #[test]
+    fn step_example() {
+        let mut input = parse(EXAMPLE).unwrap();
+        let modulo = find_mod(&input);
+
+        // Round 1
+        round(&mut input, true, modulo, &mut Silent);
+        let items: Vec<Vec<Item>> = input.iter().map(|m| m.borrow().items.clone()).collect();
+        assert_eq!(
+            items,
+            vec![
+                vec![20, 23, 27, 26],
+                vec![2080, 25, 167, 207, 401, 1046],
+                vec![],
+                vec![]
+            ]
+        );
+
+        // Round 2
+        round(&mut input, true, modulo, &mut Silent);
+        let items: Vec<Vec<Item>> = input.iter().map(|m| m.borrow().items.clone()).collect();
+        assert_eq!(
+            items,
+            vec![
+                vec![695, 10, 71, 135, 350],
+                vec![43, 49, 58, 55, 362],
+                vec![],
+                vec![]
+            ]
+        );
+
+        for _ in 3..=20 {
+            round(&mut input, true, modulo, &mut Silent);
+        }
+        let items: Vec<Vec<Item>> = input.iter().map(|m| m.borrow().items.clone()).collect();
+        assert_eq!(
+            items,
+            vec![
+                vec![10, 12, 14, 26, 34],
+                vec![245, 93, 53, 199, 115],
+                vec![],
+                vec![]
+            ]
+        );
+
+        let inspections: Vec<_> = input.iter().map(|m| m.borrow().inspect_count).collect();
+        assert_eq!(inspections, vec![101, 95, 7, 105]);
+    }
+
+    #[test]
+    fn rejects_throws() {
+        let to_itself = EXAMPLE.replace("If true: throw to monkey 2", "If true: throw to monkey 0");
+        let err = parse(&to_itself).unwrap_err();
+        assert!(err.to_string().contains("throws to itself"), "{err}");
+        let missing = EXAMPLE.replace("throw to monkey 3", "throw to monkey 9");
+        let err = parse(&missing).unwrap_err();
+        assert!(err.to_string().contains("does not exist"), "{err}");
+        parse(&EXAMPLE.replacen("Monkey 0:", "Monkey 0", 1)).unwrap_err();
+        parse(&EXAMPLE.replacen("Monkey 0:", "Ape 0:", 1)).unwrap_err();
+        let err = parse(&EXAMPLE.replace("divisible by 19", "divisible by 0")).unwrap_err();
+        assert!(err.to_string().contains("must not be zero"), "{err}");
+
+        let alone = "Monkey 0:
+  Starting items: 1
+  Operation: new = old * 2
+  Test: divisible by 2
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+";
+        let err = parse(alone).unwrap_err();
+        assert!(err.to_string().contains("does not exist"), "{err}");
+        assert!(matches!(
+            part1(&vec![], &mut Silent),
+            Err(AocError::NoSolution(_))
+        ));
+    }
+
+    #[test]
+    fn report_broken() {
+        let broken = EXAMPLE.replace("divisible by 19", "divisible by x");
+        let err = parse(&broken).unwrap_err();
+
+        let shown = report(&err, &broken).unwrap();
+        assert!(shown.contains("11 │   Test: divisible by x"), "{shown}");
+        assert!(shown.contains("Invalid divisor"), "{shown}");
+    }
+
+    #[test]
+    fn explain_example() {
+        let mut input = parse(EXAMPLE).unwrap();
+        let modulo = find_mod(&input);
+        let mut narrator = Narrator::new(Format::Text, Some(6));
+
+        round(&mut input, true, modulo, &mut narrator);
+
+        assert_eq!(
+            narrator.to_string(),
+            "Monkey 0:
+  Monkey inspects an item with a worry level of 79.
+  Worry level is multiplied by 19 to 1501.
+  Monkey gets bored with item. Worry level is divided by 3 to 500.
+  Current worry level is not divisible by 23.
+  Item with worry level 500 is thrown to monkey 3.
+  Monkey inspects an item with a worry level of 98.
+  ...
+"
+        );
+    }
+    fn monkeys() -> impl Strategy<Value = Monkey> {
+        any::<Vec<u8>>()
+            .prop_map(|bytes| Monkey::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap())
+    }
+
+    proptest! {
+        #[test]
+        fn monkey_roundtrip(monkey in monkeys(), id in 0..10_usize) {
+            let text = format!("Monkey {id}:\n{monkey}");
+            prop_assert_eq!(text.parse::<Monkey>().unwrap(), monkey);
+        }
+
+        #[test]
+        fn monkey_corrupted(
+            monkey in monkeys(),
+            at in any::<prop::sample::Index>(),
+            with in "[ 0-9a-z:,*+\n-]?",
+        ) {
+            let mut text: Vec<char> = format!("Monkey 0:\n{monkey}").chars().collect();
+            let at = at.index(text.len());
+            text.splice(at..=at, with.chars());
+            let text: String = text.into_iter().collect();
+
+            if let Ok(parsed) = text.parse::<Monkey>() {
+                let again = format!("Monkey 0:\n{parsed}").parse::<Monkey>().unwrap();
+                prop_assert_eq!(again, parsed);
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use crate::YEAR;
+use anyhow::{anyhow, Context, Result};
+use aoc_core::answer::Answer;
+use aoc_core::aoc;
+use aoc_core::coord::Coord;
+use aoc_core::dump;
+use aoc_core::error::AocError;
+use aoc_core::grid::Grid2D;
+use aoc_core::image::{self, mix, Image};
+use aoc_core::solver::Solver;
+use num::integer::sqrt;
+use pathfinding::directed::astar::astar;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, debug_span};
+
+pub type Input = Map;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Dir {
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Map {
+    start: Coord,
+    end: Coord,
+    heights: Grid2D<u8>,
+}
+
+/// # Errors
+/// If the input is malformed.
+pub fn parse(input: &str) -> Result<Input> {
+    let mut start = None;
+    let mut end = None;
+
+    let heights = Grid2D::parse(input, |coord, height| {
+        Ok(match height {
+            'a'..='z' => u8::try_from(height)? - b'a',
+            'S' => {
+                start = Some(coord);
+                0
+            }
+            'E' => {
+                end = Some(coord);
+                25
+            }
+            _ => return Err(anyhow!("Invalid height: {height:?}")),
+        })
+    })?;
+
+    Ok(Map {
+        start: start.context("No start found")?,
+        end: end.context("No end found")?,
+        heights,
+    })
+}
+
+fn neighbours(map: &Grid2D<u8>, coord: Coord, dir: Dir) -> impl Iterator<Item = Coord> + '_ {
+    let current_height = map[coord];
+
+    map.neighbors(coord)
+        .filter_map(move |(new_coord, &new_height)| {
+            let dist = match dir {
+                Dir::Up => new_height.saturating_sub(current_height),
+                Dir::Down => current_height.saturating_sub(new_height),
+            };
+            if dist <= 1 {
+                Some(new_coord)
+            } else {
+                None
+            }
+        })
+}
+
+/// # Errors
+/// [`AocError::NoSolution`] if there is no path to the end.
+pub fn part1(input: &Input) -> Result<usize, AocError> {
+    let _search = debug_span!("path search", from = ?input.start, to = ?input.end).entered();
+    let path = astar(
+        &input.start,
+        |coord| {
+            neighbours(&input.heights, *coord, Dir::Up)
+                .map(|coord| (coord, 1) /* Cost is always 1 */)
+        },
+        |c| sqrt(c.x.unsigned_abs().pow(2) + c.y.unsigned_abs().pow(2)),
+        |coord| coord == &input.end,
+    )
+    .ok_or_else(|| AocError::NoSolution("There is no path to the end".to_owned()))?;
+    debug!(steps = path.1, end = ?path.0.last(), "Found a path");
+
+    Ok(path.1)
+}
+
+/// # Errors
+/// [`AocError::NoSolution`] if there is no path to the lowest point.
+pub fn part2(input: &Input) -> Result<usize, AocError> {
+    let _search = debug_span!("path search", from = ?input.end, to = "lowest point").entered();
+    let path = astar(
+        &input.end,
+        |coord| {
+            neighbours(&input.heights, *coord, Dir::Down)
+                .map(|coord| (coord, 1) /* Cost is always 1 */)
+        },
+        |_| 0, // TODO: replace with nearest non visited 'a'
+        |&coord| input.heights[coord] == 0,
+    )
+    .ok_or_else(|| AocError::NoSolution("There is no path to the lowest point".to_owned()))?;
+    debug!(steps = path.1, end = ?path.0.last(), "Found a path");
+
+    Ok(path.1)
+}
+
+/// The heights for `--heights`, as an image from dark blue at `a` to white at `z` with the start
+/// red and the end yellow, or as the letters of the input.
+#[must_use]
+pub fn height_map(map: &Map) -> String {
+    let heights = &map.heights;
+    let colors: Vec<_> = heights
+        .iter()
+        .map(|(c, &height)| match c {
+            _ if c == map.start => [220, 40, 40],
+            _ if c == map.end => [250, 200, 0],
+            _ => mix([20, 40, 120], [240, 240, 240], f64::from(height) / 25.0),
+        })
+        .collect();
+    let width = heights.width();
+    let image = Image::from_fn(width, heights.height(), |x, y| colors[y * width + x]);
+
+    image::show(&image.scaled(4), || {
+        let mut letters: Vec<_> = heights.render(|&h| char::from(b'a' + h)).chars().collect();
+        for (c, letter) in [(map.start, 'S'), (map.end, 'E')] {
+            // Every row ends in a newline
+            letters[c.y.unsigned_abs() * (width + 1) + c.x.unsigned_abs()] = letter;
+        }
+        letters.into_iter().collect()
+    })
+}
+
+/// The example of the puzzle text.
+pub const EXAMPLE: &str = include_str!("../examples/day12.txt");
+
+#[aoc(year = 2022, day = 12)]
+pub struct Day12;
+
+impl Solver for Day12 {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 12;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Input) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+
+    fn dump(input: &Input) -> Result<String> {
+        dump::json(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input), Ok(31));
+        assert_eq!(part2(&input), Ok(29));
+    }
+
+    #[test]
+    fn walled_in() {
+        // A cliff between `b` and `z`, in both directions
+        let input = parse("SbzE\n").unwrap();
+        assert!(matches!(part1(&input), Err(AocError::NoSolution(_))));
+        assert!(matches!(part2(&input), Err(AocError::NoSolution(_))));
+    }
+
+    #[test]
+    fn letters_without_images() {
+        assert_eq!(height_map(&parse(EXAMPLE).unwrap()), EXAMPLE);
+    }
+}
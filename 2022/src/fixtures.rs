@@ -0,0 +1,19 @@
+/// Expands the examples in `fixtures/<day>.toml` into rstest cases.
+///
+/// Every example has a `name`, an `input`, the expected answers `part1` and/or `part2`, and
+/// optionally some `notes`. The calling module has to provide `fixture_part1` and/or
+/// `fixture_part2`, taking the input and returning the answer as a string.
+///
+/// ```ignore
+/// fn fixture_part1(input: &str) -> String {
+///     part1(&parse(input).unwrap()).to_string()
+/// }
+///
+/// crate::fixtures!("day09");
+/// ```
+#[macro_export]
+macro_rules! fixtures {
+    ($day:literal) => {
+        include!(concat!(env!("OUT_DIR"), "/fixtures/", $day, ".rs"));
+    };
+}
@@ -0,0 +1,364 @@
+//! Random but valid inputs of any size, for benchmarks and for checking optimized solvers against
+//! simple ones on more than the examples. `aoc input gen --day 9 --size 1000000` prints one.
+//!
+//! The same seed always gives the same input. `size` is how many of what the day has a lot of
+//! there are, at least as many as the puzzle needs to have an answer:
+//!
+//! | Day | `size`                                 |
+//! |-----|----------------------------------------|
+//! | 1   | elves                                  |
+//! | 2   | rounds                                 |
+//! | 3   | groups of three elves                  |
+//! | 4   | pairs                                  |
+//! | 5   | moves                                  |
+//! | 6   | characters before the start marker     |
+//! | 7   | directories                            |
+//! | 8   | width and height of the grid           |
+//! | 9   | moves                                  |
+//! | 10  | instructions                           |
+//! | 11  | monkeys                                |
+//! | 12  | width and height of the height map     |
+
+use crate::reference::Rng;
+use anyhow::{anyhow, Result};
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+/// Days that have a generator.
+pub const DAYS: RangeInclusive<u8> = 1..=12;
+
+/// An input for `day`, see the [module](self) for what `size` means.
+///
+/// # Errors
+/// If there is no generator for `day`.
+pub fn generate(day: u8, size: usize, seed: u64) -> Result<String> {
+    let rng = &mut Rng::new(seed);
+    Ok(match day {
+        1 => day01(rng, size),
+        2 => day02(rng, size),
+        3 => day03(rng, size),
+        4 => day04(rng, size),
+        5 => day05(rng, size),
+        6 => day06(rng, size),
+        7 => day07(rng, size),
+        8 => day08(rng, size),
+        9 => day09(rng, size),
+        10 => day10(rng, size),
+        11 => day11(rng, size),
+        12 => day12(rng, size),
+        _ => return Err(anyhow!("There is no generator for day {day}")),
+    })
+}
+
+/// Uniform-ish number in `range`.
+fn between(rng: &mut Rng, range: RangeInclusive<usize>) -> usize {
+    let len = (range.end() - range.start() + 1) as u64;
+    #[allow(clippy::cast_possible_truncation)] // Below `len`, which came from a usize
+    let offset = rng.below(len) as usize;
+    range.start() + offset
+}
+
+fn letter(rng: &mut Rng, letters: &[u8]) -> char {
+    char::from(letters[between(rng, 0..=letters.len() - 1)])
+}
+
+/// Part 2 needs three elves.
+#[must_use]
+pub fn day01(rng: &mut Rng, elves: usize) -> String {
+    let mut out = String::new();
+    for elf in 0..elves.max(3) {
+        if elf > 0 {
+            out.push('\n');
+        }
+        for _ in 0..between(rng, 1..=15) {
+            writeln!(out, "{}", between(rng, 1000..=60_000)).unwrap();
+        }
+    }
+    out
+}
+
+#[must_use]
+pub fn day02(rng: &mut Rng, rounds: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..rounds {
+        let (theirs, ours) = (letter(rng, b"ABC"), letter(rng, b"XYZ"));
+        writeln!(out, "{theirs} {ours}").unwrap();
+    }
+    out
+}
+
+/// Groups of three rucksacks that share exactly one badge, each with exactly one item in both
+/// compartments.
+#[must_use]
+pub fn day03(rng: &mut Rng, groups: usize) -> String {
+    const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    let mut out = String::new();
+    for _ in 0..groups {
+        let badge = ITEMS[between(rng, 0..=ITEMS.len() - 1)];
+        for elf in 0..3 {
+            // Every elf has its own items, so the badge is the only one shared in the group
+            let own: Vec<u8> = ITEMS
+                .iter()
+                .copied()
+                .filter(|&c| c != badge && usize::from(c) % 3 == elf)
+                .collect();
+            let start = between(rng, 0..=own.len() - 1);
+            let singles = 2 * between(rng, 0..=3);
+            let items: Vec<u8> = (0..singles + 2)
+                .map(|i| own[(start + i) % own.len()])
+                .collect();
+
+            // The badge only goes left, so the right half gets one more single item
+            let (both, singles) = (items[0], &items[1..]);
+            let (left, right) = singles.split_at(singles.len() / 2);
+            for half in [&[both, badge][..], left, &[both], right] {
+                out.extend(half.iter().map(|&c| char::from(c)));
+            }
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[must_use]
+pub fn day04(rng: &mut Rng, pairs: usize) -> String {
+    let range = |rng: &mut Rng| {
+        let start = between(rng, 1..=99);
+        (start, between(rng, start..=99))
+    };
+
+    let mut out = String::new();
+    for _ in 0..pairs {
+        let ((a, b), (c, d)) = (range(rng), range(rng));
+        writeln!(out, "{a}-{b},{c}-{d}").unwrap();
+    }
+    out
+}
+
+/// Nine stacks, like the real inputs. Every move takes from a stack that has enough boxes.
+#[must_use]
+pub fn day05(rng: &mut Rng, moves: usize) -> String {
+    const STACKS: usize = 9;
+    const BOXES: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    let mut heights: Vec<usize> = (0..STACKS).map(|_| between(rng, 1..=8)).collect();
+    let mut out = String::new();
+    for level in (0..heights.iter().copied().max().unwrap_or_default()).rev() {
+        let line: Vec<_> = heights
+            .iter()
+            .map(|&h| {
+                if level < h {
+                    format!("[{}]", letter(rng, BOXES))
+                } else {
+                    "   ".to_owned()
+                }
+            })
+            .collect();
+        writeln!(out, "{}", line.join(" ").trim_end()).unwrap();
+    }
+    let indices: Vec<_> = (1..=STACKS).map(|idx| format!(" {idx} ")).collect();
+    writeln!(out, "{}\n", indices.join(" ")).unwrap();
+
+    for _ in 0..moves {
+        let full: Vec<_> = (0..STACKS).filter(|&s| heights[s] > 0).collect();
+        let from = full[between(rng, 0..=full.len() - 1)];
+        let to = (from + between(rng, 1..=STACKS - 1)) % STACKS;
+        let amount = between(rng, 1..=heights[from].min(10));
+        heights[from] -= amount;
+        heights[to] += amount;
+        writeln!(out, "move {amount} from {} to {}", from + 1, to + 1).unwrap();
+    }
+    out
+}
+
+/// Only three letters before the marker, so no earlier four (or fourteen) are all different and
+/// both parts have to look at the whole input.
+#[must_use]
+pub fn day06(rng: &mut Rng, len: usize) -> String {
+    let mut out: String = (0..len).map(|_| letter(rng, b"abc")).collect();
+    let mut marker: Vec<u8> = (b'd'..=b'z').collect();
+    rng.shuffle(&mut marker);
+    out.extend(marker[..14].iter().map(|&c| char::from(c)));
+    out.push('\n');
+    out
+}
+
+/// A random tree with files everywhere, together between 40 000 000 and 70 000 000 big (for up
+/// to millions of directories), so part 2 has to delete something and can.
+#[must_use]
+pub fn day07(rng: &mut Rng, dirs: usize) -> String {
+    let dirs = dirs.max(1);
+    let mut children = vec![vec![]; dirs];
+    for dir in 1..dirs {
+        children[between(rng, 0..=dir - 1)].push(dir);
+    }
+    // Every file gets its share of the total, by weight
+    let files: Vec<Vec<u64>> = (0..dirs)
+        .map(|_| {
+            (0..between(rng, 1..=3))
+                .map(|_| rng.below(1000) + 1)
+                .collect()
+        })
+        .collect();
+    let total = 40_000_001 + rng.below(20_000_000);
+    let sum: u64 = files.iter().flatten().sum();
+
+    let mut out = "$ cd /\n".to_owned();
+    // Directories to visit, `None` for going back up
+    let mut todo = vec![Some(0)];
+    while let Some(next) = todo.pop() {
+        let Some(dir) = next else {
+            out.push_str("$ cd ..\n");
+            continue;
+        };
+        if dir > 0 {
+            writeln!(out, "$ cd d{dir}").unwrap();
+            todo.push(None);
+        }
+        out.push_str("$ ls\n");
+        for child in &children[dir] {
+            writeln!(out, "dir d{child}").unwrap();
+        }
+        for (file, weight) in files[dir].iter().enumerate() {
+            writeln!(out, "{} f{file}.txt", total * weight / sum + 1).unwrap();
+        }
+        todo.extend(children[dir].iter().rev().map(|&c| Some(c)));
+    }
+    out
+}
+
+#[must_use]
+pub fn day08(rng: &mut Rng, side: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..side.max(1) {
+        out.extend((0..side.max(1)).map(|_| letter(rng, b"0123456789")));
+        out.push('\n');
+    }
+    out
+}
+
+#[must_use]
+pub fn day09(rng: &mut Rng, moves: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..moves {
+        let dir = letter(rng, b"RULD");
+        writeln!(out, "{dir} {}", between(rng, 1..=20)).unwrap();
+    }
+    out
+}
+
+/// Part 2 draws 240 cycles, so there are at least 240 instructions.
+#[must_use]
+pub fn day10(rng: &mut Rng, instructions: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..instructions.max(240) {
+        if rng.below(3) == 0 {
+            out.push_str("noop\n");
+        } else {
+            #[allow(clippy::cast_possible_wrap)] // At most 20
+            let value = between(rng, 0..=20) as i64 - 10;
+            writeln!(out, "addx {value}").unwrap();
+        }
+    }
+    out
+}
+
+/// Divisors are small primes like in the real inputs, which keeps the worry levels small enough
+/// to square. Monkeys never throw to themselves.
+#[must_use]
+pub fn day11(rng: &mut Rng, monkeys: usize) -> String {
+    const PRIMES: [usize; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+    let monkeys = monkeys.max(2);
+    let mut out = String::new();
+    for monkey in 0..monkeys {
+        let items: Vec<_> = (0..between(rng, 0..=8))
+            .map(|_| between(rng, 50..=99).to_string())
+            .collect();
+        let op = match rng.below(8) {
+            0 => "old * old".to_owned(),
+            1..=3 => format!("old * {}", between(rng, 2..=19)),
+            _ => format!("old + {}", between(rng, 1..=8)),
+        };
+        let divisor = PRIMES[between(rng, 0..=PRIMES.len() - 1)];
+        let mut other = || (monkey + between(rng, 1..=monkeys - 1)) % monkeys;
+        let (on_true, on_false) = (other(), other());
+
+        if monkey > 0 {
+            out.push('\n');
+        }
+        writeln!(
+            out,
+            "Monkey {monkey}:
+  Starting items: {}
+  Operation: new = {op}
+  Test: divisible by {divisor}
+    If true: throw to monkey {on_true}
+    If false: throw to monkey {on_false}",
+            items.join(", ")
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// The heights rise from `a` on the left to `z` on the right and the row of `S` and `E` has no
+/// dips, so there is always a path. Everywhere else some places are lower.
+#[must_use]
+pub fn day12(rng: &mut Rng, side: usize) -> String {
+    const HEIGHTS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+    let (width, height) = (side.max(26), side.max(1));
+    let row = between(rng, 0..=height - 1);
+
+    let mut out = String::new();
+    for y in 0..height {
+        for x in 0..width {
+            let rise = x * 25 / (width - 1);
+            let c = if y == row && x == 0 {
+                'S'
+            } else if y == row && x == width - 1 {
+                'E'
+            } else if y != row && rng.below(4) == 0 {
+                letter(rng, &HEIGHTS[..=rise])
+            } else {
+                char::from(HEIGHTS[rise])
+            };
+            out.push(c);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::registry::solution;
+
+    #[test]
+    fn generated_inputs_solve() {
+        for day in DAYS {
+            let input = generate(day, 30, 4).unwrap();
+            assert_eq!(input, generate(day, 30, 4).unwrap());
+
+            // Only the days of the enabled features are built
+            let Some(solution) = solution(crate::YEAR, day) else {
+                continue;
+            };
+            let answers = solution.solve(&input, &[]);
+            assert!(answers.is_ok(), "Day {day}: {answers:?}\n{input}");
+        }
+        generate(25, 30, 4).unwrap_err();
+    }
+
+    #[test]
+    fn sizes() {
+        let rng = &mut Rng::new(1);
+        assert_eq!(day09(rng, 1000).lines().count(), 1000);
+        assert_eq!(day08(rng, 100).len(), 101 * 100);
+        assert_eq!(day11(rng, 100).matches("Monkey").count(), 100);
+        assert_eq!(day01(rng, 0).split("\n\n").count(), 3);
+    }
+}
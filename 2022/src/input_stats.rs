@@ -0,0 +1,432 @@
+use anyhow::{Context, Result};
+use aoc_core::registry::solution;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+
+/// Structural summary of an input, to spot truncated or otherwise unexpected inputs.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct Stats {
+    pub bytes: usize,
+    pub lines: usize,
+    pub blank_lines: usize,
+    /// Number of lines in each blank-line separated block
+    pub block_lines: Vec<usize>,
+    pub min_line_len: usize,
+    pub max_line_len: usize,
+    pub trailing_newline: bool,
+    pub tokens: BTreeMap<String, usize>,
+}
+
+/// Token as used in the histogram, numbers are grouped together as there are usually thousands of
+/// different ones.
+fn token_class(token: &str) -> &str {
+    if token.parse::<i64>().is_ok() {
+        "<int>"
+    } else {
+        token
+    }
+}
+
+fn tokens(line: &str) -> impl Iterator<Item = &str> {
+    line.split(|c: char| c.is_whitespace() || matches!(c, ',' | ':' | ';'))
+        .filter(|t| !t.is_empty())
+}
+
+impl Stats {
+    #[must_use]
+    pub fn from_input(input: &str) -> Self {
+        let mut stats = Self {
+            bytes: input.len(),
+            lines: 0,
+            blank_lines: 0,
+            block_lines: vec![],
+            min_line_len: usize::MAX,
+            max_line_len: 0,
+            trailing_newline: input.ends_with('\n'),
+            tokens: BTreeMap::new(),
+        };
+
+        let mut in_block = false;
+        for line in input.lines() {
+            stats.lines += 1;
+            stats.min_line_len = stats.min_line_len.min(line.len());
+            stats.max_line_len = stats.max_line_len.max(line.len());
+
+            if line.trim().is_empty() {
+                stats.blank_lines += 1;
+                in_block = false;
+                continue;
+            }
+
+            match stats.block_lines.last_mut() {
+                Some(block) if in_block => *block += 1,
+                _ => {
+                    stats.block_lines.push(1);
+                    in_block = true;
+                }
+            }
+
+            for token in tokens(line) {
+                *stats.tokens.entry(token_class(token).into()).or_default() += 1;
+            }
+        }
+
+        if stats.lines == 0 {
+            stats.min_line_len = 0;
+        }
+
+        stats
+    }
+
+    /// Describes every structural difference between two inputs, one per line.
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> Vec<String> {
+        let mut diffs = vec![];
+        let mut cmp = |what: &str, a: &dyn Display, b: &dyn Display| {
+            let (a, b) = (a.to_string(), b.to_string());
+            if a != b {
+                diffs.push(format!("{what}: {a} vs {b}"));
+            }
+        };
+
+        cmp("lines", &self.lines, &other.lines);
+        cmp("blank lines", &self.blank_lines, &other.blank_lines);
+        cmp("blocks", &self.block_lines.len(), &other.block_lines.len());
+        for (idx, (a, b)) in self.block_lines.iter().zip(&other.block_lines).enumerate() {
+            cmp(&format!("lines in block {}", idx + 1), a, b);
+        }
+        cmp("min line length", &self.min_line_len, &other.min_line_len);
+        cmp("max line length", &self.max_line_len, &other.max_line_len);
+        cmp(
+            "trailing newline",
+            &self.trailing_newline,
+            &other.trailing_newline,
+        );
+
+        let all_tokens: BTreeMap<_, _> = self.tokens.iter().chain(&other.tokens).collect();
+        for token in all_tokens.keys() {
+            cmp(
+                &format!("count of {token:?}"),
+                &self.tokens.get(*token).copied().unwrap_or_default(),
+                &other.tokens.get(*token).copied().unwrap_or_default(),
+            );
+        }
+
+        diffs
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "bytes:       {}", self.bytes)?;
+        writeln!(f, "lines:       {}", self.lines)?;
+        writeln!(
+            f,
+            "line length: {}..={}",
+            self.min_line_len, self.max_line_len
+        )?;
+        writeln!(f, "blank lines: {}", self.blank_lines)?;
+        writeln!(f, "blocks:      {}", self.block_lines.len())?;
+        if !self.trailing_newline {
+            writeln!(
+                f,
+                "WARNING: no trailing newline, the input might be truncated"
+            )?;
+        }
+
+        let mut tokens: Vec<_> = self.tokens.iter().collect();
+        tokens.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+
+        writeln!(f, "tokens ({} distinct):", tokens.len())?;
+        for (token, count) in tokens.iter().take(20) {
+            writeln!(f, "  {token:<12} {count}")?;
+        }
+        if tokens.len() > 20 {
+            writeln!(f, "  ...")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// What the values at one place of a parsed input look like, all of them together.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Shape {
+    /// How many values there are at this place, e.g. one amount for every move
+    pub count: usize,
+    /// `array`, `object`, `number`, `string`, `bool` or `null`
+    pub kinds: BTreeSet<&'static str>,
+    /// Fewest and most items of the arrays or characters of the strings
+    pub len: Option<(usize, usize)>,
+    /// Smallest and largest number
+    pub range: Option<(f64, f64)>,
+}
+
+/// Structural summary of a parsed input (as serialized by [`Solver::dump`]): the [`Shape`] of every
+/// place in it, by JSON path with `[]` for any item of a list.
+///
+/// [`Solver::dump`]: aoc_core::solver::Solver::dump
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParsedStats(pub BTreeMap<String, Shape>);
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Whether two values can be items of the same list, empty lists go with any list.
+fn compatible(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Array(a), Value::Array(b)) => match (a.first(), b.first()) {
+            (Some(a), Some(b)) => compatible(a, b),
+            _ => true,
+        },
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| b.get(key).is_some_and(|b| compatible(a, b)))
+        }
+        (a, b) => kind(a) == kind(b),
+    }
+}
+
+fn widen<T: PartialOrd + Copy>(range: &mut Option<(T, T)>, value: T) {
+    *range = Some(match *range {
+        Some((min, max)) => (
+            if value < min { value } else { min },
+            if value > max { value } else { max },
+        ),
+        None => (value, value),
+    });
+}
+
+fn range_text<T: PartialEq + Display>((min, max): (T, T)) -> String {
+    if min == max {
+        min.to_string()
+    } else {
+        format!("{min}..={max}")
+    }
+}
+
+impl ParsedStats {
+    /// Parses `input` with the registered day and summarizes what it parsed to.
+    ///
+    /// # Errors
+    /// If the day is not registered with `#[aoc]`, or can not parse or dump the input.
+    pub fn of_day(year: u16, day: u8, input: &str) -> Result<Self> {
+        let solution = solution(year, day).with_context(|| {
+            format!(
+                "{year} day {day} is not registered with #[aoc], so its input can not be parsed"
+            )
+        })?;
+        let parsed = solution.parse(input)?;
+        Self::from_dump(&solution.dump(parsed.as_ref())?)
+    }
+
+    /// # Errors
+    /// If `json` is not valid JSON.
+    pub fn from_dump(json: &str) -> Result<Self> {
+        Ok(Self::from_json(&serde_json::from_str(json)?))
+    }
+
+    #[must_use]
+    pub fn from_json(json: &Value) -> Self {
+        let mut stats = Self(BTreeMap::new());
+        stats.add("$", json);
+        stats
+    }
+
+    fn add(&mut self, path: &str, value: &Value) {
+        let shape = self.0.entry(path.to_owned()).or_insert_with(|| Shape {
+            count: 0,
+            kinds: BTreeSet::new(),
+            len: None,
+            range: None,
+        });
+        shape.count += 1;
+        shape.kinds.insert(kind(value));
+        match value {
+            Value::Number(n) => widen(&mut shape.range, n.as_f64().unwrap_or_default()),
+            Value::String(s) => widen(&mut shape.len, s.chars().count()),
+            Value::Array(items) => {
+                widen(&mut shape.len, items.len());
+                // Lists of alike items are summarized together, tuples item by item
+                let alike = items.iter().all(|item| compatible(&items[0], item));
+                for (i, item) in items.iter().enumerate() {
+                    let index = if alike { String::new() } else { i.to_string() };
+                    self.add(&format!("{path}[{index}]"), item);
+                }
+            }
+            Value::Object(fields) => {
+                for (key, field) in fields {
+                    self.add(&format!("{path}.{key}"), field);
+                }
+            }
+            Value::Null | Value::Bool(_) => {}
+        }
+    }
+
+    /// Describes every structural difference between two parsed inputs, one per line.
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> Vec<String> {
+        let paths: BTreeSet<_> = self.0.keys().chain(other.0.keys()).collect();
+        let mut diffs = vec![];
+        for path in paths {
+            let (a, b) = match (self.0.get(path), other.0.get(path)) {
+                (Some(a), Some(b)) => (a, b),
+                (Some(_), None) => {
+                    diffs.push(format!("{path}: only in the first"));
+                    continue;
+                }
+                (None, _) => {
+                    diffs.push(format!("{path}: only in the second"));
+                    continue;
+                }
+            };
+
+            if a.kinds != b.kinds {
+                let kinds = |s: &Shape| s.kinds.iter().copied().collect::<Vec<_>>().join("/");
+                diffs.push(format!("{path}: {} vs {}", kinds(a), kinds(b)));
+            }
+            if a.count != b.count {
+                diffs.push(format!("{path}: {} vs {} values", a.count, b.count));
+            }
+            if let (Some(x), Some(y)) = (a.len, b.len) {
+                if x != y {
+                    diffs.push(format!(
+                        "{path}: length {} vs {}",
+                        range_text(x),
+                        range_text(y)
+                    ));
+                }
+            }
+            if let (Some(x), Some(y)) = (a.range, b.range) {
+                if x != y {
+                    diffs.push(format!("{path}: {} vs {}", range_text(x), range_text(y)));
+                }
+            }
+        }
+        diffs
+    }
+}
+
+impl Display for ParsedStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let width = self.0.keys().map(String::len).max().unwrap_or_default();
+        for (path, shape) in &self.0 {
+            let kinds: Vec<_> = shape.kinds.iter().copied().collect();
+            write!(f, "{path:<width$}  {} {}", shape.count, kinds.join("/"))?;
+            if let Some(len) = shape.len {
+                write!(f, ", length {}", range_text(len))?;
+            }
+            if let Some(range) = shape.range {
+                write!(f, ", {}", range_text(range))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let input = "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+";
+
+        let stats = Stats::from_input(input);
+        assert_eq!(stats.lines, 7);
+        assert_eq!(stats.blank_lines, 1);
+        assert_eq!(stats.block_lines, vec![4, 2]);
+        assert_eq!(stats.min_line_len, 0);
+        assert_eq!(stats.max_line_len, 18);
+        assert!(stats.trailing_newline);
+        assert_eq!(stats.tokens["move"], 2);
+        assert_eq!(stats.tokens["<int>"], 9);
+        assert_eq!(stats.tokens["[D]"], 1);
+    }
+
+    #[test]
+    fn truncated() {
+        let stats = Stats::from_input("1000\n2000\n\n30");
+        assert_eq!(stats.block_lines, vec![2, 1]);
+        assert!(!stats.trailing_newline);
+        assert_eq!(stats.min_line_len, 0);
+        assert_eq!(stats.max_line_len, 4);
+    }
+
+    #[test]
+    fn diff() {
+        let a = Stats::from_input("move 1 from 2 to 1\nmove 3 from 1 to 3\n");
+        let b = Stats::from_input("move 1 from 2 to 1\n\nmove 3 from 1 to 3\n");
+
+        assert!(a.diff(&a).is_empty());
+        assert_eq!(
+            a.diff(&b),
+            vec![
+                "lines: 2 vs 3",
+                "blank lines: 0 vs 1",
+                "blocks: 1 vs 2",
+                "lines in block 1: 2 vs 1",
+                "min line length: 18 vs 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn parsed() {
+        let a = ParsedStats::from_json(&serde_json::json!([
+            [["Z", "N"], ["M", "C", "D"], ["P"]],
+            [{ "amount": 1, "from": 2 }, { "amount": 3, "from": 1 }],
+        ]));
+        assert_eq!(
+            a.to_string(),
+            "\
+$              1 array, length 2
+$[0]           1 array, length 3
+$[0][]         3 array, length 1..=3
+$[0][][]       6 string, length 1
+$[1]           1 array, length 2
+$[1][]         2 object
+$[1][].amount  2 number, 1..=3
+$[1][].from    2 number, 1..=2
+"
+        );
+
+        let b = ParsedStats::from_json(&serde_json::json!([
+            [["Z", "N"], [], ["P"]],
+            [{ "amount": 1, "from": 2 }, { "amount": 2, "from": 1 }, { "amount": 1, "from": 3 }],
+        ]));
+        assert!(a.diff(&a).is_empty());
+        assert_eq!(
+            a.diff(&b),
+            vec![
+                "$[0][]: length 1..=3 vs 0..=2",
+                "$[0][][]: 6 vs 3 values",
+                "$[1]: length 2 vs 3",
+                "$[1][]: 2 vs 3 values",
+                "$[1][].amount: 2 vs 3 values",
+                "$[1][].amount: 1..=3 vs 1..=2",
+                "$[1][].from: 2 vs 3 values",
+                "$[1][].from: 1..=2 vs 1..=3",
+            ]
+        );
+    }
+}
@@ -1 +1,43 @@
+#![warn(clippy::pedantic)]
 
+/// The year of the days in this crate.
+pub const YEAR: u16 = 2022;
+
+#[cfg(feature = "heap")]
+#[global_allocator]
+static ALLOCATOR: aoc_core::heap::Counting = aoc_core::heap::Counting;
+
+pub mod big;
+#[cfg(feature = "day2022_01")]
+pub mod day01;
+#[cfg(feature = "day2022_02")]
+pub mod day02;
+#[cfg(feature = "day2022_03")]
+pub mod day03;
+#[cfg(feature = "day2022_04")]
+pub mod day04;
+#[cfg(feature = "day2022_05")]
+pub mod day05;
+#[cfg(feature = "day2022_06")]
+pub mod day06;
+#[cfg(feature = "day2022_07")]
+pub mod day07;
+#[cfg(feature = "day2022_08")]
+pub mod day08;
+#[cfg(feature = "day2022_09")]
+pub mod day09;
+#[cfg(feature = "day2022_10")]
+pub mod day10;
+#[cfg(feature = "day2022_11")]
+pub mod day11;
+#[cfg(feature = "day2022_12")]
+pub mod day12;
+mod fixtures;
+pub mod gen;
+pub mod input_stats;
+pub mod minimize;
+pub mod parse_cache;
+pub mod reference;
+pub mod scramble;
+#[cfg(feature = "simd")]
+pub mod simd;
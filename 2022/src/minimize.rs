@@ -0,0 +1,76 @@
+/// Delta debugging: shrinks `items` to a (1-minimal) subsequence for which `fails` still holds.
+///
+/// `fails` has to hold for the initial `items`.
+pub fn ddmin<T: Clone>(mut items: Vec<T>, mut fails: impl FnMut(&[T]) -> bool) -> Vec<T> {
+    let mut n = 2;
+
+    while items.len() >= 2 {
+        let chunk = items.len().div_ceil(n);
+        let mut reduced = false;
+
+        for start in (0..items.len()).step_by(chunk) {
+            let end = (start + chunk).min(items.len());
+
+            // Does one chunk alone still fail?
+            if fails(&items[start..end]) {
+                items = items[start..end].to_vec();
+                n = 2;
+                reduced = true;
+                break;
+            }
+
+            // Or everything but the chunk?
+            let complement: Vec<_> = items[..start]
+                .iter()
+                .chain(&items[end..])
+                .cloned()
+                .collect();
+            if fails(&complement) {
+                items = complement;
+                n = (n - 1).max(2);
+                reduced = true;
+                break;
+            }
+        }
+
+        if !reduced {
+            if n >= items.len() {
+                break;
+            }
+            n = (n * 2).min(items.len());
+        }
+    }
+
+    items
+}
+
+/// Removes lines from `input` as long as `fails` holds for what is left.
+pub fn minimize_lines(input: &str, mut fails: impl FnMut(&str) -> bool) -> String {
+    let join = |lines: &[&str]| {
+        let mut joined = lines.join("\n");
+        joined.push('\n');
+        joined
+    };
+
+    join(&ddmin(input.lines().collect(), |lines| fails(&join(lines))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ddmin_pair() {
+        let items: Vec<u32> = (1..=20).collect();
+        let min = ddmin(items, |s| s.contains(&3) && s.contains(&17));
+
+        assert_eq!(min, vec![3, 17]);
+    }
+
+    #[test]
+    fn ddmin_single() {
+        let min = ddmin(vec!['a', 'b', 'c', 'd', 'e'], |s| s.contains(&'e'));
+
+        assert_eq!(min, vec!['e']);
+    }
+}
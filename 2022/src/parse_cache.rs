@@ -0,0 +1,148 @@
+//! Parsed inputs kept next to the inputs, so running a day again on the same input (like
+//! `aoc watch` does after every change of the code) skips parsing. Days use it with
+//! `--parse-cache`.
+
+use anyhow::{Context, Result};
+use aoc_core::compress;
+use aoc_core::parse::normalize;
+use aoc_core::solver::{self, Solver};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// What the parsed input was made from, the cache is only used if all of it still matches.
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct Source {
+    /// SHA-256 of the input
+    hash: String,
+    parsed_as: String,
+}
+
+impl Source {
+    fn of<T>(input: &str) -> Self {
+        Self {
+            hash: format!("{:x}", Sha256::digest(input)),
+            parsed_as: std::any::type_name::<T>().to_owned(),
+        }
+    }
+}
+
+/// `input/day10.txt` is cached in `input/day10.parsed`.
+#[must_use]
+pub fn cache_path(input: &Path) -> PathBuf {
+    input.with_extension("parsed")
+}
+
+/// Parses the input file, or loads the result of the last parse if the input did not change since.
+///
+/// The parsed structure is stored as `MessagePack` next to the input, with the hash of the input
+/// it was parsed from. A broken or outdated cache is simply replaced.
+///
+/// # Errors
+/// If the input can not be read or parsed.
+pub fn parse_cached<T: Serialize + DeserializeOwned>(
+    input: impl AsRef<Path>,
+    parse: impl FnOnce(&str) -> Result<T>,
+) -> Result<T> {
+    let input = input.as_ref();
+    let text = normalize(
+        &compress::read(input)
+            .with_context(|| format!("Could not read input {}", input.display()))?,
+    )
+    .into_owned();
+    let source = Source::of::<T>(&text);
+    let cache = cache_path(input);
+
+    let cached = fs::read(&cache)
+        .ok()
+        .and_then(|bytes| rmp_serde::from_slice::<(Source, T)>(&bytes).ok());
+    if let Some((cached_source, parsed)) = cached {
+        if cached_source == source {
+            return Ok(parsed);
+        }
+    }
+
+    let parsed = parse(&text)?;
+
+    // Only a cache, not being able to write it is no reason to fail
+    if let Ok(bytes) = rmp_serde::to_vec(&(&source, &parsed)) {
+        let _ = fs::write(cache, bytes);
+    }
+
+    Ok(parsed)
+}
+
+/// Whether `--parse-cache` was given.
+#[must_use]
+pub fn requested(mut args: impl Iterator<Item = String>) -> bool {
+    args.any(|arg| arg == "--parse-cache")
+}
+
+/// [`solver::run`], but with `--parse-cache` the input is parsed with [`parse_cached`].
+///
+/// # Errors
+/// If the input can not be read or parsed, or solving fails.
+pub fn run<S: Solver>() -> Result<()>
+where
+    S::Input: Serialize + DeserializeOwned,
+{
+    if !requested(std::env::args().skip(1)) {
+        return solver::run::<S>();
+    }
+    solver::run_with::<S>(|source| {
+        if let Some(path) = source.path()? {
+            return parse_cached(path, S::parse);
+        }
+        let input = source.read()?;
+        S::parse(&input).map_err(|e| solver::show_source(e, &input))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn reuses_until_changed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let input = dir.join("day01.txt");
+        fs::write(&input, "1\n2\n").unwrap();
+
+        let parses = Cell::new(0);
+        let parse = |s: &str| -> Result<Vec<u32>> {
+            parses.set(parses.get() + 1);
+            Ok(s.lines().map(|l| l.parse().unwrap()).collect())
+        };
+
+        assert_eq!(parse_cached(&input, parse).unwrap(), vec![1, 2]);
+        assert_eq!(parse_cached(&input, parse).unwrap(), vec![1, 2]);
+        assert_eq!(parses.get(), 1);
+        assert!(cache_path(&input).exists());
+
+        fs::write(&input, "1\n2\n3\n").unwrap();
+        assert_eq!(parse_cached(&input, parse).unwrap(), vec![1, 2, 3]);
+        assert_eq!(parses.get(), 2);
+
+        // Saved again without a change, like an editor does
+        fs::write(&input, "1\n2\n3\n").unwrap();
+        assert_eq!(parse_cached(&input, parse).unwrap(), vec![1, 2, 3]);
+        assert_eq!(parses.get(), 2);
+
+        // Same file parsed into something else
+        let sum = parse_cached(&input, |s| Ok(s.lines().count())).unwrap();
+        assert_eq!(sum, 3);
+    }
+
+    #[test]
+    fn requested_with_flag() {
+        let args = |args: &[&str]| args.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert!(requested(
+            args(&["--part", "1", "--parse-cache"]).into_iter()
+        ));
+        assert!(!requested(args(&["--part", "1"]).into_iter()));
+    }
+}
@@ -0,0 +1,313 @@
+//! Inputs that can be committed as test fixtures without publishing the real ones.
+//! `aoc input scramble --day 5` prints one.
+
+use crate::reference::Rng;
+use std::collections::{HashMap, HashSet};
+
+/// Rewrites an input so it can be committed as a test fixture without publishing the real one.
+///
+/// Where it is easy the answers stay the same (by shuffling things the puzzle does not care about
+/// the order of, renaming or mirroring), otherwise the numbers are replaced by random ones that
+/// still make a valid input, which only keeps the structure.
+///
+/// # Panics
+/// If a rucksack of day 3 is not ASCII.
+#[must_use]
+pub fn scramble(day: u8, input: &str, rng: &mut Rng) -> String {
+    let shuffled_lines = |lines: &str, rng: &mut Rng| {
+        let mut lines: Vec<_> = lines.lines().collect();
+        rng.shuffle(&mut lines);
+        lines.join("\n")
+    };
+
+    let input_lines = input.trim_end_matches('\n');
+    let scrambled = match day {
+        1 => {
+            let mut blocks: Vec<_> = input
+                .trim_end()
+                .split("\n\n")
+                .map(|block| shuffled_lines(block, rng))
+                .collect();
+            rng.shuffle(&mut blocks);
+            blocks.join("\n\n")
+        }
+        2 | 4 => shuffled_lines(input, rng),
+        3 => {
+            // Items may move within a compartment, rucksacks within a group
+            let lines: Vec<_> = input.lines().collect();
+            let mut groups: Vec<Vec<String>> = lines
+                .chunks(3)
+                .map(|group| {
+                    let mut group: Vec<String> = group
+                        .iter()
+                        .map(|line| {
+                            let (a, b) = line.split_at(line.len() / 2);
+                            let mut a = a.as_bytes().to_vec();
+                            let mut b = b.as_bytes().to_vec();
+                            rng.shuffle(&mut a);
+                            rng.shuffle(&mut b);
+                            String::from_utf8([a, b].concat()).expect("Shuffled ASCII")
+                        })
+                        .collect();
+                    rng.shuffle(&mut group);
+                    group
+                })
+                .collect();
+            rng.shuffle(&mut groups);
+            groups.concat().join("\n")
+        }
+        5 => day05(input_lines, rng),
+        // Only whether letters are the same matters
+        6 => renamed_letters(input_lines, b'a'..=b'z', rng),
+        7 => day07(input_lines, rng),
+        8 => input_lines
+            .chars()
+            .map(|c| {
+                if c.is_ascii_digit() {
+                    char::from(b'0' + u8::try_from(rng.below(10)).unwrap())
+                } else {
+                    c
+                }
+            })
+            .collect(),
+        11 => day11(input_lines, rng),
+        12 => day12(input_lines, rng),
+        _ => scramble_numbers(input_lines, rng),
+    };
+
+    if input.ends_with('\n') {
+        scrambled + "\n"
+    } else {
+        scrambled
+    }
+}
+
+/// Maps the letters in `letters` to a shuffled order of them, leaving everything else.
+fn renamed_letters(input: &str, letters: std::ops::RangeInclusive<u8>, rng: &mut Rng) -> String {
+    let mut renamed: Vec<u8> = letters.clone().collect();
+    rng.shuffle(&mut renamed);
+    input
+        .chars()
+        .map(|c| match u8::try_from(c) {
+            Ok(b) if letters.contains(&b) => char::from(renamed[usize::from(b - letters.start())]),
+            _ => c,
+        })
+        .collect()
+}
+
+/// Renamed crates and new moves that stay within the stacks and never take more crates than a
+/// stack has.
+fn day05(input: &str, rng: &mut Rng) -> String {
+    let Some((stacks, moves)) = input.split_once("\n\n") else {
+        return scramble_numbers(input, rng);
+    };
+
+    let indices = stacks.lines().last().unwrap_or_default();
+    let mut heights = vec![0; indices.split_whitespace().count()];
+    for line in stacks.lines() {
+        for (height, cell) in heights.iter_mut().zip(line.as_bytes().chunks(4)) {
+            if cell.first() == Some(&b'[') {
+                *height += 1;
+            }
+        }
+    }
+    if heights.len() < 2 || heights.iter().all(|&h| h == 0) {
+        return scramble_numbers(input, rng);
+    }
+
+    let below = |rng: &mut Rng, n: usize| usize::try_from(rng.below(n as u64)).unwrap();
+    let moves: Vec<_> = moves
+        .lines()
+        .map(|_| {
+            let filled: Vec<_> = (0..heights.len()).filter(|&s| heights[s] > 0).collect();
+            let from = filled[below(rng, filled.len())];
+            let to = (from + 1 + below(rng, heights.len() - 1)) % heights.len();
+            let amount = 1 + below(rng, heights[from]);
+            heights[from] -= amount;
+            heights[to] += amount;
+            format!("move {amount} from {} to {}", from + 1, to + 1)
+        })
+        .collect();
+
+    format!(
+        "{}\n\n{}",
+        renamed_letters(stacks, b'A'..=b'Z', rng),
+        moves.join("\n")
+    )
+}
+
+/// A new name of as many letters for `name`, which no other name got.
+fn rename<'a>(
+    name: &'a str,
+    names: &mut HashMap<&'a str, String>,
+    taken: &mut HashSet<String>,
+    rng: &mut Rng,
+) -> String {
+    if name == "/" || name == ".." {
+        return name.to_owned();
+    }
+    if let Some(renamed) = names.get(name) {
+        return renamed.clone();
+    }
+    let renamed = loop {
+        let renamed: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    char::from(b'a' + u8::try_from(rng.below(26)).unwrap())
+                } else {
+                    c
+                }
+            })
+            .collect();
+        // Two names must not become one
+        if taken.insert(renamed.clone()) {
+            break renamed;
+        }
+    };
+    names.insert(name, renamed.clone());
+    renamed
+}
+
+/// Every file and directory renamed, the sizes and so the answers stay.
+fn day07(input: &str, rng: &mut Rng) -> String {
+    let mut names = HashMap::new();
+    let mut taken = HashSet::new();
+    let mut rename = |name, rng: &mut Rng| rename(name, &mut names, &mut taken, rng);
+
+    input
+        .lines()
+        .map(|line| {
+            if let Some(dir) = line.strip_prefix("$ cd ") {
+                format!("$ cd {}", rename(dir, rng))
+            } else if let Some(dir) = line.strip_prefix("dir ") {
+                format!("dir {}", rename(dir, rng))
+            } else if let Some((size, file)) = line
+                .split_once(' ')
+                .filter(|(size, _)| size.parse::<u64>().is_ok())
+            {
+                format!("{size} {}", rename(file, rng))
+            } else {
+                line.to_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// New items and operations, but the monkeys still throw to the same ones.
+fn day11(input: &str, rng: &mut Rng) -> String {
+    input
+        .lines()
+        .map(|line| match line.trim_start().split_once(':') {
+            Some(("Starting items" | "Operation" | "Test", _)) => scramble_numbers(line, rng),
+            _ => line.to_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The heightmap mirrored, which keeps the shortest paths.
+fn day12(input: &str, rng: &mut Rng) -> String {
+    let mut lines: Vec<String> = input.lines().map(str::to_owned).collect();
+    let (flip_rows, flip_columns) = match rng.below(3) {
+        0 => (true, false),
+        1 => (false, true),
+        _ => (true, true),
+    };
+    if flip_rows {
+        lines.reverse();
+    }
+    if flip_columns {
+        for line in &mut lines {
+            *line = line.chars().rev().collect();
+        }
+    }
+    lines.join("\n")
+}
+
+/// Replaces every number with a random one with as many digits.
+fn scramble_numbers(input: &str, rng: &mut Rng) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut in_number = false;
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            // No new leading zeroes, they would make the number shorter when parsed
+            let digit = if in_number {
+                rng.below(10)
+            } else {
+                1 + rng.below(9)
+            };
+            out.push(char::from_digit(u32::try_from(digit).unwrap(), 10).unwrap());
+            in_number = true;
+        } else {
+            out.push(c);
+            in_number = false;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input_stats::Stats;
+    use aoc_core::registry::solution;
+
+    #[test]
+    fn scramble_keeps_answers() {
+        let mut rng = Rng::new(7);
+        let sorted = |s: &str| {
+            let mut lines: Vec<_> = s.lines().collect();
+            lines.sort_unstable();
+            lines.join("\n")
+        };
+
+        let day01 = "1000\n2000\n\n4000\n\n5000\n6000\n";
+        let scrambled = scramble(1, day01, &mut rng);
+        assert_eq!(sorted(&scrambled), sorted(day01));
+        assert_eq!(scrambled.split("\n\n").count(), 3);
+        assert!(scrambled.ends_with('\n'));
+
+        let day03 =
+            "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg";
+        let scrambled = scramble(3, day03, &mut rng);
+        assert_eq!(scrambled.len(), day03.len());
+        assert!(scrambled
+            .lines()
+            .any(|l| l.len() == 18 && l[..9].contains('P')));
+    }
+
+    #[test]
+    fn scrambled_examples_solve() {
+        // Shuffled, renamed or mirrored, the others get new numbers
+        const SAME_ANSWERS: [u8; 7] = [1, 2, 3, 4, 6, 7, 12];
+
+        for day in 1..=12 {
+            // Only the days of the enabled features are built
+            let Some(solution) = solution(crate::YEAR, day) else {
+                continue;
+            };
+            let example = solution.example.expect("Every day has an example");
+            for seed in 1..=20 {
+                let scrambled = scramble(day, example, &mut Rng::new(seed));
+                assert_eq!(scrambled.lines().count(), example.lines().count());
+                let answers = solution.solve(&scrambled, &[]);
+                assert!(answers.is_ok(), "Day {day}: {answers:?}\n{scrambled}");
+                if SAME_ANSWERS.contains(&day) {
+                    assert_eq!(answers.unwrap(), solution.solve(example, &[]).unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn scramble_numbers_keeps_structure() {
+        let mut rng = Rng::new(7);
+        let input = "addx 15\naddx -11\nnoop";
+        let scrambled = scramble(10, input, &mut rng);
+
+        assert_eq!(Stats::from_input(&scrambled), Stats::from_input(input));
+        assert_ne!(scrambled, input);
+    }
+}
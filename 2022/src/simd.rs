@@ -0,0 +1,224 @@
+//! The hot loops of a few days with SSE2 or AVX2 intrinsics, the widest the CPU has is picked at
+//! runtime. Only built with the `simd` feature, without it (the default) the days keep their
+//! scalar loops, as do other architectures, so everything still builds on stable.
+
+// The loads and stores are all unaligned ones
+#![allow(clippy::cast_ptr_alignment)]
+
+use aoc_nostd::day06::is_uniq;
+
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::{
+    __m128i, __m256i, _mm256_cmpeq_epi8, _mm256_loadu_si256, _mm256_movemask_epi8, _mm256_or_si256,
+    _mm256_setzero_si256, _mm_add_epi8, _mm_and_si128, _mm_cmpeq_epi8, _mm_cmpgt_epi8,
+    _mm_loadu_si128, _mm_max_epu8, _mm_movemask_epi8, _mm_or_si128, _mm_set1_epi8,
+    _mm_setzero_si128, _mm_storeu_si128,
+};
+
+/// Where the first window of `n` distinct bytes starts, for day 6. Checks 32 (or 16) windows at
+/// once for windows of up to 16 bytes.
+#[must_use]
+pub fn first_distinct(bytes: &[u8], n: usize) -> Option<usize> {
+    #[allow(unused_mut)] // Only changes on x86_64
+    let mut start = 0;
+    #[cfg(target_arch = "x86_64")]
+    if n <= 16 {
+        let searched = if is_x86_feature_detected!("avx2") {
+            // SAFETY: The CPU has AVX2
+            unsafe { first_distinct_avx2(bytes, n) }
+        } else {
+            // SAFETY: SSE2 is part of every x86_64 CPU
+            unsafe { first_distinct_sse2(bytes, n) }
+        };
+        match searched {
+            Ok(found) => return Some(found),
+            Err(unchecked) => start = unchecked,
+        }
+    }
+
+    bytes[start..]
+        .windows(n)
+        .position(is_uniq)
+        .map(|pos| start + pos)
+}
+
+/// The first window of distinct bytes, or where the windows start that are too close to the end
+/// to be loaded as a whole vector.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+fn first_distinct_avx2(bytes: &[u8], n: usize) -> Result<usize, usize> {
+    let mut start = 0;
+    while start + n + 31 <= bytes.len() {
+        // Lane `i` of `windows[j]` is byte `j` of the window starting at `start + i`
+        let mut windows = [_mm256_setzero_si256(); 16];
+        for (j, window) in windows.iter_mut().enumerate().take(n) {
+            // SAFETY: `start + j + 32 <= bytes.len()`
+            *window =
+                unsafe { _mm256_loadu_si256(bytes.as_ptr().add(start + j).cast::<__m256i>()) };
+        }
+        let mut repeated = _mm256_setzero_si256();
+        for a in 0..n {
+            for b in a + 1..n {
+                repeated = _mm256_or_si256(repeated, _mm256_cmpeq_epi8(windows[a], windows[b]));
+            }
+        }
+
+        let distinct = !_mm256_movemask_epi8(repeated).cast_unsigned();
+        if distinct != 0 {
+            return Ok(start + distinct.trailing_zeros() as usize);
+        }
+        start += 32;
+    }
+    Err(start)
+}
+
+/// [`first_distinct_avx2`] with half as many windows at once.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+fn first_distinct_sse2(bytes: &[u8], n: usize) -> Result<usize, usize> {
+    let mut start = 0;
+    while start + n + 15 <= bytes.len() {
+        let mut windows = [_mm_setzero_si128(); 16];
+        for (j, window) in windows.iter_mut().enumerate().take(n) {
+            // SAFETY: `start + j + 16 <= bytes.len()`
+            *window = unsafe { _mm_loadu_si128(bytes.as_ptr().add(start + j).cast::<__m128i>()) };
+        }
+        let mut repeated = _mm_setzero_si128();
+        for a in 0..n {
+            for b in a + 1..n {
+                repeated = _mm_or_si128(repeated, _mm_cmpeq_epi8(windows[a], windows[b]));
+            }
+        }
+
+        let distinct = !_mm_movemask_epi8(repeated).cast_unsigned() & 0xffff;
+        if distinct != 0 {
+            return Ok(start + distinct.trailing_zeros() as usize);
+        }
+        start += 16;
+    }
+    Err(start)
+}
+
+/// The items as a bit set of their priorities, bit 0 for anything that is no item, for day 3.
+/// Turns 16 items at once into priorities.
+#[must_use]
+pub fn priority_mask(items: &[u8]) -> u64 {
+    let mut mask = 0;
+    let mut chunks = items.chunks_exact(16);
+    #[cfg(target_arch = "x86_64")]
+    for chunk in &mut chunks {
+        // SAFETY: SSE2 is part of every x86_64 CPU
+        for priority in unsafe { priorities_sse2(chunk) } {
+            mask |= 1 << priority;
+        }
+    }
+    for &item in chunks.remainder() {
+        mask |= 1 << aoc_nostd::day03::priority(item).unwrap_or(0);
+    }
+    mask
+}
+
+/// `a..=z` to `1..=26`, `A..=Z` to `27..=52`, anything else to 0, of 16 bytes.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+fn priorities_sse2(chunk: &[u8]) -> [u8; 16] {
+    assert!(chunk.len() >= 16);
+    // SAFETY: There are 16 bytes to read
+    let items = unsafe { _mm_loadu_si128(chunk.as_ptr().cast::<__m128i>()) };
+    // Bytes above 127 are negative and land in neither range
+    let within = |low: u8, high: u8| {
+        _mm_and_si128(
+            _mm_cmpgt_epi8(items, _mm_set1_epi8((low - 1).cast_signed())),
+            _mm_cmpgt_epi8(_mm_set1_epi8((high + 1).cast_signed()), items),
+        )
+    };
+    let lower = _mm_and_si128(within(b'a', b'z'), _mm_add_epi8(items, _mm_set1_epi8(-96)));
+    let upper = _mm_and_si128(within(b'A', b'Z'), _mm_add_epi8(items, _mm_set1_epi8(-38)));
+
+    let mut priorities = [0; 16];
+    // SAFETY: There are 16 bytes to write
+    unsafe { _mm_storeu_si128(priorities.as_mut_ptr().cast(), _mm_or_si128(lower, upper)) };
+    priorities
+}
+
+/// One row of the sweep of day 8: which trees are taller than all before them in their column,
+/// and the new tallest ones. Heights have to be below 128.
+pub fn sweep_row(tallest: &mut [u8], heights: &[u8], seeable: &mut [bool]) {
+    let len = tallest.len().min(heights.len()).min(seeable.len());
+    #[cfg(target_arch = "x86_64")]
+    // SAFETY: SSE2 is part of every x86_64 CPU
+    let done = unsafe { sweep_row_sse2(&mut tallest[..len], &heights[..len], &mut seeable[..len]) };
+    #[cfg(not(target_arch = "x86_64"))]
+    let done = 0;
+
+    for i in done..len {
+        seeable[i] = heights[i] > tallest[i];
+        tallest[i] = tallest[i].max(heights[i]);
+    }
+}
+
+/// Sweeps 16 columns at once, returns how many it did, the rest are too few for a vector.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+fn sweep_row_sse2(tallest: &mut [u8], heights: &[u8], seeable: &mut [bool]) -> usize {
+    let mut done = 0;
+    while done + 16 <= tallest.len() {
+        // SAFETY: All three have 16 more elements, a `bool` is a byte that is 0 or 1
+        unsafe {
+            let tall = _mm_loadu_si128(tallest.as_ptr().add(done).cast::<__m128i>());
+            let height = _mm_loadu_si128(heights.as_ptr().add(done).cast::<__m128i>());
+            let taller = _mm_and_si128(_mm_cmpgt_epi8(height, tall), _mm_set1_epi8(1));
+            _mm_storeu_si128(seeable.as_mut_ptr().add(done).cast(), taller);
+            _mm_storeu_si128(
+                tallest.as_mut_ptr().add(done).cast(),
+                _mm_max_epu8(tall, height),
+            );
+        }
+        done += 16;
+    }
+    done
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reference::Rng;
+
+    #[test]
+    fn finds_distinct_windows() {
+        let mut rng = Rng::new(6);
+        for len in [0, 20, 40, 100, 300] {
+            let bytes: Vec<u8> = (0..len)
+                .map(|_| b'a' + u8::try_from(rng.below(6)).unwrap())
+                .collect();
+            for n in [1, 4, 6, 14] {
+                let scalar = bytes.windows(n).position(is_uniq);
+                assert_eq!(first_distinct(&bytes, n), scalar, "{n} {bytes:?}");
+            }
+        }
+        let marker = b"mjqjpqmgbljsphdztnvjfqwrcgsmlbmjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        assert_eq!(first_distinct(marker, 4), Some(3));
+        assert_eq!(first_distinct(marker, 14), Some(5));
+    }
+
+    #[test]
+    fn priority_masks() {
+        let items = b"vJrwpWtwJgWrhcsFMMfFFhFpz\xffZ";
+        let scalar = items.iter().fold(0, |mask, &item| {
+            mask | 1 << aoc_nostd::day03::priority(item).unwrap_or(0)
+        });
+        assert_eq!(priority_mask(items), scalar);
+    }
+
+    #[test]
+    fn sweeps_rows() {
+        let heights: Vec<u8> = (0..40u8).map(|i| i % 10 * 7 % 10).collect();
+        let mut tallest = vec![4; 40];
+        let mut seeable = vec![false; 40];
+        sweep_row(&mut tallest, &heights, &mut seeable);
+        for i in 0..40 {
+            assert_eq!(seeable[i], heights[i] > 4, "{i}");
+            assert_eq!(tallest[i], heights[i].max(4), "{i}");
+        }
+    }
+}
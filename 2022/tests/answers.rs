@@ -0,0 +1,38 @@
+//! Runs every day on its real input and compares with the known answers in `answers.toml`.
+//!
+//! Inputs may be compressed, or encrypted when the key is there. Days without an input or known
+//! answers are skipped, so this passes on a fresh checkout. Store the answers of a day with
+//! `aoc run --day <day> --bless`.
+
+use aoc_core::days::{crate_binaries, parse_answers};
+use aoc_core::fetch::{input_path, INPUT_DIR};
+use aoc_core::verify::{Answers, Verification, ANSWERS_FILE};
+use aoc_core::{compress, crypt};
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn known_answers() {
+    let known = Answers::load(Path::new(ANSWERS_FILE)).unwrap();
+
+    let days = crate_binaries(Path::new(env!("CARGO_MANIFEST_DIR")), aoc2022::YEAR).unwrap();
+    let mut wrong = vec![];
+    for (day, exe) in days {
+        let input = input_path(Path::new(INPUT_DIR), day);
+        crypt::decrypt_missing(&input).unwrap();
+        if known.day(day).is_none() || compress::find(&input).is_none() {
+            continue;
+        }
+
+        // The days read their input relative to the package root, which is where cargo runs us
+        let out = Command::new(exe).output().unwrap();
+        assert!(out.status.success(), "day {day} failed with {}", out.status);
+
+        let got = parse_answers(&String::from_utf8_lossy(&out.stdout));
+        if let Verification::Wrong(mismatches) = known.verify(day, &got) {
+            wrong.extend(mismatches.iter().map(|m| format!("day {day} {m}")));
+        }
+    }
+
+    assert!(wrong.is_empty(), "{}", wrong.join("\n"));
+}
@@ -0,0 +1,17 @@
+//! Runs every day on its real input and checks that it stays within its budget in `budgets.toml`.
+//!
+//! Only with `--features slow-tests`, and meant for `--release`. Days without an input are skipped.
+#![cfg(feature = "slow-tests")]
+
+use aoc_core::budget::{Budgets, BUDGETS_FILE};
+use aoc_core::days::crate_binaries;
+use std::path::Path;
+
+#[test]
+fn within_budget() {
+    // The days read their input relative to the package root, which is where cargo runs us
+    let budgets = Budgets::load(Path::new(BUDGETS_FILE)).unwrap();
+    let days = crate_binaries(Path::new(env!("CARGO_MANIFEST_DIR")), aoc2022::YEAR).unwrap();
+    let over = budgets.over_budget(&days, !cfg!(debug_assertions));
+    assert!(over.is_empty(), "{}", over.join("\n"));
+}
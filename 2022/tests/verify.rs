@@ -0,0 +1,39 @@
+//! `--verify` makes a day fail when its answers differ from the known ones, also for the days
+//! that can not use `solver::run`.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Runs day 7 with `--verify` on its example, as if that were the real input with `answers`.
+fn verify_day07(answers: &str) -> Output {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("input")).unwrap();
+    let example = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/day07.txt");
+    fs::copy(example, dir.path().join("input/day07.txt")).unwrap();
+    fs::write(dir.path().join("answers.toml"), answers).unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_day07"))
+        .arg("--verify")
+        .current_dir(dir.path())
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn right_answers_pass() {
+    let out = verify_day07("[day07]\n\"Part 1\" = \"95437\"\n\"Part 2\" = \"24933642\"\n");
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+}
+
+#[test]
+fn wrong_answer_fails() {
+    let out = verify_day07("[day07]\n\"Part 1\" = \"1\"\n\"Part 2\" = \"24933642\"\n");
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("Part 1: expected 1, got 95437"), "{stderr}");
+}
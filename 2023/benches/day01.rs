@@ -0,0 +1,20 @@
+//! Part 1 of day 1, which looks for the digits of every line 8 bytes at a time from both ends,
+//! against its first version, which decoded chars and parsed the digits. Run with
+//! `cargo bench --bench day01`, criterion keeps its reports in `target/criterion`.
+
+use aoc2023::day01::{part1, part1_chars, EXAMPLE};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+fn part1_scans(c: &mut Criterion) {
+    // About a megabyte, there is no generator for day 1 and the real inputs are too small to tell
+    let input = EXAMPLE.repeat((1 << 20) / EXAMPLE.len());
+
+    let mut group = c.benchmark_group("day01/part1");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    group.bench_function("words", |b| b.iter(|| part1(black_box(&input)).unwrap()));
+    group.bench_function("chars", |b| b.iter(|| part1_chars(black_box(&input))));
+    group.finish();
+}
+
+criterion_group!(benches, part1_scans);
+criterion_main!(benches);
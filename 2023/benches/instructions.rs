@@ -0,0 +1,25 @@
+//! Instruction counts of every day, measured with callgrind.
+//!
+//! Unlike wall clock times these are the same on every run, so a refactor that changes them is a
+//! real change. Needs valgrind and `cargo install iai-callgrind-runner`, then
+//! `cargo bench --bench instructions`.
+
+use aoc_core::days::crate_binaries;
+use iai_callgrind::{binary_benchmark_group, main, Arg, BinaryBenchmarkGroup, Run};
+use std::path::Path;
+
+fn setup_days(group: &mut BinaryBenchmarkGroup) {
+    let days = crate_binaries(Path::new(env!("CARGO_MANIFEST_DIR")), aoc2023::YEAR).unwrap();
+    for (day, exe) in days {
+        // The days read their input relative to the package root, which is where cargo runs us
+        let id = format!("day{day:02}");
+        group.bench(Run::with_cmd(&*exe.to_string_lossy(), Arg::empty(&id)));
+    }
+}
+
+binary_benchmark_group!(
+    name = days;
+    benchmark = |group: &mut BinaryBenchmarkGroup| setup_days(group)
+);
+
+main!(binary_benchmark_groups = days);
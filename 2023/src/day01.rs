@@ -0,0 +1,279 @@
+use crate::YEAR;
+use anyhow::{bail, Context, Result};
+use aoc_core::answer::Answer;
+use aoc_core::aoc;
+use aoc_core::complexity::measure;
+use aoc_core::lines::{map_lines, par_map_lines};
+use aoc_core::solver::Solver;
+use memchr::memchr_iter;
+use std::iter::once;
+
+/// The calibration document, every line is looked at on its own.
+pub type Input = String;
+
+/// Bytes of `word` that are ASCII digits get their high bit set, every other bit is clear. Unlike
+/// the usual "has a byte below n" trick no borrow crosses into the next byte, so every byte is
+/// exact.
+const fn digit_bytes(word: u64) -> u64 {
+    const LOW: u64 = u64::from_ne_bytes([0x01; 8]);
+    const HIGH: u64 = LOW << 7;
+
+    let at_least_0 = (word | HIGH) - LOW * b'0' as u64;
+    let above_9 = (word | HIGH) - LOW * (b'9' + 1) as u64;
+    at_least_0 & !above_9 & !word & HIGH
+}
+
+fn word(chunk: &[u8]) -> u64 {
+    u64::from_le_bytes(chunk.try_into().expect("Chunks of 8 bytes"))
+}
+
+/// Looks at 8 bytes at once from the start, like `memchr` does for one byte.
+fn first_digit(line: &[u8]) -> Option<u8> {
+    let mut chunks = line.chunks_exact(8);
+    for chunk in chunks.by_ref() {
+        let digits = digit_bytes(word(chunk));
+        if digits != 0 {
+            return Some(chunk[digits.trailing_zeros() as usize / 8]);
+        }
+    }
+    chunks.remainder().iter().copied().find(u8::is_ascii_digit)
+}
+
+/// Looks at 8 bytes at once from the end, like `memrchr` does for one byte.
+fn last_digit(line: &[u8]) -> Option<u8> {
+    let mut chunks = line.rchunks_exact(8);
+    for chunk in chunks.by_ref() {
+        let digits = digit_bytes(word(chunk));
+        if digits != 0 {
+            return Some(chunk[7 - digits.leading_zeros() as usize / 8]);
+        }
+    }
+    chunks.remainder().iter().copied().rfind(u8::is_ascii_digit)
+}
+
+/// # Errors
+/// If a line has no digit.
+pub fn part1(input: &str) -> Result<usize> {
+    let bytes = input.as_bytes();
+    let mut start = 0;
+    let mut sum = 0;
+
+    // Jump from newline to newline instead of decoding chars, the digits are plain ASCII anyway,
+    // and search every line for its digits a word at a time from both ends
+    for end in memchr_iter(b'\n', bytes).chain(once(bytes.len())) {
+        let line = &bytes[start..end];
+        start = end + 1;
+
+        if line.is_empty() {
+            continue;
+        }
+
+        let (Some(first), Some(last)) = (first_digit(line), last_digit(line)) else {
+            bail!("{:?} has no digit", String::from_utf8_lossy(line));
+        };
+
+        sum += usize::from(first - b'0') * 10 + usize::from(last - b'0');
+    }
+
+    Ok(sum)
+}
+
+/// The first version of [`part1`], decoding chars and parsing the digits, kept to benchmark
+/// against: `cargo bench --bench day01`.
+///
+/// # Panics
+/// If a line has no digit.
+#[must_use]
+pub fn part1_chars(input: &str) -> usize {
+    input
+        .lines()
+        .map(|line| {
+            let first = line.chars().find(char::is_ascii_digit).unwrap();
+            let last = line.chars().rfind(char::is_ascii_digit).unwrap();
+
+            let num = format!("{first}{last}");
+            num.parse::<usize>().unwrap()
+        })
+        .sum()
+}
+
+/// # Errors
+/// If a line has no digit, spelled out or not.
+pub fn part2(input: &str) -> Result<usize> {
+    Ok(par_map_lines(input, part2_line)?.into_iter().sum())
+}
+
+fn part2_line(line: &str) -> Result<usize> {
+    let digits = [
+        "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "zero", "one", "two", "three", "four",
+        "five", "six", "seven", "eight", "nine",
+    ];
+
+    let first = find_any(&digits, line, Dir::Forward).context("No digit in line")?;
+    let first = first % 10;
+
+    let last = find_any(&digits, line, Dir::Reverse).context("No digit in line")?;
+    let last = last % 10;
+
+    Ok(first * 10 + last)
+}
+
+#[derive(Copy, Clone)]
+enum Dir {
+    Forward,
+    Reverse,
+}
+
+fn find_any(needles: &[&str; 20], mut haystack: &str, dir: Dir) -> Option<usize> {
+    loop {
+        for (idx, d) in needles.iter().enumerate() {
+            match dir {
+                Dir::Forward if haystack.starts_with(d) => return Some(idx),
+                Dir::Reverse if haystack.ends_with(d) => return Some(idx),
+                _ => {}
+            }
+        }
+        haystack = match dir {
+            Dir::Forward => haystack.get(1..)?,
+            Dir::Reverse => haystack.get(..haystack.len() - 1)?,
+        };
+    }
+}
+
+/// Times part 2 with and without threads on growing inputs, to see from how many lines on the
+/// parallel version is worth it.
+///
+/// # Panics
+/// Never, every generated line has a digit.
+pub fn crossover() {
+    let lines = [
+        "two1nine",
+        "eightwothree",
+        "abcone2threexyz",
+        "4nineeightseven2",
+    ];
+    let generate = |n: u64| -> String {
+        lines
+            .iter()
+            .cycle()
+            .take(usize::try_from(n).unwrap())
+            .flat_map(|l| [*l, "\n"])
+            .collect()
+    };
+    let sizes: Vec<u64> = (0..16).map(|i| 1 << i).collect();
+
+    let seq = measure(sizes.iter().copied(), 5, generate, |input| {
+        map_lines(input, part2_line).unwrap();
+    });
+    let par = measure(sizes.iter().copied(), 5, generate, |input| {
+        par_map_lines(input, part2_line).unwrap();
+    });
+
+    println!("{:>8} {:>12} {:>12}", "lines", "sequential", "parallel");
+    for (s, p) in seq.iter().zip(&par) {
+        println!("{:>8} {:>12.2?} {:>12.2?}", s.n, s.time, p.time);
+    }
+    match seq.iter().zip(&par).find(|(s, p)| p.time < s.time) {
+        Some((s, _)) => println!("Parallel is faster from {} lines on", s.n),
+        None => println!("Parallel never got faster"),
+    }
+}
+
+/// The example of the puzzle text.
+pub const EXAMPLE: &str = include_str!("../examples/day01.txt");
+
+#[aoc(year = 2023, day = 1)]
+pub struct Day01;
+
+impl Solver for Day01 {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 1;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Input) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn t_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 142);
+        assert_eq!(part1(EXAMPLE.trim_end()).unwrap(), 142);
+        assert!(part1("one\n").is_err());
+    }
+
+    #[test]
+    fn part1_matches_char_scan() {
+        let input = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+9
+x0y
+77abc12
+°ab°cdefgh1ijklmnopq°
+abcdefgh2abcdefgh3abcdefgh
+12345678
+";
+
+        assert_eq!(part1(input).unwrap(), part1_chars(input));
+    }
+
+    #[test]
+    fn digits_in_words() {
+        let all: Vec<u8> = (0..=255).collect();
+        for window in all.windows(8) {
+            let expected = window.iter().fold(0, |mask, &b| {
+                (mask >> 8) | (u64::from(b.is_ascii_digit()) << 63)
+            });
+            assert_eq!(digit_bytes(word(window)), expected, "{window:?}");
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn finds_digits(line in "[a-z°0-9]{0,40}") {
+            let bytes = line.as_bytes();
+            prop_assert_eq!(
+                first_digit(bytes),
+                bytes.iter().copied().find(u8::is_ascii_digit)
+            );
+            prop_assert_eq!(
+                last_digit(bytes),
+                bytes.iter().copied().rfind(u8::is_ascii_digit)
+            );
+        }
+    }
+
+    #[test]
+    fn t_part2() {
+        // Part 2 has an example of its own, with the digits spelled out
+        let input = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+";
+
+        assert_eq!(part2_line("two1nine").unwrap(), 29);
+        assert_eq!(part2(input).unwrap(), 281);
+        assert!(part2("one\nxyz\n").is_err());
+    }
+}
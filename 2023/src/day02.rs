@@ -0,0 +1,267 @@
+use crate::YEAR;
+use anyhow::{anyhow, bail, Result};
+use aoc_core::answer::Answer;
+use aoc_core::aoc;
+use aoc_core::dump;
+use aoc_core::solver::Solver;
+use arbitrary::{Arbitrary, Unstructured};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[must_use]
+pub fn part1(games: &[Game]) -> u32 {
+    let red = 12;
+    let green = 13;
+    let blue = 14;
+
+    games
+        .iter()
+        .filter(|g| {
+            g.rounds
+                .iter()
+                .all(|r| r.red <= red && r.green <= green && r.blue <= blue)
+        })
+        .map(|game| game.id)
+        .sum()
+}
+
+#[must_use]
+pub fn part2(games: &[Game]) -> u32 {
+    games
+        .iter()
+        .map(Game::min_stones)
+        .map(|Round { red, green, blue }| red * green * blue)
+        .sum()
+}
+
+/// # Errors
+/// If a game is malformed.
+pub fn parse_games(input: &str) -> Result<Vec<Game>> {
+    input.lines().map(Game::from_str).collect()
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Game {
+    id: u32,
+    rounds: Vec<Round>,
+}
+
+impl Game {
+    fn min_stones(&self) -> Round {
+        self.rounds
+            .iter()
+            .fold(Round::default(), |acc, round| Round {
+                red: acc.red.max(round.red),
+                green: acc.green.max(round.green),
+                blue: acc.blue.max(round.blue),
+            })
+    }
+}
+
+impl FromStr for Game {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, rounds) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Weird game {s:?}"))?;
+        let start = start.trim_start_matches("Game ");
+        let id = start.parse()?;
+
+        let rounds = rounds
+            .split(';')
+            .map(Round::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { id, rounds })
+    }
+}
+
+/// A game has at least one round, there is nothing to write after `Game <id>:` otherwise.
+impl<'a> Arbitrary<'a> for Game {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let id = u.arbitrary()?;
+        let mut rounds = vec![u.arbitrary()?];
+        rounds.extend(
+            u.arbitrary_iter()?
+                .collect::<arbitrary::Result<Vec<Round>>>()?,
+        );
+        Ok(Self { id, rounds })
+    }
+}
+
+impl Display for Game {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let rounds: Vec<_> = self.rounds.iter().map(Round::to_string).collect();
+        write!(f, "Game {}: {}", self.id, rounds.join("; "))
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Default, Arbitrary, Serialize, Deserialize)]
+pub struct Round {
+    red: u32,
+    green: u32,
+    blue: u32,
+}
+
+impl FromStr for Round {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut r = None;
+        let mut g = None;
+        let mut b = None;
+
+        for elem in s.split(',') {
+            let (num, name) = elem
+                .trim()
+                .split_once(' ')
+                .ok_or_else(|| anyhow!("Could not split element: {elem}"))?;
+            let num = num.parse()?;
+            let old = match name {
+                "red" => r.replace(num),
+                "green" => g.replace(num),
+                "blue" => b.replace(num),
+                other => bail!("Weird color: {other}"),
+            };
+            if old.is_some() {
+                bail!("Double color: {name}");
+            }
+        }
+
+        Ok(Self {
+            red: r.unwrap_or_default(),
+            green: g.unwrap_or_default(),
+            blue: b.unwrap_or_default(),
+        })
+    }
+}
+
+/// Leaves out colors that were not shown, but a round shows at least one.
+impl Display for Round {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let shown: Vec<_> = [
+            (self.red, "red"),
+            (self.green, "green"),
+            (self.blue, "blue"),
+        ]
+        .into_iter()
+        .filter(|&(n, _)| n > 0)
+        .map(|(n, color)| format!("{n} {color}"))
+        .collect();
+        if shown.is_empty() {
+            write!(f, "0 red")
+        } else {
+            write!(f, "{}", shown.join(", "))
+        }
+    }
+}
+
+/// The example of the puzzle text.
+pub const EXAMPLE: &str = include_str!("../examples/day02.txt");
+
+#[aoc(year = 2023, day = 2)]
+pub struct Day02;
+
+impl Solver for Day02 {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 2;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Vec<Game>> {
+        parse_games(input)
+    }
+
+    fn part1(input: &Vec<Game>) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Vec<Game>) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+
+    fn dump(input: &Vec<Game>) -> Result<String> {
+        dump::json(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    #[test]
+    fn parsing() {
+        let games = parse_games(EXAMPLE).unwrap();
+
+        assert_eq!(
+            games[1],
+            Game {
+                id: 2,
+                rounds: vec![
+                    Round {
+                        red: 0,
+                        green: 2,
+                        blue: 1,
+                    },
+                    Round {
+                        red: 1,
+                        green: 3,
+                        blue: 4,
+                    },
+                    Round {
+                        red: 0,
+                        green: 1,
+                        blue: 1,
+                    }
+                ]
+            }
+        );
+
+        assert_eq!(part1(&games), 8);
+        assert_eq!(part2(&games), 2286);
+    }
+
+    #[rstest]
+    #[case("1 red", Round { red: 1, green: 0, blue: 0})]
+    #[case("1 red, 2 blue", Round { red: 1, green: 0, blue: 2})]
+    #[case("1 green, 22 blue, 123 red", Round { red: 123, green: 1, blue: 22})]
+    fn parse_game(#[case] input: &str, #[case] expected: Round) {
+        assert_eq!(input.parse::<Round>().unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", Round { red: 4, green: 2, blue: 6})]
+    #[case("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue", Round { red: 1, green: 3, blue: 4})]
+    #[case("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red", Round { red: 20, green: 13, blue: 6})]
+    #[case("Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red", Round { red: 14, green: 3, blue: 15})]
+    #[case("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green", Round { red: 6, green: 3, blue: 2})]
+    fn min_stones(#[case] game: &str, #[case] expected: Round) {
+        let game: Game = game.parse().unwrap();
+
+        assert_eq!(game.min_stones(), expected);
+    }
+
+    fn games() -> impl Strategy<Value = Game> {
+        any::<Vec<u8>>()
+            .prop_map(|bytes| Game::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap())
+    }
+
+    proptest! {
+        #[test]
+        fn game_roundtrip(game in games()) {
+            prop_assert_eq!(game.to_string().parse::<Game>().unwrap(), game);
+        }
+
+        #[test]
+        fn game_near_miss(s in "(Game )?-?[0-9]{0,11}:?( ?[0-9x]{0,11} (red|green|blue|pink)?[,;]?){0,6}") {
+            if let Ok(game) = s.parse::<Game>() {
+                prop_assert_eq!(game.to_string().parse::<Game>().unwrap(), game);
+            }
+        }
+    }
+}
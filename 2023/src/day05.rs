@@ -0,0 +1,489 @@
+use crate::YEAR;
+use anyhow::{bail, Context};
+use aoc_core::answer::Answer;
+use aoc_core::aoc;
+use aoc_core::cancel::Cancel;
+use aoc_core::checkpoint::Checkpoint;
+use aoc_core::dot::Graph;
+use aoc_core::dump;
+use aoc_core::error::AocError;
+use aoc_core::parse::{blocks, lines_to, numbers, At, Span};
+use aoc_core::solver::Solver;
+use arbitrary::{Arbitrary, Unstructured};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use tracing::trace;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+struct Range {
+    src_start: u32,
+    dst_start: u32,
+    len: u32,
+}
+
+impl Range {
+    fn try_map(self, src: u32) -> Option<u32> {
+        let offset = src.checked_sub(self.src_start)?;
+        if offset >= self.len {
+            return None;
+        }
+
+        Some(self.dst_start + offset)
+    }
+}
+
+impl FromStr for Range {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [dst_start, src_start, len] = numbers(s)?[..] else {
+            bail!(Span::new(s, format!("range kapott: {s:?}")));
+        };
+
+        Ok(Self {
+            src_start,
+            dst_start,
+            len,
+        })
+    }
+}
+
+/// Neither end of a range goes past `u32::MAX`, which real almanacs never do.
+impl<'a> Arbitrary<'a> for Range {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let src_start: u32 = u.arbitrary()?;
+        let dst_start: u32 = u.arbitrary()?;
+        let len = u.int_in_range(0..=u32::MAX - src_start.max(dst_start))?;
+        Ok(Self {
+            src_start,
+            dst_start,
+            len,
+        })
+    }
+}
+
+impl Display for Range {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.dst_start, self.src_start, self.len)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Map {
+    from: String,
+    to: String,
+    ranges: Vec<Range>,
+}
+
+impl Map {
+    fn map(&self, src: u32) -> u32 {
+        let idx = self
+            .ranges
+            .binary_search_by_key(&src, |r| r.src_start)
+            .unwrap_or_else(|e| e.saturating_sub(1));
+
+        self.ranges
+            .get(idx)
+            .and_then(|r| r.try_map(src))
+            .unwrap_or(src)
+    }
+}
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+/// Ranges are sorted like [`Map::from_str`] leaves them, but may overlap.
+impl<'a> Arbitrary<'a> for Map {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut ranges: Vec<Range> = u.arbitrary()?;
+        ranges.sort_by_key(|r| r.src_start);
+        Ok(Self {
+            from: u.choose(&CATEGORIES)?.to_string(),
+            to: u.choose(&CATEGORIES)?.to_string(),
+            ranges,
+        })
+    }
+}
+
+impl FromStr for Map {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (header, ranges) = s.split_once('\n').at(s, || "no header")?;
+        let header_parts = header.split(&['-', ' ']).collect::<Vec<_>>();
+        let [from, "to", to, "map:"] = header_parts.as_slice() else {
+            bail!(Span::new(header, format!("header kapott: {header:?}")));
+        };
+        let mut ranges: Vec<Range> = lines_to(ranges)?;
+
+        ranges.sort_by_key(|r| r.src_start);
+
+        Ok(Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            ranges,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Input {
+    seeds: Vec<u32>,
+    maps: Vec<Map>,
+}
+
+impl Input {
+    /// Follows the maps, which [`Input::from_str`] checked to lead on from `seed`.
+    fn location(&self, seed: u32) -> u32 {
+        let mut val = seed;
+        for map in &self.maps {
+            val = map.map(val);
+        }
+
+        val
+    }
+
+    fn seed_ranges(&self) -> Result<Vec<std::ops::Range<u32>>, AocError> {
+        self.seeds
+            .chunks_exact(2)
+            .map(|ch| {
+                let end = ch[0].checked_add(ch[1]).ok_or_else(|| {
+                    AocError::Overflow(format!("Seeds {} + {} do not fit a u32", ch[0], ch[1]))
+                })?;
+                Ok(ch[0]..end)
+            })
+            .collect()
+    }
+
+    /// The categories the maps lead through for `--dot`, with how many ranges each map has.
+    #[must_use]
+    pub fn map_chain(&self) -> Graph {
+        let mut graph = Graph::new("day05");
+        graph.node("seed", format!("seed\n{} numbers", self.seeds.len()));
+        for map in &self.maps {
+            graph.node(&map.to, &map.to);
+            let ranges = format!("{} ranges", map.ranges.len());
+            graph.edge(&map.from, &map.to, Some(&ranges));
+        }
+        graph
+    }
+}
+
+/// Seeds come in pairs and the maps lead from `seed` to wherever, like [`Input::location`]
+/// expects.
+impl<'a> Arbitrary<'a> for Input {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let seeds: Vec<[u32; 2]> = u.arbitrary()?;
+        let mut maps: Vec<Map> = u.arbitrary()?;
+        let mut from = "seed".to_string();
+        for map in &mut maps {
+            map.from.clone_from(&from);
+            from.clone_from(&map.to);
+        }
+        Ok(Self {
+            seeds: seeds.concat(),
+            maps,
+        })
+    }
+}
+
+impl FromStr for Input {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut blocks = blocks(s);
+
+        let seeds_block = blocks.next().context("no seeds")?;
+        let seeds: Vec<u32> = numbers(seeds_block)?;
+        if !seeds.len().is_multiple_of(2) {
+            bail!(Span::new(
+                seeds_block,
+                format!("{} seeds do not make pairs for part 2", seeds.len())
+            ));
+        }
+
+        let mut maps = Vec::new();
+        let mut from = "seed".to_owned();
+        for block in blocks {
+            let map: Map = block.parse()?;
+            if map.from != from {
+                let header = block.lines().next().unwrap_or(block);
+                bail!(Span::new(
+                    header,
+                    format!("map from {} does not follow the one to {from}", map.from)
+                ));
+            }
+            from.clone_from(&map.to);
+            maps.push(map);
+        }
+
+        Ok(Self { seeds, maps })
+    }
+}
+
+/// How far the brute force of part 2 got
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+struct Progress {
+    range: usize,
+    next_seed: u32,
+    min_location: u32,
+}
+
+/// # Errors
+/// [`AocError::NoSolution`] if there are no seeds.
+pub fn part1(input: &Input) -> Result<u32, AocError> {
+    input
+        .seeds
+        .iter()
+        .map(|seed| input.location(*seed))
+        .min()
+        .ok_or_else(|| AocError::NoSolution("There are no seeds".to_owned()))
+}
+
+/// Tries every seed, in chunks so it can be resumed from the `checkpoint` and stopped by `cancel`.
+///
+/// # Errors
+/// [`AocError::NoSolution`] if there are no seeds, if a seed range does not fit a `u32`, the
+/// checkpoint can not be written or it was cancelled.
+pub fn part2(input: &Input, checkpoint: &mut Checkpoint, cancel: &Cancel) -> anyhow::Result<u32> {
+    const CHUNK: u32 = 1 << 20;
+
+    let ranges = input.seed_ranges()?;
+    if ranges.iter().all(std::ops::Range::is_empty) {
+        return Err(AocError::NoSolution("There are no seeds".to_owned()).into());
+    }
+    let mut progress = checkpoint.resume()?.unwrap_or(Progress {
+        range: 0,
+        next_seed: ranges.first().map_or(0, |r| r.start),
+        min_location: u32::MAX,
+    });
+
+    while let Some(range) = ranges.get(progress.range) {
+        let end = range.end.min(progress.next_seed.saturating_add(CHUNK));
+        progress.min_location = (progress.next_seed..end)
+            .map(|seed| input.location(seed))
+            .fold(progress.min_location, u32::min);
+
+        if end == range.end {
+            progress.range += 1;
+            progress.next_seed = ranges.get(progress.range).map_or(0, |r| r.start);
+        } else {
+            progress.next_seed = end;
+        }
+
+        trace!(?progress, "Searched up to the next seed");
+        checkpoint.tick(&progress)?;
+
+        if let Err(cancelled) = cancel.check(|| {
+            format!(
+                "lowest location so far: {} (at seed {})",
+                progress.min_location, progress.next_seed
+            )
+        }) {
+            // Whatever was done so far does not need to be redone
+            checkpoint.save(&progress)?;
+            return Err(cancelled.into());
+        }
+    }
+
+    Ok(progress.min_location)
+}
+
+/// The example of the puzzle text.
+pub const EXAMPLE: &str = include_str!("../examples/day05.txt");
+
+#[aoc(year = 2023, day = 5)]
+pub struct Day05;
+
+impl Solver for Day05 {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 5;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Input;
+
+    fn parse(input: &str) -> anyhow::Result<Input> {
+        input.parse()
+    }
+
+    fn part1(input: &Input) -> anyhow::Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Input) -> anyhow::Result<Answer> {
+        Ok(part2(input, &mut Checkpoint::default(), &Cancel::start())?.into())
+    }
+
+    fn dump(input: &Input) -> anyhow::Result<String> {
+        dump::json(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn parse() {
+        let parsed: Input = EXAMPLE.parse().unwrap();
+
+        assert_eq!(parsed.seeds, vec![79, 14, 55, 13]);
+        assert_eq!(parsed.maps[0].from, "seed");
+        assert_eq!(parsed.maps[0].to, "soil");
+        assert_eq!(parsed.maps[0].ranges[1].dst_start, 50);
+        assert_eq!(parsed.maps[0].ranges[1].src_start, 98);
+        assert_eq!(parsed.maps[0].ranges[1].len, 2);
+
+        assert_eq!(parsed.maps[0].map(79), 81);
+        assert_eq!(parsed.maps[0].map(14), 14);
+        assert_eq!(parsed.maps[0].map(55), 57);
+        assert_eq!(parsed.maps[0].map(13), 13);
+
+        assert_eq!(parsed.maps[2].map(53), 49);
+
+        assert_eq!(parsed.location(79), 82);
+
+        assert_eq!(parsed.maps[0].map(14), 14);
+        assert_eq!(parsed.maps[1].map(14), 53);
+        assert_eq!(parsed.maps[2].map(53), 49);
+        assert_eq!(parsed.maps[3].map(49), 42);
+        assert_eq!(parsed.maps[4].map(42), 42);
+        assert_eq!(parsed.maps[5].map(42), 43);
+        assert_eq!(parsed.maps[6].map(43), 43);
+
+        assert_eq!(parsed.location(55), 86);
+        assert_eq!(parsed.location(13), 35);
+    }
+
+    #[test]
+    fn dot_chain() {
+        let input: Input = EXAMPLE.parse().unwrap();
+        let graph = input.map_chain();
+        assert_eq!((graph.node_count(), graph.edge_count()), (8, 7));
+        let dot = graph.to_string();
+        assert!(
+            dot.contains(r#""seed" -> "soil" [label="2 ranges"];"#),
+            "{dot}"
+        );
+    }
+
+    #[test]
+    fn example() {
+        let input: Input = EXAMPLE.parse().unwrap();
+
+        assert_eq!(part1(&input), Ok(35));
+        assert_eq!(
+            part2(&input, &mut Checkpoint::default(), &Cancel::default()).unwrap(),
+            46
+        );
+    }
+
+    #[test]
+    fn no_seeds() {
+        let mut input: Input = EXAMPLE.parse().unwrap();
+        input.seeds.clear();
+
+        assert!(matches!(part1(&input), Err(AocError::NoSolution(_))));
+        let err = part2(&input, &mut Checkpoint::default(), &Cancel::default()).unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(AocError::NoSolution(_))));
+    }
+
+    #[test]
+    fn broken_inputs() {
+        let odd = EXAMPLE.replacen("seeds: 79 14 55 13", "seeds: 79 14 55", 1);
+        let err = AocError::parse(5, &odd, &odd.parse::<Input>().unwrap_err());
+        assert!(
+            matches!(err, AocError::ParseError { line: Some(1), .. }),
+            "{err:?}"
+        );
+
+        let skipped = EXAMPLE.replacen("soil-to-fertilizer", "water-to-fertilizer", 1);
+        let err = AocError::parse(5, &skipped, &skipped.parse::<Input>().unwrap_err());
+        assert!(
+            matches!(err, AocError::ParseError { line: Some(7), .. }),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        let mut input: Input = EXAMPLE.parse().unwrap();
+        input.seeds = vec![u32::MAX, 2];
+
+        let err = part2(&input, &mut Checkpoint::default(), &Cancel::default()).unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(AocError::Overflow(_))));
+    }
+
+    #[test]
+    fn timeout() {
+        let input: Input = EXAMPLE.parse().unwrap();
+        let cancel = Cancel::new(Some(std::time::Duration::ZERO));
+
+        let err = part2(&input, &mut Checkpoint::default(), &cancel).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Timed out, lowest location so far: 46 (at seed 55)"
+        );
+    }
+
+    #[test]
+    fn resume() {
+        let input: Input = EXAMPLE.parse().unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("day05.checkpoint");
+
+        // Pretend the first seed range has been searched already
+        let mut checkpoint = Checkpoint::new(&path, std::time::Duration::ZERO);
+        let progress = Progress {
+            range: 1,
+            next_seed: 55,
+            min_location: 46,
+        };
+        checkpoint.save(&progress).unwrap();
+        assert_eq!(checkpoint.resume().unwrap(), Some(progress));
+
+        assert_eq!(
+            part2(&input, &mut checkpoint, &Cancel::default()).unwrap(),
+            46
+        );
+        checkpoint.done().unwrap();
+        assert!(!path.exists());
+    }
+
+    proptest! {
+        #[test]
+        fn range_roundtrip(dst_start: u32, src_start: u32, len: u32) {
+            let range = Range { src_start, dst_start, len };
+            prop_assert_eq!(range.to_string().parse::<Range>().unwrap(), range);
+        }
+
+        #[test]
+        fn arbitrary_inputs(bytes: Vec<u8>) {
+            let input = Input::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+            for &seed in &input.seeds {
+                input.location(seed);
+            }
+            match input.seed_ranges() {
+                Ok(ranges) => prop_assert_eq!(ranges.len() * 2, input.seeds.len()),
+                Err(e) => prop_assert!(matches!(e, AocError::Overflow(_))),
+            }
+        }
+
+        #[test]
+        fn range_near_miss(s in "-?[0-9]{0,11}( +-?[0-9x]{0,11}){0,3} ?") {
+            if let Ok(range) = s.parse::<Range>() {
+                prop_assert_eq!(range.to_string().parse::<Range>().unwrap(), range);
+            }
+        }
+    }
+}
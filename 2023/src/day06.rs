@@ -0,0 +1,178 @@
+use crate::YEAR;
+use anyhow::{ensure, Context, Error, Result};
+use aoc_core::answer::Answer;
+use aoc_core::aoc;
+use aoc_core::solver::Solver;
+use std::str::FromStr;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Race {
+    time: u64,
+    dist: u64,
+}
+
+impl Race {
+    #[must_use]
+    pub fn min_hold(&self) -> Option<u64> {
+        for hold in 0..=self.time {
+            let remain = self.time - hold;
+
+            let mut dist = 0;
+            for _ in 0..remain {
+                dist += hold;
+            }
+
+            if dist > self.dist {
+                return Some(hold);
+            }
+        }
+
+        None
+    }
+
+    #[must_use]
+    pub fn max_hold(&self) -> Option<u64> {
+        for hold in (0..=self.time).rev() {
+            let remain = self.time - hold;
+
+            let mut dist = 0;
+            for _ in 0..remain {
+                dist += hold;
+            }
+
+            if dist > self.dist {
+                return Some(hold);
+            }
+        }
+
+        None
+    }
+
+    /// How many ways there are to win, none if no hold beats the record.
+    #[must_use]
+    pub fn num_holds(&self) -> u64 {
+        match (self.min_hold(), self.max_hold()) {
+            (Some(a), Some(b)) => b - a + 1,
+            _ => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Input(Vec<Race>);
+
+impl Input {
+    #[must_use]
+    pub fn p1(&self) -> u64 {
+        self.0.iter().map(Race::num_holds).product()
+    }
+
+    /// The one long race of part 2, from ignoring the spaces between the numbers.
+    ///
+    /// # Errors
+    /// If the numbers do not fit a `u64` together.
+    pub fn into_p2(self) -> Result<Race> {
+        let time = self
+            .0
+            .iter()
+            .map(|r| r.time.to_string())
+            .collect::<Vec<_>>()
+            .concat();
+        let dist = self
+            .0
+            .into_iter()
+            .map(|r| r.dist.to_string())
+            .collect::<Vec<_>>()
+            .concat();
+
+        Ok(Race {
+            time: time.parse().with_context(|| format!("Time {time}"))?,
+            dist: dist.parse().with_context(|| format!("Distance {dist}"))?,
+        })
+    }
+}
+
+impl FromStr for Input {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let times: Result<Vec<_>, _> = lines
+            .next()
+            .context("")?
+            .split_whitespace()
+            .skip(1)
+            .map(u64::from_str)
+            .collect();
+        let distances: Result<Vec<_>, _> = lines
+            .next()
+            .context("")?
+            .split_whitespace()
+            .skip(1)
+            .map(u64::from_str)
+            .collect();
+        ensure!(lines.next().is_none(), "More than a time and a distance");
+
+        let races = times?
+            .iter()
+            .zip(distances?)
+            .map(|(&time, dist)| Race { time, dist })
+            .collect();
+
+        Ok(Self(races))
+    }
+}
+
+/// The example of the puzzle text.
+pub const EXAMPLE: &str = include_str!("../examples/day06.txt");
+
+#[aoc(year = 2023, day = 6)]
+pub struct Day06;
+
+impl Solver for Day06 {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = 6;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Input> {
+        input.parse()
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(input.p1().into())
+    }
+
+    fn part2(input: &Input) -> Result<Answer> {
+        Ok(input.clone().into_p2()?.num_holds().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn p1() {
+        let input = Input::from_str(EXAMPLE).unwrap();
+
+        let r = input.0.clone();
+
+        assert_eq!(r.len(), 3);
+        assert_eq!(
+            r[2],
+            Race {
+                time: 30,
+                dist: 200
+            }
+        );
+        assert_eq!(r[0].min_hold(), Some(2));
+        assert_eq!(r[0].max_hold(), Some(5));
+        assert_eq!(r[0].num_holds(), 4);
+        assert_eq!(r[1].num_holds(), 8);
+        assert_eq!(r[2].num_holds(), 9);
+        assert_eq!(input.p1(), 288);
+        assert_eq!(input.into_p2().unwrap().num_holds(), 71503);
+    }
+}
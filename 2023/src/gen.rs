@@ -0,0 +1,63 @@
+//! Valid inputs of any size, for `aoc complexity --year 2023 --day 6`. `size` is how many of what
+//! the day has a lot of there are:
+//!
+//! | Day | `size`                                 |
+//! |-----|----------------------------------------|
+//! | 6   | milliseconds of the one race           |
+
+use anyhow::{anyhow, Result};
+use std::ops::RangeInclusive;
+
+/// Days that have a generator.
+pub const DAYS: RangeInclusive<u8> = 6..=6;
+
+/// An input for `day`, see the [module](self) for what `size` means. The seed is not used yet, the
+/// inputs are the hardest of their size.
+///
+/// # Errors
+/// If there is no generator for the day.
+pub fn generate(day: u8, size: usize, _seed: u64) -> Result<String> {
+    match day {
+        6 => Ok(day06(size as u64)),
+        _ => Err(anyhow!("There is no generator for day {day}")),
+    }
+}
+
+/// A race where only holding for half the time wins, so finding the holds has to go all the way.
+#[must_use]
+pub fn day06(time: u64) -> String {
+    let dist = (time * time / 4).saturating_sub(1);
+    format!("Time: {time}\nDistance: {dist}\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::answer::Answer;
+    use aoc_core::registry::solution;
+
+    #[test]
+    fn generated_inputs_solve() {
+        for day in DAYS {
+            let input = generate(day, 30, 4).unwrap();
+
+            // Only the days of the enabled features are built
+            let Some(solution) = solution(crate::YEAR, day) else {
+                continue;
+            };
+            let answers = solution.solve(&input, &[]);
+            assert!(answers.is_ok(), "Day {day}: {answers:?}\n{input}");
+        }
+        generate(25, 30, 4).unwrap_err();
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "day2023_06"), ignore = "Needs day 6")]
+    fn one_winning_hold() {
+        let answers = solution(crate::YEAR, 6)
+            .unwrap()
+            .solve(&day06(1000), &[])
+            .unwrap();
+        assert_eq!(answers[0], ("Part 1".to_owned(), Answer::from(1)));
+    }
+}
@@ -0,0 +1,14 @@
+#![warn(clippy::pedantic)]
+
+/// The year of the days in this crate.
+pub const YEAR: u16 = 2023;
+
+#[cfg(feature = "day2023_01")]
+pub mod day01;
+#[cfg(feature = "day2023_02")]
+pub mod day02;
+#[cfg(feature = "day2023_05")]
+pub mod day05;
+#[cfg(feature = "day2023_06")]
+pub mod day06;
+pub mod gen;
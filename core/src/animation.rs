@@ -0,0 +1,217 @@
+use crate::export::{self, Export};
+use crate::style::{self, Role};
+use anyhow::{ensure, Context, Result};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Shows a simulation in the terminal, one frame per step, when a day is run with
+/// `--animate [<fps>]`.
+///
+/// Frames are drawn on stderr, so the answers on stdout can still be read by the runner. The
+/// default animation is disabled and does nothing, so simulations can always take one.
+///
+/// With `--export <file>` the frames are kept for [`Animation::finish`] to write them to a GIF or
+/// animated PNG instead, at the frames per second of `--animate` and with `--scale` pixels per
+/// character, see [`export`].
+#[derive(Debug, Default)]
+pub struct Animation {
+    delay: Option<Duration>,
+    last: Option<Instant>,
+    frames: usize,
+    export: Option<Export>,
+}
+
+impl Animation {
+    pub const DEFAULT_FPS: u32 = 10;
+
+    /// # Errors
+    /// If `fps` is zero.
+    pub fn new(fps: u32) -> Result<Self> {
+        ensure!(fps > 0, "Can not animate with 0 frames per second");
+        Ok(Self {
+            delay: Some(Duration::from_secs(1) / fps),
+            ..Self::default()
+        })
+    }
+
+    /// Keeps the frames to write them to `path` when finished, instead of drawing them.
+    ///
+    /// # Errors
+    /// If the path is not one of an [`export::Format`], or `fps` or `scale` is zero.
+    pub fn export(path: impl Into<PathBuf>, fps: u32, scale: usize) -> Result<Self> {
+        Ok(Self {
+            export: Some(Export::new(path, fps, scale)?),
+            ..Self::default()
+        })
+    }
+
+    /// Animates when `--animate` is given, optionally followed by the frames per second, or exports
+    /// with `--export <file>` and `--scale <pixels>`. Other arguments are ignored.
+    ///
+    /// # Errors
+    /// If the frames per second or the scale are not a positive number, or the file of `--export`
+    /// is missing or of an unknown format.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let (mut fps, mut path, mut scale) = (None, None, None);
+        let mut args = args.into_iter().peekable();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--animate" => {
+                    fps = Some(match args.next_if(|a| !a.starts_with('-')) {
+                        Some(fps) => fps.parse().with_context(|| {
+                            format!("--animate takes frames per second, not {fps:?}")
+                        })?,
+                        None => Self::DEFAULT_FPS,
+                    });
+                }
+                "--export" => path = Some(args.next().context("--export needs a path")?),
+                "--scale" => {
+                    let pixels = args.next().context("--scale needs pixels per character")?;
+                    scale = Some(pixels.parse().with_context(|| {
+                        format!("--scale takes pixels per character, not {pixels:?}")
+                    })?);
+                }
+                _ => {}
+            }
+        }
+
+        match (path, fps) {
+            (Some(path), fps) => Self::export(
+                path,
+                fps.unwrap_or(Self::DEFAULT_FPS),
+                scale.unwrap_or(export::DEFAULT_SCALE),
+            ),
+            (None, Some(fps)) => Self::new(fps),
+            (None, None) => Ok(Self::default()),
+        }
+    }
+
+    /// Simulations can skip work that is only needed for drawing if this is false.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.delay.is_some() || self.export.is_some()
+    }
+
+    /// Waits until the next frame is due and draws what `render` returns. `render` is only called
+    /// when animating.
+    ///
+    /// Errors writing to the terminal are ignored, they should not stop the simulation.
+    pub fn frame(&mut self, render: impl FnOnce() -> String) {
+        if let Some(export) = &mut self.export {
+            export.push(render());
+            self.frames += 1;
+            return;
+        }
+        let Some(delay) = self.delay else {
+            return;
+        };
+
+        let frame = style::paint(Role::Picture, &render());
+        if let Some(last) = self.last {
+            std::thread::sleep(delay.saturating_sub(last.elapsed()));
+        }
+        self.last = Some(Instant::now());
+
+        let _ = draw(&mut io::stderr().lock(), self.frames, &frame);
+        self.frames += 1;
+    }
+
+    /// The number of frames drawn so far.
+    #[must_use]
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Writes the frames when exporting, does nothing otherwise.
+    ///
+    /// # Errors
+    /// If there were no frames, or they could not be encoded or written.
+    pub fn finish(&mut self) -> Result<()> {
+        match self.export.take() {
+            Some(export) => export.write(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Clears the terminal and draws `frame` at the top, with its number below.
+fn draw(out: &mut impl Write, number: usize, frame: &str) -> io::Result<()> {
+    write!(out, "\x1b[2J\x1b[H{frame}")?;
+    if !frame.ends_with('\n') {
+        writeln!(out)?;
+    }
+    writeln!(out, "Frame {number}")?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_args(args: &[&str]) -> Result<Animation> {
+        Animation::from_args(args.iter().map(ToString::to_string))
+    }
+
+    #[test]
+    fn args() {
+        assert!(!from_args(&["--part", "1"]).unwrap().is_enabled());
+
+        let delay = |args| from_args(args).unwrap().delay.unwrap();
+        assert_eq!(delay(&["--animate"]), Duration::from_millis(100));
+        assert_eq!(
+            delay(&["--animate", "--part", "1"]),
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            delay(&["--part", "1", "--animate", "50"]),
+            Duration::from_millis(20)
+        );
+
+        from_args(&["--animate", "0"]).unwrap_err();
+        from_args(&["--animate", "fast"]).unwrap_err();
+    }
+
+    #[test]
+    fn exports() {
+        let export = from_args(&["--export", "rope.gif", "--animate", "20"]).unwrap();
+        assert!(export.is_enabled());
+        assert_eq!(export.delay, None);
+
+        from_args(&["--export"]).unwrap_err();
+        from_args(&["--export", "rope.mp4"]).unwrap_err();
+        from_args(&["--export", "rope.gif", "--scale", "0"]).unwrap_err();
+        from_args(&["--export", "rope.gif", "--scale"]).unwrap_err();
+
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("rope.png");
+        let mut animation = Animation::export(&path, 10, 2).unwrap();
+        animation.frame(|| "H.\n".to_owned());
+        animation.frame(|| ".H\n".to_owned());
+        assert_eq!(animation.frames(), 2);
+        animation.finish().unwrap();
+        assert!(std::fs::read(&path).unwrap().starts_with(b"\x89PNG"));
+
+        Animation::export("empty.gif", 10, 2)
+            .unwrap()
+            .finish()
+            .unwrap_err();
+    }
+
+    #[test]
+    fn disabled_does_not_render() {
+        let mut animation = Animation::default();
+        animation.frame(|| unreachable!("Rendered while disabled"));
+        assert_eq!(animation.frames(), 0);
+    }
+
+    #[test]
+    fn drawing() {
+        let mut out = vec![];
+        draw(&mut out, 3, "#.\n.#").unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b[2J\x1b[H#.\n.#\nFrame 3\n"
+        );
+    }
+}
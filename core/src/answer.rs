@@ -0,0 +1,181 @@
+use crate::ocr;
+use crate::screen::{Density, Screen};
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// What a part of a puzzle returns, so numbers, words and drawn letters can all be printed,
+/// serialized and compared the same way.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(untagged, from = "Repr")]
+pub enum Answer {
+    Int(i128),
+    Text(String),
+    /// Text over several lines, like a map that has to be looked at
+    Grid(String),
+    /// Letters drawn on a screen, like 2022 day 10
+    Bitmap(Screen),
+}
+
+/// Untagged enums can not deserialize `i128`, so integers are read as 64 bit.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Repr {
+    Signed(i64),
+    Unsigned(u64),
+    Text(String),
+    Bitmap(Screen),
+}
+
+impl From<Repr> for Answer {
+    fn from(repr: Repr) -> Self {
+        match repr {
+            Repr::Signed(i) => i.into(),
+            Repr::Unsigned(u) => u.into(),
+            Repr::Text(s) => s.into(),
+            Repr::Bitmap(screen) => screen.into(),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Self::Int(i128::from(value))
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i8, i16, i32, i64, i128, u8, u16, u32, u64);
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Int(i128::try_from(value).expect("usize fits in i128"))
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Self::Int(i128::try_from(value).expect("isize fits in i128"))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        if value.contains('\n') {
+            Self::Grid(value)
+        } else {
+            Self::Text(value)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        value.to_owned().into()
+    }
+}
+
+/// Reads a printed answer back, numbers become [`Answer::Int`] again. Pictures can only be read
+/// as [`Answer::Grid`].
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Infallible> {
+        Ok(s.parse::<i128>().map_or_else(|_| s.into(), Self::Int))
+    }
+}
+
+impl From<Screen> for Answer {
+    fn from(value: Screen) -> Self {
+        Self::Bitmap(value)
+    }
+}
+
+impl Answer {
+    /// Pictures of letters become the letters, if they are in one of the known fonts.
+    #[must_use]
+    pub fn read_letters(self) -> Self {
+        match self {
+            Self::Bitmap(screen) => ocr::read(&screen).map_or(Self::Bitmap(screen), Self::Text),
+            other => other,
+        }
+    }
+
+    /// As it is displayed, with bitmaps drawn in `density`.
+    #[must_use]
+    pub fn draw(&self, density: Density) -> String {
+        match self {
+            Self::Bitmap(screen) => screen.draw(density),
+            other => other.to_string(),
+        }
+    }
+
+    /// Pictures are printed starting on their own line and can not be submitted.
+    #[must_use]
+    pub fn is_picture(&self) -> bool {
+        matches!(self, Self::Grid(_) | Self::Bitmap(_))
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(i) => write!(f, "{i}"),
+            Self::Text(s) | Self::Grid(s) => write!(f, "{s}"),
+            Self::Bitmap(screen) => write!(f, "{screen}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Answer::from(13_140_usize), Answer::Int(13_140));
+        assert_eq!(Answer::from(-3_i64).to_string(), "-3");
+        assert_eq!(Answer::from("CMZ").to_string(), "CMZ");
+        assert_eq!(Answer::from("#.\n.#"), Answer::Grid("#.\n.#".to_owned()));
+        assert!(Answer::from("#.\n.#").is_picture());
+
+        let mut screen = Screen::new(2, 1);
+        screen.set(1, 0, true);
+        assert_eq!(Answer::from(screen.clone()).to_string(), " █\n");
+        // Not a letter
+        assert_eq!(
+            Answer::from(screen.clone()).read_letters(),
+            Answer::Bitmap(screen)
+        );
+        assert_eq!(Answer::from(3_u8).read_letters(), Answer::Int(3));
+    }
+
+    #[test]
+    fn printed() {
+        let read = |s: &str| s.parse::<Answer>().unwrap();
+        assert_eq!(read("-3"), Answer::Int(-3));
+        assert_eq!(read("18446744073709551616"), Answer::Int(1 << 64));
+        assert_eq!(read("CMZ"), Answer::Text("CMZ".to_owned()));
+        assert_eq!(read("3 4"), Answer::Text("3 4".to_owned()));
+        assert_eq!(read("##\n#"), Answer::Grid("##\n#".to_owned()));
+    }
+
+    #[test]
+    fn json() {
+        let answers = vec![
+            Answer::from(u64::MAX),
+            Answer::from("CMZ"),
+            Answer::from("#.\n.#"),
+        ];
+        let json = serde_json::to_string(&answers).unwrap();
+
+        assert_eq!(json, r##"[18446744073709551615,"CMZ","#.\n.#"]"##);
+        assert_eq!(serde_json::from_str::<Vec<Answer>>(&json).unwrap(), answers);
+    }
+}
@@ -0,0 +1,167 @@
+//! Answers computed before, by year, day and SHA-256 of the input, so running a day again on the
+//! same input is instant. The answers are only as fresh as the code was, `--force` computes them
+//! again.
+
+use crate::answer::Answer;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+
+/// What is cached for one input.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+struct Cached {
+    /// Whether all parts ran, not only some selected ones
+    complete: bool,
+    answers: Vec<(String, Answer)>,
+}
+
+/// A directory with one JSON file per input.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AnswerCache {
+    dir: PathBuf,
+}
+
+impl AnswerCache {
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// `~/.cache/aoc`, or `aoc` in `XDG_CACHE_HOME`. `None` if neither that nor `HOME` is set.
+    #[must_use]
+    pub fn open() -> Option<Self> {
+        let cache = std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| Some(PathBuf::from(std::env::var_os("HOME")?).join(".cache")))?;
+        Some(Self::new(cache.join("aoc")))
+    }
+
+    /// `<dir>/2022/day05-<sha256 of the input>.json`
+    #[must_use]
+    pub fn path(&self, year: u16, day: u8, input: &str) -> PathBuf {
+        let hash = Sha256::digest(input);
+        self.dir
+            .join(year.to_string())
+            .join(format!("day{day:02}-{hash:x}.json"))
+    }
+
+    fn load(&self, year: u16, day: u8, input: &str) -> Option<Cached> {
+        let json = fs::read_to_string(self.path(year, day, input)).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// The cached answers of the parts selected by name or number, all parts if `selection` is
+    /// empty. `None` unless every one of them is cached.
+    #[must_use]
+    pub fn get(
+        &self,
+        year: u16,
+        day: u8,
+        input: &str,
+        selection: &[String],
+    ) -> Option<Vec<(String, Answer)>> {
+        let cached = self.load(year, day, input)?;
+        if selection.is_empty() {
+            return cached.complete.then_some(cached.answers);
+        }
+
+        selection
+            .iter()
+            .map(|wanted| {
+                let by_number = wanted.parse::<u8>().ok().map(|n| format!("Part {n}"));
+                cached
+                    .answers
+                    .iter()
+                    .find(|(name, _)| name == wanted || Some(name) == by_number.as_ref())
+                    .cloned()
+            })
+            .collect()
+    }
+
+    /// Adds the answers of the selected parts to what is cached for `input`.
+    ///
+    /// # Errors
+    /// If the cache can not be written.
+    pub fn put(
+        &self,
+        year: u16,
+        day: u8,
+        input: &str,
+        selection: &[String],
+        answers: &[(String, Answer)],
+    ) -> Result<()> {
+        let mut cached = self.load(year, day, input).unwrap_or_default();
+        if selection.is_empty() {
+            cached = Cached {
+                complete: true,
+                answers: answers.to_vec(),
+            };
+        } else {
+            for (name, answer) in answers {
+                match cached.answers.iter_mut().find(|(n, _)| n == name) {
+                    Some((_, old)) => old.clone_from(answer),
+                    None => cached.answers.push((name.clone(), answer.clone())),
+                }
+            }
+        }
+
+        let path = self.path(year, day, input);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(&cached)? + "\n")
+            .with_context(|| format!("Could not write {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(|&p| p.to_owned()).collect()
+    }
+
+    #[test]
+    fn caches_by_input() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let cache = AnswerCache::new(dir);
+        let part1 = ("Part 1".to_owned(), Answer::Int(24_000));
+        let part2 = ("Part 2".to_owned(), Answer::from("#.\n.#"));
+
+        assert_eq!(cache.get(2022, 1, "1\n2\n", &[]), None);
+        cache
+            .put(
+                2022,
+                1,
+                "1\n2\n",
+                &parts(&["1"]),
+                std::slice::from_ref(&part1),
+            )
+            .unwrap();
+        assert_eq!(
+            cache.get(2022, 1, "1\n2\n", &parts(&["1"])),
+            Some(vec![part1.clone()])
+        );
+        // Only part 1 ran so far
+        assert_eq!(cache.get(2022, 1, "1\n2\n", &[]), None);
+        assert_eq!(cache.get(2022, 1, "1\n2\n", &parts(&["2"])), None);
+
+        cache
+            .put(2022, 1, "1\n2\n", &[], &[part1.clone(), part2.clone()])
+            .unwrap();
+        assert_eq!(
+            cache.get(2022, 1, "1\n2\n", &[]),
+            Some(vec![part1, part2.clone()])
+        );
+        assert_eq!(
+            cache.get(2022, 1, "1\n2\n", &parts(&["Part 2"])),
+            Some(vec![part2])
+        );
+        assert_eq!(cache.get(2022, 1, "1\n3\n", &[]), None);
+        assert_eq!(cache.get(2022, 2, "1\n2\n", &[]), None);
+    }
+}
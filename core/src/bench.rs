@@ -0,0 +1,527 @@
+//! The `days` bench of every year, [`bench_days`], and what it measured, read from the results
+//! criterion keeps, for the exports of the bench and for comparing against a baseline with
+//! `aoc bench --compare`. `aoc bench` also keeps every run in a [`BenchHistory`], to follow a day
+//! over months.
+
+use crate::chart::{self, Bar};
+use crate::fetch::{input_path, INPUT_DIR};
+use crate::perf::{PerfCounters, PhaseCounts};
+use crate::registry::{implementations, solutions, Solution, MAIN};
+use crate::{compress, crypt};
+use anyhow::{ensure, Context, Result};
+use criterion::Criterion;
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::env;
+use std::fs;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The benchmarks of every implementation of a day.
+pub const PHASES: [&str; 3] = ["parse", "part1", "part2"];
+
+/// What criterion calls the results of the last run.
+pub const NEW: &str = "new";
+
+/// In the root of the workspace, next to `target` so `cargo clean` does not lose it.
+pub const HISTORY_DB: &str = "bench-history.sqlite";
+
+/// Where criterion keeps its results, found the way it does, for the workspace at `root`.
+#[must_use]
+pub fn criterion_dir(root: &Path) -> PathBuf {
+    if let Some(dir) = env::var_os("CRITERION_HOME") {
+        return dir.into();
+    }
+    env::var_os("CARGO_TARGET_DIR")
+        .map_or_else(|| root.join("target"), PathBuf::from)
+        .join("criterion")
+}
+
+/// The criterion group of the benchmarks of a day, like `2022-day05`.
+#[must_use]
+pub fn bench_group(year: u16, day: u8) -> String {
+    format!("{year}-day{day:02}")
+}
+
+/// `part1` for the main implementation, `part1/<name>` for the others.
+#[must_use]
+pub fn bench_id(phase: &str, implementation: &str) -> String {
+    if implementation == MAIN {
+        phase.to_owned()
+    } else {
+        format!("{phase}/{implementation}")
+    }
+}
+
+/// The mean time of a benchmark in milliseconds, in the last run or in the baseline of that name.
+///
+/// # Errors
+/// If it has not been run, or criterion changed how it stores its results.
+pub fn mean_ms(dir: &Path, year: u16, day: u8, id: &str, baseline: &str) -> Result<f64> {
+    // Criterion turns the `/` in `part1/<name>` into `_` for its directories
+    let path = dir
+        .join(bench_group(year, day))
+        .join(id.replace('/', "_"))
+        .join(baseline)
+        .join("estimates.json");
+    let estimates: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(&path).with_context(|| format!("Could not read {}", path.display()))?,
+    )?;
+    let ns = estimates["mean"]["point_estimate"]
+        .as_f64()
+        .with_context(|| format!("{} has no mean", path.display()))?;
+    Ok(ns / 1e6)
+}
+
+/// How the time of one benchmark changed since a baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub day: u8,
+    pub id: String,
+    pub before_ms: f64,
+    pub after_ms: f64,
+}
+
+impl Change {
+    /// Negative when it got faster.
+    #[must_use]
+    pub fn percent(&self) -> f64 {
+        (self.after_ms - self.before_ms) / self.before_ms * 100.0
+    }
+}
+
+/// The changes of the benchmarks of `ids` (day and id) of `year` since `baseline`, leaving out the
+/// ones that were not in both runs.
+#[must_use]
+pub fn compare(dir: &Path, year: u16, ids: &[(u8, String)], baseline: &str) -> Vec<Change> {
+    ids.iter()
+        .filter_map(|(day, id)| {
+            Some(Change {
+                day: *day,
+                id: id.clone(),
+                before_ms: mean_ms(dir, year, *day, id, baseline).ok()?,
+                after_ms: mean_ms(dir, year, *day, id, NEW).ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Benchmarks parsing and both parts of every day of `year` registered with `#[aoc]`, the
+/// `benches/days.rs` of every year. Days with more than one implementation get them all in their
+/// group, as `part1/<name>` and so on next to the main one.
+///
+/// Uses the real input when `input/dayNN.txt` exists, also compressed or encrypted, and the
+/// example of the day otherwise.
+pub fn bench_days(c: &mut Criterion, year: u16) {
+    for solution in solutions() {
+        if solution.year == year {
+            bench_day(c, solution);
+        }
+    }
+}
+
+/// The real input, which may be compressed or encrypted, or the example.
+fn bench_input(solution: &Solution) -> Option<String> {
+    let path = input_path(Path::new(INPUT_DIR), solution.day);
+    if let Err(e) = crypt::decrypt_missing(&path) {
+        eprintln!("Could not decrypt the input of day {}: {e:#}", solution.day);
+    }
+    compress::read(&path)
+        .ok()
+        .or_else(|| solution.example.map(str::to_owned))
+}
+
+fn bench_day(c: &mut Criterion, main: &Solution) {
+    let day = main.day;
+    let Some(input) = bench_input(main) else {
+        eprintln!("Skipping day {day}, it has neither an input nor an example");
+        return;
+    };
+
+    let mut group = c.benchmark_group(bench_group(main.year, day));
+    for solution in implementations(main.year, day) {
+        let parsed = match solution.parse(&input) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                eprintln!(
+                    "Skipping day {day} ({}), its input does not parse: {e:#}",
+                    solution.name
+                );
+                continue;
+            }
+        };
+        // The main implementation keeps the names from before there were others
+        let id = |phase: &str| bench_id(phase, solution.name);
+
+        group.bench_function(id("parse"), |b| {
+            b.iter(|| solution.parse(black_box(&input)));
+        });
+        group.bench_function(id("part1"), |b| {
+            b.iter(|| solution.part1(black_box(parsed.as_ref())));
+        });
+        group.bench_function(id("part2"), |b| {
+            b.iter(|| solution.part2(black_box(parsed.as_ref())));
+        });
+    }
+    group.finish();
+}
+
+/// The mean times of one implementation of a day, in milliseconds by phase.
+#[derive(Debug, Serialize)]
+struct DayResult {
+    year: u16,
+    day: u8,
+    implementation: &'static str,
+    parse_ms: f64,
+    part1_ms: f64,
+    part2_ms: f64,
+    /// Left out without performance counters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    counts: Option<PhaseCounts>,
+}
+
+/// The baseline `--save-baseline` saved this run as, otherwise criterion keeps it as [`NEW`].
+fn saved_as() -> String {
+    let args: Vec<_> = env::args().collect();
+    args.iter()
+        .position(|arg| arg == "--save-baseline")
+        .and_then(|i| args.get(i + 1).cloned())
+        .unwrap_or_else(|| NEW.to_owned())
+}
+
+/// Every implementation criterion has results for, from the last run of each, counted once more
+/// with `perf`.
+fn results(
+    dir: &Path,
+    year: u16,
+    baseline: &str,
+    mut perf: Option<&mut PerfCounters>,
+) -> Vec<DayResult> {
+    let mut results = vec![];
+    for main in solutions().into_iter().filter(|s| s.year == year) {
+        let input = bench_input(main);
+        for solution in implementations(year, main.day) {
+            let times: Result<Vec<_>> = PHASES
+                .iter()
+                .map(|phase| {
+                    mean_ms(
+                        dir,
+                        year,
+                        main.day,
+                        &bench_id(phase, solution.name),
+                        baseline,
+                    )
+                })
+                .collect();
+            if let Ok(times) = times {
+                let counts = perf
+                    .as_deref_mut()
+                    .zip(input.as_deref())
+                    .and_then(|(perf, input)| perf.phases(solution, input).ok());
+                results.push(DayResult {
+                    year,
+                    day: main.day,
+                    implementation: solution.name,
+                    parse_ms: times[0],
+                    part1_ms: times[1],
+                    part2_ms: times[2],
+                    counts,
+                });
+            }
+        }
+    }
+    results
+}
+
+/// Writes the mean times of every day of `year` from the run [`bench_days`] just did to
+/// `target/criterion/days-YEAR.json`, and draws them as a bar chart to `days-YEAR.svg` and
+/// `days-YEAR.png` next to it. Where the hardware performance counters can be read, the JSON also
+/// has the cycles, instructions, cache and branch misses of one run of every phase.
+///
+/// # Errors
+/// If the results can not be written.
+pub fn export_days(year: u16) -> Result<()> {
+    // Benches run in the directory of their crate
+    let dir = criterion_dir(Path::new(".."));
+    let mut perf = PerfCounters::open()
+        .inspect_err(|e| eprintln!("Not counting the days: {e:#}"))
+        .ok();
+    let results = results(&dir, year, &saved_as(), perf.as_mut());
+    if results.is_empty() {
+        return Ok(());
+    }
+
+    let json = dir.join(format!("days-{year}.json"));
+    fs::write(&json, serde_json::to_string_pretty(&results)? + "\n")
+        .with_context(|| format!("Could not write {}", json.display()))?;
+    println!("Wrote {}", json.display());
+
+    let bars: Vec<_> = results
+        .iter()
+        .filter(|r| r.implementation == MAIN)
+        .map(|r| Bar {
+            label: r.day.to_string(),
+            segments: vec![
+                ("parse".to_owned(), r.parse_ms),
+                ("part 1".to_owned(), r.part1_ms),
+                ("part 2".to_owned(), r.part2_ms),
+            ],
+        })
+        .collect();
+    for extension in ["svg", "png"] {
+        let path = dir.join(format!("days-{year}.{extension}"));
+        chart::runtimes(&path, &year.to_string(), &bars)
+            .with_context(|| format!("Could not draw {}", path.display()))?;
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+/// Where and when a bench ran.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    /// Short hash, with `-dirty` if there were uncommitted changes
+    pub commit: String,
+    pub machine: String,
+    /// Seconds since the epoch
+    pub time: u64,
+}
+
+impl Run {
+    /// The commit checked out in `root`, on this machine, now.
+    ///
+    /// # Errors
+    /// If `root` is not in a git repository.
+    pub fn current(root: &Path) -> Result<Self> {
+        let git = |args: &[&str]| -> Result<String> {
+            let output = Command::new("git").current_dir(root).args(args).output()?;
+            ensure!(output.status.success(), "git {} failed", args.join(" "));
+            Ok(String::from_utf8(output.stdout)?.trim().to_owned())
+        };
+        let mut commit = git(&["rev-parse", "--short", "HEAD"]).context("Unknown commit")?;
+        if !git(&["status", "--porcelain", "--untracked-files=no"])?.is_empty() {
+            commit.push_str("-dirty");
+        }
+
+        Ok(Self {
+            commit,
+            machine: machine(),
+            time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        })
+    }
+}
+
+/// `AOC_MACHINE` or the host name, times of different machines do not compare.
+#[must_use]
+pub fn machine() -> String {
+    env::var("AOC_MACHINE")
+        .ok()
+        .or_else(|| fs::read_to_string("/proc/sys/kernel/hostname").ok())
+        .or_else(|| env::var("COMPUTERNAME").ok())
+        .map(|name| name.trim().to_owned())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_owned())
+}
+
+/// One benchmark of one run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub run: Run,
+    pub id: String,
+    pub mean_ms: f64,
+}
+
+/// The results of every run, by commit and machine. Running again on the same commit replaces the
+/// results of the benchmarks that ran.
+pub struct BenchHistory {
+    db: Connection,
+}
+
+impl BenchHistory {
+    /// Creates the database if it does not exist yet.
+    ///
+    /// # Errors
+    /// If it can not be opened or is no bench history.
+    pub fn open(path: &Path) -> Result<Self> {
+        let db =
+            Connection::open(path).with_context(|| format!("Could not open {}", path.display()))?;
+        db.execute_batch(
+            "CREATE TABLE IF NOT EXISTS results (
+                 commit_id TEXT NOT NULL,
+                 machine TEXT NOT NULL,
+                 time INTEGER NOT NULL,
+                 year INTEGER NOT NULL,
+                 day INTEGER NOT NULL,
+                 bench TEXT NOT NULL,
+                 mean_ms REAL NOT NULL,
+                 PRIMARY KEY (commit_id, machine, year, day, bench)
+             );
+             CREATE TABLE IF NOT EXISTS energy (
+                 commit_id TEXT NOT NULL,
+                 machine TEXT NOT NULL,
+                 time INTEGER NOT NULL,
+                 year INTEGER NOT NULL,
+                 day INTEGER NOT NULL,
+                 joules REAL NOT NULL,
+                 PRIMARY KEY (commit_id, machine, year, day)
+             )",
+        )
+        .with_context(|| format!("{} is no bench history", path.display()))?;
+        Ok(Self { db })
+    }
+
+    /// Keeps the mean times of `results` (day, id and milliseconds).
+    ///
+    /// # Errors
+    /// If the database can not be written.
+    pub fn record(&mut self, run: &Run, year: u16, results: &[(u8, String, f64)]) -> Result<()> {
+        let transaction = self.db.transaction()?;
+        for (day, id, mean_ms) in results {
+            transaction.execute(
+                "INSERT OR REPLACE INTO results VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![run.commit, run.machine, run.time, year, day, id, mean_ms],
+            )?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Keeps the joules one solve of a day used (day and joules).
+    ///
+    /// # Errors
+    /// If the database can not be written.
+    pub fn record_energy(&mut self, run: &Run, year: u16, energy: &[(u8, f64)]) -> Result<()> {
+        let transaction = self.db.transaction()?;
+        for (day, joules) in energy {
+            transaction.execute(
+                "INSERT OR REPLACE INTO energy VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![run.commit, run.machine, run.time, year, day, joules],
+            )?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// The joules of a day on `machine` by commit, oldest first.
+    ///
+    /// # Errors
+    /// If the database can not be read.
+    pub fn day_energy(&self, year: u16, day: u8, machine: &str) -> Result<Vec<(String, f64)>> {
+        let mut query = self.db.prepare(
+            "SELECT commit_id, joules FROM energy
+             WHERE year = ?1 AND day = ?2 AND machine = ?3 ORDER BY time",
+        )?;
+        let energy = query.query_map(params![year, day, machine], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?;
+        Ok(energy.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Every result of a day on `machine`, oldest first.
+    ///
+    /// # Errors
+    /// If the database can not be read.
+    pub fn day(&self, year: u16, day: u8, machine: &str) -> Result<Vec<Record>> {
+        let mut query = self.db.prepare(
+            "SELECT commit_id, machine, time, bench, mean_ms FROM results
+             WHERE year = ?1 AND day = ?2 AND machine = ?3 ORDER BY time, bench",
+        )?;
+        let records = query.query_map(params![year, day, machine], |row| {
+            Ok(Record {
+                run: Run {
+                    commit: row.get(0)?,
+                    machine: row.get(1)?,
+                    time: row.get(2)?,
+                },
+                id: row.get(3)?,
+                mean_ms: row.get(4)?,
+            })
+        })?;
+        Ok(records.collect::<rusqlite::Result<_>>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_estimate(dir: &Path, id: &str, baseline: &str, ns: f64) {
+        let dir = dir.join("2022-day05").join(id).join(baseline);
+        fs::create_dir_all(&dir).unwrap();
+        let json =
+            format!(r#"{{"mean":{{"point_estimate":{ns}}},"median":{{"point_estimate":0}}}}"#);
+        fs::write(dir.join("estimates.json"), json).unwrap();
+    }
+
+    #[test]
+    fn compares() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        write_estimate(dir, "parse", "before", 2_000_000.0);
+        write_estimate(dir, "parse", NEW, 500_000.0);
+        write_estimate(dir, "part1_fast", "before", 1_000_000.0);
+        write_estimate(dir, "part1_fast", NEW, 1_100_000.0);
+        write_estimate(dir, "part2", NEW, 1_000_000.0);
+
+        let ids = ["parse", "part1/fast", "part2"].map(|id| (5, id.to_owned()));
+        let changes = compare(dir, 2022, &ids, "before");
+        assert!(compare(dir, 2023, &ids, "before").is_empty());
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].id, "parse");
+        assert!((changes[0].percent() + 75.0).abs() < 1e-9);
+        assert_eq!(changes[1].id, "part1/fast");
+        assert!((changes[1].percent() - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn keeps_history() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("bench.sqlite");
+        let run = |commit: &str, time| Run {
+            commit: commit.to_owned(),
+            machine: "laptop".to_owned(),
+            time,
+        };
+        let mut history = BenchHistory::open(&path).unwrap();
+        history
+            .record(&run("abc1234", 10), 2022, &[(11, "part2".to_owned(), 9.0)])
+            .unwrap();
+        history
+            .record(&run("abc1234", 20), 2022, &[(11, "part2".to_owned(), 8.0)])
+            .unwrap();
+        history
+            .record(&run("def5678", 30), 2022, &[(11, "part2".to_owned(), 2.0)])
+            .unwrap();
+        drop(history);
+
+        let mut history = BenchHistory::open(&path).unwrap();
+        let records = history.day(2022, 11, "laptop").unwrap();
+        let trend: Vec<_> = records
+            .iter()
+            .map(|r| (r.run.commit.as_str(), r.mean_ms))
+            .collect();
+        assert_eq!(trend, [("abc1234", 8.0), ("def5678", 2.0)]);
+        assert!(history.day(2022, 11, "desktop").unwrap().is_empty());
+
+        history
+            .record_energy(&run("abc1234", 20), 2022, &[(11, 0.5)])
+            .unwrap();
+        history
+            .record_energy(&run("def5678", 30), 2022, &[(11, 0.125)])
+            .unwrap();
+        assert_eq!(
+            history.day_energy(2022, 11, "laptop").unwrap(),
+            [("abc1234".to_owned(), 0.5), ("def5678".to_owned(), 0.125)]
+        );
+    }
+
+    #[test]
+    fn ids() {
+        assert_eq!(bench_id("part1", MAIN), "part1");
+        assert_eq!(bench_id("part1", "fast"), "part1/fast");
+    }
+}
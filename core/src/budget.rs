@@ -0,0 +1,96 @@
+//! How long each day may take on its real input, checked by the opt-in `slow-tests` feature of
+//! every year, so a slow rewrite (or a quadratic loop) does not go unnoticed.
+
+use crate::compress;
+use crate::fetch::{input_path, INPUT_DIR};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+/// Next to `Cargo.toml` of every year.
+pub const BUDGETS_FILE: &str = "budgets.toml";
+
+/// The budgets are for optimized builds, without optimizations (a plain `cargo test`) days get
+/// this many times as long.
+pub const DEBUG_FACTOR: u32 = 10;
+
+/// Milliseconds per day, as `dayNN = <ms>` in `[days]`, and a `default` for the others.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct Budgets {
+    default: u64,
+    #[serde(default)]
+    days: BTreeMap<String, u64>,
+}
+
+impl Budgets {
+    /// # Errors
+    /// If the file can not be read or parsed.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("Invalid budgets {}", path.display()))
+    }
+
+    #[must_use]
+    pub fn budget(&self, day: u8, optimized: bool) -> Duration {
+        let ms = self
+            .days
+            .get(&format!("day{day:02}"))
+            .copied()
+            .unwrap_or(self.default);
+        let budget = Duration::from_millis(ms);
+        if optimized {
+            budget
+        } else {
+            budget * DEBUG_FACTOR
+        }
+    }
+
+    /// Runs the binary of each day on its real input in `input/`, relative to the current
+    /// directory, and returns the days that failed or took longer than their budget. Days without
+    /// an input are skipped.
+    #[must_use]
+    pub fn over_budget(&self, days: &[(u8, PathBuf)], optimized: bool) -> Vec<String> {
+        let mut over = vec![];
+        for (day, exe) in days {
+            if compress::find(&input_path(Path::new(INPUT_DIR), *day)).is_none() {
+                continue;
+            }
+
+            let start = Instant::now();
+            let status = Command::new(exe).output().map(|out| out.status);
+            let took = start.elapsed();
+            let budget = self.budget(*day, optimized);
+            match status {
+                Ok(status) if !status.success() => {
+                    over.push(format!("day {day} failed with {status}"));
+                }
+                Err(e) => over.push(format!("day {day} could not run: {e}")),
+                Ok(_) if took > budget => {
+                    over.push(format!(
+                        "day {day} took {took:.1?}, its budget is {budget:?}"
+                    ));
+                }
+                Ok(_) => {}
+            }
+        }
+        over
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budgets() {
+        let budgets: Budgets = toml::from_str("default = 1000\n[days]\nday06 = 50\n").unwrap();
+        assert_eq!(budgets.budget(6, true), Duration::from_millis(50));
+        assert_eq!(budgets.budget(6, false), Duration::from_millis(500));
+        assert_eq!(budgets.budget(7, true), Duration::from_secs(1));
+    }
+}
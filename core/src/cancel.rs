@@ -0,0 +1,172 @@
+use anyhow::{anyhow, Context, Result};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Cancellation token that long-running loops should poll every now and then.
+///
+/// It is cancelled once the deadline has passed or [`Cancel::cancel`] was called on any clone.
+#[derive(Debug, Clone, Default)]
+pub struct Cancel {
+    deadline: Option<Instant>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Cancel {
+    /// Starts the clock now, without a timeout the token is only cancelled manually.
+    #[must_use]
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            deadline: timeout.map(|t| Instant::now() + t),
+            ..Self::default()
+        }
+    }
+
+    /// [`Cancel::new`] with the [`timeout`] set with [`init`], for solvers that only get the input.
+    #[must_use]
+    pub fn start() -> Self {
+        Self::new(timeout())
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed) || self.deadline.is_some_and(|d| Instant::now() >= d)
+    }
+
+    /// For use with `?`, `partial` describes how far the computation got.
+    ///
+    /// # Errors
+    /// If the token was cancelled.
+    pub fn check(&self, partial: impl FnOnce() -> String) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled {
+                partial: Some(partial()),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Cancelled {
+    pub partial: Option<String>,
+}
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.partial {
+            None => write!(f, "Timed out"),
+            Some(partial) => write!(f, "Timed out, {partial}"),
+        }
+    }
+}
+
+impl Error for Cancelled {}
+
+/// Parses durations like `30s`, `500ms` or `2m`, plain numbers are seconds.
+///
+/// # Errors
+/// If it is none of the above.
+pub fn parse_duration(s: &str) -> Result<Duration> {
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (num, unit) = s.split_at(split);
+    let num: u64 = num
+        .parse()
+        .with_context(|| format!("Invalid duration {s:?}"))?;
+
+    match unit {
+        "ms" => Ok(Duration::from_millis(num)),
+        "" | "s" => Ok(Duration::from_secs(num)),
+        "m" => num
+            .checked_mul(60)
+            .map(Duration::from_secs)
+            .with_context(|| format!("Duration {s:?} is too long")),
+        _ => Err(anyhow!("Unknown unit in duration {s:?}")),
+    }
+}
+
+/// Reads `--timeout <duration>`, other arguments are ignored.
+///
+/// # Errors
+/// If the duration is missing or invalid.
+pub fn timeout_from_args(args: impl IntoIterator<Item = String>) -> Result<Option<Duration>> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--timeout" {
+            return parse_duration(&args.next().context("--timeout needs a duration")?).map(Some);
+        }
+    }
+
+    Ok(None)
+}
+
+static TIMEOUT: OnceLock<Option<Duration>> = OnceLock::new();
+
+/// Sets the timeout [`timeout`] returns. Does nothing if it is set already.
+pub fn init(timeout: Option<Duration>) {
+    let _already = TIMEOUT.set(timeout);
+}
+
+/// [`init`] with the timeout given by `--timeout`.
+///
+/// # Errors
+/// If the duration is missing or invalid.
+pub fn init_from_args(args: impl IntoIterator<Item = String>) -> Result<()> {
+    init(timeout_from_args(args)?);
+    Ok(())
+}
+
+/// The timeout set with [`init`], none if it was not.
+#[must_use]
+pub fn timeout() -> Option<Duration> {
+    TIMEOUT.get().copied().flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_duration("30").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_duration("2m").unwrap(), Duration::from_mins(2));
+        parse_duration("2h").unwrap_err();
+        parse_duration("s").unwrap_err();
+        parse_duration(&format!("{}m", u64::MAX)).unwrap_err();
+    }
+
+    #[test]
+    fn cancel() {
+        let cancel = Cancel::new(None);
+        assert!(cancel.check(|| unreachable!()).is_ok());
+
+        cancel.clone().cancel();
+        assert_eq!(
+            cancel.check(|| "at 42".into()).unwrap_err().to_string(),
+            "Timed out, at 42"
+        );
+
+        assert!(Cancel::new(Some(Duration::ZERO)).is_cancelled());
+    }
+
+    #[test]
+    fn args() {
+        let args = |a: &[&str]| timeout_from_args(a.iter().map(|&s| s.to_owned()));
+
+        assert_eq!(args(&["--checkpoint", "x"]).unwrap(), None);
+        assert_eq!(
+            args(&["--timeout", "1m"]).unwrap(),
+            Some(Duration::from_mins(1))
+        );
+        args(&["--timeout"]).unwrap_err();
+    }
+}
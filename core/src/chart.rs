@@ -0,0 +1,165 @@
+//! Bar charts of how long the days of a year take, to see at a glance which ones are worth a
+//! better algorithm. Written as SVG or PNG, by the extension of the file.
+
+use anyhow::{bail, ensure, Result};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// One bar, stacked from the times of its phases.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bar {
+    pub label: String,
+    /// Phase and milliseconds, from the bottom up
+    pub segments: Vec<(String, f64)>,
+}
+
+impl Bar {
+    #[must_use]
+    pub fn total(&self) -> f64 {
+        self.segments.iter().map(|(_, ms)| ms).sum()
+    }
+}
+
+/// `runtimes-2022.svg` for `runtimes.svg`, for one chart per year.
+#[must_use]
+pub fn year_path(path: &Path, year: u16) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{stem}-{year}.{}", extension.to_string_lossy()),
+        None => format!("{stem}-{year}"),
+    };
+    path.with_file_name(name)
+}
+
+/// Draws `bars` with the phases in the same colors in every bar, and a legend of them.
+///
+/// # Errors
+/// If there are no bars, the extension is neither `svg` nor `png`, or drawing fails, e.g. because
+/// there is no font for the labels of a PNG.
+pub fn runtimes(path: &Path, title: &str, bars: &[Bar]) -> Result<()> {
+    ensure!(!bars.is_empty(), "There is nothing to draw");
+    let size = (160 + 40 * u32::try_from(bars.len())?, 480);
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("svg") => draw(
+            &SVGBackend::new(path, size).into_drawing_area(),
+            title,
+            bars,
+        ),
+        Some("png") => draw(
+            &BitMapBackend::new(path, size).into_drawing_area(),
+            title,
+            bars,
+        ),
+        _ => bail!(
+            "Can only draw charts as .svg or .png, not {}",
+            path.display()
+        ),
+    }
+}
+
+fn draw<DB: DrawingBackend>(root: &DrawingArea<DB, Shift>, title: &str, bars: &[Bar]) -> Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let max = bars.iter().map(Bar::total).fold(0.0, f64::max);
+    let mut phases: Vec<&str> = vec![];
+    for (phase, _) in bars.iter().flat_map(|bar| &bar.segments) {
+        if !phases.contains(&phase.as_str()) {
+            phases.push(phase);
+        }
+    }
+
+    root.fill(&WHITE)?;
+    let mut chart = ChartBuilder::on(root)
+        .caption(title, ("sans-serif", 24))
+        .margin(10)
+        .x_label_area_size(30)
+        .y_label_area_size(60)
+        .build_cartesian_2d((0..bars.len()).into_segmented(), 0.0..max * 1.05)?;
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_labels(bars.len())
+        .x_label_formatter(&|x| match x {
+            SegmentValue::CenterOf(i) => bars.get(*i).map(|b| b.label.clone()).unwrap_or_default(),
+            _ => String::new(),
+        })
+        .y_desc("ms")
+        .draw()?;
+
+    for (n, &phase) in phases.iter().enumerate() {
+        let color = Palette99::pick(n).to_rgba();
+        let rectangles = bars.iter().enumerate().filter_map(|(i, bar)| {
+            let below: f64 = bar
+                .segments
+                .iter()
+                .take_while(|(p, _)| p != phase)
+                .map(|(_, ms)| ms)
+                .sum();
+            let (_, ms) = bar.segments.iter().find(|(p, _)| p == phase)?;
+            let mut rectangle = Rectangle::new(
+                [
+                    (SegmentValue::Exact(i), below),
+                    (SegmentValue::Exact(i + 1), below + ms),
+                ],
+                color.filled(),
+            );
+            rectangle.set_margin(0, 0, 5, 5);
+            Some(rectangle)
+        });
+        chart
+            .draw_series(rectangles)?
+            .label(phase)
+            .legend(move |(x, y)| Rectangle::new([(x, y - 5), (x + 10, y + 5)], color.filled()));
+    }
+
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperLeft)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+    root.present()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(label: &str, segments: &[(&str, f64)]) -> Bar {
+        Bar {
+            label: label.to_owned(),
+            segments: segments.iter().map(|&(p, ms)| (p.to_owned(), ms)).collect(),
+        }
+    }
+
+    #[test]
+    fn draws_svg() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("chart.svg");
+        let bars = [
+            bar("1", &[("parse", 0.1), ("part 1", 0.2), ("part 2", 0.3)]),
+            bar("11", &[("parse", 0.1), ("part 1", 2.0), ("part 2", 90.0)]),
+            bar("12", &[("total", 5.0)]),
+        ];
+        runtimes(&path, "2022", &bars).unwrap();
+
+        let svg = std::fs::read_to_string(&path).unwrap();
+        for text in ["2022", "part 2", "total", "11"] {
+            assert!(svg.lines().any(|line| line.trim() == text), "{text}");
+        }
+
+        runtimes(Path::new("chart.txt"), "2022", &bars).unwrap_err();
+        runtimes(Path::new("chart.svg"), "2022", &[]).unwrap_err();
+    }
+
+    #[test]
+    fn year_paths() {
+        assert_eq!(
+            year_path(Path::new("charts/runtimes.svg"), 2023),
+            Path::new("charts/runtimes-2023.svg")
+        );
+    }
+}
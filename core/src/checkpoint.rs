@@ -0,0 +1,183 @@
+use anyhow::{anyhow, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Periodically saves the state of a long-running computation to a file as JSON, and resumes from
+/// it if it already exists.
+///
+/// Without a path nothing is saved or loaded.
+#[derive(Debug)]
+pub struct Checkpoint {
+    path: Option<PathBuf>,
+    /// Whether the file has to be there, for `--resume`
+    required: bool,
+    interval: Duration,
+    last_save: Instant,
+}
+
+impl Default for Checkpoint {
+    fn default() -> Self {
+        Self {
+            path: None,
+            required: false,
+            interval: Duration::from_secs(10),
+            last_save: Instant::now(),
+        }
+    }
+}
+
+impl Checkpoint {
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>, interval: Duration) -> Self {
+        Self {
+            path: Some(path.into()),
+            interval,
+            ..Self::default()
+        }
+    }
+
+    /// Uses the file given with `--checkpoint <file>`, or `--resume <file>` which also fails if
+    /// there is nothing to resume. Other arguments are ignored.
+    ///
+    /// # Errors
+    /// If the path is missing.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == "--checkpoint" || arg == "--resume" {
+                let path = args.next().with_context(|| format!("{arg} needs a path"))?;
+                return Ok(Self {
+                    required: arg == "--resume",
+                    ..Self::new(path, Self::default().interval)
+                });
+            }
+        }
+
+        Ok(Self::default())
+    }
+
+    /// Loads the state saved by a previous, interrupted run.
+    ///
+    /// # Errors
+    /// If the checkpoint exists but can not be read or is not a `T`, or it is missing after
+    /// `--resume`.
+    pub fn resume<T: DeserializeOwned>(&self) -> Result<Option<T>> {
+        let Some(path) = &self.path else {
+            return Ok(None);
+        };
+        if !path.exists() {
+            return if self.required {
+                Err(anyhow!(
+                    "There is no checkpoint {} to resume",
+                    path.display()
+                ))
+            } else {
+                Ok(None)
+            };
+        }
+
+        let saved = fs::read_to_string(path)
+            .with_context(|| format!("Could not read checkpoint {}", path.display()))?;
+        let state = serde_json::from_str(&saved)
+            .with_context(|| format!("Invalid checkpoint {}", path.display()))?;
+
+        eprintln!("Resuming from {}", path.display());
+        Ok(Some(state))
+    }
+
+    /// Saves `state` if the last save is longer than the interval ago. Call this regularly, but
+    /// not in the innermost loop.
+    ///
+    /// # Errors
+    /// If the checkpoint can not be written.
+    pub fn tick<T: Serialize + ?Sized>(&mut self, state: &T) -> Result<()> {
+        if self.last_save.elapsed() < self.interval {
+            return Ok(());
+        }
+
+        self.save(state)
+    }
+
+    /// # Errors
+    /// If the checkpoint can not be written.
+    pub fn save<T: Serialize + ?Sized>(&mut self, state: &T) -> Result<()> {
+        self.last_save = Instant::now();
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        // Write next to it first, so an interrupt while saving does not lose the old checkpoint
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_string(state)?)
+            .with_context(|| format!("Could not write checkpoint {}", tmp.display()))?;
+        fs::rename(&tmp, path)?;
+
+        Ok(())
+    }
+
+    /// Removes the checkpoint once the computation has finished.
+    ///
+    /// # Errors
+    /// If the checkpoint exists but can not be removed.
+    pub fn done(self) -> Result<()> {
+        match self.path {
+            Some(path) if path.exists() => Ok(fs::remove_file(path)?),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+    struct Counter(u64);
+
+    fn args<'a>(args: &'a [&str]) -> impl Iterator<Item = String> + 'a {
+        args.iter().map(|&a| a.to_owned())
+    }
+
+    #[test]
+    fn save_and_resume() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("checkpoint");
+
+        let mut checkpoint = Checkpoint::new(&path, Duration::ZERO);
+        assert_eq!(checkpoint.resume::<Counter>().unwrap(), None);
+        checkpoint.tick(&Counter(42)).unwrap();
+
+        let checkpoint =
+            Checkpoint::from_args(args(&["--resume", path.to_str().unwrap()])).unwrap();
+        assert_eq!(checkpoint.resume().unwrap(), Some(Counter(42)));
+        checkpoint.resume::<String>().unwrap_err();
+
+        checkpoint.done().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn disabled() {
+        let mut checkpoint = Checkpoint::from_args(args(&["--other"])).unwrap();
+        checkpoint.save(&Counter(1)).unwrap();
+        assert_eq!(checkpoint.resume::<Counter>().unwrap(), None);
+    }
+
+    #[test]
+    fn resume_needs_checkpoint() {
+        let tmp = tempfile::tempdir().unwrap();
+        let missing = tmp.path().join("missing");
+        let missing = missing.to_str().unwrap();
+
+        let checkpoint = Checkpoint::from_args(args(&["--checkpoint", missing])).unwrap();
+        assert_eq!(checkpoint.resume::<Counter>().unwrap(), None);
+        let checkpoint = Checkpoint::from_args(args(&["--resume", missing])).unwrap();
+        let err = checkpoint.resume::<Counter>().unwrap_err();
+        assert!(err.to_string().contains("no checkpoint"), "{err}");
+        Checkpoint::from_args(args(&["--resume"])).unwrap_err();
+    }
+}
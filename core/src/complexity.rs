@@ -0,0 +1,145 @@
+//! How the time of a solver grows with its input, for `aoc complexity`: times it on generated
+//! inputs of several sizes and fits `time = c * n^k`, to catch an accidental O(n²).
+
+use crate::energy::Rapl;
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Sample {
+    pub n: u64,
+    pub time: Duration,
+    /// Joules used by the fastest run, if the energy counters are readable
+    pub energy: Option<f64>,
+}
+
+/// Times `solve` on generated inputs of the given sizes, keeping the fastest of `runs` runs each.
+pub fn measure<I>(
+    sizes: impl IntoIterator<Item = u64>,
+    runs: usize,
+    generate: impl Fn(u64) -> I,
+    solve: impl Fn(&I),
+) -> Vec<Sample> {
+    let rapl = Rapl::open();
+
+    sizes
+        .into_iter()
+        .map(|n| {
+            let input = generate(n);
+            let (time, energy) = (0..runs.max(1))
+                .map(|_| {
+                    let before = rapl.as_ref().and_then(Rapl::read);
+                    let start = Instant::now();
+                    solve(black_box(&input));
+                    let time = start.elapsed();
+                    let after = rapl.as_ref().and_then(Rapl::read);
+
+                    let energy = match (&rapl, before, after) {
+                        (Some(rapl), Some(before), Some(after)) => {
+                            Some(rapl.joules(&before, &after))
+                        }
+                        _ => None,
+                    };
+                    (time, energy)
+                })
+                .min_by_key(|(time, _)| *time)
+                .unwrap_or_default();
+
+            Sample { n, time, energy }
+        })
+        .collect()
+}
+
+/// Least squares fit of `time = c * n^k` in log-log space, returns `k`.
+///
+/// Needs at least two different sizes.
+#[must_use]
+#[allow(clippy::cast_precision_loss)] // Nobody measures inputs of 2^52 elements
+pub fn fit_exponent(samples: &[Sample]) -> Option<f64> {
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .filter(|s| s.n > 0 && !s.time.is_zero())
+        .map(|s| ((s.n as f64).ln(), s.time.as_secs_f64().ln()))
+        .collect();
+
+    let count = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / count;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / count;
+
+    let cov: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let var: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    (var > 0.0).then(|| cov / var)
+}
+
+/// One line per sample, followed by the estimated exponent.
+///
+/// # Panics
+/// Never, writing to a `String` does not fail.
+#[must_use]
+pub fn report(samples: &[Sample]) -> String {
+    let mut out = String::new();
+    for s in samples {
+        write!(out, "{:>12} {:>12.3?}", s.n, s.time).expect("Writing to a String does not fail");
+        if let Some(joules) = s.energy {
+            write!(out, " {joules:>10.6} J").expect("Writing to a String does not fail");
+        }
+        out.push('\n');
+    }
+
+    match fit_exponent(samples) {
+        Some(k) => writeln!(out, "Estimated complexity: O(n^{k:.2})"),
+        None => writeln!(out, "Not enough samples to estimate the complexity"),
+    }
+    .expect("Writing to a String does not fail");
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(f: impl Fn(u64) -> u64) -> Vec<Sample> {
+        [10, 100, 1000, 10_000]
+            .into_iter()
+            .map(|n| Sample {
+                n,
+                time: Duration::from_nanos(f(n)),
+                energy: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn exponents() {
+        let linear = fit_exponent(&samples(|n| 5 * n)).unwrap();
+        assert!((linear - 1.0).abs() < 1e-9, "{linear}");
+
+        let quadratic = fit_exponent(&samples(|n| n * n + 3)).unwrap();
+        assert!((quadratic - 2.0).abs() < 0.01, "{quadratic}");
+
+        assert_eq!(fit_exponent(&samples(|n| n)[..1]), None);
+    }
+
+    #[test]
+    fn measures_every_size() {
+        let samples = measure(
+            [1, 2, 3],
+            2,
+            |n| vec![0u8; usize::try_from(n).unwrap()],
+            |v| {
+                assert!(!v.is_empty());
+            },
+        );
+
+        assert_eq!(
+            samples.iter().map(|s| s.n).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+    }
+}
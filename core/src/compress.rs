@@ -0,0 +1,98 @@
+//! Inputs may be stored compressed, `input/day09.txt.gz` or `input/day09.txt.zst` are read as if
+//! they were `input/day09.txt`.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// The extensions of compressed inputs, in the order they are looked for.
+pub const EXTENSIONS: [&str; 2] = ["gz", "zst"];
+
+/// `input/day09.txt.gz` for `input/day09.txt` and `gz`.
+#[must_use]
+pub fn compressed_path(path: &Path, extension: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(extension);
+    PathBuf::from(name)
+}
+
+/// The file that holds `path`: the file itself if it exists, or one of its compressed versions.
+#[must_use]
+pub fn find(path: &Path) -> Option<PathBuf> {
+    if path.exists() {
+        return Some(path.to_owned());
+    }
+    EXTENSIONS
+        .iter()
+        .map(|extension| compressed_path(path, extension))
+        .find(|compressed| compressed.exists())
+}
+
+/// Reads `path` or the compressed version [`find`] gives, decompressing by the extension.
+///
+/// # Errors
+/// If there is no such file, it can not be read, is broken or is no UTF-8.
+pub fn read(path: &Path) -> Result<String> {
+    let found = find(path).unwrap_or_else(|| path.to_owned());
+    let bytes = fs::read(&found).with_context(|| format!("Could not read {}", found.display()))?;
+    let extension = found.extension().and_then(|e| e.to_str());
+    let text = decompress(&bytes, extension)
+        .with_context(|| format!("Could not decompress {}", found.display()))?;
+    String::from_utf8(text).with_context(|| format!("{} is no UTF-8", found.display()))
+}
+
+/// `bytes` as compressed with the format of `extension`, as they are for anything else.
+///
+/// # Errors
+/// If they are not properly compressed.
+pub fn decompress(bytes: &[u8], extension: Option<&str>) -> Result<Vec<u8>> {
+    let mut out = vec![];
+    match extension {
+        Some("gz") => {
+            flate2::read::MultiGzDecoder::new(bytes).read_to_end(&mut out)?;
+        }
+        Some("zst") => {
+            let mut bytes = bytes;
+            ruzstd::decoding::StreamingDecoder::new(&mut bytes)?.read_to_end(&mut out)?;
+        }
+        _ => out = bytes.to_vec(),
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn reads_compressed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let input = "1000\n2000\n";
+
+        let mut gz = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+        gz.write_all(input.as_bytes()).unwrap();
+        fs::write(dir.join("day01.txt.gz"), gz.finish().unwrap()).unwrap();
+        let zst = ruzstd::encoding::compress_to_vec(
+            input.as_bytes(),
+            ruzstd::encoding::CompressionLevel::Fastest,
+        );
+        fs::write(dir.join("day02.txt.zst"), zst).unwrap();
+        fs::write(dir.join("day03.txt"), input).unwrap();
+
+        for day in 1..=3 {
+            let path = dir.join(format!("day{day:02}.txt"));
+            assert_eq!(read(&path).unwrap(), input, "{}", path.display());
+        }
+        assert_eq!(find(&dir.join("day01.txt")), Some(dir.join("day01.txt.gz")));
+        assert_eq!(read(&dir.join("day01.txt.gz")).unwrap(), input);
+
+        assert_eq!(find(&dir.join("day04.txt")), None);
+        read(&dir.join("day04.txt")).unwrap_err();
+        fs::write(dir.join("day05.txt.gz"), input).unwrap();
+        read(&dir.join("day05.txt")).unwrap_err();
+    }
+}
@@ -0,0 +1,223 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Sub};
+
+/// Position on a grid, `x` grows to the right and `y` downwards.
+#[derive(
+    Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize, Deserialize,
+)]
+pub struct Coord {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Coord {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    #[must_use]
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    /// The neighbouring position in `dir`.
+    #[must_use]
+    pub fn step(self, dir: impl Direction) -> Self {
+        self + dir.offset()
+    }
+
+    /// `n` steps in `dir`, backwards for negative `n`.
+    #[must_use]
+    pub fn step_n(self, dir: impl Direction, n: isize) -> Self {
+        let offset = dir.offset();
+        Self::new(self.x + offset.x * n, self.y + offset.y * n)
+    }
+
+    /// Steps needed to get to `other` when only going along the axes.
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Steps needed to get to `other` when diagonal steps are allowed.
+    #[must_use]
+    pub fn chebyshev(self, other: Self) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+impl Add for Coord {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Coord {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Display for Coord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Something a [`Coord`] can step into.
+pub trait Direction: Copy {
+    /// The step from a position to its neighbour in this direction.
+    fn offset(self) -> Coord;
+}
+
+/// The directions along the axes, clockwise starting upwards.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// `quarters` quarter turns clockwise, counterclockwise for negative ones.
+    #[must_use]
+    pub fn turn(self, quarters: isize) -> Self {
+        Self::ALL[(self as isize + quarters).rem_euclid(4).unsigned_abs()]
+    }
+
+    #[must_use]
+    pub fn right(self) -> Self {
+        self.turn(1)
+    }
+
+    #[must_use]
+    pub fn left(self) -> Self {
+        self.turn(-1)
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        self.turn(2)
+    }
+}
+
+impl Direction for Dir4 {
+    fn offset(self) -> Coord {
+        Dir8::from(self).offset()
+    }
+}
+
+/// The directions along the axes and diagonals, clockwise starting upwards.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// `eighths` turns of 45° clockwise, counterclockwise for negative ones.
+    #[must_use]
+    pub fn turn(self, eighths: isize) -> Self {
+        Self::ALL[(self as isize + eighths).rem_euclid(8).unsigned_abs()]
+    }
+
+    #[must_use]
+    pub fn right(self) -> Self {
+        self.turn(2)
+    }
+
+    #[must_use]
+    pub fn left(self) -> Self {
+        self.turn(-2)
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        self.turn(4)
+    }
+}
+
+impl Direction for Dir8 {
+    fn offset(self) -> Coord {
+        let (x, y) = match self {
+            Self::Up => (0, -1),
+            Self::UpRight => (1, -1),
+            Self::Right => (1, 0),
+            Self::DownRight => (1, 1),
+            Self::Down => (0, 1),
+            Self::DownLeft => (-1, 1),
+            Self::Left => (-1, 0),
+            Self::UpLeft => (-1, -1),
+        };
+        Coord::new(x, y)
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::Up => Self::Up,
+            Dir4::Right => Self::Right,
+            Dir4::Down => Self::Down,
+            Dir4::Left => Self::Left,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning() {
+        assert_eq!(Dir4::Up.right(), Dir4::Right);
+        assert_eq!(Dir4::Up.left(), Dir4::Left);
+        assert_eq!(Dir4::Left.reverse(), Dir4::Right);
+        assert_eq!(Dir4::Down.turn(-7), Dir4::Left);
+
+        assert_eq!(Dir8::Up.turn(1), Dir8::UpRight);
+        assert_eq!(Dir8::Up.turn(-1), Dir8::UpLeft);
+        assert_eq!(Dir8::DownLeft.right(), Dir8::UpLeft);
+        assert_eq!(Dir8::DownLeft.reverse(), Dir8::UpRight);
+    }
+
+    #[test]
+    fn stepping() {
+        let c = Coord::new(2, 3);
+        assert_eq!(c.step(Dir4::Up), Coord::new(2, 2));
+        assert_eq!(c.step(Dir8::DownLeft), Coord::new(1, 4));
+        assert_eq!(c.step_n(Dir4::Right, 3), Coord::new(5, 3));
+        assert_eq!(c.step_n(Dir4::Right, -3), Coord::new(-1, 3));
+
+        // Stepping there and back again leads to the start in all directions
+        for dir in Dir8::ALL {
+            assert_eq!(c.step(dir).step(dir.reverse()), c);
+        }
+
+        assert_eq!(c.manhattan(Coord::new(-1, 5)), 5);
+        assert_eq!(c.chebyshev(Coord::new(-1, 5)), 3);
+    }
+}
@@ -0,0 +1,226 @@
+//! Inputs may not be published, but they can be committed encrypted: `input/dayNN.txt.enc` next to
+//! the git ignored `input/dayNN.txt`, which is decrypted from it when it is missing and a key is
+//! there.
+
+use anyhow::{anyhow, ensure, Context, Result};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::fmt::{Debug, Formatter, Write as _};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// At the start of every encrypted file, also authenticated with it.
+const MAGIC: &[u8] = b"aoc-input-v1\n";
+
+/// The git ignored key file in the current directory, besides `AOC_INPUT_KEY` and
+/// `~/.config/aoc/input-key`.
+pub const KEY_FILE: &str = ".input-key";
+
+/// `input/day01.txt.enc` for `input/day01.txt`.
+#[must_use]
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".enc");
+    PathBuf::from(name)
+}
+
+/// An AES-256 key, written as 64 hex digits.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Key([u8; 32]);
+
+impl Debug for Key {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Key(..)")
+    }
+}
+
+impl Key {
+    /// # Errors
+    /// If the system has no randomness to offer.
+    pub fn generate() -> Result<Self> {
+        let mut key = [0; 32];
+        SystemRandom::new()
+            .fill(&mut key)
+            .map_err(|_| anyhow!("Could not generate a key"))?;
+        Ok(Self(key))
+    }
+
+    /// # Errors
+    /// If it is not 64 hex digits.
+    pub fn from_hex(hex: &str) -> Result<Self> {
+        let hex = hex.trim();
+        // `from_str_radix` would also take a sign like `+f`
+        ensure!(
+            hex.len() == 64 && hex.bytes().all(|b| b.is_ascii_hexdigit()),
+            "A key has to be 64 hex digits"
+        );
+        let mut key = [0; 32];
+        for (byte, digits) in key.iter_mut().zip(hex.as_bytes().chunks(2)) {
+            let digits = std::str::from_utf8(digits)?;
+            *byte = u8::from_str_radix(digits, 16)
+                .with_context(|| format!("{digits:?} in the key is not hex"))?;
+        }
+        Ok(Self(key))
+    }
+
+    /// # Panics
+    /// Never, writing to a `String` does not fail.
+    #[must_use]
+    pub fn to_hex(&self) -> String {
+        self.0.iter().fold(String::new(), |mut hex, b| {
+            write!(hex, "{b:02x}").unwrap();
+            hex
+        })
+    }
+
+    /// The key from `AOC_INPUT_KEY`, [`KEY_FILE`] or `~/.config/aoc/input-key`, `None` if there is
+    /// none.
+    ///
+    /// # Errors
+    /// If a key is set but is not valid.
+    pub fn load() -> Result<Option<Self>> {
+        if let Ok(hex) = std::env::var("AOC_INPUT_KEY") {
+            return Self::from_hex(&hex)
+                .context("Invalid AOC_INPUT_KEY")
+                .map(Some);
+        }
+
+        let files = std::iter::once(PathBuf::from(KEY_FILE)).chain(config_key_path());
+        for file in files {
+            if let Ok(hex) = fs::read_to_string(&file) {
+                return Self::from_hex(&hex)
+                    .with_context(|| format!("Invalid key in {}", file.display()))
+                    .map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn aead(&self) -> LessSafeKey {
+        LessSafeKey::new(UnboundKey::new(&AES_256_GCM, &self.0).expect("The key has 32 bytes"))
+    }
+
+    /// # Errors
+    /// If the system has no randomness for the nonce.
+    pub fn encrypt(&self, plain: &[u8]) -> Result<Vec<u8>> {
+        let mut nonce = [0; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| anyhow!("Could not generate a nonce"))?;
+
+        let mut sealed = plain.to_vec();
+        self.aead()
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(MAGIC),
+                &mut sealed,
+            )
+            .map_err(|_| anyhow!("Could not encrypt"))?;
+
+        Ok([MAGIC, &nonce, &sealed].concat())
+    }
+
+    /// # Errors
+    /// If `data` was not encrypted with this key, or was changed since.
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let sealed = data
+            .strip_prefix(MAGIC)
+            .context("This is not an encrypted input")?;
+        ensure!(sealed.len() > NONCE_LEN, "The encrypted input is cut off");
+        let (nonce, sealed) = sealed.split_at(NONCE_LEN);
+
+        let mut sealed = sealed.to_vec();
+        let plain = self
+            .aead()
+            .open_in_place(
+                Nonce::try_assume_unique_for_key(nonce).map_err(|_| anyhow!("Invalid nonce"))?,
+                Aad::from(MAGIC),
+                &mut sealed,
+            )
+            .map_err(|_| anyhow!("Could not decrypt, the key is wrong or the file was changed"))?;
+        Ok(plain.to_vec())
+    }
+}
+
+/// `~/.config/aoc/input-key`, next to the session cookie.
+#[must_use]
+pub fn config_key_path() -> Option<PathBuf> {
+    crate::fetch::config_path("input-key")
+}
+
+/// Decrypts `path` from [`encrypted_path`] if it is missing, the encrypted file exists and there is
+/// a key. Returns whether it did.
+///
+/// # Errors
+/// If the key is invalid or wrong, or the files can not be read or written.
+pub fn decrypt_missing(path: &Path) -> Result<bool> {
+    let encrypted = encrypted_path(path);
+    if path.exists() || !encrypted.exists() {
+        return Ok(false);
+    }
+    let Some(key) = Key::load()? else {
+        return Ok(false);
+    };
+
+    decrypt_file(&key, &encrypted, path)?;
+    Ok(true)
+}
+
+/// # Errors
+/// If the files can not be read or written.
+pub fn encrypt_file(key: &Key, plain: &Path, encrypted: &Path) -> Result<()> {
+    let data = fs::read(plain).with_context(|| format!("Could not read {}", plain.display()))?;
+    fs::write(encrypted, key.encrypt(&data)?)
+        .with_context(|| format!("Could not write {}", encrypted.display()))
+}
+
+/// # Errors
+/// If the key is wrong or the files can not be read or written.
+pub fn decrypt_file(key: &Key, encrypted: &Path, plain: &Path) -> Result<()> {
+    let data =
+        fs::read(encrypted).with_context(|| format!("Could not read {}", encrypted.display()))?;
+    let data = key
+        .decrypt(&data)
+        .with_context(|| encrypted.display().to_string())?;
+    fs::write(plain, data).with_context(|| format!("Could not write {}", plain.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let key = Key::generate().unwrap();
+        let encrypted = key.encrypt(b"1000\n2000\n").unwrap();
+        assert!(encrypted.starts_with(MAGIC));
+        assert_ne!(key.encrypt(b"1000\n2000\n").unwrap(), encrypted);
+        assert_eq!(key.decrypt(&encrypted).unwrap(), b"1000\n2000\n");
+
+        let other = Key::generate().unwrap();
+        other.decrypt(&encrypted).unwrap_err();
+        let mut changed = encrypted.clone();
+        *changed.last_mut().unwrap() ^= 1;
+        key.decrypt(&changed).unwrap_err();
+        key.decrypt(b"1000\n2000\n").unwrap_err();
+    }
+
+    #[test]
+    fn hex() {
+        let key = Key::generate().unwrap();
+        assert_eq!(Key::from_hex(&format!("{}\n", key.to_hex())).unwrap(), key);
+        Key::from_hex("abc").unwrap_err();
+        Key::from_hex(&"g".repeat(64)).unwrap_err();
+        Key::from_hex(&"+f".repeat(32)).unwrap_err();
+        assert_eq!(format!("{key:?}"), "Key(..)");
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(
+            encrypted_path(Path::new("input/day01.txt")),
+            PathBuf::from("input/day01.txt.enc")
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// States from `start` on repeat every `period` steps.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// Index before the cycle completes for the first time with the same state as index `n`.
+    #[must_use]
+    pub fn equivalent_index(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+
+    /// Extrapolates an accumulated value (e.g. a height or a score), which grows by the same
+    /// amount every period, to index `n`. `values` needs at least `start + period + 1` entries.
+    ///
+    /// # Panics
+    /// If `values` is too short.
+    #[must_use]
+    pub fn extrapolate(&self, values: &[i64], n: usize) -> i64 {
+        let end = self.start + self.period;
+        assert!(values.len() > end, "Need values up to the end of the cycle");
+
+        if n < end {
+            return values[n];
+        }
+
+        let per_period = values[end] - values[self.start];
+        let periods = i64::try_from((n - self.start) / self.period).expect("Too many periods");
+        values[self.equivalent_index(n)] + periods * per_period
+    }
+}
+
+/// Finds the first repeated state by remembering all states seen. The iterator is consumed until
+/// the first repetition.
+pub fn find_cycle<S: Hash + Eq>(states: impl IntoIterator<Item = S>) -> Option<Cycle> {
+    let mut seen = HashMap::new();
+
+    for (idx, state) in states.into_iter().enumerate() {
+        if let Some(start) = seen.insert(state, idx) {
+            return Some(Cycle {
+                start,
+                period: idx - start,
+            });
+        }
+    }
+
+    None
+}
+
+/// Brent's algorithm for `x0, f(x0), f(f(x0)), ...`, only keeps two states in memory and calls
+/// `f` about `3 * (start + period)` times.
+pub fn brent<S: Eq + Clone>(x0: S, mut f: impl FnMut(&S) -> S) -> Cycle {
+    // Find the period by letting the tortoise teleport to the hare at powers of two
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(&x0);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = f(&hare);
+        period += 1;
+    }
+
+    // Hare runs `period` ahead, they meet at the start of the cycle
+    let mut tortoise = x0.clone();
+    let mut hare = x0;
+    for _ in 0..period {
+        hare = f(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    Cycle { start, period }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_cycles() {
+        // 0 1 2 3 4 5 6 7 | 3 ...
+        let next = |x: &u32| if *x == 7 { 3 } else { x + 1 };
+        let expected = Cycle {
+            start: 3,
+            period: 5,
+        };
+
+        assert_eq!(brent(0, next), expected);
+        assert_eq!(
+            find_cycle(std::iter::successors(Some(0), |x| Some(next(x)))),
+            Some(expected)
+        );
+
+        assert_eq!(
+            brent(5, |x| *x),
+            Cycle {
+                start: 0,
+                period: 1
+            }
+        );
+        assert_eq!(find_cycle(0..100), None);
+    }
+
+    #[test]
+    fn agrees_on_lcg() {
+        let next = |x: &u64| (x * x + 1) % 1_000;
+        for x0 in 0..50 {
+            let cycle = brent(x0, next);
+            let states = std::iter::successors(Some(x0), |x| Some(next(x)));
+            assert_eq!(find_cycle(states), Some(cycle), "x0 = {x0}");
+        }
+    }
+
+    #[test]
+    fn extrapolates() {
+        let cycle = Cycle {
+            start: 2,
+            period: 3,
+        };
+        // Prefix adds 10 and 1, then every period adds 1 + 2 + 3
+        let values = [0, 10, 11, 12, 14, 17, 18];
+
+        assert_eq!(cycle.equivalent_index(1), 1);
+        assert_eq!(cycle.equivalent_index(5), 2);
+        assert_eq!(cycle.equivalent_index(9), 3);
+
+        for (n, &value) in values.iter().enumerate() {
+            assert_eq!(cycle.extrapolate(&values, n), value);
+        }
+        assert_eq!(cycle.extrapolate(&values, 9), 24);
+        assert_eq!(cycle.extrapolate(&values, 2 + 3 * 1_000), 11 + 6 * 1_000);
+    }
+}
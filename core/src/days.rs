@@ -0,0 +1,117 @@
+use crate::answer::Answer;
+use crate::registry::{bin_name, day_feature, implemented_days};
+use anyhow::{anyhow, Context, Result};
+use std::path::{Path, PathBuf};
+
+/// The binary of a day, next to the tool that is running.
+///
+/// # Errors
+/// If it has not been built.
+pub fn day_binary(year: u16, day: u8) -> Result<PathBuf> {
+    let exe = std::env::current_exe()?;
+    let path = exe.with_file_name(bin_name(year, day));
+
+    if path.exists() {
+        Ok(path)
+    } else {
+        Err(anyhow!(
+            "{} does not exist, build it with `cargo build --bins` (or `--features {}`)",
+            path.display(),
+            day_feature(year, day)
+        ))
+    }
+}
+
+/// The binaries of every day of the year crate at `root` (see [`implemented_days`]), for the tests
+/// and benches of that crate. Cargo builds them next to the `deps/` directory those run from, so
+/// a new day is picked up without listing its `CARGO_BIN_EXE_dayNN`.
+///
+/// # Errors
+/// If the running executable can not be found.
+pub fn crate_binaries(root: &Path, year: u16) -> Result<Vec<(u8, PathBuf)>> {
+    let exe = std::env::current_exe()?;
+    let deps = exe.parent().context("The executable has no directory")?;
+    let dir = if deps.ends_with("deps") {
+        deps.parent().unwrap_or(deps)
+    } else {
+        deps
+    };
+
+    Ok(implemented_days(root)
+        .into_iter()
+        .map(|day| (day, dir.join(bin_name(year, day))))
+        .collect())
+}
+
+/// The answers a day printed as `Part 1: <answer>`, answers that start on the next line (like
+/// ASCII art) go until the next part. Parts that printed nothing are left out.
+#[must_use]
+pub fn parse_answers(output: &str) -> Vec<(String, Answer)> {
+    let mut answers: Vec<(String, String)> = vec![];
+    let mut multiline = false;
+
+    for line in output.lines() {
+        if let Some((part, answer)) = line
+            .split_once(':')
+            .filter(|(part, _)| part.starts_with("Part "))
+        {
+            multiline = answer.trim().is_empty();
+            answers.push((part.to_owned(), answer.trim().to_owned()));
+        } else if let (true, Some((_, answer))) = (multiline, answers.last_mut()) {
+            if !answer.is_empty() {
+                answer.push('\n');
+            }
+            answer.push_str(line);
+        }
+    }
+
+    answers
+        .into_iter()
+        .filter(|(_, answer)| !answer.is_empty())
+        .map(|(part, answer)| {
+            let Ok(answer) = answer.parse();
+            (part, answer)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers() {
+        let output = "Part 1: 13140\nsome debug output\nPart 2:\n##  ##\n#  #\nPart 3:\n";
+        assert_eq!(
+            parse_answers(output),
+            vec![
+                ("Part 1".to_owned(), Answer::Int(13140)),
+                ("Part 2".to_owned(), Answer::Grid("##  ##\n#  #".to_owned()))
+            ]
+        );
+    }
+    #[test]
+    fn binaries_of_a_crate() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("src/bin")).unwrap();
+        for file in ["day01.rs", "day04.rs", "report.rs"] {
+            std::fs::write(root.join("src/bin").join(file), "").unwrap();
+        }
+
+        let binaries = crate_binaries(root, 2023).unwrap();
+        assert_eq!(
+            binaries.iter().map(|(day, _)| *day).collect::<Vec<_>>(),
+            [1, 4]
+        );
+        // Unit tests run from `target/debug/deps` too
+        let dir = std::env::current_exe()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .to_owned();
+        assert_eq!(binaries[1].1, dir.join("aoc2023-day04"));
+    }
+}
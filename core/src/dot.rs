@@ -0,0 +1,138 @@
+//! `--dot <file>`: graph-shaped data of a day as Graphviz DOT, to look at with
+//! `dot -Tsvg <file> > graph.svg`.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A directed graph, nodes and edges are kept sorted so the same input always gives the same
+/// file. Adding a node or edge twice keeps one of them.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Graph {
+    name: String,
+    /// Labels by id
+    nodes: BTreeMap<String, String>,
+    edges: BTreeSet<(String, String, Option<String>)>,
+}
+
+impl Graph {
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Adds a node or changes its label, labels may span lines.
+    pub fn node(&mut self, id: impl Display, label: impl Display) {
+        self.nodes.insert(id.to_string(), label.to_string());
+    }
+
+    /// Edges to nodes that were not added get their id as label.
+    pub fn edge(&mut self, from: impl Display, to: impl Display, label: Option<&str>) {
+        self.edges
+            .insert((from.to_string(), to.to_string(), label.map(str::to_owned)));
+    }
+
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+/// A quoted DOT string.
+fn quoted(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+impl Display for Graph {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "digraph {} {{", quoted(&self.name))?;
+        for (id, label) in &self.nodes {
+            writeln!(f, "    {} [label={}];", quoted(id), quoted(label))?;
+        }
+        for (from, to, label) in &self.edges {
+            write!(f, "    {} -> {}", quoted(from), quoted(to))?;
+            match label {
+                Some(label) => writeln!(f, " [label={}];", quoted(label))?,
+                None => writeln!(f, ";")?,
+            }
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// The file given with `--dot <file>`, other arguments are ignored.
+///
+/// # Errors
+/// If the path is missing.
+pub fn path_from_args(args: impl IntoIterator<Item = String>) -> Result<Option<PathBuf>> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--dot" {
+            return Ok(Some(args.next().context("--dot needs a path")?.into()));
+        }
+    }
+    Ok(None)
+}
+
+/// Writes the graph to `path` and says so.
+///
+/// # Errors
+/// If the file can not be written.
+pub fn write(path: &Path, graph: &Graph) -> Result<()> {
+    fs::write(path, graph.to_string())
+        .with_context(|| format!("Could not write {}", path.display()))?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders() {
+        let mut graph = Graph::new("day07");
+        graph.node("/a", "a\n\"quoted\"");
+        graph.node("/", "/");
+        graph.edge("/", "/a", None);
+        graph.edge("/a", "/", Some("up"));
+        graph.edge("/", "/a", None);
+
+        assert_eq!(
+            graph.to_string(),
+            r#"digraph "day07" {
+    "/" [label="/"];
+    "/a" [label="a\n\"quoted\""];
+    "/" -> "/a";
+    "/a" -> "/" [label="up"];
+}
+"#
+        );
+        assert_eq!((graph.node_count(), graph.edge_count()), (2, 2));
+    }
+
+    #[test]
+    fn args() {
+        let args = |args: &[&str]| path_from_args(args.iter().map(ToString::to_string));
+        assert_eq!(args(&["--part", "1"]).unwrap(), None);
+        assert_eq!(
+            args(&["--dot", "tree.dot"]).unwrap(),
+            Some(PathBuf::from("tree.dot"))
+        );
+        args(&["--dot"]).unwrap_err();
+    }
+}
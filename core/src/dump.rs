@@ -0,0 +1,46 @@
+//! `--parse-only`: the parsed input of a day as JSON instead of its answers, to debug a parser
+//! without running the (sometimes slow) parts.
+
+use crate::solver::Solver;
+use anyhow::Result;
+use serde::Serialize;
+
+/// Whether `--parse-only` was given.
+#[must_use]
+pub fn requested(mut args: impl Iterator<Item = String>) -> bool {
+    args.any(|arg| arg == "--parse-only")
+}
+
+/// Pretty JSON, for the [`Solver::dump`] of days whose input can be serialized.
+///
+/// # Errors
+/// If serializing fails, e.g. because of a map with keys that are not strings.
+pub fn json<T: Serialize + ?Sized>(input: &T) -> Result<String> {
+    Ok(serde_json::to_string_pretty(input)? + "\n")
+}
+
+/// Prints the [`Solver::dump`] of a parsed input, for days with their own `main`.
+///
+/// # Errors
+/// If the day can not show its input.
+pub fn print<S: Solver>(input: &S::Input) -> Result<()> {
+    print!("{}", S::dump(input)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dumps() {
+        let args = ["--part", "1", "--parse-only"].map(str::to_owned);
+        assert!(requested(args.into_iter()));
+        assert!(!requested(["--part".to_owned()].into_iter()));
+
+        assert_eq!(
+            json(&[(1, 'a')]).unwrap(),
+            "[\n  [\n    1,\n    \"a\"\n  ]\n]\n"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Joules used by the CPU while something runs, for `aoc complexity` and `aoc bench`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const POWERCAP: &str = "/sys/class/powercap";
+
+/// Energy counter of one CPU package, in microjoules.
+#[derive(Debug, Clone)]
+struct Zone {
+    energy: PathBuf,
+    max_energy: u64,
+}
+
+/// Energy counters of Intel RAPL (also used by AMD), as exposed by the Linux powercap driver.
+///
+/// Reading them usually needs root, without access or on other systems there are no readings.
+#[derive(Debug, Clone)]
+pub struct Rapl {
+    zones: Vec<Zone>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Reading(Vec<u64>);
+
+fn read_number(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+impl Rapl {
+    /// `None` if there are no readable counters.
+    #[must_use]
+    pub fn open() -> Option<Self> {
+        Self::open_in(Path::new(POWERCAP))
+    }
+
+    fn open_in(powercap: &Path) -> Option<Self> {
+        let mut zones: Vec<Zone> = fs::read_dir(powercap)
+            .ok()?
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                let name = path.file_name()?.to_str()?;
+                // Only packages like `intel-rapl:0`, their subzones like `intel-rapl:0:0` are
+                // already included in them
+                if !name.starts_with("intel-rapl:") || name.matches(':').count() != 1 {
+                    return None;
+                }
+
+                let energy = path.join("energy_uj");
+                read_number(&energy)?;
+                Some(Zone {
+                    energy,
+                    max_energy: read_number(&path.join("max_energy_range_uj"))?,
+                })
+            })
+            .collect();
+        zones.sort_by(|a, b| a.energy.cmp(&b.energy));
+
+        (!zones.is_empty()).then_some(Self { zones })
+    }
+
+    #[must_use]
+    pub fn read(&self) -> Option<Reading> {
+        self.zones
+            .iter()
+            .map(|z| read_number(&z.energy))
+            .collect::<Option<_>>()
+            .map(Reading)
+    }
+
+    /// Joules used by all packages between two readings, the counters wrap around every few
+    /// minutes under load.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Counters are far below 2^52 microjoules
+    pub fn joules(&self, start: &Reading, end: &Reading) -> f64 {
+        let micro: u64 = self
+            .zones
+            .iter()
+            .zip(start.0.iter().zip(&end.0))
+            .map(|(zone, (&start, &end))| {
+                if end >= start {
+                    end - start
+                } else {
+                    zone.max_energy - start + end
+                }
+            })
+            .sum();
+
+        micro as f64 / 1e6
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fake_powercap() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        for (zone, energy) in [("intel-rapl:0", "900"), ("intel-rapl:0:0", "5")] {
+            fs::create_dir_all(dir.join(zone)).unwrap();
+            fs::write(dir.join(zone).join("energy_uj"), energy).unwrap();
+            fs::write(dir.join(zone).join("max_energy_range_uj"), "1000").unwrap();
+        }
+
+        let rapl = Rapl::open_in(dir).unwrap();
+        assert_eq!(rapl.zones.len(), 1);
+
+        let start = rapl.read().unwrap();
+        assert_eq!(start, Reading(vec![900]));
+        fs::write(dir.join("intel-rapl:0/energy_uj"), "400").unwrap();
+        let end = rapl.read().unwrap();
+        assert!((rapl.joules(&start, &end) - 500e-6).abs() < 1e-12);
+
+        assert!(Rapl::open_in(&dir.join("missing")).is_none());
+    }
+}
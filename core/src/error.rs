@@ -0,0 +1,100 @@
+use crate::parse;
+use thiserror::Error;
+
+/// What went wrong when solving a day, for the runner, server and WebAssembly page to react to.
+///
+/// Days themselves keep using `anyhow`. Their errors are sorted into these where the input is
+/// read and where [`Solution`](crate::registry::Solution) runs them, a day can also return one of
+/// these (e.g. [`AocError::Overflow`]) to pick the kind itself.
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum AocError {
+    #[error("The input is missing: {reason}")]
+    InputMissing { reason: String },
+    #[error("Day {day} could not parse its input{}: {reason}", .line.map_or(String::new(), |l| format!(" in line {l}")))]
+    ParseError {
+        day: u8,
+        /// Known if the day pointed at the broken part of the input, see [`parse::At`].
+        line: Option<usize>,
+        reason: String,
+    },
+    #[error("There is no solution: {0}")]
+    NoSolution(String),
+    #[error("Overflow: {0}")]
+    Overflow(String),
+    #[error("{0}")]
+    UnknownPart(String),
+}
+
+impl AocError {
+    /// An error of parsing `input` for `day`.
+    #[must_use]
+    pub fn parse(day: u8, input: &str, err: &anyhow::Error) -> Self {
+        Self::chosen(err).unwrap_or_else(|| Self::ParseError {
+            day,
+            line: parse::line(err, input),
+            reason: format!("{err:#}"),
+        })
+    }
+
+    /// An error of solving a part.
+    #[must_use]
+    pub fn solve(err: &anyhow::Error) -> Self {
+        Self::chosen(err).unwrap_or_else(|| Self::NoSolution(format!("{err:#}")))
+    }
+
+    /// Name of the variant, for JSON and other places that only take strings.
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::InputMissing { .. } => "input_missing",
+            Self::ParseError { .. } => "parse_error",
+            Self::NoSolution(_) => "no_solution",
+            Self::Overflow(_) => "overflow",
+            Self::UnknownPart(_) => "unknown_part",
+        }
+    }
+
+    /// The error the day chose itself, if any.
+    fn chosen(err: &anyhow::Error) -> Option<Self> {
+        err.downcast_ref::<Self>().cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::lines_to;
+    use anyhow::anyhow;
+
+    #[test]
+    fn sorts_errors() {
+        let input = "1\n2\nx\n";
+        let err = lines_to::<u8>(input).unwrap_err();
+        let parsed = AocError::parse(3, input, &err);
+        assert_eq!(
+            parsed,
+            AocError::ParseError {
+                day: 3,
+                line: Some(3),
+                reason: "Line 3: \"x\": invalid digit found in string".to_owned()
+            }
+        );
+        assert_eq!(
+            parsed.to_string(),
+            "Day 3 could not parse its input in line 3: Line 3: \"x\": invalid digit found in string"
+        );
+
+        assert_eq!(
+            AocError::solve(&anyhow!("nope")),
+            AocError::NoSolution("nope".to_owned())
+        );
+
+        // Days can pick the kind themselves, even below some context
+        let overflow =
+            anyhow::Error::from(AocError::Overflow("1 + u32::MAX".to_owned())).context("Part 2");
+        assert_eq!(
+            AocError::solve(&overflow),
+            AocError::Overflow("1 + u32::MAX".to_owned())
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use anyhow::{anyhow, Context, Result};
+use std::fmt::{Display, Formatter, Write};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Event {
+    /// Starts a new indentation level, closed by [`Event::End`]
+    Section(String),
+    Step(String),
+    Value(String, String),
+    End,
+}
+
+/// Sink for explanation events emitted by a solver.
+///
+/// Solvers should only use the provided methods, those skip formatting entirely if nobody listens.
+pub trait Explain {
+    fn enabled(&self) -> bool;
+
+    fn event(&mut self, event: Event);
+
+    fn section(&mut self, title: impl FnOnce() -> String)
+    where
+        Self: Sized,
+    {
+        if self.enabled() {
+            self.event(Event::Section(title()));
+        }
+    }
+
+    fn step(&mut self, text: impl FnOnce() -> String)
+    where
+        Self: Sized,
+    {
+        if self.enabled() {
+            self.event(Event::Step(text()));
+        }
+    }
+
+    fn value(&mut self, name: &str, value: impl Display)
+    where
+        Self: Sized,
+    {
+        if self.enabled() {
+            self.event(Event::Value(name.to_owned(), value.to_string()));
+        }
+    }
+
+    fn end(&mut self)
+    where
+        Self: Sized,
+    {
+        if self.enabled() {
+            self.event(Event::End);
+        }
+    }
+}
+
+/// Throws everything away, for the normal (fast) runs.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Silent;
+
+impl Explain for Silent {
+    fn enabled(&self) -> bool {
+        false
+    }
+
+    fn event(&mut self, _event: Event) {}
+}
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    Markdown,
+}
+
+/// Renders the events as indented text or as a nested markdown list.
+#[derive(Debug, Default, Clone)]
+pub struct Narrator {
+    enabled: bool,
+    format: Format,
+    limit: Option<usize>,
+    steps: usize,
+    depth: usize,
+    out: String,
+}
+
+impl Narrator {
+    #[must_use]
+    pub fn new(format: Format, limit: Option<usize>) -> Self {
+        Self {
+            enabled: true,
+            format,
+            limit,
+            ..Self::default()
+        }
+    }
+
+    /// Understands `--explain [--markdown] [--limit N]`, without `--explain` the narrator stays
+    /// silent. Unknown arguments are ignored so the binaries can have their own.
+    ///
+    /// # Errors
+    /// If the value of `--limit` is missing or not a number.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut narrator = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--explain" => narrator.enabled = true,
+                "--markdown" => narrator.format = Format::Markdown,
+                "--limit" => {
+                    let limit = args.next().context("--limit needs a value")?;
+                    narrator.limit = Some(
+                        limit
+                            .parse()
+                            .map_err(|e| anyhow!("Invalid limit {limit:?}: {e}"))?,
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        Ok(narrator)
+    }
+
+    fn line(&mut self, text: &str) {
+        let indent = "  ".repeat(self.depth);
+        match self.format {
+            Format::Text => writeln!(self.out, "{indent}{text}"),
+            Format::Markdown => writeln!(self.out, "{indent}- {text}"),
+        }
+        .expect("Writing to a String does not fail");
+    }
+}
+
+impl Explain for Narrator {
+    fn enabled(&self) -> bool {
+        self.enabled && self.limit.is_none_or(|limit| self.steps < limit)
+    }
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Section(title) => {
+                match self.format {
+                    Format::Text => self.line(&format!("{title}:")),
+                    Format::Markdown => self.line(&format!("**{title}**")),
+                }
+                self.depth += 1;
+            }
+            Event::Step(text) => {
+                self.steps += 1;
+                self.line(&text);
+                if !self.enabled() {
+                    self.line("...");
+                }
+            }
+            Event::Value(name, value) => match self.format {
+                Format::Text => self.line(&format!("{name} = {value}")),
+                Format::Markdown => self.line(&format!("{name}: `{value}`")),
+            },
+            Event::End => self.depth = self.depth.saturating_sub(1),
+        }
+    }
+}
+
+impl Display for Narrator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn explain_something(ex: &mut impl Explain) {
+        ex.section(|| "Monkey 0".into());
+        ex.step(|| "Monkey inspects an item".into());
+        ex.value("worry", 1501);
+        ex.end();
+        ex.step(|| "Done".into());
+    }
+
+    #[test]
+    fn text() {
+        let mut n = Narrator::new(Format::Text, None);
+        explain_something(&mut n);
+
+        assert_eq!(
+            n.to_string(),
+            "Monkey 0:
+  Monkey inspects an item
+  worry = 1501
+Done
+"
+        );
+    }
+
+    #[test]
+    fn markdown() {
+        let mut n = Narrator::new(Format::Markdown, None);
+        explain_something(&mut n);
+
+        assert_eq!(
+            n.to_string(),
+            "- **Monkey 0**
+  - Monkey inspects an item
+  - worry: `1501`
+- Done
+"
+        );
+    }
+
+    #[test]
+    fn limit() {
+        let mut n = Narrator::new(Format::Text, Some(1));
+        explain_something(&mut n);
+
+        assert_eq!(
+            n.to_string(),
+            "Monkey 0:
+  Monkey inspects an item
+  ...
+"
+        );
+    }
+
+    #[test]
+    fn args() {
+        let args = |a: &[&str]| Narrator::from_args(a.iter().map(|&s| s.to_owned()));
+
+        assert!(!args(&[]).unwrap().enabled());
+        assert!(args(&["--explain"]).unwrap().enabled());
+
+        let n = args(&["--explain", "--limit", "3", "--markdown"]).unwrap();
+        assert_eq!(n.limit, Some(3));
+        assert_eq!(n.format, Format::Markdown);
+
+        args(&["--limit", "x"]).unwrap_err();
+    }
+}
@@ -0,0 +1,554 @@
+//! `--export <file>`: the frames of an animation written to an animated GIF or PNG instead of the
+//! terminal, to share a simulation. Every character of a frame becomes a square of `--scale`
+//! pixels, colored by what it is.
+
+use crate::image::{mix, Image, Palette, Rgb, BLACK, WHITE};
+use anyhow::{bail, ensure, Context, Result};
+use flate2::write::ZlibEncoder;
+use flate2::{Compression, Crc};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Pixels per character.
+pub const DEFAULT_SCALE: usize = 4;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    Gif,
+    /// An animated PNG
+    Apng,
+}
+
+impl Format {
+    /// By the extension: `.gif`, or `.png` or `.apng`.
+    ///
+    /// # Errors
+    /// If it is none of them.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("gif") => Ok(Self::Gif),
+            Some("png" | "apng") => Ok(Self::Apng),
+            _ => bail!(
+                "Can only export to .gif, .png or .apng, not {}",
+                path.display()
+            ),
+        }
+    }
+}
+
+/// The frames of an animation, kept as text until they are written.
+#[derive(Debug)]
+pub struct Export {
+    path: PathBuf,
+    format: Format,
+    fps: u32,
+    scale: usize,
+    /// Every frame with how many frames in a row it is shown
+    frames: Vec<(String, u32)>,
+}
+
+impl Export {
+    /// # Errors
+    /// If the path is not one of a [`Format`], or `fps` or `scale` is zero.
+    pub fn new(path: impl Into<PathBuf>, fps: u32, scale: usize) -> Result<Self> {
+        let path = path.into();
+        ensure!(fps > 0, "Can not export with 0 frames per second");
+        ensure!(scale > 0, "Can not export at a scale of 0");
+        Ok(Self {
+            format: Format::from_path(&path)?,
+            path,
+            fps,
+            scale,
+            frames: vec![],
+        })
+    }
+
+    /// Adds a frame, the same frame again only makes the last one last longer.
+    pub fn push(&mut self, frame: String) {
+        match self.frames.last_mut() {
+            Some((last, shown)) if *last == frame => *shown += 1,
+            _ => self.frames.push((frame, 1)),
+        }
+    }
+
+    /// The number of frames pushed so far.
+    #[must_use]
+    pub fn frames(&self) -> usize {
+        self.frames.iter().map(|&(_, shown)| shown as usize).sum()
+    }
+
+    /// The distinct frames as images of the same size, with how many frames they are shown. Smaller
+    /// frames are at the bottom left, so growing ones, like stacks, grow upwards.
+    #[must_use]
+    pub fn images(&self) -> Vec<(Image, u32)> {
+        let grids: Vec<Vec<Vec<char>>> = self
+            .frames
+            .iter()
+            .map(|(frame, _)| frame.lines().map(|line| line.chars().collect()).collect())
+            .collect();
+        let width = grids.iter().flatten().map(Vec::len).max().unwrap_or(0);
+        let height = grids.iter().map(Vec::len).max().unwrap_or(0);
+
+        grids
+            .iter()
+            .zip(&self.frames)
+            .map(|(grid, &(_, shown))| {
+                let top = height - grid.len();
+                let image = Image::from_fn(width, height, |x, y| {
+                    let c = y.checked_sub(top).and_then(|y| grid[y].get(x));
+                    c.map_or(BLACK, |&c| color(c))
+                });
+                (image.scaled(self.scale), shown)
+            })
+            .collect()
+    }
+
+    /// Encodes the frames and writes them to the file.
+    ///
+    /// # Errors
+    /// If there is nothing to show, the frames are too large for the format or the file can not be
+    /// written.
+    pub fn write(&self) -> Result<()> {
+        let images = self.images();
+        ensure!(
+            images.first().is_some_and(|(image, _)| image.width() > 0),
+            "There is nothing to export to {}",
+            self.path.display()
+        );
+        let bytes = match self.format {
+            Format::Gif => gif(&images, self.fps)?,
+            Format::Apng => apng(&images, self.fps)?,
+        };
+        fs::write(&self.path, bytes)
+            .with_context(|| format!("Could not write {}", self.path.display()))?;
+        println!("Wrote {} frames to {}", self.frames(), self.path.display());
+        Ok(())
+    }
+}
+
+/// The color of a character of a frame: nothing black, walls and trails gray, digits (like the
+/// knots of a rope) from orange to brown, and every letter a hue of its own.
+fn color(c: char) -> Rgb {
+    match c {
+        ' ' | '.' => BLACK,
+        '#' => [110, 110, 110],
+        '[' | ']' => [90, 70, 40],
+        '@' => [230, 50, 40],
+        '0'..='9' => {
+            let digit = c.to_digit(10).map_or(0.0, f64::from);
+            mix([250, 170, 40], [120, 70, 20], digit / 9.0)
+        }
+        'A'..='Z' => hue(f64::from(u32::from(c) - u32::from('A')) / 26.0),
+        'a'..='z' => hue(f64::from(u32::from(c) - u32::from('a')) / 26.0),
+        _ => WHITE,
+    }
+}
+
+/// A bright color of hue `h`, between 0 and 1.
+fn hue(h: f64) -> Rgb {
+    // HSV with a saturation of 0.8 and a value of 0.9
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Between 0 and 255
+    let channel = |n: f64| {
+        let k = (n + h * 6.0) % 6.0;
+        (229.5 * (1.0 - 0.8 * k.min(4.0 - k).clamp(0.0, 1.0))).round() as u8
+    };
+    [channel(5.0), channel(3.0), channel(1.0)]
+}
+
+/// A GIF that loops forever, with a palette of all frames.
+fn gif(frames: &[(Image, u32)], fps: u32) -> Result<Vec<u8>> {
+    let (first, _) = &frames[0];
+    let size = |n| u16::try_from(n).context("The frames are too large for a GIF");
+    let (width, height) = (size(first.width())?, size(first.height())?);
+    let palette = Palette::new(frames.iter().flat_map(|(image, _)| image.pixels()));
+    // The color table has 2^bits colors, at least 2
+    let bits = (1..=8)
+        .find(|&bits| palette.len() <= 1 << bits)
+        .unwrap_or(8);
+
+    let mut out = b"GIF89a".to_vec();
+    out.extend(width.to_le_bytes());
+    out.extend(height.to_le_bytes());
+    out.extend([0xf0 | (bits - 1), 0, 0]);
+    let mut table = vec![0; 3 << bits];
+    for (rgb, i) in palette.colors() {
+        let i = usize::from(i) * 3;
+        table[i..i + 3].copy_from_slice(&rgb);
+    }
+    out.extend(table);
+    out.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+    for (image, shown) in frames {
+        // In hundredths of seconds, there is no shorter delay
+        let delay = (u64::from(*shown) * 100 / u64::from(fps)).max(1);
+        let delay = u16::try_from(delay).unwrap_or(u16::MAX);
+        out.extend([0x21, 0xf9, 4, 0]);
+        out.extend(delay.to_le_bytes());
+        out.extend([0, 0]);
+
+        out.extend([0x2c, 0, 0, 0, 0]);
+        out.extend(width.to_le_bytes());
+        out.extend(height.to_le_bytes());
+        out.push(0);
+
+        let indices: Vec<u8> = image
+            .pixels()
+            .iter()
+            .map(|&rgb| palette.index(rgb))
+            .collect();
+        let min_code_size = bits.max(2);
+        out.push(min_code_size);
+        for block in lzw(&indices, min_code_size).chunks(255) {
+            #[allow(clippy::cast_possible_truncation)] // Blocks are at most 255 bytes
+            out.push(block.len() as u8);
+            out.extend(block);
+        }
+        out.push(0);
+    }
+    out.push(0x3b);
+    Ok(out)
+}
+
+/// Codes packed least significant bit first.
+#[derive(Debug, Default)]
+struct Bits {
+    bytes: Vec<u8>,
+    buffer: u32,
+    len: u8,
+}
+
+impl Bits {
+    fn push(&mut self, code: u16, width: u8) {
+        self.buffer |= u32::from(code) << self.len;
+        self.len += width;
+        while self.len >= 8 {
+            self.bytes.push(self.buffer.to_le_bytes()[0]);
+            self.buffer >>= 8;
+            self.len -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.bytes.push(self.buffer.to_le_bytes()[0]);
+        }
+        self.bytes
+    }
+}
+
+/// The codes of LZW so far.
+#[derive(Debug)]
+struct Table {
+    codes: HashMap<(u16, u8), u16>,
+    next: u16,
+    width: u8,
+}
+
+impl Table {
+    fn new(min_code_size: u8) -> Self {
+        Self {
+            codes: HashMap::new(),
+            next: (1 << min_code_size) + 2,
+            width: min_code_size + 1,
+        }
+    }
+
+    /// The number of a new code if there is one left. The decoder adds its codes one behind, and
+    /// widens them as soon as the next one would not fit.
+    fn next_code(&mut self) -> Option<u16> {
+        (self.next < 4096).then(|| {
+            let code = self.next;
+            self.next += 1;
+            if self.next > 1 << self.width && self.width < 12 {
+                self.width += 1;
+            }
+            code
+        })
+    }
+}
+
+/// The LZW of GIFs: codes that grow up to 12 bits wide, starting over with a clear code when there
+/// are no more.
+fn lzw(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut out = Bits::default();
+    let mut table = Table::new(min_code_size);
+
+    out.push(clear, table.width);
+    let Some((&first, rest)) = indices.split_first() else {
+        out.push(end, table.width);
+        return out.finish();
+    };
+    let mut current = u16::from(first);
+    for &index in rest {
+        if let Some(&code) = table.codes.get(&(current, index)) {
+            current = code;
+            continue;
+        }
+        out.push(current, table.width);
+        if let Some(code) = table.next_code() {
+            table.codes.insert((current, index), code);
+        } else {
+            out.push(clear, table.width);
+            table = Table::new(min_code_size);
+        }
+        current = u16::from(index);
+    }
+    out.push(current, table.width);
+    // The decoder still adds a code for the last one before it reads the end
+    table.next_code();
+    out.push(end, table.width);
+    out.finish()
+}
+
+/// An animated PNG that loops forever, in RGB.
+fn apng(frames: &[(Image, u32)], fps: u32) -> Result<Vec<u8>> {
+    let (first, _) = &frames[0];
+    let size = |n| u32::try_from(n).context("The frames are too large for a PNG");
+    let (width, height) = (size(first.width())?, size(first.height())?);
+    let fps = u16::try_from(fps).context("An animated PNG has at most 65535 frames per second")?;
+
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+    let header = [
+        &width.to_be_bytes()[..],
+        &height.to_be_bytes(),
+        &[8, 2, 0, 0, 0],
+    ]
+    .concat();
+    chunk(&mut out, *b"IHDR", &header);
+    let count = u32::try_from(frames.len())?;
+    chunk(&mut out, *b"acTL", &[count.to_be_bytes(), [0; 4]].concat());
+
+    // Frame controls and frame data share their sequence numbers
+    let mut sequence = 0u32;
+    for (i, (image, shown)) in frames.iter().enumerate() {
+        let delay = u16::try_from(*shown).unwrap_or(u16::MAX);
+        let control = [
+            &sequence.to_be_bytes()[..],
+            &width.to_be_bytes(),
+            &height.to_be_bytes(),
+            &[0; 8],
+            &delay.to_be_bytes(),
+            &fps.to_be_bytes(),
+            &[0, 0],
+        ]
+        .concat();
+        chunk(&mut out, *b"fcTL", &control);
+        sequence += 1;
+
+        let data = compressed(image)?;
+        if i == 0 {
+            chunk(&mut out, *b"IDAT", &data);
+        } else {
+            chunk(
+                &mut out,
+                *b"fdAT",
+                &[&sequence.to_be_bytes()[..], &data].concat(),
+            );
+            sequence += 1;
+        }
+    }
+    chunk(&mut out, *b"IEND", &[]);
+    Ok(out)
+}
+
+/// The rows of the image without filters, zlib compressed.
+fn compressed(image: &Image) -> Result<Vec<u8>> {
+    let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+    for row in image.pixels().chunks(image.width()) {
+        encoder.write_all(&[0])?;
+        encoder.write_all(row.as_flattened())?;
+    }
+    Ok(encoder.finish()?)
+}
+
+fn chunk(out: &mut Vec<u8>, kind: [u8; 4], data: &[u8]) {
+    #[allow(clippy::cast_possible_truncation)] // Frames are far smaller than 4 GiB
+    out.extend((data.len() as u32).to_be_bytes());
+    let mut crc = Crc::new();
+    crc.update(&kind);
+    crc.update(data);
+    out.extend(kind);
+    out.extend(data);
+    out.extend(crc.sum().to_be_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::ZlibDecoder;
+    use std::io::Read;
+
+    /// What a GIF decoder reads from the codes.
+    fn unlzw(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1u16 << min_code_size;
+        let end = clear + 1;
+        let bit = |i: usize| (bytes[i / 8] >> (i % 8)) & 1;
+        let (mut pos, mut width) = (0, min_code_size + 1);
+        let mut table: Vec<Vec<u8>> = vec![];
+        let mut previous: Option<Vec<u8>> = None;
+        let mut out = vec![];
+        loop {
+            let code = (0..width).fold(0u16, |code, i| {
+                code | u16::from(bit(pos + usize::from(i))) << i
+            });
+            pos += usize::from(width);
+            if code == clear {
+                table = (0..clear).map(|i| vec![u8::try_from(i).unwrap()]).collect();
+                table.extend([vec![], vec![]]);
+                width = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return out;
+            }
+            let entry = match (table.get(usize::from(code)), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => [&previous[..], &previous[..1]].concat(),
+                (None, None) => panic!("Unknown first code {code}"),
+            };
+            if let Some(previous) = previous {
+                if table.len() < 4096 {
+                    table.push([&previous[..], &entry[..1]].concat());
+                }
+                if table.len() >= 1 << width && width < 12 {
+                    width += 1;
+                }
+            }
+            out.extend(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn lzw_like_the_spec() {
+        // The sample image of "What's in a GIF"
+        let rows = [
+            "1111122222",
+            "1111122222",
+            "1111122222",
+            "1110000222",
+            "1110000222",
+            "2220000111",
+            "2220000111",
+            "2222211111",
+            "2222211111",
+            "2222211111",
+        ];
+        let indices: Vec<u8> = rows.concat().bytes().map(|b| b - b'0').collect();
+        assert_eq!(
+            lzw(&indices, 2),
+            [
+                0x8c, 0x2d, 0x99, 0x87, 0x2a, 0x1c, 0xdc, 0x33, 0xa0, 0x02, 0x75, 0xec, 0x95, 0xfa,
+                0xa8, 0xde, 0x60, 0x8c, 0x04, 0x91, 0x4c, 0x01
+            ]
+        );
+    }
+
+    #[test]
+    fn formats() {
+        let format = |path: &str| Format::from_path(Path::new(path));
+        assert_eq!(format("rope.gif").unwrap(), Format::Gif);
+        assert_eq!(format("rope.png").unwrap(), Format::Apng);
+        assert_eq!(format("rope.apng").unwrap(), Format::Apng);
+        format("rope.mp4").unwrap_err();
+        format("rope").unwrap_err();
+        Export::new("rope.gif", 0, 4).unwrap_err();
+        Export::new("rope.gif", 10, 0).unwrap_err();
+    }
+
+    #[test]
+    fn lzw_round_trips() {
+        let mut state = 7u32;
+        let noise: Vec<u8> = (0..20_000)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                state.to_le_bytes()[2] % 5
+            })
+            .collect();
+        for indices in [vec![], vec![1], vec![0; 5000], noise] {
+            assert_eq!(unlzw(&lzw(&indices, 3), 3), indices);
+        }
+        let all: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+        assert_eq!(unlzw(&lzw(&all, 8), 8), all);
+    }
+
+    #[test]
+    fn frames() {
+        let mut export = Export::new("stacks.gif", 10, 1).unwrap();
+        export.push("A\n".to_owned());
+        export.push("A\n".to_owned());
+        export.push(".B\n1\n".to_owned());
+        assert_eq!(export.frames(), 3);
+
+        let images = export.images();
+        assert_eq!(images.len(), 2);
+        let (image, shown) = &images[0];
+        assert_eq!((image.width(), image.height(), *shown), (2, 2, 2));
+        assert_eq!(image.get(0, 0), BLACK);
+        assert_eq!(image.get(0, 1), color('A'));
+        let (image, _) = &images[1];
+        assert_eq!(image.get(1, 0), color('B'));
+        assert_eq!(image.get(0, 1), color('1'));
+        assert_ne!(color('A'), color('B'));
+    }
+
+    #[test]
+    fn gifs() {
+        let mut export = Export::new("rope.gif", 20, 2).unwrap();
+        export.push("H.\n.1\n".to_owned());
+        export.push(".H\n.1\n".to_owned());
+        let gif = gif(&export.images(), 20).unwrap();
+        assert!(gif.starts_with(b"GIF89a\x04\x00\x04\x00"));
+        assert!(gif.ends_with(&[0x3b]));
+
+        // Four colors, after the header, the table and the loop come the frames
+        let first = 13 + 3 * 4 + 19;
+        assert_eq!(gif[first..first + 8], [0x21, 0xf9, 4, 0, 5, 0, 0, 0]);
+        let data = &gif[first + 18..];
+        let (min_code_size, len) = (data[0], usize::from(data[1]));
+        let pixels = unlzw(&data[2..2 + len], min_code_size);
+        assert_eq!(pixels.len(), 16);
+        assert_eq!(pixels[0], pixels[1]);
+        assert_ne!(pixels[0], pixels[2]);
+    }
+
+    #[test]
+    fn apngs() {
+        let mut export = Export::new("walk.png", 10, 1).unwrap();
+        export.push("@#\n".to_owned());
+        export.push("#@\n".to_owned());
+        let png = apng(&export.images(), 10).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        let mut chunks = vec![];
+        let mut rest = &png[8..];
+        while !rest.is_empty() {
+            let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            let (kind, data) = (&rest[4..8], &rest[8..8 + len]);
+            let mut crc = Crc::new();
+            crc.update(&rest[4..8 + len]);
+            assert_eq!(crc.sum().to_be_bytes(), rest[8 + len..12 + len]);
+            chunks.push((std::str::from_utf8(kind).unwrap(), data));
+            rest = &rest[12 + len..];
+        }
+        let kinds: Vec<_> = chunks.iter().map(|&(kind, _)| kind).collect();
+        assert_eq!(
+            kinds,
+            ["IHDR", "acTL", "fcTL", "IDAT", "fcTL", "fdAT", "IEND"]
+        );
+        assert_eq!(chunks[1].1, [0, 0, 0, 2, 0, 0, 0, 0]);
+        assert_eq!(chunks[4].1[..4], [0, 0, 0, 1]);
+        assert_eq!(chunks[5].1[..4], [0, 0, 0, 2]);
+
+        let mut rows = vec![];
+        ZlibDecoder::new(&chunks[5].1[4..])
+            .read_to_end(&mut rows)
+            .unwrap();
+        assert_eq!(rows, [&[0][..], &color('#'), &color('@')].concat());
+    }
+}
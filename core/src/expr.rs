@@ -0,0 +1,303 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl BinOp {
+    fn from_token(token: &str) -> Option<Self> {
+        Some(match token {
+            "+" => Self::Add,
+            "-" => Self::Sub,
+            "*" => Self::Mul,
+            "/" => Self::Div,
+            _ => return None,
+        })
+    }
+
+    fn precedence(self) -> u8 {
+        match self {
+            Self::Add | Self::Sub => 1,
+            Self::Mul | Self::Div => 2,
+        }
+    }
+
+    /// # Errors
+    /// On overflow or division by zero.
+    pub fn apply(self, a: i64, b: i64) -> Result<i64> {
+        match self {
+            Self::Add => a.checked_add(b),
+            Self::Sub => a.checked_sub(b),
+            Self::Mul => a.checked_mul(b),
+            Self::Div => a.checked_div(b),
+        }
+        .with_context(|| format!("Can not calculate {a} {self} {b}"))
+    }
+}
+
+impl Display for BinOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+        })
+    }
+}
+
+/// Integer expression with variables, e.g. `old * old` or `root: pppw + sjmn`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Expr {
+    Lit(i64),
+    Var(String),
+    Bin(BinOp, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    #[must_use]
+    pub fn bin(op: BinOp, a: Expr, b: Expr) -> Self {
+        Self::Bin(op, Box::new(a), Box::new(b))
+    }
+
+    #[must_use]
+    pub fn contains(&self, var: &str) -> bool {
+        match self {
+            Self::Lit(_) => false,
+            Self::Var(name) => name == var,
+            Self::Bin(_, a, b) => a.contains(var) || b.contains(var),
+        }
+    }
+
+    /// # Errors
+    /// If a variable is unknown, on overflow or division by zero.
+    pub fn eval(&self, vars: &impl Fn(&str) -> Option<i64>) -> Result<i64> {
+        match self {
+            Self::Lit(val) => Ok(*val),
+            Self::Var(name) => vars(name).with_context(|| format!("Unknown variable {name:?}")),
+            Self::Bin(op, a, b) => op.apply(a.eval(vars)?, b.eval(vars)?),
+        }
+    }
+
+    /// Finds the value of `var` that makes the expression evaluate to `target`, by inverting the
+    /// operations on the path to `var`. `var` has to occur exactly once.
+    ///
+    /// # Errors
+    /// If `var` does not occur exactly once, or there is no integer solution.
+    pub fn solve(
+        &self,
+        var: &str,
+        target: i64,
+        vars: &impl Fn(&str) -> Option<i64>,
+    ) -> Result<i64> {
+        let (op, a, b) = match self {
+            Self::Var(name) if name == var => return Ok(target),
+            Self::Bin(op, a, b) => (*op, a, b),
+            _ => return Err(anyhow!("{var:?} does not occur in {self}")),
+        };
+
+        let solution = match (a.contains(var), b.contains(var)) {
+            (true, true) => return Err(anyhow!("{var:?} occurs more than once in {self}")),
+            (false, false) => return Err(anyhow!("{var:?} does not occur in {self}")),
+            (true, false) => {
+                let b = b.eval(vars)?;
+                let inner = match op {
+                    // x + b = t, x - b = t, x * b = t, x / b = t
+                    BinOp::Add => BinOp::Sub.apply(target, b)?,
+                    BinOp::Sub => BinOp::Add.apply(target, b)?,
+                    BinOp::Mul => exact_div(target, b)?,
+                    BinOp::Div => BinOp::Mul.apply(target, b)?,
+                };
+                a.solve(var, inner, vars)?
+            }
+            (false, true) => {
+                let a = a.eval(vars)?;
+                let inner = match op {
+                    // a + x = t, a - x = t, a * x = t, a / x = t
+                    BinOp::Add => BinOp::Sub.apply(target, a)?,
+                    BinOp::Sub => BinOp::Sub.apply(a, target)?,
+                    BinOp::Mul => exact_div(target, a)?,
+                    BinOp::Div => exact_div(a, target)?,
+                };
+                b.solve(var, inner, vars)?
+            }
+        };
+
+        Ok(solution)
+    }
+}
+
+fn exact_div(a: i64, b: i64) -> Result<i64> {
+    if b != 0 && a % b == 0 {
+        Ok(a / b)
+    } else {
+        Err(anyhow!("{a} is not divisible by {b}"))
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Lit(val) => write!(f, "{val}"),
+            Self::Var(name) => write!(f, "{name}"),
+            Self::Bin(op, a, b) => write!(f, "({a} {op} {b})"),
+        }
+    }
+}
+
+fn tokenize(s: &str) -> Result<Vec<&str>> {
+    let mut tokens = vec![];
+    let mut rest = s.trim_start();
+
+    while let Some(c) = rest.chars().next() {
+        let len = if c.is_ascii_alphanumeric() || c == '_' {
+            rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len())
+        } else if "+-*/()".contains(c) {
+            1
+        } else {
+            return Err(anyhow!("Unexpected {c:?} in expression {s:?}"));
+        };
+
+        tokens.push(&rest[..len]);
+        rest = rest[len..].trim_start();
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<&'a str>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Result<&'a str> {
+        let token = self.peek().context("Unexpected end of expression")?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    /// Precedence climbing, all operators are left associative.
+    fn expr(&mut self, min_precedence: u8) -> Result<Expr> {
+        let mut lhs = self.primary()?;
+
+        while let Some(op) = self.peek().and_then(BinOp::from_token) {
+            if op.precedence() < min_precedence {
+                break;
+            }
+            self.pos += 1;
+            let rhs = self.expr(op.precedence() + 1)?;
+            lhs = Expr::bin(op, lhs, rhs);
+        }
+
+        Ok(lhs)
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        match self.next()? {
+            "(" => {
+                let inner = self.expr(0)?;
+                match self.next()? {
+                    ")" => Ok(inner),
+                    other => Err(anyhow!("Expected ')', found {other:?}")),
+                }
+            }
+            "-" => match self.next()?.parse::<i64>() {
+                Ok(val) => Ok(Expr::Lit(-val)),
+                Err(_) => Err(anyhow!("Expected a number after '-'")),
+            },
+            token if token.starts_with(|c: char| c.is_ascii_digit()) => {
+                Ok(Expr::Lit(token.parse()?))
+            }
+            token if token.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') => {
+                Ok(Expr::Var(token.into()))
+            }
+            token => Err(anyhow!("Unexpected {token:?}")),
+        }
+    }
+}
+
+impl FromStr for Expr {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            pos: 0,
+        };
+        let expr = parser
+            .expr(0)
+            .with_context(|| format!("Invalid expression {s:?}"))?;
+
+        match parser.peek() {
+            None => Ok(expr),
+            Some(token) => Err(anyhow!("Unexpected {token:?} in expression {s:?}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_vars(_: &str) -> Option<i64> {
+        None
+    }
+
+    #[test]
+    fn parse_and_eval() {
+        let e: Expr = "1 + 2 * 3 - 8 / (2 + 2)".parse().unwrap();
+        assert_eq!(e.to_string(), "((1 + (2 * 3)) - (8 / (2 + 2)))");
+        assert_eq!(e.eval(&no_vars).unwrap(), 5);
+
+        let e: Expr = "old * old".parse().unwrap();
+        assert_eq!(e.eval(&|v| (v == "old").then_some(7)).unwrap(), 49);
+        assert!(e.eval(&no_vars).is_err());
+
+        assert_eq!(
+            "-3 - -4".parse::<Expr>().unwrap().eval(&no_vars).unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for s in ["old ^ 6", "1 +", "(1 + 2", "1 2", ""] {
+            assert!(s.parse::<Expr>().is_err(), "{s:?}");
+        }
+        assert!("1 / 0".parse::<Expr>().unwrap().eval(&no_vars).is_err());
+    }
+
+    #[test]
+    fn solve() {
+        // 2022 day 21 example: root = pppw == sjmn, solved for humn
+        let lhs: Expr = "(4 + 2 * (humn - 3)) / 4".parse().unwrap();
+        assert_eq!(lhs.solve("humn", 150, &no_vars).unwrap(), 301);
+
+        let e: Expr = "100 - 60 / x".parse().unwrap();
+        assert_eq!(e.solve("x", 85, &no_vars).unwrap(), 4);
+
+        assert!("x * x"
+            .parse::<Expr>()
+            .unwrap()
+            .solve("x", 4, &no_vars)
+            .is_err());
+        assert!("2 * x"
+            .parse::<Expr>()
+            .unwrap()
+            .solve("x", 3, &no_vars)
+            .is_err());
+    }
+}
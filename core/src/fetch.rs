@@ -0,0 +1,437 @@
+use crate::compress;
+use crate::error::AocError;
+use crate::net::Client;
+use crate::parse::normalize;
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Pause between two requests, the puzzle author asks to not hammer the servers.
+pub const THROTTLE: Duration = Duration::from_secs(3);
+
+/// Where the inputs are kept, in the directory of every year.
+pub const INPUT_DIR: &str = "input";
+
+/// `~/.config/aoc/<name>`, or in `XDG_CONFIG_HOME`.
+pub(crate) fn config_path(name: &str) -> Option<PathBuf> {
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| Some(PathBuf::from(std::env::var_os("HOME")?).join(".config")))?;
+    Some(config.join("aoc").join(name))
+}
+
+/// Session cookie from `AOC_SESSION`, the (git ignored) `.session` file or
+/// `~/.config/aoc/session`.
+///
+/// # Errors
+/// If none of them is set.
+pub fn session() -> Result<String> {
+    if let Ok(session) = std::env::var("AOC_SESSION") {
+        return Ok(session.trim().to_owned());
+    }
+
+    let files = std::iter::once(PathBuf::from(".session")).chain(config_path("session"));
+    for file in files {
+        if let Ok(session) = fs::read_to_string(file) {
+            return Ok(session.trim().to_owned());
+        }
+    }
+
+    Err(anyhow!(
+        "No session cookie, set AOC_SESSION or write it to .session or ~/.config/aoc/session"
+    ))
+}
+
+/// Session cookie of another account, from `AOC_SESSION_<ACCOUNT>` (upper case, `-` as `_`),
+/// `.session-<account>` or `~/.config/aoc/session-<account>`.
+///
+/// # Errors
+/// If none of them is set.
+pub fn account_session(account: &str) -> Result<String> {
+    let var = format!(
+        "AOC_SESSION_{}",
+        account.to_ascii_uppercase().replace('-', "_")
+    );
+    if let Ok(session) = std::env::var(&var) {
+        return Ok(session.trim().to_owned());
+    }
+
+    let name = format!("session-{account}");
+    let files = std::iter::once(PathBuf::from(format!(".{name}"))).chain(config_path(&name));
+    for file in files {
+        if let Ok(session) = fs::read_to_string(file) {
+            return Ok(session.trim().to_owned());
+        }
+    }
+
+    Err(anyhow!(
+        "No session cookie for {account}, set {var} or write it to .{name} or ~/.config/aoc/{name}"
+    ))
+}
+
+/// Where the inputs of `account` are kept in the directory of a year: [`INPUT_DIR`] for the
+/// default one, `input/<account>` for others.
+#[must_use]
+pub fn input_dir(account: Option<&str>) -> PathBuf {
+    let dir = PathBuf::from(INPUT_DIR);
+    match account {
+        Some(account) => dir.join(account),
+        None => dir,
+    }
+}
+
+/// Path of the input of a day, which may be compressed (see [`compress`](crate::compress)),
+/// decrypting it (see [`crypt`](crate::crypt)) or downloading it first if it is not there yet.
+///
+/// The input of another account is in `input/<account>`, and downloaded with its session cookie.
+///
+/// # Errors
+/// If the input is missing and can not be decrypted or downloaded.
+pub fn ensure_input(year: u16, day: u8, account: Option<&str>) -> Result<PathBuf> {
+    let dir = input_dir(account);
+    let path = input_path(&dir, day);
+    if let Some(found) = compress::find(&path) {
+        return Ok(found);
+    }
+    #[cfg(not(target_family = "wasm"))]
+    crate::crypt::decrypt_missing(&path)?;
+    if path.exists() {
+        return Ok(path);
+    }
+
+    eprintln!("{} is missing, downloading it", path.display());
+    let input = fetch_input(&Client::for_account(account)?, year, day)
+        .with_context(|| format!("Could not download the input of day {day}"))?;
+    fs::create_dir_all(&dir)?;
+    fs::write(&path, input).with_context(|| format!("Could not write {}", path.display()))?;
+
+    Ok(path)
+}
+
+/// Where the input of a day comes from.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum InputSource {
+    /// `input/dayNN.txt` of the year, downloaded on the first run, or `input/<account>/dayNN.txt`
+    /// with `--account <account>`
+    Default {
+        year: u16,
+        day: u8,
+        account: Option<String>,
+    },
+    /// `--input <path>`
+    File(PathBuf),
+    /// `--input -`
+    Stdin,
+}
+
+impl InputSource {
+    #[must_use]
+    pub fn from_args(year: u16, day: u8, mut args: impl Iterator<Item = String>) -> Self {
+        let mut source = None;
+        let mut account = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => match args.next() {
+                    Some(path) if path == "-" => source = Some(Self::Stdin),
+                    Some(path) => source = Some(Self::File(path.into())),
+                    None => {}
+                },
+                "--account" => account = args.next().or(account),
+                _ => {}
+            }
+        }
+        source.unwrap_or(Self::Default { year, day, account })
+    }
+
+    /// The account whose input it is, `None` for the default one and other files.
+    #[must_use]
+    pub fn account(&self) -> Option<&str> {
+        match self {
+            Self::Default { account, .. } => account.as_deref(),
+            Self::File(_) | Self::Stdin => None,
+        }
+    }
+
+    /// The file to read, `None` for stdin.
+    ///
+    /// # Errors
+    /// If the default input is missing and can not be downloaded.
+    pub fn path(&self) -> Result<Option<PathBuf>, AocError> {
+        match self {
+            Self::Default { year, day, account } => ensure_input(*year, *day, account.as_deref())
+                .map(Some)
+                .map_err(|e| missing(&e)),
+            Self::File(path) => Ok(Some(path.clone())),
+            Self::Stdin => Ok(None),
+        }
+    }
+
+    /// # Errors
+    /// If the input can not be read, or the default input can not be downloaded.
+    pub fn read(&self) -> Result<String, AocError> {
+        let input = match self.path()? {
+            Some(path) => compress::read(&path),
+            None => std::io::read_to_string(std::io::stdin()).context("Could not read stdin"),
+        };
+        input
+            .map(|input| normalize(&input).into_owned())
+            .map_err(|e| missing(&e))
+    }
+}
+
+fn missing(err: &anyhow::Error) -> AocError {
+    AocError::InputMissing {
+        reason: format!("{err:#}"),
+    }
+}
+
+/// Reads the input of a day from the source given by `--input` or `--account`, by default
+/// downloading it on the first run.
+///
+/// # Errors
+/// If the input is missing and can not be downloaded, or can not be read.
+pub fn read_input(year: u16, day: u8) -> Result<String, AocError> {
+    InputSource::from_args(year, day, std::env::args().skip(1)).read()
+}
+
+/// Seconds since the epoch for midnight (UTC) of a date, after Howard Hinnant's `days_from_civil`.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The UTC date of `time` as `2022-12-01`, the inverse of [`days_from_civil`].
+#[must_use]
+pub fn utc_date(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let days = i64::try_from(secs / 86_400).unwrap_or_default() + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year}-{month:02}-{day:02}")
+}
+
+/// Puzzles unlock at midnight EST, which is 05:00 UTC.
+///
+/// # Panics
+/// For years before 1970.
+#[must_use]
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    let days = days_from_civil(i64::from(year), 12, i64::from(day));
+    let secs = u64::try_from(days * 86_400 + 5 * 3_600).expect("Advent of Code is after 1970");
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+/// The last day with a puzzle, Advent of Code has 12 days since 2025.
+#[must_use]
+pub fn last_day(year: u16) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+#[must_use]
+pub fn unlocked_days(year: u16, now: SystemTime) -> Vec<u8> {
+    (1..=last_day(year))
+        .filter(|&day| unlock_time(year, day) <= now)
+        .collect()
+}
+
+#[must_use]
+pub fn input_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{day:02}.txt"))
+}
+
+/// Downloads the input of one day.
+///
+/// # Errors
+/// On network errors or if the server does not hand out the input (e.g. expired session).
+pub fn fetch_input(client: &Client, year: u16, day: u8) -> Result<String> {
+    client.get(&format!("https://adventofcode.com/{year}/day/{day}/input"))
+}
+
+/// The page of a day, with the descriptions of the parts unlocked so far, see
+/// [`to_markdown`](crate::puzzle::to_markdown).
+///
+/// # Errors
+/// On network errors or if the day is not unlocked yet.
+pub fn fetch_puzzle(client: &Client, year: u16, day: u8) -> Result<String> {
+    client.get(&format!("https://adventofcode.com/{year}/day/{day}"))
+}
+
+/// The JSON of a private leaderboard, which includes the star timestamps of all members.
+///
+/// # Errors
+/// On network errors or if the session has no access to the leaderboard.
+pub fn fetch_leaderboard(client: &Client, year: u16, id: &str) -> Result<String> {
+    client.get(&format!(
+        "https://adventofcode.com/{year}/leaderboard/private/view/{id}.json"
+    ))
+}
+
+/// Sends an answer and returns the page the server responds with, see
+/// [`Verdict::parse`](crate::submit::Verdict::parse).
+///
+/// # Errors
+/// On network errors or if the session is not accepted.
+pub fn submit_answer(
+    client: &Client,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> Result<String> {
+    client.post_form(
+        &format!("https://adventofcode.com/{year}/day/{day}/answer"),
+        &[("level", &part.to_string()), ("answer", answer)],
+    )
+}
+
+/// Whether the input at `path` is there: non-empty, or compressed (see
+/// [`compress`](crate::compress)), or encrypted (see [`crypt`](crate::crypt)).
+#[must_use]
+pub fn has_input(path: &Path) -> bool {
+    let found =
+        compress::find(path).is_some_and(|found| fs::metadata(found).is_ok_and(|m| m.len() > 0));
+    #[cfg(not(target_family = "wasm"))]
+    let found = found || crate::crypt::encrypted_path(path).exists();
+    found
+}
+
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Backfill {
+    pub fetched: Vec<u8>,
+    pub skipped: Vec<u8>,
+}
+
+/// Fetches every day that does not have an input in `dir` yet (see [`has_input`]), calling `pause`
+/// between two downloads.
+///
+/// # Errors
+/// If a download fails or the input can not be written, days fetched before stay on disk.
+pub fn backfill(
+    dir: &Path,
+    days: &[u8],
+    mut fetch: impl FnMut(u8) -> Result<String>,
+    mut pause: impl FnMut(),
+) -> Result<Backfill> {
+    fs::create_dir_all(dir).with_context(|| format!("Could not create {}", dir.display()))?;
+    let mut result = Backfill::default();
+
+    for &day in days {
+        let path = input_path(dir, day);
+        if has_input(&path) {
+            result.skipped.push(day);
+            continue;
+        }
+
+        if !result.fetched.is_empty() {
+            pause();
+        }
+        let input = fetch(day).with_context(|| format!("Day {day}"))?;
+        fs::write(&path, input).with_context(|| format!("Could not write {}", path.display()))?;
+        result.fetched.push(day);
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlocks() {
+        // 2022-12-01T05:00:00Z
+        let secs = unlock_time(2022, 1).duration_since(UNIX_EPOCH).unwrap();
+        assert_eq!(secs.as_secs(), 1_669_870_800);
+
+        let during = unlock_time(2023, 10) + Duration::from_secs(1);
+        assert_eq!(unlocked_days(2023, during), (1..=10).collect::<Vec<_>>());
+        assert_eq!(unlocked_days(2022, during).len(), 25);
+        let after = unlock_time(2025, 25);
+        assert_eq!(unlocked_days(2025, after), (1..=12).collect::<Vec<_>>());
+
+        assert_eq!(utc_date(unlock_time(2022, 1)), "2022-12-01");
+        assert_eq!(utc_date(unlock_time(2024, 25)), "2024-12-25");
+        assert_eq!(
+            utc_date(UNIX_EPOCH + Duration::from_secs(951_794_745)),
+            "2000-02-29"
+        );
+    }
+
+    #[test]
+    fn input_sources() {
+        let source =
+            |args: &[&str]| InputSource::from_args(2022, 5, args.iter().map(|&a| a.to_owned()));
+
+        assert_eq!(
+            source(&["--part", "2"]),
+            InputSource::Default {
+                year: 2022,
+                day: 5,
+                account: None
+            }
+        );
+        let work = source(&["--account", "work", "--part", "2"]);
+        assert_eq!(work.account(), Some("work"));
+        assert_eq!(input_dir(work.account()), Path::new("input/work"));
+        assert_eq!(
+            source(&["--input", "test.txt", "--part", "1"]),
+            InputSource::File("test.txt".into())
+        );
+        assert_eq!(source(&["--input", "-"]), InputSource::Stdin);
+        assert_eq!(source(&["--input", "-"]).path().unwrap(), None);
+    }
+
+    #[test]
+    fn backfills_missing() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        fs::write(input_path(dir, 2), "already here").unwrap();
+        fs::write(input_path(dir, 3), "").unwrap();
+        fs::write(compress::compressed_path(&input_path(dir, 4), "gz"), "gz").unwrap();
+        fs::write(crate::crypt::encrypted_path(&input_path(dir, 5)), "enc").unwrap();
+
+        let mut pauses = 0;
+        let result = backfill(
+            dir,
+            &[1, 2, 3, 4, 5],
+            |day| Ok(format!("input {day}")),
+            || pauses += 1,
+        )
+        .unwrap();
+
+        assert_eq!(
+            result,
+            Backfill {
+                fetched: vec![1, 3],
+                skipped: vec![2, 4, 5]
+            }
+        );
+        assert_eq!(pauses, 1);
+        assert_eq!(fs::read_to_string(input_path(dir, 3)).unwrap(), "input 3");
+        assert_eq!(
+            fs::read_to_string(input_path(dir, 2)).unwrap(),
+            "already here"
+        );
+    }
+}
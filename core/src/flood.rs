@@ -0,0 +1,270 @@
+//! Flood fill and connected components, over a [`Grid2D`] for maps like the outside of a loop,
+//! or over [`Point`]s of any dimension for shapes like the cubes of 2022 day 18.
+
+use crate::coord::Coord;
+use crate::grid::Grid2D;
+use crate::point::{BoundingBox, Point};
+use std::collections::HashSet;
+
+/// Which cells or points count as adjacent.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Connectivity {
+    /// Sharing a side or face: 4 neighbors in 2D, 6 in 3D
+    Faces,
+    /// Touching at all: 8 neighbors in 2D, 26 in 3D
+    Diagonal,
+}
+
+impl Connectivity {
+    /// The neighbors of `c` inside the grid.
+    #[must_use]
+    pub fn neighbors<'a, T>(
+        self,
+        grid: &'a Grid2D<T>,
+        c: Coord,
+    ) -> Box<dyn Iterator<Item = (Coord, &'a T)> + 'a> {
+        match self {
+            Self::Faces => Box::new(grid.neighbors(c)),
+            Self::Diagonal => Box::new(grid.neighbors_diagonal(c)),
+        }
+    }
+
+    #[must_use]
+    pub fn point_neighbors<const D: usize>(
+        self,
+        p: Point<D>,
+    ) -> Box<dyn Iterator<Item = Point<D>>> {
+        match self {
+            Self::Faces => Box::new(p.neighbors()),
+            Self::Diagonal => Box::new(p.neighbors_diagonal()),
+        }
+    }
+}
+
+/// All cells reachable from `start` through `passable` cells, including `start` itself if it is
+/// passable.
+pub fn flood_fill<T>(
+    grid: &Grid2D<T>,
+    start: Coord,
+    connectivity: Connectivity,
+    passable: impl Fn(Coord, &T) -> bool,
+) -> HashSet<Coord> {
+    let mut filled = HashSet::new();
+    if !grid.get(start).is_some_and(|cell| passable(start, cell)) {
+        return filled;
+    }
+
+    let mut todo = vec![start];
+    filled.insert(start);
+    while let Some(c) = todo.pop() {
+        for (n, cell) in connectivity.neighbors(grid, c) {
+            if passable(n, cell) && filled.insert(n) {
+                todo.push(n);
+            }
+        }
+    }
+
+    filled
+}
+
+/// The cells for which `member` holds, split into connected components, largest first.
+pub fn components<T>(
+    grid: &Grid2D<T>,
+    connectivity: Connectivity,
+    member: impl Fn(Coord, &T) -> bool,
+) -> Vec<HashSet<Coord>> {
+    let mut seen = HashSet::new();
+    let mut components = vec![];
+    for (c, cell) in grid.iter() {
+        if seen.contains(&c) || !member(c, cell) {
+            continue;
+        }
+        let component = flood_fill(grid, c, connectivity, &member);
+        seen.extend(component.iter().copied());
+        components.push(component);
+    }
+
+    components.sort_by_key(|c| std::cmp::Reverse(c.len()));
+    components
+}
+
+/// All points reachable from `start` through `passable` points inside `bounds`, including
+/// `start` itself if it is passable.
+pub fn flood_fill_points<const D: usize>(
+    start: Point<D>,
+    bounds: &BoundingBox<D>,
+    connectivity: Connectivity,
+    passable: impl Fn(Point<D>) -> bool,
+) -> HashSet<Point<D>> {
+    let mut filled = HashSet::new();
+    if !bounds.contains(start) || !passable(start) {
+        return filled;
+    }
+
+    let mut todo = vec![start];
+    filled.insert(start);
+    while let Some(p) = todo.pop() {
+        for n in connectivity.point_neighbors(p) {
+            if bounds.contains(n) && passable(n) && filled.insert(n) {
+                todo.push(n);
+            }
+        }
+    }
+
+    filled
+}
+
+/// Splits `points` into connected components, largest first.
+pub fn point_components<const D: usize>(
+    points: impl IntoIterator<Item = Point<D>>,
+    connectivity: Connectivity,
+) -> Vec<HashSet<Point<D>>> {
+    let mut remaining: HashSet<_> = points.into_iter().collect();
+    let mut components = vec![];
+
+    while let Some(&start) = remaining.iter().next() {
+        remaining.remove(&start);
+        let mut component = HashSet::from([start]);
+        let mut todo = vec![start];
+
+        while let Some(p) = todo.pop() {
+            for n in connectivity.point_neighbors(p) {
+                if remaining.remove(&n) {
+                    component.insert(n);
+                    todo.push(n);
+                }
+            }
+        }
+
+        components.push(component);
+    }
+
+    components.sort_by_key(|c| std::cmp::Reverse(c.len()));
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Point3;
+
+    #[test]
+    fn ring() {
+        let grid = Grid2D::from_chars(
+            ".......
+.#####.
+.#...#.
+.#.#.#.
+.#...#.
+.#####.
+.......",
+        )
+        .unwrap();
+        let fill = |start| flood_fill(&grid, start, Connectivity::Faces, |_, &c| c == '.');
+
+        assert_eq!(fill(Coord::ORIGIN).len(), 7 * 7 - 5 * 5);
+        assert_eq!(fill(Coord::new(2, 2)).len(), 8);
+        assert!(fill(Coord::new(1, 1)).is_empty());
+        assert!(fill(Coord::new(-1, 0)).is_empty());
+    }
+
+    #[test]
+    fn diagonal_leaks() {
+        let grid = Grid2D::from_chars(
+            "..#..
+.#.#.
+..#..",
+        )
+        .unwrap();
+        let open = |_, &c: &char| c == '.';
+        let middle = Coord::new(2, 1);
+
+        assert_eq!(
+            flood_fill(&grid, middle, Connectivity::Faces, open).len(),
+            1
+        );
+        assert_eq!(
+            flood_fill(&grid, middle, Connectivity::Diagonal, open).len(),
+            5 * 3 - 4
+        );
+    }
+
+    #[test]
+    fn labels_components() {
+        let grid = Grid2D::from_chars(
+            "##..#
+#...#
+..#..
+...#.",
+        )
+        .unwrap();
+
+        let sizes = |connectivity| {
+            components(&grid, connectivity, |_, &c| c == '#')
+                .iter()
+                .map(HashSet::len)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(sizes(Connectivity::Faces), [3, 2, 1, 1]);
+        assert_eq!(sizes(Connectivity::Diagonal), [3, 2, 2]);
+    }
+
+    #[test]
+    fn inside_of_a_loop() {
+        // 2023 day 10: whatever the outside does not reach from the border is enclosed
+        let grid = Grid2D::from_chars(
+            "......
+.####.
+.#..#.
+.#..##
+.####.
+......",
+        )
+        .unwrap();
+        let outside = flood_fill(&grid, Coord::ORIGIN, Connectivity::Faces, |_, &c| c == '.');
+        let inside = grid
+            .iter()
+            .filter(|&(c, &cell)| cell == '.' && !outside.contains(&c))
+            .count();
+        assert_eq!(inside, 4);
+    }
+
+    #[test]
+    fn hollow_cube() {
+        // 2022 day 18: a 3x3x3 cube with an air pocket in the middle only has its outer faces
+        // exposed to the exterior
+        let mut cube: HashSet<Point3> = HashSet::new();
+        for x in 0..3 {
+            for y in 0..3 {
+                for z in 0..3 {
+                    if [x, y, z] != [1, 1, 1] {
+                        cube.insert(Point([x, y, z]));
+                    }
+                }
+            }
+        }
+        let bounds = BoundingBox::from_points(cube.iter().copied())
+            .unwrap()
+            .grow(1);
+
+        let exterior = flood_fill_points(bounds.min, &bounds, Connectivity::Faces, |p| {
+            !cube.contains(&p)
+        });
+        let exposed = cube
+            .iter()
+            .flat_map(|p| p.neighbors())
+            .filter(|n| exterior.contains(n))
+            .count();
+        assert_eq!(exposed, 6 * 9);
+
+        assert_eq!(point_components(cube, Connectivity::Faces).len(), 1);
+    }
+
+    #[test]
+    fn corners_touch_in_3d() {
+        let cubes = [Point([0, 0, 0]), Point([1, 1, 1]), Point([3, 3, 3])];
+
+        assert_eq!(point_components(cubes, Connectivity::Faces).len(), 3);
+        assert_eq!(point_components(cubes, Connectivity::Diagonal).len(), 2);
+    }
+}
@@ -0,0 +1,280 @@
+use crate::coord::{Coord, Dir4, Dir8, Direction};
+use anyhow::{anyhow, ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// Dense rectangular grid, stored row by row, for the puzzles that come as a map.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Grid2D<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid2D<T> {
+    /// Every cell set to `fill`.
+    #[must_use]
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid2D<T> {
+    /// One line per row, every character turned into a cell by `cell`.
+    ///
+    /// # Errors
+    /// If the lines are not all the same length, or `cell` fails.
+    pub fn parse(input: &str, mut cell: impl FnMut(Coord, char) -> Result<T>) -> Result<Self> {
+        let mut grid = Self {
+            width: 0,
+            height: 0,
+            cells: vec![],
+        };
+
+        for (y, line) in input.lines().enumerate() {
+            let before = grid.cells.len();
+            for (x, c) in line.chars().enumerate() {
+                let coord = Coord::new(to_isize(x), to_isize(y));
+                grid.cells
+                    .push(cell(coord, c).with_context(|| format!("Invalid cell at {coord}"))?);
+            }
+
+            let width = grid.cells.len() - before;
+            if y == 0 {
+                grid.width = width;
+            }
+            ensure!(
+                width == grid.width,
+                "Line {} is {width} wide, expected {}",
+                y + 1,
+                grid.width
+            );
+            grid.height += 1;
+        }
+
+        Ok(grid)
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn contains(&self, c: Coord) -> bool {
+        self.offset(c).is_some()
+    }
+
+    #[must_use]
+    pub fn get(&self, c: Coord) -> Option<&T> {
+        self.offset(c).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, c: Coord) -> Option<&mut T> {
+        self.offset(c).map(|i| &mut self.cells[i])
+    }
+
+    /// All coordinates, row by row.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width.max(1);
+        (0..self.cells.len()).map(move |i| Coord::new(to_isize(i % width), to_isize(i / width)))
+    }
+
+    /// All cells with their coordinates, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(&self.cells)
+    }
+
+    /// # Panics
+    /// If `y` is outside of the grid.
+    #[must_use]
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "Row {y} is outside of the grid");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// # Panics
+    /// If `x` is outside of the grid.
+    pub fn col(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {x} is outside of the grid");
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.col(x))
+    }
+
+    /// The neighbour of `c` in `dir`, unless that is outside of the grid.
+    #[must_use]
+    pub fn step(&self, c: Coord, dir: impl Direction) -> Option<Coord> {
+        let next = c.step(dir);
+        self.contains(next).then_some(next)
+    }
+
+    /// The up to four cells that share an edge with `c`, clockwise starting upwards.
+    pub fn neighbors(&self, c: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        Dir4::ALL.into_iter().filter_map(move |d| {
+            let next = self.step(c, d)?;
+            Some((next, &self[next]))
+        })
+    }
+
+    /// The up to eight cells that touch `c`, including diagonally, clockwise starting upwards.
+    pub fn neighbors_diagonal(&self, c: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        Dir8::ALL.into_iter().filter_map(move |d| {
+            let next = self.step(c, d)?;
+            Some((next, &self[next]))
+        })
+    }
+
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid2D<U> {
+        Grid2D {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// One line per row, each ending in a newline.
+    #[must_use]
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            s.extend(row.iter().map(&mut cell));
+            s.push('\n');
+        }
+        s
+    }
+
+    fn offset(&self, c: Coord) -> Option<usize> {
+        let x = usize::try_from(c.x).ok()?;
+        let y = usize::try_from(c.y).ok()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+}
+
+impl Grid2D<char> {
+    /// # Errors
+    /// If the lines are not all the same length.
+    pub fn from_chars(input: &str) -> Result<Self> {
+        Self::parse(input, |_, c| Ok(c))
+    }
+}
+
+impl Grid2D<u8> {
+    /// # Errors
+    /// If the lines are not all the same length, or contain something other than digits.
+    pub fn from_digits(input: &str) -> Result<Self> {
+        Self::parse(input, |_, c| {
+            c.to_digit(10)
+                .and_then(|d| u8::try_from(d).ok())
+                .ok_or_else(|| anyhow!("{c:?} is not a digit"))
+        })
+    }
+}
+
+impl<T> Index<Coord> for Grid2D<T> {
+    type Output = T;
+
+    fn index(&self, c: Coord) -> &T {
+        self.get(c)
+            .unwrap_or_else(|| panic!("{c} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid2D<T> {
+    fn index_mut(&mut self, c: Coord) -> &mut T {
+        self.get_mut(c)
+            .unwrap_or_else(|| panic!("{c} is outside of the grid"))
+    }
+}
+
+impl<T: Display> Display for Grid2D<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Grids can not be larger than `isize::MAX` cells, so their indices always fit.
+fn to_isize(i: usize) -> isize {
+    isize::try_from(i).expect("Grid larger than isize::MAX")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digits() {
+        let mut grid = Grid2D::from_digits("123\n456\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Coord::new(2, 1)], 6);
+        assert_eq!(grid.get(Coord::new(3, 0)), None);
+        assert_eq!(grid.get(Coord::new(0, -1)), None);
+
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.col(1).copied().collect::<Vec<_>>(), [2, 5]);
+        assert_eq!(grid.cols().count(), 3);
+
+        grid[Coord::new(0, 0)] = 9;
+        assert_eq!(grid.to_string(), "923\n456\n");
+        assert_eq!(
+            grid.map(|&d| d % 2 == 0)
+                .render(|&e| if e { '#' } else { '.' }),
+            ".#.\n#.#\n"
+        );
+
+        Grid2D::from_digits("12\n3\n").unwrap_err();
+        Grid2D::from_digits("1x\n").unwrap_err();
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid2D::from_chars("abc\ndef\nghi").unwrap();
+        let around = |c, diagonal| -> String {
+            if diagonal {
+                grid.neighbors_diagonal(c).map(|(_, &c)| c).collect()
+            } else {
+                grid.neighbors(c).map(|(_, &c)| c).collect()
+            }
+        };
+
+        assert_eq!(around(Coord::new(1, 1), false), "bfhd");
+        assert_eq!(around(Coord::new(1, 1), true), "bcfihgda");
+        assert_eq!(around(Coord::new(0, 0), false), "bd");
+        assert_eq!(around(Coord::new(0, 0), true), "bed");
+
+        assert_eq!(
+            grid.step(Coord::new(0, 2), Dir4::Right),
+            Some(Coord::new(1, 2))
+        );
+        assert_eq!(grid.step(Coord::new(0, 2), Dir4::Down), None);
+        assert_eq!(grid.step(Coord::new(0, 2), Dir8::UpLeft), None);
+
+        assert_eq!(
+            grid.iter().find(|&(_, &c)| c == 'f').map(|(c, _)| c),
+            Some(Coord::new(2, 1))
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering::Relaxed};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting what goes through it. Years install it with their `heap`
+/// feature, as
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: aoc_core::heap::Counting = aoc_core::heap::Counting;
+/// ```
+///
+/// Counts the whole process, so allocations of other threads (like rayon's) end up in
+/// [`measure`] too.
+#[derive(Debug, Default)]
+pub struct Counting;
+
+fn allocated(size: usize) {
+    INSTALLED.store(true, Relaxed);
+    ALLOCATIONS.fetch_add(1, Relaxed);
+    ALLOCATED.fetch_add(size as u64, Relaxed);
+    let now = CURRENT.fetch_add(size, Relaxed) + size;
+    PEAK.fetch_max(now, Relaxed);
+}
+
+// SAFETY: Everything is passed on to the system allocator unchanged
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            CURRENT.fetch_sub(layout.size(), Relaxed);
+            allocated(new_size);
+        }
+        new
+    }
+}
+
+/// What one measured section did with the heap.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct HeapUsage {
+    /// Including growing existing allocations
+    pub allocations: u64,
+    pub bytes: u64,
+    /// Most memory in use at once, on top of what was in use before
+    pub peak: usize,
+}
+
+/// Runs `f` and counts its allocations, `None` if [`Counting`] is not the global allocator.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<HeapUsage>) {
+    let start = CURRENT.load(Relaxed);
+    PEAK.store(start, Relaxed);
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let bytes = ALLOCATED.load(Relaxed);
+
+    let result = f();
+
+    let usage = INSTALLED.load(Relaxed).then(|| HeapUsage {
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+        bytes: ALLOCATED.load(Relaxed) - bytes,
+        peak: PEAK.load(Relaxed).saturating_sub(start),
+    });
+    (result, usage)
+}
+
+/// `1.5 KiB` and the like.
+#[must_use]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    #[allow(clippy::cast_precision_loss)] // Only shown with one decimal
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+impl Display for HeapUsage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak as u64)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    #[test]
+    fn counts_allocations() {
+        let (v, usage) = measure(|| {
+            let mut v = vec![0_u8; 1000];
+            v.extend([1; 1000]);
+            v
+        });
+        assert_eq!(v.len(), 2000);
+
+        // Other tests run at the same time and allocate too
+        let usage = usage.unwrap();
+        assert!(usage.allocations >= 2, "{usage}");
+        assert!(usage.bytes >= 3000, "{usage}");
+        assert!(usage.peak >= 2000, "{usage}");
+    }
+
+    #[test]
+    fn bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 << 20), "3.0 MiB");
+    }
+}
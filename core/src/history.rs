@@ -0,0 +1,253 @@
+use crate::fetch::{last_day, unlock_time};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const HISTORY_FILE: &str = "history.json";
+
+/// When each part was first solved, as seconds since the epoch, by year, day and part.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    pub solved: BTreeMap<u16, BTreeMap<u8, BTreeMap<u8, u64>>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct YearSummary {
+    pub year: u16,
+    pub stars: usize,
+    pub median_time_to_solve: Option<Duration>,
+    /// Most consecutive days with both stars within 24 hours of the unlock
+    pub streak: usize,
+}
+
+impl History {
+    /// An empty history if the file does not exist yet.
+    ///
+    /// # Errors
+    /// If the file can not be read or parsed.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        serde_json::from_str(&json).with_context(|| format!("Invalid history {}", path.display()))
+    }
+
+    /// # Errors
+    /// If the file can not be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+            .with_context(|| format!("Could not write {}", path.display()))
+    }
+
+    /// Keeps the earlier time if the part was already solved, returns whether it was new.
+    pub fn record(&mut self, year: u16, day: u8, part: u8, when: SystemTime) -> bool {
+        let secs = when.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        let parts = self.solved.entry(year).or_default().entry(day).or_default();
+
+        match parts.get(&part) {
+            Some(&earlier) if earlier <= secs => false,
+            _ => {
+                parts.insert(part, secs);
+                true
+            }
+        }
+    }
+
+    /// Merges the star timestamps of one member from a private leaderboard (the JSON the API
+    /// returns), returns how many were new or earlier.
+    ///
+    /// # Errors
+    /// If the JSON does not look like a leaderboard or the member is missing.
+    pub fn import_leaderboard(&mut self, year: u16, json: &str, member: &str) -> Result<usize> {
+        let board: Value = serde_json::from_str(json)?;
+        let days = board["members"][member]["completion_day_level"]
+            .as_object()
+            .with_context(|| format!("No member {member:?} in the leaderboard"))?;
+
+        let mut new = 0;
+        for (day, parts) in days {
+            let day: u8 = day.parse()?;
+            for (part, star) in parts.as_object().context("Invalid day in leaderboard")? {
+                let ts = star["get_star_ts"]
+                    .as_u64()
+                    .context("Missing get_star_ts in leaderboard")?;
+                if self.record(
+                    year,
+                    day,
+                    part.parse()?,
+                    UNIX_EPOCH + Duration::from_secs(ts),
+                ) {
+                    new += 1;
+                }
+            }
+        }
+
+        Ok(new)
+    }
+
+    #[must_use]
+    pub fn time_to_solve(&self, year: u16, day: u8, part: u8) -> Option<Duration> {
+        let secs = *self.solved.get(&year)?.get(&day)?.get(&part)?;
+        (UNIX_EPOCH + Duration::from_secs(secs))
+            .duration_since(unlock_time(year, day))
+            .ok()
+    }
+
+    #[must_use]
+    pub fn summary(&self, year: u16) -> YearSummary {
+        let mut times: Vec<Duration> = (1..=last_day(year))
+            .flat_map(|day| (1..=2).filter_map(move |part| self.time_to_solve(year, day, part)))
+            .collect();
+        times.sort();
+
+        let mut streak = 0;
+        let mut longest = 0;
+        for day in 1..=last_day(year) {
+            let same_day = self
+                .time_to_solve(year, day, 2)
+                .is_some_and(|t| t < minutes(24 * 60));
+            streak = if same_day { streak + 1 } else { 0 };
+            longest = longest.max(streak);
+        }
+
+        YearSummary {
+            year,
+            stars: self
+                .solved
+                .get(&year)
+                .map_or(0, |days| days.values().map(BTreeMap::len).sum()),
+            median_time_to_solve: times.get(times.len() / 2).copied(),
+            streak: longest,
+        }
+    }
+
+    /// Per day times for every year, followed by a comparison of the years.
+    ///
+    /// # Panics
+    /// Never, writing to a `String` does not fail.
+    #[must_use]
+    pub fn report(&self) -> String {
+        let mut out = String::new();
+
+        for (&year, days) in &self.solved {
+            writeln!(out, "{year}\n day      part 1      part 2").unwrap();
+            for &day in days.keys() {
+                let time = |part| self.time_to_solve(year, day, part).map(format_duration);
+                writeln!(
+                    out,
+                    "  {day:>2}  {:>10}  {:>10}",
+                    time(1).unwrap_or_else(|| "-".into()),
+                    time(2).unwrap_or_else(|| "-".into())
+                )
+                .unwrap();
+            }
+            writeln!(out).unwrap();
+        }
+
+        writeln!(out, "year  stars    median  streak").unwrap();
+        for &year in self.solved.keys() {
+            let s = self.summary(year);
+            let median = s
+                .median_time_to_solve
+                .map_or_else(|| "-".into(), format_duration);
+            writeln!(out, "{year}  {:>5}  {median:>8}  {:>6}", s.stars, s.streak).unwrap();
+        }
+
+        out
+    }
+}
+
+fn minutes(mins: u64) -> Duration {
+    Duration::from_secs(mins * 60)
+}
+
+/// Coarse, like `3d04h`, `2h15m` or `12m`.
+#[must_use]
+pub fn format_duration(d: Duration) -> String {
+    let mins = d.as_secs() / 60;
+    let (days, hours, mins) = (mins / (24 * 60), mins / 60 % 24, mins % 60);
+
+    match (days, hours) {
+        (0, 0) => format!("{mins}m"),
+        (0, _) => format!("{hours}h{mins:02}m"),
+        _ => format!("{days}d{hours:02}h"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn after_unlock(year: u16, day: u8, mins: u64) -> SystemTime {
+        unlock_time(year, day) + minutes(mins)
+    }
+
+    #[test]
+    fn records_first_solve() {
+        let mut history = History::default();
+        assert!(history.record(2022, 1, 1, after_unlock(2022, 1, 10)));
+        assert!(!history.record(2022, 1, 1, after_unlock(2022, 1, 20)));
+        assert!(history.record(2022, 1, 1, after_unlock(2022, 1, 5)));
+
+        assert_eq!(history.time_to_solve(2022, 1, 1), Some(minutes(5)));
+        assert_eq!(history.time_to_solve(2022, 1, 2), None);
+
+        let json = serde_json::to_string(&history).unwrap();
+        assert_eq!(serde_json::from_str::<History>(&json).unwrap(), history);
+    }
+
+    #[test]
+    fn leaderboard() {
+        let unlock = unlock_time(2022, 2)
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let json = format!(
+            r#"{{"event": "2022", "members": {{"42": {{"completion_day_level": {{
+                "2": {{"1": {{"get_star_ts": {}, "star_index": 1}},
+                       "2": {{"get_star_ts": {}, "star_index": 2}}}}}}}}}}}}"#,
+            unlock + 600,
+            unlock + 3_600
+        );
+
+        let mut history = History::default();
+        assert_eq!(history.import_leaderboard(2022, &json, "42").unwrap(), 2);
+        assert_eq!(history.import_leaderboard(2022, &json, "42").unwrap(), 0);
+        history.import_leaderboard(2022, &json, "7").unwrap_err();
+
+        assert_eq!(history.time_to_solve(2022, 2, 2), Some(minutes(60)));
+    }
+
+    #[test]
+    fn summary() {
+        let mut history = History::default();
+        for day in [1, 2, 3, 5] {
+            history.record(2022, day, 1, after_unlock(2022, day, 30));
+            history.record(2022, day, 2, after_unlock(2022, day, 60));
+        }
+        history.record(2022, 4, 2, after_unlock(2022, 4, 2 * 24 * 60));
+
+        let s = history.summary(2022);
+        assert_eq!(s.stars, 9);
+        assert_eq!(s.streak, 3);
+        assert_eq!(s.median_time_to_solve, Some(minutes(60)));
+
+        assert!(history.report().contains("   1         30m       1h00m"));
+        assert!(history.report().contains("2022      9     1h00m       3"));
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(format_duration(Duration::from_secs(12 * 60 + 59)), "12m");
+        assert_eq!(format_duration(minutes(135)), "2h15m");
+        assert_eq!(format_duration(minutes(76 * 60)), "3d04h");
+    }
+}
@@ -0,0 +1,381 @@
+//! Pictures as actual images in terminals that can show them, with the kitty graphics protocol or
+//! sixels, and as text everywhere else. `AOC_IMAGES` set to `kitty`, `sixel` or `off` overrides
+//! what the terminal looks like it supports.
+
+use crate::screen::Screen;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::io::IsTerminal;
+
+/// Overrides the detected [`Protocol`].
+pub const IMAGES_VAR: &str = "AOC_IMAGES";
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+/// `t` of the way from `from` to `to`, `t` between 0 and 1.
+#[must_use]
+pub fn mix(from: Rgb, to: Rgb, t: f64) -> Rgb {
+    let t = t.clamp(0.0, 1.0);
+    // Stays within `from` and `to`, so within a `u8`
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let channel =
+        |i: usize| (f64::from(from[i]) + (f64::from(to[i]) - f64::from(from[i])) * t).round() as u8;
+    [channel(0), channel(1), channel(2)]
+}
+
+/// Pixels in color, row by row.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// The color of every pixel from its `x` and `y`.
+    #[must_use]
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        mut pixel: impl FnMut(usize, usize) -> Rgb,
+    ) -> Self {
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| pixel(x, y))
+            .collect();
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Row by row.
+    #[must_use]
+    pub fn pixels(&self) -> &[Rgb] {
+        &self.pixels
+    }
+
+    /// # Panics
+    /// If the pixel is outside of the image.
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is off the image"
+        );
+        self.pixels[y * self.width + x]
+    }
+
+    /// Every pixel as a square of `factor` by `factor`, the grids of most puzzles are tiny
+    /// otherwise.
+    #[must_use]
+    pub fn scaled(&self, factor: usize) -> Self {
+        Self::from_fn(self.width * factor, self.height * factor, |x, y| {
+            self.get(x / factor, y / factor)
+        })
+    }
+
+    /// The escape codes of the kitty graphics protocol that show the image at the cursor.
+    #[must_use]
+    pub fn kitty(&self) -> String {
+        if self.pixels.is_empty() {
+            return String::new();
+        }
+        let data = base64(&self.pixels.concat());
+        // The protocol wants chunks of at most 4096 bytes, `m=1` on all but the last
+        let chunks: Vec<_> = data.as_bytes().chunks(4096).collect();
+        let mut out = String::new();
+        for (i, chunk) in chunks.iter().enumerate() {
+            let more = u8::from(i + 1 < chunks.len());
+            // Base64 is ASCII, so every chunk is whole characters
+            let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+            if i == 0 {
+                let (width, height) = (self.width, self.height);
+                write!(
+                    out,
+                    "\x1b_Ga=T,f=24,s={width},v={height},m={more};{chunk}\x1b\\"
+                )
+                .unwrap();
+            } else {
+                write!(out, "\x1b_Gm={more};{chunk}\x1b\\").unwrap();
+            }
+        }
+        out.push('\n');
+        out
+    }
+
+    /// The image as sixels, six rows of pixels at a time.
+    #[must_use]
+    pub fn sixel(&self) -> String {
+        if self.pixels.is_empty() {
+            return String::new();
+        }
+        let palette = Palette::new(&self.pixels);
+        let color = |x: usize, y: usize| palette.index(self.get(x, y));
+
+        let mut out = format!("\x1bPq\"1;1;{};{}", self.width, self.height);
+        for (rgb, i) in palette.colors() {
+            let [r, g, b] = rgb.map(|c| u32::from(c) * 100 / 255);
+            write!(out, "#{i};2;{r};{g};{b}").unwrap();
+        }
+        for top in (0..self.height).step_by(6) {
+            let rows = top..(top + 6).min(self.height);
+            for (_, i) in palette.colors() {
+                let sixels: Vec<u8> = (0..self.width)
+                    .map(|x| {
+                        let bits = rows
+                            .clone()
+                            .filter(|&y| color(x, y) == i)
+                            .fold(0, |bits, y| bits | 1 << (y - top));
+                        b'?' + bits
+                    })
+                    .collect();
+                // `?` has no pixels of this color, there is no need to end with them
+                let Some(last) = sixels.iter().rposition(|&sixel| sixel != b'?') else {
+                    continue;
+                };
+                write!(out, "#{i}").unwrap();
+                run_lengths(&mut out, &sixels[..=last]);
+                out.push('$');
+            }
+            out.push('-');
+        }
+        out.push_str("\x1b\\\n");
+        out
+    }
+}
+
+/// At most 256 colors, numbered, for the formats that need them. Pixels of more colors lose their
+/// lowest bits until they fit.
+#[derive(Debug, Clone)]
+pub(crate) struct Palette {
+    shift: u32,
+    indices: BTreeMap<Rgb, u8>,
+}
+
+impl Palette {
+    pub(crate) fn new<'a>(pixels: impl IntoIterator<Item = &'a Rgb>) -> Self {
+        let colors: BTreeSet<Rgb> = pixels.into_iter().copied().collect();
+        let mut shift = 0;
+        loop {
+            let quantized: BTreeSet<Rgb> = colors
+                .iter()
+                .map(|color| color.map(|c| c >> shift << shift))
+                .collect();
+            if quantized.len() <= 256 {
+                let indices = quantized.into_iter().zip(0..=u8::MAX).collect();
+                return Self { shift, indices };
+            }
+            shift += 1;
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// The number of the color closest to `pixel`, which has to be one the palette was made of.
+    pub(crate) fn index(&self, pixel: Rgb) -> u8 {
+        self.indices[&pixel.map(|c| c >> self.shift << self.shift)]
+    }
+
+    /// The colors with their numbers, in no particular order.
+    pub(crate) fn colors(&self) -> impl Iterator<Item = (Rgb, u8)> + '_ {
+        self.indices.iter().map(|(&rgb, &i)| (rgb, i))
+    }
+}
+
+/// Runs of more than three of the same sixel as `!<count><sixel>`.
+fn run_lengths(out: &mut String, sixels: &[u8]) {
+    for run in sixels.chunk_by(|a, b| a == b) {
+        let sixel = char::from(run[0]);
+        if run.len() > 3 {
+            write!(out, "!{}{sixel}", run.len()).unwrap();
+        } else {
+            out.extend(std::iter::repeat_n(sixel, run.len()));
+        }
+    }
+}
+
+/// Pixels that are on in white, the others black.
+impl From<&Screen> for Image {
+    fn from(screen: &Screen) -> Self {
+        Self::from_fn(screen.width(), screen.height(), |x, y| {
+            if screen.get(x, y) {
+                WHITE
+            } else {
+                BLACK
+            }
+        })
+    }
+}
+
+/// Standard base64 with padding, all the image protocols need.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(char::from(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize]));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// How a terminal can be told to show an image.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Protocol {
+    Kitty,
+    Sixel,
+}
+
+impl Protocol {
+    /// What the terminal stdout goes to supports, `None` if it is no terminal or does not look like
+    /// one that shows images.
+    #[must_use]
+    pub fn detect() -> Option<Self> {
+        let var = |name| std::env::var(name).ok();
+        let overridden = var(IMAGES_VAR);
+        if overridden.is_none() && !std::io::stdout().is_terminal() {
+            return None;
+        }
+        Self::from_env(
+            overridden.as_deref(),
+            var("TERM").as_deref(),
+            var("TERM_PROGRAM").as_deref(),
+            var("KITTY_WINDOW_ID").is_some(),
+        )
+    }
+
+    /// [`Protocol::detect`] from the environment variables: `AOC_IMAGES`, `TERM`, `TERM_PROGRAM` and
+    /// whether `KITTY_WINDOW_ID` is set.
+    fn from_env(
+        overridden: Option<&str>,
+        term: Option<&str>,
+        program: Option<&str>,
+        kitty_window: bool,
+    ) -> Option<Self> {
+        if let Some(overridden) = overridden {
+            return match overridden {
+                "kitty" => Some(Protocol::Kitty),
+                "sixel" => Some(Protocol::Sixel),
+                _ => None,
+            };
+        }
+        let term = term.unwrap_or_default();
+        match program.unwrap_or_default() {
+            _ if kitty_window || term == "xterm-kitty" => Some(Protocol::Kitty),
+            "WezTerm" | "ghostty" => Some(Protocol::Kitty),
+            _ if ["foot", "mlterm", "contour"].contains(&term) || term.contains("sixel") => {
+                Some(Protocol::Sixel)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// `image` in the protocol of the terminal, `text` where images can not be shown.
+pub fn show(image: &Image, text: impl FnOnce() -> String) -> String {
+    match Protocol::detect() {
+        Some(Protocol::Kitty) => image.kitty(),
+        Some(Protocol::Sixel) => image.sixel(),
+        None => text(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_base64() {
+        assert_eq!(base64(b"Man"), "TWFu");
+        assert_eq!(base64(b"Ma"), "TWE=");
+        assert_eq!(base64(b"M"), "TQ==");
+        assert_eq!(base64(&[0xff, 0xfe, 0x00, 0x01]), "//4AAQ==");
+    }
+
+    #[test]
+    fn kitty() {
+        let image = Image::from_fn(1, 1, |_, _| [255, 0, 0]);
+        assert_eq!(image.kitty(), "\x1b_Ga=T,f=24,s=1,v=1,m=0;/wAA\x1b\\\n");
+
+        let large = Image::from_fn(64, 64, |x, y| [0, 0, u8::try_from(x ^ y).unwrap()]).kitty();
+        assert_eq!(large.matches("\x1b_G").count(), 4);
+        assert!(large.contains("m=1;") && large.contains("\x1b_Gm=0;"));
+    }
+
+    #[test]
+    fn sixel() {
+        let red = [255, 0, 0];
+        let image = Image::from_fn(5, 2, |x, y| if x == 0 && y == 1 { red } else { BLACK });
+        assert_eq!(
+            image.sixel(),
+            "\x1bPq\"1;1;5;2#0;2;0;0;0#1;2;100;0;0#0@!4B$#1A$-\x1b\\\n"
+        );
+        assert_eq!(Image::from_fn(0, 0, |_, _| BLACK).sixel(), "");
+
+        let many = Image::from_fn(300, 1, |x, _| [u8::try_from(x % 256).unwrap(), 0, 0]);
+        assert!(many.sixel().contains("#255;"));
+        assert!(!many.sixel().contains("#256;"));
+    }
+
+    #[test]
+    fn scales() {
+        let mut screen = Screen::new(2, 1);
+        screen.set(1, 0, true);
+        let image = Image::from(&screen).scaled(2);
+        assert_eq!((image.width(), image.height()), (4, 2));
+        assert_eq!(image.get(0, 1), BLACK);
+        assert_eq!(image.get(3, 1), WHITE);
+        assert_eq!(mix(BLACK, [200, 100, 0], 0.5), [100, 50, 0]);
+    }
+
+    #[test]
+    fn detects() {
+        let detect = |overridden, term, program, window| {
+            Protocol::from_env(overridden, term, program, window)
+        };
+        assert_eq!(
+            detect(None, Some("xterm-kitty"), None, false),
+            Some(Protocol::Kitty)
+        );
+        assert_eq!(detect(None, None, None, true), Some(Protocol::Kitty));
+        assert_eq!(
+            detect(None, None, Some("WezTerm"), false),
+            Some(Protocol::Kitty)
+        );
+        assert_eq!(
+            detect(None, Some("foot"), None, false),
+            Some(Protocol::Sixel)
+        );
+        assert_eq!(detect(None, Some("xterm-256color"), None, false), None);
+        assert_eq!(
+            detect(Some("sixel"), Some("xterm-kitty"), None, false),
+            Some(Protocol::Sixel)
+        );
+        assert_eq!(detect(Some("off"), Some("xterm-kitty"), None, true), None);
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt::Debug;
+#[cfg(not(debug_assertions))]
+use std::marker::PhantomData;
+
+#[cfg(debug_assertions)]
+type Check<S> = Box<dyn Fn(&S) -> Result<(), String>>;
+
+/// Named checks a simulation runs against its state after every step.
+///
+/// The checks only exist with `debug_assertions`, in release builds [`Invariants::register`]
+/// drops them without boxing and [`Invariants::check`] is empty.
+pub struct Invariants<S: ?Sized> {
+    #[cfg(debug_assertions)]
+    checks: Vec<(&'static str, Check<S>)>,
+    #[cfg(not(debug_assertions))]
+    checks: PhantomData<fn(&S)>,
+}
+
+impl<S: Debug + ?Sized> Invariants<S> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            #[cfg(debug_assertions)]
+            checks: vec![],
+            #[cfg(not(debug_assertions))]
+            checks: PhantomData,
+        }
+    }
+
+    /// The check returns a description of what is wrong on violation.
+    #[must_use]
+    #[cfg_attr(not(debug_assertions), allow(unused_mut))]
+    pub fn register(
+        mut self,
+        name: &'static str,
+        check: impl Fn(&S) -> Result<(), String> + 'static,
+    ) -> Self {
+        #[cfg(debug_assertions)]
+        self.checks.push((name, Box::new(check)));
+        #[cfg(not(debug_assertions))]
+        let _ = (name, check);
+        self
+    }
+
+    /// # Panics
+    /// If any invariant does not hold, with the reason and the full state.
+    #[inline]
+    #[cfg_attr(not(debug_assertions), allow(unused_variables))]
+    pub fn check(&self, state: &S) {
+        #[cfg(debug_assertions)]
+        for (name, check) in &self.checks {
+            if let Err(reason) = check(state) {
+                panic!("Invariant {name:?} violated: {reason}\nState: {state:#?}");
+            }
+        }
+    }
+}
+
+impl<S: Debug + ?Sized> Default for Invariants<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted() -> Invariants<[u32]> {
+        Invariants::new().register("sorted", |s: &[u32]| {
+            match s.windows(2).position(|w| w[0] > w[1]) {
+                Some(idx) => Err(format!("{} > {} at {idx}", s[idx], s[idx + 1])),
+                None => Ok(()),
+            }
+        })
+    }
+
+    #[test]
+    fn holds() {
+        sorted().check(&[1, 2, 2, 5]);
+    }
+
+    #[test]
+    #[cfg_attr(
+        debug_assertions,
+        should_panic(expected = "Invariant \"sorted\" violated: 5 > 3 at 1")
+    )]
+    fn violated() {
+        sorted().check(&[1, 5, 3]);
+    }
+}
@@ -0,0 +1,269 @@
+//! `aoc leaderboard`: the stars of the members of a private leaderboard in the terminal.
+
+use crate::fetch::unlock_time;
+use crate::history::format_duration;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The API asks to not fetch a leaderboard more often than this, it is not updated more often.
+pub const CACHE_TIME: Duration = Duration::from_mins(15);
+
+/// Where a fetched leaderboard is kept until it may be fetched again. It contains the names of the
+/// members, so it is git ignored.
+#[must_use]
+pub fn cache_path(year: u16, id: &str) -> PathBuf {
+    PathBuf::from(format!("leaderboard-{year}-{id}.json"))
+}
+
+/// The JSON cached at `path`, if it is younger than [`CACHE_TIME`] at `now`, otherwise `fetch`ed
+/// and cached again. Also returns when the JSON was fetched.
+///
+/// # Errors
+/// If fetching fails or the cache can not be written.
+pub fn load_or_fetch(
+    path: &Path,
+    now: SystemTime,
+    fetch: impl FnOnce() -> Result<String>,
+) -> Result<(String, SystemTime)> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+    if let Some(modified) = modified.filter(|&m| m + CACHE_TIME > now) {
+        if let Ok(json) = fs::read_to_string(path) {
+            return Ok((json, modified));
+        }
+    }
+
+    let json = fetch()?;
+    fs::write(path, &json).with_context(|| format!("Could not write {}", path.display()))?;
+    Ok((json, now))
+}
+
+/// The parts of the JSON of a private leaderboard shown here.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// `None` for anonymous members
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u32,
+    /// Seconds since the epoch of each star by day and part
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Copy, Clone, Deserialize)]
+pub struct Star {
+    pub get_star_ts: u64,
+}
+
+impl Member {
+    #[must_use]
+    pub fn name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// How long after the unlock the member got a star.
+    #[must_use]
+    pub fn time_to_solve(&self, year: u16, day: u8, part: u8) -> Option<Duration> {
+        let star = self.completion_day_level.get(&day)?.get(&part)?;
+        (UNIX_EPOCH + Duration::from_secs(star.get_star_ts))
+            .duration_since(unlock_time(year, day))
+            .ok()
+    }
+
+    fn stars_on(&self, day: u8) -> usize {
+        self.completion_day_level.get(&day).map_or(0, BTreeMap::len)
+    }
+}
+
+impl Leaderboard {
+    /// # Errors
+    /// If the JSON does not look like a private leaderboard.
+    pub fn parse(json: &str) -> Result<Self> {
+        serde_json::from_str(json).context("Invalid leaderboard")
+    }
+
+    /// By local score, like the leaderboard on the website.
+    #[must_use]
+    pub fn ranking(&self) -> Vec<&Member> {
+        let mut members: Vec<_> = self.members.values().collect();
+        members.sort_by_key(|m| (std::cmp::Reverse((m.local_score, m.stars)), m.id));
+        members
+    }
+
+    /// The score, stars and a column per day of every member: `*` for both stars, `.` for only the
+    /// first.
+    ///
+    /// # Panics
+    /// Never, writing to a `String` does not fail.
+    #[must_use]
+    pub fn report(&self) -> String {
+        let mut out = String::new();
+        writeln!(
+            out,
+            "             {}\n             {}",
+            (1..=25).map(tens).collect::<String>(),
+            (1..=25)
+                .map(|d| char::from(b'0' + d % 10))
+                .collect::<String>()
+        )
+        .unwrap();
+
+        for (rank, member) in self.ranking().into_iter().enumerate() {
+            let days: String = (1..=25)
+                .map(|day| match member.stars_on(day) {
+                    0 => ' ',
+                    1 => '.',
+                    _ => '*',
+                })
+                .collect();
+            writeln!(
+                out,
+                "{:>3}) {:>4} {:>2} {days} {}",
+                rank + 1,
+                member.local_score,
+                member.stars,
+                member.name()
+            )
+            .unwrap();
+        }
+
+        out
+    }
+
+    /// When the members solved the parts of one day, fastest first.
+    ///
+    /// # Panics
+    /// Never, writing to a `String` does not fail.
+    #[must_use]
+    pub fn day_report(&self, year: u16, day: u8) -> String {
+        let mut solved: Vec<_> = self
+            .members
+            .values()
+            .filter(|m| m.stars_on(day) > 0)
+            .map(|m| {
+                (
+                    m.time_to_solve(year, day, 2),
+                    m.time_to_solve(year, day, 1),
+                    m,
+                )
+            })
+            .collect();
+        // Both stars before one star
+        solved.sort_by_key(|&(part2, part1, m)| (part2.is_none(), part2, part1, m.id));
+
+        let mut out = String::new();
+        writeln!(out, "Day {day}      part 1      part 2").unwrap();
+        for (part2, part1, member) in solved {
+            let time = |t: Option<Duration>| t.map_or_else(|| "-".into(), format_duration);
+            writeln!(
+                out,
+                "      {:>10}  {:>10}  {}",
+                time(part1),
+                time(part2),
+                member.name()
+            )
+            .unwrap();
+        }
+
+        out
+    }
+}
+
+fn tens(day: u8) -> char {
+    if day < 10 {
+        ' '
+    } else {
+        char::from(b'0' + day / 10)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board() -> Leaderboard {
+        let ts = |day, mins: u64| {
+            unlock_time(2022, day)
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+                + mins * 60
+        };
+        let json = format!(
+            r#"{{"event": "2022", "owner_id": 1, "members": {{
+                "1": {{"id": 1, "name": "Owner", "local_score": 3, "stars": 3,
+                      "last_star_ts": 0, "global_score": 0,
+                      "completion_day_level": {{
+                        "1": {{"1": {{"get_star_ts": {}, "star_index": 1}},
+                              "2": {{"get_star_ts": {}, "star_index": 2}}}},
+                        "10": {{"1": {{"get_star_ts": {}, "star_index": 3}}}}}}}},
+                "7": {{"id": 7, "name": null, "local_score": 4, "stars": 2,
+                      "completion_day_level": {{
+                        "1": {{"1": {{"get_star_ts": {}, "star_index": 1}},
+                              "2": {{"get_star_ts": {}, "star_index": 2}}}}}}}},
+                "9": {{"id": 9, "name": "Late", "local_score": 0, "stars": 0,
+                      "completion_day_level": {{}}}}}}}}"#,
+            ts(1, 30),
+            ts(1, 90),
+            ts(10, 5),
+            ts(1, 10),
+            ts(1, 20)
+        );
+        Leaderboard::parse(&json).unwrap()
+    }
+
+    #[test]
+    fn reports_stars() {
+        let report = board().report();
+        let lines: Vec<_> = report.lines().collect();
+        assert_eq!(lines[0], "                      1111111111222222");
+        assert_eq!(lines[1], "             1234567890123456789012345");
+        assert_eq!(
+            lines[2],
+            "  1)    4  2 *                         (anonymous user #7)"
+        );
+        assert_eq!(lines[3], "  2)    3  3 *        .                Owner");
+        assert_eq!(lines[4], "  3)    0  0                           Late");
+    }
+
+    #[test]
+    fn reports_day() {
+        assert_eq!(
+            board().day_report(2022, 1),
+            "Day 1      part 1      part 2
+             10m         20m  (anonymous user #7)
+             30m       1h30m  Owner
+"
+        );
+        assert!(board()
+            .day_report(2022, 10)
+            .ends_with("  5m           -  Owner\n"));
+    }
+
+    #[test]
+    fn caches() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("leaderboard.json");
+        let now = SystemTime::now();
+
+        let (json, fetched) = load_or_fetch(&path, now, || Ok("first".into())).unwrap();
+        assert_eq!((json.as_str(), fetched), ("first", now));
+        let (json, _) = load_or_fetch(&path, now, || Ok("second".into())).unwrap();
+        assert_eq!(json, "first");
+        let later = now + CACHE_TIME + Duration::from_secs(1);
+        let (json, _) = load_or_fetch(&path, later, || Ok("third".into())).unwrap();
+        assert_eq!(json, "third");
+    }
+}
@@ -0,0 +1,80 @@
+#![warn(clippy::pedantic)]
+
+//! What every year shares: getting inputs, running and checking days, keeping track of answers,
+//! and the data structures many puzzles need.
+
+// So `#[aoc(...)]`, which refers to `::aoc_core`, also works in here.
+extern crate self as aoc_core;
+
+pub use aoc_macros::aoc;
+#[doc(hidden)]
+pub use inventory;
+
+pub mod animation;
+pub mod answer;
+pub mod answer_cache;
+#[cfg(not(target_family = "wasm"))]
+pub mod bench;
+pub mod budget;
+pub mod cancel;
+#[cfg(not(target_family = "wasm"))]
+pub mod chart;
+pub mod checkpoint;
+#[cfg(not(target_family = "wasm"))]
+pub mod complexity;
+pub mod compress;
+pub mod coord;
+#[cfg(not(target_family = "wasm"))]
+pub mod crypt;
+pub mod cycle;
+pub mod days;
+pub mod dot;
+pub mod dump;
+#[cfg(not(target_family = "wasm"))]
+pub mod energy;
+pub mod error;
+pub mod explain;
+pub mod export;
+pub mod expr;
+pub mod fetch;
+pub mod flood;
+pub mod grid;
+pub mod heap;
+pub mod history;
+pub mod image;
+pub mod invariant;
+pub mod leaderboard;
+pub mod lines;
+pub mod logging;
+pub mod memo;
+pub mod net;
+pub mod ocr;
+pub mod parse;
+pub mod parts;
+// The counters are only read on Linux, elsewhere they fail to open
+#[cfg(not(target_family = "wasm"))]
+pub mod perf;
+#[cfg(not(target_family = "wasm"))]
+pub mod plugins;
+pub mod point;
+pub mod profile;
+pub mod puzzle;
+pub mod record;
+pub mod registry;
+pub mod scaffold;
+pub mod screen;
+#[cfg(not(target_family = "wasm"))]
+pub mod serve;
+pub mod sniff;
+pub mod solver;
+pub mod sparse_grid;
+#[cfg(not(target_family = "wasm"))]
+pub mod stats;
+pub mod style;
+pub mod submit;
+#[cfg(not(target_family = "wasm"))]
+pub mod timing;
+pub mod verify;
+pub mod vm;
+#[cfg(not(target_family = "wasm"))]
+pub mod watch;
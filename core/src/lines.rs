@@ -0,0 +1,76 @@
+use anyhow::{anyhow, Result};
+use rayon::prelude::*;
+
+/// Applies `f` to every line in parallel, keeping the order of the lines.
+///
+/// Only pays off once there is enough work per input, see `aoc2023-day01 --crossover`.
+///
+/// # Errors
+/// Lists every failed line with its (1-based) line number.
+pub fn par_map_lines<T: Send>(input: &str, f: impl Fn(&str) -> Result<T> + Sync) -> Result<Vec<T>> {
+    let lines: Vec<&str> = input.lines().collect();
+    let results: Vec<Result<T>> = lines.par_iter().map(|line| f(line)).collect();
+    collect_errors(results)
+}
+
+/// Same as [`par_map_lines`] on a single thread, as a baseline.
+///
+/// # Errors
+/// Lists every failed line with its (1-based) line number.
+pub fn map_lines<T>(input: &str, f: impl Fn(&str) -> Result<T>) -> Result<Vec<T>> {
+    collect_errors(input.lines().map(f).collect())
+}
+
+fn collect_errors<T>(results: Vec<Result<T>>) -> Result<Vec<T>> {
+    let mut values = Vec::with_capacity(results.len());
+    let mut errors = vec![];
+
+    for (idx, result) in results.into_iter().enumerate() {
+        match result {
+            Ok(value) => values.push(value),
+            Err(e) => errors.push(format!("line {}: {e:#}", idx + 1)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(anyhow!(
+            "{} lines failed:\n{}",
+            errors.len(),
+            errors.join("\n")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_order() {
+        let input = (0..10_000)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let parsed = par_map_lines(&input, |l| Ok(l.parse::<u32>()?)).unwrap();
+
+        assert_eq!(parsed, (0..10_000).collect::<Vec<_>>());
+        assert_eq!(
+            map_lines(&input, |l| Ok(l.parse::<u32>()?)).unwrap(),
+            parsed
+        );
+    }
+
+    #[test]
+    fn reports_all_errors() {
+        let err = par_map_lines("1\nx\n3\n-4\n", |l| Ok(l.parse::<u32>()?)).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "2 lines failed:
+line 2: invalid digit found in string
+line 4: invalid digit found in string"
+        );
+    }
+}
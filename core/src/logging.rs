@@ -0,0 +1,69 @@
+//! What the days log with `tracing` goes to stderr, so it never mixes with the answers.
+
+use anyhow::{Context, Result};
+use std::io::IsTerminal;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+
+/// Only warnings, unless asked for more with `--log-level`.
+pub const DEFAULT_LEVEL: LevelFilter = LevelFilter::WARN;
+
+/// Reads `--log-level <level>` (`off`, `error`, `warn`, `info`, `debug` or `trace`), other
+/// arguments are ignored.
+///
+/// # Errors
+/// If the level is missing or unknown.
+pub fn level_from_args(args: impl IntoIterator<Item = String>) -> Result<LevelFilter> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--log-level" {
+            let level = args.next().context("--log-level needs a level")?;
+            return level
+                .parse()
+                .with_context(|| format!("Unknown log level {level:?}"));
+        }
+    }
+
+    Ok(DEFAULT_LEVEL)
+}
+
+/// Logs events up to `level` to stderr, with how long each span took when it closes. Does nothing
+/// if logging is set up already.
+pub fn init(level: LevelFilter) {
+    let _already = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .try_init();
+}
+
+/// [`init`] with the level given by `--log-level`.
+///
+/// # Errors
+/// If the level is missing or unknown.
+pub fn init_from_args(args: impl IntoIterator<Item = String>) -> Result<()> {
+    init(level_from_args(args)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(args: &[&str]) -> Result<LevelFilter> {
+        level_from_args(args.iter().map(|&a| a.to_owned()))
+    }
+
+    #[test]
+    fn levels() {
+        assert_eq!(level(&["--part", "1"]).unwrap(), DEFAULT_LEVEL);
+        assert_eq!(
+            level(&["--log-level", "debug"]).unwrap(),
+            LevelFilter::DEBUG
+        );
+        assert_eq!(level(&["--log-level", "off"]).unwrap(), LevelFilter::OFF);
+        level(&["--log-level", "loud"]).unwrap_err();
+        level(&["--log-level"]).unwrap_err();
+    }
+}
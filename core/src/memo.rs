@@ -0,0 +1,154 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::hash::Hash;
+use std::path::PathBuf;
+
+/// Memoizes the results of one function.
+///
+/// With [`Memo::on_disk`] the results are also written to a directory, so expensive intermediate
+/// results survive across runs while iterating on the rest of a solution. The disk cache is best
+/// effort: unreadable entries, and keys that can not be serialized, are recomputed and failed
+/// writes are ignored.
+#[derive(Debug)]
+pub struct Memo<K, V> {
+    name: &'static str,
+    map: HashMap<K, V>,
+    dir: Option<PathBuf>,
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Hash + Eq + Clone + Serialize,
+    V: Serialize + DeserializeOwned,
+{
+    /// `name` identifies the function, change it when the function changes to not get stale
+    /// results from disk.
+    #[must_use]
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            map: HashMap::new(),
+            dir: None,
+        }
+    }
+
+    #[must_use]
+    pub fn on_disk(self, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: Some(dir.into()),
+            ..self
+        }
+    }
+
+    /// Uses the directory in `AOC_MEMO_DIR` if set.
+    #[must_use]
+    pub fn on_disk_from_env(self) -> Self {
+        match std::env::var_os("AOC_MEMO_DIR") {
+            Some(dir) => self.on_disk(dir),
+            None => self,
+        }
+    }
+
+    pub fn get(&mut self, key: &K, compute: impl FnOnce(&K) -> V) -> &V {
+        if !self.map.contains_key(key) {
+            let value = self
+                .load(key)
+                .unwrap_or_else(|| self.store(key, compute(key)));
+            self.map.insert(key.clone(), value);
+        }
+
+        &self.map[key]
+    }
+
+    /// The file of the entry, with the serialized key.
+    fn path(&self, key: &K) -> Option<(PathBuf, Vec<u8>)> {
+        let dir = self.dir.as_ref()?;
+        let key = rmp_serde::to_vec(key).ok()?;
+        let hash = Sha256::new()
+            .chain_update(self.name)
+            .chain_update([0])
+            .chain_update(&key)
+            .finalize();
+        Some((dir.join(format!("{}-{hash:x}.msgpack", self.name)), key))
+    }
+
+    fn load(&self, key: &K) -> Option<V> {
+        let (path, key) = self.path(key)?;
+        let entry: Entry<V> = rmp_serde::from_slice(&fs::read(path).ok()?).ok()?;
+        (entry.key == key).then_some(entry.value)
+    }
+
+    fn store(&self, key: &K, value: V) -> V {
+        if let Some((path, key)) = self.path(key) {
+            let entry = Entry { key, value };
+            if let Ok(bytes) = rmp_serde::to_vec(&entry) {
+                let _ = path
+                    .parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|()| fs::write(path, bytes));
+            }
+            return entry.value;
+        }
+
+        value
+    }
+}
+
+/// A result on disk, with the key it is for since different keys could share a file.
+#[derive(Serialize, Deserialize)]
+struct Entry<V> {
+    key: Vec<u8>,
+    value: V,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn in_memory() {
+        let calls = Cell::new(0);
+        let mut memo = Memo::new("square");
+        let mut square = |n: u64| {
+            *memo.get(&n, |n| {
+                calls.set(calls.get() + 1);
+                n * n
+            })
+        };
+
+        assert_eq!(square(3), 9);
+        assert_eq!(square(3), 9);
+        assert_eq!(square(4), 16);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn survives_runs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+
+        let mut first: Memo<(u8, u8), Vec<u8>> = Memo::new("paths").on_disk(dir);
+        assert_eq!(first.get(&(1, 2), |_| vec![1, 2, 3]), &[1, 2, 3]);
+
+        let mut second: Memo<(u8, u8), Vec<u8>> = Memo::new("paths").on_disk(dir);
+        assert_eq!(second.get(&(1, 2), |_| unreachable!()), &[1, 2, 3]);
+
+        let mut other: Memo<(u8, u8), Vec<u8>> = Memo::new("other").on_disk(dir);
+        assert_eq!(other.get(&(1, 2), |_| vec![]), &[0u8; 0]);
+
+        // An entry of another key in the same file is not taken
+        let (path, _) = second.path(&(1, 2)).unwrap();
+        let (_, key) = second.path(&(3, 4)).unwrap();
+        let entry = Entry {
+            key,
+            value: vec![9],
+        };
+        fs::write(&path, rmp_serde::to_vec(&entry).unwrap()).unwrap();
+        let mut third: Memo<(u8, u8), Vec<u8>> = Memo::new("paths").on_disk(dir);
+        assert_eq!(third.get(&(1, 2), |_| vec![4]), &[4]);
+    }
+}
@@ -0,0 +1,271 @@
+//! The HTTP client every network feature shares, so they all identify themselves the same way and
+//! back off the same way when adventofcode.com is busy.
+
+use anyhow::{anyhow, Result};
+use std::fmt::{Debug, Formatter};
+use std::time::Duration;
+
+/// The puzzle author asks automated tools to say where they come from, `AOC_CONTACT` (e.g. an
+/// email address) is added if it is set.
+const USER_AGENT: &str = "github.com/tdittr/aoc";
+
+/// How often a request is tried again after being rate limited, a server error or a network error.
+pub const RETRIES: u32 = 4;
+
+const FIRST_BACKOFF: Duration = Duration::from_secs(1);
+
+const MAX_BACKOFF: Duration = Duration::from_mins(1);
+
+#[must_use]
+pub fn user_agent() -> String {
+    match std::env::var("AOC_CONTACT") {
+        Ok(contact) if !contact.trim().is_empty() => format!("{USER_AGENT} by {}", contact.trim()),
+        _ => USER_AGENT.to_owned(),
+    }
+}
+
+/// How long to wait before the retry after `attempt` failed ones: as long as the server asked for
+/// with `Retry-After`, or else doubling from one second up to a minute.
+#[must_use]
+pub fn backoff(attempt: u32, retry_after: Option<Duration>) -> Duration {
+    retry_after.unwrap_or_else(|| {
+        FIRST_BACKOFF
+            .saturating_mul(1 << attempt.min(16))
+            .min(MAX_BACKOFF)
+    })
+}
+
+/// Whether a response with this status might be different when asked again: rate limiting and
+/// server errors. Only rate limiting for requests that change something, like submitting an
+/// answer, as after a server error it might have been taken already.
+#[must_use]
+pub fn is_transient(status: u16, idempotent: bool) -> bool {
+    status == 429 || (idempotent && (500..600).contains(&status))
+}
+
+/// Sends the session cookie with every request. Build it once and pass it around.
+#[derive(Clone)]
+#[cfg_attr(target_family = "wasm", allow(dead_code))] // Nothing is sent from WebAssembly
+pub struct Client {
+    session: String,
+    retries: u32,
+    #[cfg(not(target_family = "wasm"))]
+    agent: ureq::Agent,
+}
+
+/// Without the session cookie, so it does not end up in logs and errors.
+impl Debug for Client {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Client { session: .. }")
+    }
+}
+
+impl Client {
+    #[must_use]
+    pub fn new(session: impl Into<String>) -> Self {
+        Self {
+            session: session.into(),
+            retries: RETRIES,
+            #[cfg(not(target_family = "wasm"))]
+            agent: ureq::AgentBuilder::new()
+                .user_agent(&user_agent())
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// With the session cookie of [`session`](crate::fetch::session).
+    ///
+    /// # Errors
+    /// If there is no session cookie.
+    pub fn from_session() -> Result<Self> {
+        Ok(Self::new(crate::fetch::session()?))
+    }
+
+    /// With the session cookie of `account`, see [`account_session`](crate::fetch::account_session),
+    /// or the default one.
+    ///
+    /// # Errors
+    /// If there is no session cookie for it.
+    pub fn for_account(account: Option<&str>) -> Result<Self> {
+        match account {
+            Some(account) => Ok(Self::new(crate::fetch::account_session(account)?)),
+            None => Self::from_session(),
+        }
+    }
+
+    /// Gives up after this many retries instead of [`RETRIES`], e.g. 0 to only check something.
+    #[must_use]
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// # Errors
+    /// On network errors, or if the server does not answer with success even after retrying.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn get(&self, url: &str) -> Result<String> {
+        self.send("Fetching", &self.request("GET", url), None)
+    }
+
+    /// # Errors
+    /// On network errors, or if the server does not answer with success even after retrying.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn post_form(&self, url: &str, form: &[(&str, &str)]) -> Result<String> {
+        self.send("Posting to", &self.request("POST", url), Some(form))
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        self.agent
+            .request(method, url)
+            .set("Cookie", &format!("session={}", self.session))
+    }
+
+    /// Makes the request, with the form if there is one, until it succeeds, it fails for good or
+    /// the retries are used up. Only requests without a form are idempotent.
+    #[cfg(not(target_family = "wasm"))]
+    fn send(
+        &self,
+        what: &str,
+        request: &ureq::Request,
+        form: Option<&[(&str, &str)]>,
+    ) -> Result<String> {
+        let url = request.url();
+        let idempotent = form.is_none();
+        let mut attempt = 0;
+        loop {
+            let response = match form {
+                Some(form) => request.clone().send_form(form),
+                None => request.clone().call(),
+            };
+            let error = match response {
+                Ok(response) => return Ok(response.into_string()?),
+                Err(error) => error,
+            };
+            let retry_after = match &error {
+                ureq::Error::Status(status, response) if is_transient(*status, idempotent) => {
+                    response
+                        .header("Retry-After")
+                        .and_then(|secs| secs.trim().parse().ok())
+                        .map(Duration::from_secs)
+                }
+                ureq::Error::Transport(_) if idempotent => None,
+                _ => return Err(anyhow!("{what} {url} failed: {error}")),
+            };
+            if attempt == self.retries {
+                return Err(anyhow!(
+                    "{what} {url} failed {} times, the last time with: {error}",
+                    attempt + 1
+                ));
+            }
+
+            let wait = backoff(attempt, retry_after);
+            eprintln!("{what} {url} failed ({error}), trying again in {wait:?}");
+            std::thread::sleep(wait);
+            attempt += 1;
+        }
+    }
+
+    /// The browser would not send the session cookie to another site anyway, the input has to be
+    /// pasted.
+    ///
+    /// # Errors
+    /// Always.
+    #[cfg(target_family = "wasm")]
+    pub fn get(&self, url: &str) -> Result<String> {
+        Err(anyhow!("Can not fetch {url} from WebAssembly"))
+    }
+
+    /// # Errors
+    /// Always.
+    #[cfg(target_family = "wasm")]
+    pub fn post_form(&self, url: &str, _form: &[(&str, &str)]) -> Result<String> {
+        Err(anyhow!("Can not post to {url} from WebAssembly"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backs_off() {
+        assert_eq!(backoff(0, None), Duration::from_secs(1));
+        assert_eq!(backoff(3, None), Duration::from_secs(8));
+        assert_eq!(backoff(30, None), MAX_BACKOFF);
+        assert_eq!(
+            backoff(0, Some(Duration::from_secs(5))),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            backoff(0, Some(Duration::from_hours(1))),
+            Duration::from_hours(1)
+        );
+    }
+
+    #[test]
+    fn hides_session() {
+        let client = Client::new("53616c7465645f5f");
+        assert_eq!(format!("{client:?}"), "Client { session: .. }");
+    }
+
+    #[test]
+    fn transient() {
+        assert!(is_transient(429, false));
+        assert!(is_transient(503, true));
+        assert!(!is_transient(503, false));
+        assert!(!is_transient(404, true));
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    #[test]
+    fn retries_when_rate_limited() {
+        use tiny_http::{Header, Response, Server};
+
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/2022/day/1/input", server.server_addr());
+        let handle = std::thread::spawn(move || {
+            let mut agents = vec![];
+            for status in [429, 200] {
+                let request = server.recv().unwrap();
+                agents.push(
+                    request
+                        .headers()
+                        .iter()
+                        .find(|h| h.field.equiv("User-Agent"))
+                        .map(|h| h.value.to_string()),
+                );
+                let retry_after = Header::from_bytes("Retry-After", "0").unwrap();
+                let response = Response::from_string("1000\n")
+                    .with_status_code(status)
+                    .with_header(retry_after);
+                request.respond(response).unwrap();
+            }
+            agents
+        });
+
+        assert_eq!(Client::new("secret").get(&url).unwrap(), "1000\n");
+        let agents = handle.join().unwrap();
+        assert_eq!(agents, vec![Some(user_agent()); 2]);
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    #[test]
+    fn gives_up() {
+        use tiny_http::{Response, Server};
+
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", server.server_addr());
+        let handle = std::thread::spawn(move || {
+            let request = server.recv().unwrap();
+            request
+                .respond(Response::from_string("").with_status_code(404))
+                .unwrap();
+        });
+
+        let err = Client::new("secret").get(&url).unwrap_err();
+        assert!(err.to_string().contains("404"), "{err}");
+        handle.join().unwrap();
+    }
+}
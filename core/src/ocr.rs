@@ -0,0 +1,195 @@
+//! Reads the letters some puzzles draw instead of printing their answer, in the two fonts Advent of
+//! Code uses: 6 pixels high (e.g. 2022 day 10) and 10 pixels high (e.g. 2018 day 10).
+
+use crate::screen::Screen;
+use anyhow::{bail, Result};
+
+/// The letters known in the font that is 6 pixels high, most of them 4 pixels wide.
+const SMALL: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// The letters known in the font that is 10 pixels high and 6 wide.
+const LARGE: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// The letters on the screen. Empty rows around them are ignored, letters are separated by
+/// empty columns.
+///
+/// # Errors
+/// If the letters are neither 6 nor 10 pixels high, or one of them is not in the font.
+pub fn read(screen: &Screen) -> Result<String> {
+    let lit_row = |y| (0..screen.width()).any(|x| screen.get(x, y));
+    let Some(top) = (0..screen.height()).find(|&y| lit_row(y)) else {
+        bail!("The screen is empty");
+    };
+    let bottom = (0..screen.height()).rfind(|&y| lit_row(y)).unwrap_or(top) + 1;
+
+    let font = match bottom - top {
+        6 => SMALL,
+        10 => LARGE,
+        height => bail!("Letters are 6 or 10 pixels high, not {height}"),
+    };
+
+    let lit_col = |x| (top..bottom).any(|y| screen.get(x, y));
+    let mut letters = String::new();
+    let mut x = 0;
+    while x < screen.width() {
+        if !lit_col(x) {
+            x += 1;
+            continue;
+        }
+
+        let left = x;
+        while x < screen.width() && lit_col(x) {
+            x += 1;
+        }
+
+        let glyph = (top..bottom)
+            .map(|y| {
+                (left..x)
+                    .map(|x| if screen.get(x, y) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        match font.iter().find(|(_, g)| *g == glyph) {
+            Some(&(letter, _)) => letters.push(letter),
+            None => bail!("Unknown letter at x = {left}:\n{glyph}"),
+        }
+    }
+
+    Ok(letters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws the letters like the puzzles do, with `gap` empty columns between them.
+    fn draw(font: &[(char, &str)], text: &str, gap: usize) -> Screen {
+        let glyphs: Vec<&str> = text
+            .chars()
+            .map(|c| font.iter().find(|(l, _)| *l == c).unwrap().1)
+            .collect();
+        let height = glyphs[0].lines().count();
+        let width = glyphs
+            .iter()
+            .map(|g| g.lines().next().unwrap().len() + gap)
+            .sum();
+
+        // One empty row above, like a screen that is taller than the letters
+        let mut screen = Screen::new(width, height + 1);
+        let mut left = 0;
+        for glyph in glyphs {
+            for (y, row) in glyph.lines().enumerate() {
+                for (x, pixel) in row.chars().enumerate() {
+                    screen.set(left + x, y + 1, pixel == '#');
+                }
+            }
+            left += glyph.lines().next().unwrap().len() + gap;
+        }
+        screen
+    }
+
+    #[test]
+    fn reads_both_fonts() {
+        let small: String = SMALL.iter().map(|(l, _)| l).collect();
+        assert_eq!(read(&draw(SMALL, &small, 1)).unwrap(), small);
+
+        let large: String = LARGE.iter().map(|(l, _)| l).collect();
+        assert_eq!(read(&draw(LARGE, &large, 2)).unwrap(), large);
+    }
+
+    #[test]
+    fn rejects_unknown_letters() {
+        read(&Screen::new(5, 6)).unwrap_err();
+
+        let mut screen = draw(SMALL, "EH", 1);
+        // A dent in the middle of the E
+        screen.set(2, 3, false);
+        read(&screen).unwrap_err();
+
+        // Too low for either font
+        let mut screen = Screen::new(3, 3);
+        screen.set(1, 1, true);
+        read(&screen).unwrap_err();
+    }
+}
@@ -0,0 +1,252 @@
+use anyhow::{anyhow, Result};
+use miette::{GraphicalReportHandler, GraphicalTheme, LabeledSpan, MietteDiagnostic};
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The part of the input a parse error is about, added with [`At::at`] and shown by [`report`].
+///
+/// Only the address of the slice is kept, so it has to be borrowed from the input that is later
+/// given to [`report`], like the lines or blocks the parsers split it into.
+#[derive(Debug)]
+pub struct Span {
+    start: usize,
+    len: usize,
+    label: String,
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
+pub trait At<T> {
+    /// Like [`anyhow::Context::with_context`], and remembers that the error is about `span`.
+    ///
+    /// # Errors
+    /// If `self` is an error or `None`.
+    fn at<L: Display>(self, span: &str, label: impl FnOnce() -> L) -> Result<T>;
+}
+
+impl<T, E: Into<anyhow::Error>> At<T> for Result<T, E> {
+    fn at<L: Display>(self, span: &str, label: impl FnOnce() -> L) -> Result<T> {
+        self.map_err(|e| e.into().context(Span::new(span, label())))
+    }
+}
+
+impl<T> At<T> for Option<T> {
+    fn at<L: Display>(self, span: &str, label: impl FnOnce() -> L) -> Result<T> {
+        self.ok_or_else(|| anyhow!(Span::new(span, label())))
+    }
+}
+
+/// The [`Span`] of the error and where it starts in `input`.
+fn span_in<'e>(err: &'e anyhow::Error, input: &str) -> Option<(usize, &'e Span)> {
+    let span = err.downcast_ref::<Span>()?;
+    let start = span.start.checked_sub(input.as_ptr() as usize)?;
+    (start + span.len <= input.len()).then_some((start, span))
+}
+
+/// The line of `input` the error is about, starting at 1, if it has a [`Span`] into `input`.
+#[must_use]
+pub fn line(err: &anyhow::Error, input: &str) -> Option<usize> {
+    let (start, _) = span_in(err, input)?;
+    Some(input[..start].matches('\n').count() + 1)
+}
+
+impl Span {
+    /// For errors that are not caused by another one, as `Err(anyhow!(Span::new(line, "...")))`.
+    #[must_use]
+    pub fn new(span: &str, label: impl Display) -> Self {
+        Self {
+            start: span.as_ptr() as usize,
+            len: span.len(),
+            label: label.to_string(),
+        }
+    }
+}
+
+/// The error with the lines of `input` it is about and the offending part underlined, if it has a
+/// [`Span`] into `input`.
+#[must_use]
+pub fn report(err: &anyhow::Error, input: &str) -> Option<String> {
+    let (start, span) = span_in(err, input)?;
+
+    let diagnostic = MietteDiagnostic::new(format!("{err:#}")).with_label(LabeledSpan::at(
+        start..start + span.len,
+        err.root_cause().to_string(),
+    ));
+    let report = miette::Report::new(diagnostic).with_source_code(input.to_owned());
+
+    let mut out = String::new();
+    GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor())
+        .render_report(&mut out, report.as_ref())
+        .ok()?;
+    Some(out)
+}
+
+/// The input as the parsers expect it, however it was saved: without a byte order mark, with `\n`
+/// instead of `\r\n` and without blank lines at the end. A final newline is kept.
+///
+/// Borrows `input` if it is fine already, so spans into the result are spans into `input`.
+#[must_use]
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let stripped = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut normalized = if stripped.contains('\r') {
+        Cow::Owned(stripped.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(stripped)
+    };
+
+    let content = normalized.trim_end_matches('\n').len();
+    // Keep the newline after the last line, if there is a last line
+    let end = if content == 0 {
+        0
+    } else {
+        (content + 1).min(normalized.len())
+    };
+    match &mut normalized {
+        Cow::Borrowed(borrowed) => *borrowed = &borrowed[..end],
+        Cow::Owned(owned) => owned.truncate(end),
+    }
+    normalized
+}
+
+/// The parts of the input separated by blank lines, e.g. one per elf or monkey.
+///
+/// Only newlines are stripped, so indentation at the start of a block is kept.
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split("\n\n")
+        .map(|block| block.trim_matches('\n'))
+        .filter(|block| !block.is_empty())
+}
+
+/// Parses every line, errors tell which line was broken.
+///
+/// # Errors
+/// If a line does not parse.
+pub fn lines_to<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            line.parse()
+                .at(line, || format!("Line {}: {line:?}", idx + 1))
+        })
+        .collect()
+}
+
+/// All integers in `line`, ignoring everything around them.
+///
+/// A `-` right before a number makes it negative, unless it comes after a digit like in `2-4`.
+///
+/// # Errors
+/// If a number does not fit into `T`.
+pub fn numbers<T>(line: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    let bytes = line.as_bytes();
+    let mut numbers = vec![];
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        if !bytes[idx].is_ascii_digit() {
+            idx += 1;
+            continue;
+        }
+
+        let negative =
+            idx > 0 && bytes[idx - 1] == b'-' && (idx < 2 || !bytes[idx - 2].is_ascii_digit());
+        let start = if negative { idx - 1 } else { idx };
+        while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+            idx += 1;
+        }
+
+        let number = &line[start..idx];
+        numbers.push(
+            number
+                .parse()
+                .at(number, || format!("{number} is out of range"))?,
+        );
+    }
+
+    Ok(numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes() {
+        assert!(matches!(normalize("1\n2\n"), Cow::Borrowed("1\n2\n")));
+        assert!(matches!(
+            normalize("\u{feff}1\n2\n\n\n"),
+            Cow::Borrowed("1\n2\n")
+        ));
+        assert_eq!(normalize("1\r\n\r\n2\r\n\r\n"), "1\n\n2\n");
+        assert_eq!(normalize("1\n2"), "1\n2");
+        assert_eq!(normalize("\n\n"), "");
+        assert_eq!(normalize(""), "");
+    }
+
+    #[test]
+    fn splits_blocks() {
+        assert_eq!(
+            blocks("\n1\n2\n\n3\n\n\n    [4]\n").collect::<Vec<_>>(),
+            ["1\n2", "3", "    [4]"]
+        );
+        assert_eq!(blocks("").count(), 0);
+    }
+
+    #[test]
+    fn parses_lines() {
+        assert_eq!(lines_to::<u8>("1\n2\n3\n").unwrap(), [1, 2, 3]);
+
+        let err = lines_to::<u8>("1\nx\n").unwrap_err();
+        assert_eq!(format!("{err}"), "Line 2: \"x\"");
+    }
+
+    #[test]
+    fn reports_spans() {
+        let input = "1\n2\n\n3\nx4\n";
+        let block = blocks(input).nth(1).unwrap();
+
+        let err = lines_to::<u8>(block).unwrap_err();
+        let shown = report(&err, input).unwrap();
+        assert!(shown.contains("Line 2: \"x4\""), "{shown}");
+        assert!(shown.contains("5 │ x4"), "{shown}");
+        assert!(shown.contains("invalid digit"), "{shown}");
+
+        // Only spans into the given input are shown
+        assert!(report(&err, "x4\n").is_none());
+        assert!(report(&anyhow!("no span"), input).is_none());
+
+        assert_eq!(line(&err, input), Some(5));
+
+        let err = None::<u8>.at(&input[2..3], || "missing").unwrap_err();
+        assert!(report(&err, input).unwrap().contains("2 │ 2"));
+        assert_eq!(line(&err, input), Some(2));
+    }
+
+    #[test]
+    fn finds_numbers() {
+        assert_eq!(
+            numbers::<i32>("move 12 from -3 to x=-4,y=5").unwrap(),
+            [12, -3, -4, 5]
+        );
+        assert_eq!(numbers::<u8>("2-4,6-8").unwrap(), [2, 4, 6, 8]);
+        assert!(numbers::<u8>("no numbers").unwrap().is_empty());
+
+        numbers::<u8>("256").unwrap_err();
+        numbers::<u8>("-1").unwrap_err();
+    }
+}
@@ -0,0 +1,222 @@
+use crate::answer::Answer;
+use crate::profile::profiled;
+use crate::screen;
+use anyhow::{anyhow, Result};
+use std::fmt::Write as _;
+use std::time::{Duration, Instant};
+use tracing::debug_span;
+
+pub type Part<I> = Box<dyn Fn(&I) -> Result<Answer>>;
+
+/// The named parts of a puzzle, usually `Part 1` and `Part 2`, but e.g. Everybody Codes quests
+/// have three.
+pub struct Parts<I: ?Sized> {
+    parts: Vec<(String, Part<I>)>,
+    /// Which day they belong to, for the flamegraphs of [`profiled`]
+    label: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct PartResult {
+    pub name: String,
+    pub answer: Result<Answer>,
+    pub time: Duration,
+}
+
+impl<I: ?Sized> Default for Parts<I> {
+    fn default() -> Self {
+        Self {
+            parts: vec![],
+            label: None,
+        }
+    }
+}
+
+impl<I: ?Sized> Parts<I> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The usual shape, named `Part 1` and `Part 2`.
+    #[must_use]
+    pub fn two<A: Into<Answer>, B: Into<Answer>>(
+        part1: impl Fn(&I) -> Result<A> + 'static,
+        part2: impl Fn(&I) -> Result<B> + 'static,
+    ) -> Self {
+        Self::new().part("Part 1", part1).part("Part 2", part2)
+    }
+
+    #[must_use]
+    pub fn part<A: Into<Answer>>(
+        mut self,
+        name: impl Into<String>,
+        solve: impl Fn(&I) -> Result<A> + 'static,
+    ) -> Self {
+        self.parts.push((
+            name.into(),
+            Box::new(move |input| solve(input).map(Into::into)),
+        ));
+        self
+    }
+
+    /// Names the flamegraphs of the `profile` feature after `label` and the part.
+    #[must_use]
+    pub fn labeled(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.parts.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Picks parts by name, or by their number (`2` for the second part). All parts if `selection`
+    /// is empty.
+    ///
+    /// # Errors
+    /// If a selected part does not exist.
+    pub fn select(&self, selection: &[String]) -> Result<Vec<&(String, Part<I>)>> {
+        if selection.is_empty() {
+            return Ok(self.parts.iter().collect());
+        }
+
+        selection
+            .iter()
+            .map(|wanted| {
+                let by_number = wanted
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|n| self.parts.get(n.checked_sub(1)?));
+                by_number
+                    .or_else(|| self.parts.iter().find(|(name, _)| name == wanted))
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "No part {wanted:?}, available are: {}",
+                            self.names().collect::<Vec<_>>().join(", ")
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    /// Runs the selected parts in order, timing each.
+    ///
+    /// # Errors
+    /// If a selected part does not exist, errors of the parts themselves are in the results.
+    pub fn run(&self, input: &I, selection: &[String]) -> Result<Vec<PartResult>> {
+        Ok(self
+            .select(selection)?
+            .into_iter()
+            .map(|(name, solve)| {
+                let _part = debug_span!("part", name = %name).entered();
+                let label = match &self.label {
+                    Some(label) => format!("{label} {name}"),
+                    None => name.clone(),
+                };
+                let start = Instant::now();
+                let answer = profiled(&label, || solve(input));
+                PartResult {
+                    name: name.clone(),
+                    answer,
+                    time: start.elapsed(),
+                }
+            })
+            .collect())
+    }
+}
+
+/// Reads every `--part <name>`, other arguments are ignored.
+pub fn parts_from_args(args: impl Iterator<Item = String>) -> Vec<String> {
+    let mut parts = vec![];
+    let mut args = args.peekable();
+    while let Some(arg) = args.next() {
+        if arg == "--part" {
+            parts.extend(args.next());
+        }
+    }
+    parts
+}
+
+/// One row per part with its time and answer, multi-line answers are printed below the table.
+///
+/// # Panics
+/// Never, writing to a `String` does not fail.
+#[must_use]
+pub fn table(results: &[PartResult]) -> String {
+    let width = results.iter().map(|r| r.name.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    let mut below = vec![];
+
+    for r in results {
+        let answer = match &r.answer {
+            Ok(answer) => answer.draw(screen::density()),
+            Err(e) => format!("error: {e:#}"),
+        };
+        let shown = if answer.trim_end().contains('\n') {
+            below.push((&r.name, answer));
+            "(see below)".to_owned()
+        } else {
+            answer
+        };
+        writeln!(out, "{:<width$}  {:>10.2?}  {shown}", r.name, r.time).unwrap();
+    }
+
+    for (name, answer) in below {
+        write!(out, "\n{name}:\n{answer}").unwrap();
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quest() -> Parts<str> {
+        Parts::new()
+            .part("Part 1", |i: &str| Ok(i.len()))
+            .part("Part 2", |i: &str| Ok(i.to_uppercase()))
+            .part("Part 3", |_: &str| -> Result<u8> {
+                Err(anyhow!("not yet"))
+            })
+    }
+
+    #[test]
+    fn selects() {
+        let names = |sel: &[&str]| {
+            let sel: Vec<String> = sel.iter().map(|s| (*s).to_owned()).collect();
+            quest()
+                .run("abc", &sel)
+                .map(|rs| rs.into_iter().map(|r| r.name).collect::<Vec<_>>())
+        };
+
+        assert_eq!(names(&[]).unwrap(), ["Part 1", "Part 2", "Part 3"]);
+        assert_eq!(names(&["3", "Part 1"]).unwrap(), ["Part 3", "Part 1"]);
+        names(&["4"]).unwrap_err();
+
+        let args = ["--part", "2", "--other", "--part", "3"].map(String::from);
+        assert_eq!(parts_from_args(args.into_iter()), ["2", "3"]);
+    }
+
+    #[test]
+    fn tables() {
+        let mut results = quest().run("abc", &[]).unwrap();
+        for r in &mut results {
+            r.time = Duration::from_micros(5);
+        }
+        results[1].answer = Ok(Answer::from("#.\n.#\n"));
+
+        assert_eq!(
+            table(&results),
+            "Part 1      5.00µs  3
+Part 2      5.00µs  (see below)
+Part 3      5.00µs  error: not yet
+
+Part 2:
+#.
+.#
+"
+        );
+    }
+}
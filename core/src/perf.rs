@@ -0,0 +1,321 @@
+//! Hardware performance counters (cycles, instructions, cache and branch misses) of a section of
+//! code, for `aoc run --perf`, the day 8 binary and the bench report.
+
+use crate::registry::Solution;
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::hint::black_box;
+use sys::Counter;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Event {
+    Cycles = 0,
+    Instructions = 1,
+    CacheMisses = 3,
+    BranchMisses = 5,
+}
+
+const EVENTS: [Event; 4] = [
+    Event::Cycles,
+    Event::Instructions,
+    Event::CacheMisses,
+    Event::BranchMisses,
+];
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use super::Event;
+    use std::fs::File;
+    use std::io::{self, Read};
+    use std::os::fd::{AsRawFd, FromRawFd};
+
+    /// The first version of `struct perf_event_attr`, the kernel fills in the rest with zeroes.
+    #[repr(C)]
+    #[derive(Default)]
+    pub(super) struct PerfEventAttr {
+        kind: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+    }
+
+    const PERF_TYPE_HARDWARE: u32 = 0;
+    const FLAG_DISABLED: u64 = 1 << 0;
+    const FLAG_EXCLUDE_KERNEL: u64 = 1 << 5;
+    const FLAG_EXCLUDE_HV: u64 = 1 << 6;
+
+    const IOC_ENABLE: libc::c_ulong = 0x2400;
+    const IOC_DISABLE: libc::c_ulong = 0x2401;
+    const IOC_RESET: libc::c_ulong = 0x2403;
+
+    /// One open counter, disabled until [`Counter::enable`].
+    #[derive(Debug)]
+    pub(super) struct Counter(File);
+
+    impl Counter {
+        pub(super) fn open(event: Event) -> io::Result<Self> {
+            let attr = PerfEventAttr {
+                kind: PERF_TYPE_HARDWARE,
+                size: u32::try_from(std::mem::size_of::<PerfEventAttr>()).unwrap(),
+                config: event as u64,
+                flags: FLAG_DISABLED | FLAG_EXCLUDE_KERNEL | FLAG_EXCLUDE_HV,
+                ..PerfEventAttr::default()
+            };
+
+            // SAFETY: `attr` is a valid perf_event_attr, this process on any CPU, no group, no
+            // flags
+            let fd = unsafe {
+                libc::syscall(
+                    libc::SYS_perf_event_open,
+                    std::ptr::from_ref(&attr),
+                    0,
+                    -1,
+                    -1,
+                    0,
+                )
+            };
+
+            let fd = i32::try_from(fd)
+                .ok()
+                .filter(|&fd| fd >= 0)
+                .ok_or_else(io::Error::last_os_error)?;
+            // SAFETY: A non-negative return value is a new file descriptor that nobody else owns
+            Ok(Self(unsafe { File::from_raw_fd(fd) }))
+        }
+
+        fn ioctl(&self, request: libc::c_ulong) {
+            // SAFETY: The fd is an open perf event, these requests take no argument
+            unsafe { libc::ioctl(self.0.as_raw_fd(), request, 0) };
+        }
+
+        /// Starts counting again from zero.
+        pub(super) fn enable(&self) {
+            self.ioctl(IOC_RESET);
+            self.ioctl(IOC_ENABLE);
+        }
+
+        pub(super) fn disable(&self) {
+            self.ioctl(IOC_DISABLE);
+        }
+
+        pub(super) fn read(&mut self) -> io::Result<u64> {
+            let mut buf = [0; 8];
+            self.0.read_exact(&mut buf)?;
+            Ok(u64::from_ne_bytes(buf))
+        }
+    }
+}
+
+/// `perf_event_open` only exists on Linux, elsewhere no counter opens.
+#[cfg(not(target_os = "linux"))]
+mod sys {
+    use super::Event;
+    use std::io;
+
+    #[derive(Debug)]
+    pub(super) enum Counter {}
+
+    impl Counter {
+        pub(super) fn open(_: Event) -> io::Result<Self> {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "performance counters are only read on Linux",
+            ))
+        }
+
+        pub(super) fn enable(&self) {
+            match *self {}
+        }
+
+        pub(super) fn disable(&self) {
+            match *self {}
+        }
+
+        pub(super) fn read(&mut self) -> io::Result<u64> {
+            match *self {}
+        }
+    }
+}
+
+/// Hardware performance counters of this process, via `perf_event_open`.
+///
+/// Only counts the calling thread, not rayon's workers. Counters the CPU (or VM) does not have are
+/// left out, needs `perf_event_paranoid` <= 2. Only Linux has them, elsewhere they do not open.
+#[derive(Debug)]
+pub struct PerfCounters {
+    counters: Vec<(Event, Counter)>,
+}
+
+/// Counts of one measured section, `None` if the counter is not available.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize)]
+pub struct Counts {
+    pub cycles: Option<u64>,
+    pub instructions: Option<u64>,
+    pub cache_misses: Option<u64>,
+    pub branch_misses: Option<u64>,
+}
+
+/// Counts of parsing and both parts of a day.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize)]
+pub struct PhaseCounts {
+    pub parse: Counts,
+    pub part1: Counts,
+    pub part2: Counts,
+}
+
+impl PerfCounters {
+    /// # Errors
+    /// If not a single counter can be opened.
+    pub fn open() -> Result<Self> {
+        let mut counters = vec![];
+        let mut error = None;
+        for event in EVENTS {
+            match Counter::open(event) {
+                Ok(counter) => counters.push((event, counter)),
+                Err(e) => error = Some(e),
+            }
+        }
+
+        if counters.is_empty() {
+            Err(anyhow!(
+                "No performance counters available: {}",
+                error.map_or_else(String::new, |e| e.to_string())
+            ))
+        } else {
+            Ok(Self { counters })
+        }
+    }
+
+    /// Opens the counters with `--perf`, other arguments are ignored.
+    ///
+    /// # Errors
+    /// If the counters are requested but not available.
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Result<Option<Self>> {
+        if args.any(|a| a == "--perf") {
+            Self::open().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Counts while running `f`.
+    ///
+    /// # Errors
+    /// If the counters can not be read.
+    pub fn measure<T>(&mut self, f: impl FnOnce() -> T) -> Result<(T, Counts)> {
+        for (_, counter) in &self.counters {
+            counter.enable();
+        }
+        let result = f();
+        for (_, counter) in &self.counters {
+            counter.disable();
+        }
+
+        let mut counts = Counts::default();
+        for (event, counter) in &mut self.counters {
+            let value = Some(counter.read()?);
+
+            match event {
+                Event::Cycles => counts.cycles = value,
+                Event::Instructions => counts.instructions = value,
+                Event::CacheMisses => counts.cache_misses = value,
+                Event::BranchMisses => counts.branch_misses = value,
+            }
+        }
+
+        Ok((result, counts))
+    }
+
+    /// Counts parsing and both parts of a registered day once, parts without an answer are
+    /// counted all the same.
+    ///
+    /// # Errors
+    /// If the input does not parse or the counters can not be read.
+    pub fn phases(&mut self, solution: &Solution, input: &str) -> Result<PhaseCounts> {
+        let (parsed, parse) = self.measure(|| solution.parse(input))?;
+        let parsed = parsed.with_context(|| format!("{} day {}", solution.year, solution.day))?;
+        let (_, part1) = self.measure(|| black_box(solution.part1(parsed.as_ref())))?;
+        let (_, part2) = self.measure(|| black_box(solution.part2(parsed.as_ref())))?;
+        Ok(PhaseCounts {
+            parse,
+            part1,
+            part2,
+        })
+    }
+}
+
+/// Runs `f`, counting if `perf` is given.
+///
+/// # Errors
+/// If the counters can not be read.
+pub fn maybe_measure<T>(
+    perf: Option<&mut PerfCounters>,
+    f: impl FnOnce() -> T,
+) -> Result<(T, Option<Counts>)> {
+    match perf {
+        Some(perf) => perf.measure(f).map(|(r, c)| (r, Some(c))),
+        None => Ok((f(), None)),
+    }
+}
+
+impl Display for Counts {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let show = |v: Option<u64>| v.map_or_else(|| "-".to_owned(), |v| v.to_string());
+
+        write!(
+            f,
+            "{:>14} cycles {:>14} instructions {:>10} cache misses {:>10} branch misses",
+            show(self.cycles),
+            show(self.instructions),
+            show(self.cache_misses),
+            show(self.branch_misses)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn attr_layout() {
+        // PERF_ATTR_SIZE_VER0
+        assert_eq!(std::mem::size_of::<sys::PerfEventAttr>(), 64);
+    }
+
+    #[test]
+    fn counts_if_available() {
+        // Containers and VMs often have no counters at all
+        let Ok(mut perf) = PerfCounters::open() else {
+            return;
+        };
+
+        let (sum, counts) = perf
+            .measure(|| (0..10_000u64).map(std::hint::black_box).sum::<u64>())
+            .unwrap();
+        assert_eq!(sum, 49_995_000);
+        if let Some(instructions) = counts.instructions {
+            assert!(instructions > 10_000, "{counts}");
+        }
+    }
+
+    #[test]
+    fn display() {
+        let counts = Counts {
+            cycles: Some(10),
+            ..Counts::default()
+        };
+        assert_eq!(
+            counts.to_string(),
+            "            10 cycles              - instructions          - cache misses          - branch misses"
+        );
+    }
+}
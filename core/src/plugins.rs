@@ -0,0 +1,290 @@
+//! Solvers compiled as separate shared libraries and loaded at startup.
+//!
+//! A plugin is a `cdylib` that exports
+//!
+//! ```ignore
+//! #[no_mangle]
+//! pub unsafe extern "C" fn aoc_register(register: RegisterFn, ctx: *mut c_void) {
+//!     register(ctx, 2022, 3, 1, c"bits".as_ptr(), solve_part1);
+//! }
+//! ```
+//!
+//! where every solver gets the input and writes its answer as UTF-8 into the buffer, returning its
+//! length or a negative number on failure. Answers longer than the buffer return their length
+//! without writing, the solver is then called again with a buffer that fits.
+//!
+//! The runner loads every plugin in [`PLUGIN_DIR`] at startup, and [`register`]s them as the
+//! implementations `plugin:<name>` of their day, for any year.
+
+use crate::registry::{self, RawPart, Solution};
+use anyhow::{anyhow, ensure, Context, Result};
+use libloading::Library;
+use std::collections::BTreeMap;
+use std::ffi::{c_char, c_void, CStr};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+pub type SolveFn = unsafe extern "C" fn(
+    input: *const u8,
+    input_len: usize,
+    answer: *mut u8,
+    answer_cap: usize,
+) -> isize;
+
+pub type RegisterFn = unsafe extern "C" fn(
+    ctx: *mut c_void,
+    year: u32,
+    day: u32,
+    part: u32,
+    name: *const c_char,
+    solve: SolveFn,
+);
+
+type EntryFn = unsafe extern "C" fn(register: RegisterFn, ctx: *mut c_void);
+
+const ANSWER_CAP: usize = 4096;
+
+/// Where the runner looks for plugins, in the workspace next to the crates of the years.
+pub const PLUGIN_DIR: &str = "plugins";
+
+#[derive(Clone)]
+pub struct PluginSolver {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub name: String,
+    solve: SolveFn,
+    /// Keeps the library loaded as long as its functions can be called
+    #[allow(dead_code)]
+    library: Option<Arc<Library>>,
+}
+
+impl std::fmt::Debug for PluginSolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} day{:02} part {} {}",
+            self.year, self.day, self.part, self.name
+        )
+    }
+}
+
+impl PluginSolver {
+    /// # Errors
+    /// If the plugin reports a failure, an answer that does not fit even the buffer of the size it
+    /// asked for, or something that is not UTF-8.
+    pub fn solve(&self, input: &str) -> Result<String> {
+        let mut answer = self.call(input, ANSWER_CAP)?;
+        if answer.len() > ANSWER_CAP {
+            let len = answer.len();
+            answer = self.call(input, len)?;
+            ensure!(
+                answer.len() <= len,
+                "Plugin {self:?} asked for {len} bytes, then for {}",
+                answer.len()
+            );
+        }
+        String::from_utf8(answer).with_context(|| format!("Plugin {self:?} returned no UTF-8"))
+    }
+
+    /// The answer written into a buffer of `cap` bytes, or zeros of the length the solver reports
+    /// if it does not fit.
+    fn call(&self, input: &str, cap: usize) -> Result<Vec<u8>> {
+        let mut answer = vec![0u8; cap];
+        // SAFETY: The plugin promised this signature when registering, and the buffers are valid
+        let len = unsafe { (self.solve)(input.as_ptr(), input.len(), answer.as_mut_ptr(), cap) };
+
+        let len = usize::try_from(len).map_err(|_| anyhow!("Plugin {self:?} failed ({len})"))?;
+        answer.resize(len, 0);
+        Ok(answer)
+    }
+}
+
+unsafe extern "C" fn collect(
+    ctx: *mut c_void,
+    year: u32,
+    day: u32,
+    part: u32,
+    name: *const c_char,
+    solve: SolveFn,
+) {
+    // SAFETY: `ctx` is the vector passed to the plugin in `register_all`
+    let solvers = unsafe { &mut *ctx.cast::<Vec<PluginSolver>>() };
+    // SAFETY: The plugin has to pass a valid C string
+    let name = unsafe { CStr::from_ptr(name) }
+        .to_string_lossy()
+        .into_owned();
+
+    solvers.push(PluginSolver {
+        year,
+        day,
+        part,
+        name,
+        solve,
+        library: None,
+    });
+}
+
+fn register_all(entry: EntryFn, library: Option<&Arc<Library>>) -> Vec<PluginSolver> {
+    let mut solvers: Vec<PluginSolver> = vec![];
+    // SAFETY: `collect` only lives as long as this call and gets our vector as context
+    unsafe { entry(collect, std::ptr::from_mut(&mut solvers).cast()) };
+
+    for solver in &mut solvers {
+        solver.library = library.cloned();
+    }
+    solvers
+}
+
+/// Loads one plugin and returns the solvers it registers.
+///
+/// # Errors
+/// If the library can not be loaded or does not export `aoc_register`.
+pub fn load(path: impl AsRef<Path>) -> Result<Vec<PluginSolver>> {
+    let path = path.as_ref();
+    // SAFETY: Loading a library runs its initializers, plugins are trusted code
+    let library = unsafe { Library::new(path) }
+        .with_context(|| format!("Could not load plugin {}", path.display()))?;
+    let library = Arc::new(library);
+
+    // SAFETY: The signature is what plugins have to export
+    let entry: EntryFn = *unsafe { library.get::<EntryFn>(b"aoc_register\0") }
+        .with_context(|| format!("{} has no aoc_register", path.display()))?;
+
+    Ok(register_all(entry, Some(&library)))
+}
+
+/// Loads all shared libraries in `dir`, a missing directory just has no plugins.
+///
+/// # Errors
+/// If any plugin fails to load.
+pub fn load_dir(dir: impl AsRef<Path>) -> Result<Vec<PluginSolver>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(vec![]);
+    };
+
+    let mut solvers = vec![];
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) == Some(std::env::consts::DLL_EXTENSION) {
+            solvers.extend(load(&path)?);
+        }
+    }
+
+    Ok(solvers)
+}
+
+/// Adds the solvers to the [`registry::implementations`] of their day, one implementation named
+/// `plugin:<name>` per day and name, with the parts registered under that name.
+pub fn register(solvers: &[PluginSolver]) {
+    let mut implementations: BTreeMap<(u32, u32, &str), Vec<&PluginSolver>> = BTreeMap::new();
+    for solver in solvers {
+        implementations
+            .entry((solver.year, solver.day, &solver.name))
+            .or_default()
+            .push(solver);
+    }
+
+    for ((year, day, name), solvers) in implementations {
+        let (Ok(year), Ok(day)) = (u16::try_from(year), u8::try_from(day)) else {
+            eprintln!("Ignoring plugin {:?}, there is no such day", solvers[0]);
+            continue;
+        };
+        let parts = solvers
+            .into_iter()
+            .filter_map(|solver| {
+                let part = u8::try_from(solver.part).ok()?;
+                let solver = solver.clone();
+                let solve: RawPart = Arc::new(move |input| {
+                    let Ok(answer) = solver.solve(input)?.trim().parse();
+                    Ok(answer)
+                });
+                Some((part, solve))
+            })
+            .collect();
+        let name = format!("plugin:{name}").leak();
+        registry::register(Solution::raw(year, day, name, parts));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answer::Answer;
+
+    unsafe extern "C" fn count_lines(
+        input: *const u8,
+        input_len: usize,
+        answer: *mut u8,
+        answer_cap: usize,
+    ) -> isize {
+        let input = unsafe { std::slice::from_raw_parts(input, input_len) };
+        let lines = input.split(|&c| c == b'\n').count().to_string();
+        if lines.len() > answer_cap {
+            return -1;
+        }
+
+        unsafe { std::ptr::copy_nonoverlapping(lines.as_ptr(), answer, lines.len()) };
+        isize::try_from(lines.len()).unwrap()
+    }
+
+    /// One `x` per byte of input, more than fits the first buffer for long inputs.
+    unsafe extern "C" fn repeat(
+        _: *const u8,
+        input_len: usize,
+        answer: *mut u8,
+        answer_cap: usize,
+    ) -> isize {
+        if input_len <= answer_cap {
+            unsafe { std::ptr::write_bytes(answer, b'x', input_len) };
+        }
+        isize::try_from(input_len).unwrap()
+    }
+
+    unsafe extern "C" fn fail(_: *const u8, _: usize, _: *mut u8, _: usize) -> isize {
+        -1
+    }
+
+    unsafe extern "C" fn aoc_register(register: RegisterFn, ctx: *mut c_void) {
+        unsafe {
+            register(ctx, 2, 1, 1, c"lines".as_ptr(), count_lines);
+            register(ctx, 2, 1, 2, c"fail".as_ptr(), fail);
+            register(ctx, 2, 2, 1, c"repeat".as_ptr(), repeat);
+        }
+    }
+
+    #[test]
+    fn registration() {
+        let solvers = register_all(aoc_register, None);
+
+        assert_eq!(
+            format!("{solvers:?}"),
+            "[2 day01 part 1 lines, 2 day01 part 2 fail, 2 day02 part 1 repeat]"
+        );
+        assert_eq!(solvers[0].solve("a\nb\nc").unwrap(), "3");
+        solvers[1].solve("").unwrap_err();
+        assert_eq!(solvers[2].solve("abc").unwrap(), "xxx");
+        let long = "a".repeat(ANSWER_CAP * 2 + 1);
+        assert_eq!(solvers[2].solve(&long).unwrap(), "x".repeat(long.len()));
+    }
+
+    #[test]
+    fn registers_implementations() {
+        register(&register_all(aoc_register, None));
+
+        let lines = registry::implementation(2, 1, "plugin:lines").unwrap();
+        assert_eq!(
+            lines.solve("a\nb", &[]).unwrap(),
+            [("Part 1".to_owned(), Answer::Int(2))]
+        );
+        let fail = registry::implementation(2, 1, "plugin:fail").unwrap();
+        fail.solve("", &[]).unwrap_err();
+        assert!(registry::implementation(2, 2, "plugin:repeat").is_some());
+    }
+
+    #[test]
+    fn missing_dir() {
+        assert!(load_dir("does/not/exist").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,205 @@
+use std::ops::{Add, Index, IndexMut, Mul, Neg, Sub};
+
+/// Integer point in `D` dimensions, for the puzzles that go beyond a flat grid.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Point<const D: usize>(pub [i64; D]);
+
+pub type Point2 = Point<2>;
+pub type Point3 = Point<3>;
+pub type Point4 = Point<4>;
+
+impl<const D: usize> Default for Point<D> {
+    fn default() -> Self {
+        Self([0; D])
+    }
+}
+
+impl<const D: usize> Point<D> {
+    pub const ORIGIN: Self = Self([0; D]);
+
+    /// Unit vector along `axis`.
+    ///
+    /// # Panics
+    /// If `axis >= D`.
+    #[must_use]
+    pub fn unit(axis: usize) -> Self {
+        let mut p = Self::ORIGIN;
+        p[axis] = 1;
+        p
+    }
+
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> u64 {
+        (0..D).map(|i| self[i].abs_diff(other[i])).sum()
+    }
+
+    #[must_use]
+    pub fn chebyshev(self, other: Self) -> u64 {
+        (0..D)
+            .map(|i| self[i].abs_diff(other[i]))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The `2 * D` points that share a face with this one.
+    pub fn neighbors(self) -> impl Iterator<Item = Self> {
+        (0..D).flat_map(move |axis| [self - Self::unit(axis), self + Self::unit(axis)])
+    }
+
+    /// The `3^D - 1` points that touch this one, including diagonally.
+    pub fn neighbors_diagonal(self) -> impl Iterator<Item = Self> {
+        let count: i64 = (0..D).fold(1, |count, _| count * 3);
+
+        (0..count).filter_map(move |mut idx| {
+            let mut offset = Self::ORIGIN;
+            for axis in 0..D {
+                offset[axis] = idx % 3 - 1;
+                idx /= 3;
+            }
+
+            (offset != Self::ORIGIN).then(|| self + offset)
+        })
+    }
+}
+
+impl<const D: usize> Index<usize> for Point<D> {
+    type Output = i64;
+
+    fn index(&self, axis: usize) -> &i64 {
+        &self.0[axis]
+    }
+}
+
+impl<const D: usize> IndexMut<usize> for Point<D> {
+    fn index_mut(&mut self, axis: usize) -> &mut i64 {
+        &mut self.0[axis]
+    }
+}
+
+impl<const D: usize> Add for Point<D> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(std::array::from_fn(|i| self[i] + rhs[i]))
+    }
+}
+
+impl<const D: usize> Sub for Point<D> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(std::array::from_fn(|i| self[i] - rhs[i]))
+    }
+}
+
+impl<const D: usize> Neg for Point<D> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(self.0.map(|c| -c))
+    }
+}
+
+impl<const D: usize> Mul<i64> for Point<D> {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self {
+        Self(self.0.map(|c| c * rhs))
+    }
+}
+
+impl<const D: usize> From<[i64; D]> for Point<D> {
+    fn from(coords: [i64; D]) -> Self {
+        Self(coords)
+    }
+}
+
+/// Smallest axis aligned box containing some points, both corners are inclusive.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct BoundingBox<const D: usize> {
+    pub min: Point<D>,
+    pub max: Point<D>,
+}
+
+impl<const D: usize> BoundingBox<D> {
+    /// `None` if there are no points.
+    pub fn from_points(points: impl IntoIterator<Item = Point<D>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        Some(points.fold(Self::new(first, first), |mut bb, p| {
+            bb.extend(p);
+            bb
+        }))
+    }
+
+    #[must_use]
+    pub fn new(min: Point<D>, max: Point<D>) -> Self {
+        Self { min, max }
+    }
+
+    pub fn extend(&mut self, p: Point<D>) {
+        for axis in 0..D {
+            self.min[axis] = self.min[axis].min(p[axis]);
+            self.max[axis] = self.max[axis].max(p[axis]);
+        }
+    }
+
+    /// Grows the box by `by` in every direction, e.g. to flood fill around an object.
+    #[must_use]
+    pub fn grow(self, by: i64) -> Self {
+        Self::new(self.min - Point([by; D]), self.max + Point([by; D]))
+    }
+
+    #[must_use]
+    pub fn contains(&self, p: Point<D>) -> bool {
+        (0..D).all(|axis| (self.min[axis]..=self.max[axis]).contains(&p[axis]))
+    }
+
+    /// Number of integer points inside the box.
+    #[must_use]
+    pub fn volume(&self) -> u64 {
+        (0..D)
+            .map(|axis| self.max[axis].abs_diff(self.min[axis]) + 1)
+            .product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbors() {
+        let p = Point([1, 2, 3]);
+        assert_eq!(p.neighbors().count(), 6);
+        assert!(p.neighbors().all(|n| n.manhattan(p) == 1));
+
+        assert_eq!(Point2::ORIGIN.neighbors_diagonal().count(), 8);
+        assert_eq!(p.neighbors_diagonal().count(), 26);
+        assert_eq!(Point4::ORIGIN.neighbors_diagonal().count(), 80);
+        assert!(p.neighbors_diagonal().all(|n| n.chebyshev(p) == 1));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Point([1, -2, 3, 0]);
+        assert_eq!(a + a, a * 2);
+        assert_eq!(a - a, Point4::ORIGIN);
+        assert_eq!(-a, Point([-1, 2, -3, 0]));
+        assert_eq!(a.manhattan(Point4::default()), 6);
+    }
+
+    #[test]
+    fn bounding_box() {
+        let bb = BoundingBox::from_points([Point([1, 5, 2]), Point([3, 1, 2]), Point([2, 2, 4])])
+            .unwrap();
+
+        assert_eq!(bb, BoundingBox::new(Point([1, 1, 2]), Point([3, 5, 4])));
+        assert_eq!(bb.volume(), 3 * 5 * 3);
+        assert!(bb.contains(Point([2, 3, 3])));
+        assert!(!bb.contains(Point([0, 3, 3])));
+        assert!(bb.grow(1).contains(Point([0, 3, 3])));
+        assert_eq!(BoundingBox::<3>::from_points([]), None);
+    }
+}
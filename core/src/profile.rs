@@ -0,0 +1,73 @@
+//! With the `profile` feature every part is sampled while it runs and written as a flamegraph to
+//! `profiles/<name>.svg`, without setting up `perf` for each binary. Without it this costs nothing.
+
+use std::path::PathBuf;
+
+/// Where the flamegraphs go, in the directory the day runs in.
+pub const PROFILE_DIR: &str = "profiles";
+
+/// Samples per second.
+#[cfg(all(feature = "profile", not(target_family = "wasm")))]
+const FREQUENCY: i32 = 1000;
+
+/// `profiles/2022-day12-part-1.svg` for `2022-day12 Part 1`.
+#[must_use]
+pub fn flamegraph_path(name: &str) -> PathBuf {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    PathBuf::from(PROFILE_DIR).join(format!("{name}.svg"))
+}
+
+/// Runs `f` and writes a flamegraph of it to [`flamegraph_path`], if the `profile` feature is on.
+/// Profiling errors are only reported, they should not fail the part.
+#[cfg(all(feature = "profile", not(target_family = "wasm")))]
+pub fn profiled<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build();
+    let result = f();
+
+    let path = flamegraph_path(name);
+    let written = guard
+        .map_err(anyhow::Error::from)
+        .and_then(|guard| Ok(guard.report().build()?))
+        .and_then(|report| {
+            std::fs::create_dir_all(PROFILE_DIR)?;
+            report.flamegraph(std::fs::File::create(&path)?)?;
+            Ok(())
+        });
+    match written {
+        Ok(()) => eprintln!("Wrote {}", path.display()),
+        Err(e) => eprintln!("Could not profile {name}: {e:#}"),
+    }
+
+    result
+}
+
+/// Runs `f`, the `profile` feature is off.
+#[cfg(not(all(feature = "profile", not(target_family = "wasm"))))]
+pub fn profiled<T>(_name: &str, f: impl FnOnce() -> T) -> T {
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths() {
+        assert_eq!(
+            flamegraph_path("2022-day12 Part 1"),
+            PathBuf::from("profiles/2022-day12-part-1.svg")
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! `aoc fetch-puzzle`: the puzzle texts as markdown, to read them without a connection.
+
+use anyhow::{anyhow, Result};
+use std::path::{Path, PathBuf};
+
+/// Where the puzzle texts are kept, in the root of the workspace. Like the inputs they are not
+/// to be shared, so the directory is git ignored.
+pub const PUZZLE_DIR: &str = "puzzles";
+
+const SITE: &str = "https://adventofcode.com";
+
+/// The page of the puzzle on the site.
+#[must_use]
+pub fn puzzle_url(year: u16, day: u8) -> String {
+    format!("{SITE}/{year}/day/{day}")
+}
+
+#[must_use]
+pub fn puzzle_path(root: &Path, year: u16, day: u8) -> PathBuf {
+    root.join(PUZZLE_DIR)
+        .join(year.to_string())
+        .join(format!("day{day:02}.md"))
+}
+
+/// The descriptions of the parts in the page of a day as markdown, part 2 only once part 1 is
+/// solved.
+///
+/// # Errors
+/// If the page contains no description, e.g. because the day is not unlocked yet.
+pub fn to_markdown(html: &str) -> Result<String> {
+    let mut parts = vec![];
+    let mut rest = html;
+    while let Some(start) = rest.find("<article class=\"day-desc\">") {
+        let article = &rest[start..];
+        let end = article.find("</article>").unwrap_or(article.len());
+        parts.push(convert(&article[..end]));
+        rest = &article[end..];
+    }
+
+    if parts.is_empty() {
+        Err(anyhow!("The page contains no puzzle description"))
+    } else {
+        Ok(parts.join("\n"))
+    }
+}
+
+/// Markdown for the few tags the puzzle texts use, everything else is dropped.
+fn convert(html: &str) -> String {
+    let mut out = String::new();
+    let mut links = vec![];
+    let mut pre = false;
+    let mut code = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            text(&mut out, rest, pre);
+            break;
+        };
+        text(&mut out, &rest[..tag_start], pre);
+        let Some(tag_len) = rest[tag_start..].find('>') else {
+            break;
+        };
+        let tag = &rest[tag_start + 1..tag_start + tag_len];
+        rest = &rest[tag_start + tag_len + 1..];
+
+        let (closing, tag) = tag.strip_prefix('/').map_or((false, tag), |t| (true, t));
+        let name = tag.split_whitespace().next().unwrap_or_default();
+        match (name, closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p" | "ul", true) => blank_line(&mut out),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("pre", false) => {
+                out.push_str("```\n");
+                pre = true;
+            }
+            ("pre", true) => {
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```");
+                blank_line(&mut out);
+                pre = false;
+            }
+            ("code", _) if !pre => {
+                out.push('`');
+                code = !closing;
+            }
+            ("em", _) if !pre && !code => out.push('*'),
+            ("a", false) => {
+                out.push('[');
+                links.push(attribute(tag, "href").unwrap_or_default());
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str("](");
+                if href.starts_with('/') {
+                    out.push_str(SITE);
+                }
+                out.push_str(&href);
+                out.push(')');
+            }
+            _ => {}
+        }
+    }
+
+    out.trim_end().to_owned() + "\n"
+}
+
+fn blank_line(out: &mut String) {
+    out.truncate(out.trim_end_matches('\n').len());
+    out.push_str("\n\n");
+}
+
+fn text(out: &mut String, text: &str, pre: bool) {
+    let text = unescape(text);
+    if pre {
+        out.push_str(&text);
+    } else if out.is_empty() || out.ends_with('\n') {
+        out.push_str(&text.trim_start().replace('\n', " "));
+    } else {
+        // Line breaks in the HTML are not ones in the text
+        out.push_str(&text.replace('\n', " "));
+    }
+}
+
+/// `<a href="/2022/day/1/input">` and the like.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let (_, value) = tag.split_once(&format!("{name}=\""))?;
+    Some(unescape(value.split('"').next()?))
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let entity = rest
+            .find(';')
+            .and_then(|end| Some((end, entity(&rest[1..end])?)));
+        if let Some((end, c)) = entity {
+            out.push(c);
+            rest = &rest[end + 1..];
+        } else {
+            out.push('&');
+            rest = &rest[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn entity(name: &str) -> Option<char> {
+    match name {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => {
+            let number = name.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<script>window.ads = 1;</script>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2><p>The jungle must be too overgrown and difficult to navigate in vehicles or access from the air; the Elves' expedition traditionally goes on foot. As your boats approach land, the Elves begin taking <em>inventory</em> of their supplies.</p>
+<pre><code>1000
+2000
+
+<em>3000</em>
+</code></pre>
+<ul>
+<li>The first Elf is carrying food with <code>1000</code> and <code><em>2000</em></code> Calories.</li>
+</ul>
+<p>Find the Elf carrying the most Calories, <a href="/2022/day/1/input" target="_blank">get your puzzle input</a> &amp; check <span title="Or &quot;5 &lt; 6&quot;">it</span>&#x21;</p>
+</article>
+<p>Your puzzle answer was <code>24000</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Now the top three.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn converts_descriptions() {
+        assert_eq!(
+            to_markdown(PAGE).unwrap(),
+            "## --- Day 1: Calorie Counting ---
+
+The jungle must be too overgrown and difficult to navigate in vehicles or access from the air; \
+the Elves' expedition traditionally goes on foot. As your boats approach land, the Elves begin \
+taking *inventory* of their supplies.
+
+```
+1000
+2000
+
+3000
+```
+
+- The first Elf is carrying food with `1000` and `2000` Calories.
+
+Find the Elf carrying the most Calories, \
+[get your puzzle input](https://adventofcode.com/2022/day/1/input) & check it!
+
+## --- Part Two ---
+
+Now the top three.
+"
+        );
+
+        assert!(to_markdown("<p>Please don't repeatedly request this endpoint</p>").is_err());
+    }
+
+    #[test]
+    fn unescapes() {
+        assert_eq!(
+            unescape("a &lt;&gt; b &amp;&amp; &#39;c&#x27; &unknown; &"),
+            "a <> b && 'c' &unknown; &"
+        );
+    }
+}
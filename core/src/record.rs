@@ -0,0 +1,173 @@
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// Writes the state of a simulation after every step as `MessagePack` frames (with field names,
+/// so `aoc replay` can show and diff them without knowing the types).
+///
+/// The default recorder is disabled and does nothing, so solvers can always take one.
+#[derive(Debug, Default)]
+pub struct Recorder {
+    out: Option<BufWriter<File>>,
+    error: Option<rmp_serde::encode::Error>,
+    steps: usize,
+}
+
+impl Recorder {
+    /// # Errors
+    /// If the file can not be created.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::create(path)
+            .with_context(|| format!("Could not create recording {}", path.display()))?;
+
+        Ok(Self {
+            out: Some(BufWriter::new(file)),
+            ..Self::default()
+        })
+    }
+
+    /// Records into the file given with `--record <path>`, other arguments are ignored.
+    ///
+    /// # Errors
+    /// If the path is missing or the file can not be created.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == "--record" {
+                return Self::create(args.next().context("--record needs a path")?);
+            }
+        }
+
+        Ok(Self::default())
+    }
+
+    /// Errors are kept until [`Recorder::finish`], so the simulation does not need to care.
+    pub fn record<S: Serialize + ?Sized>(&mut self, state: &S) {
+        let Some(out) = self.out.as_mut() else {
+            return;
+        };
+
+        if self.error.is_none() {
+            self.error = rmp_serde::encode::write_named(out, state).err();
+            self.steps += 1;
+        }
+    }
+
+    /// Flushes the recording and returns the number of recorded steps.
+    ///
+    /// # Errors
+    /// If any state could not be written.
+    pub fn finish(self) -> Result<usize> {
+        if let Some(err) = self.error {
+            return Err(err).context("Recording failed");
+        }
+
+        if let Some(mut out) = self.out {
+            out.flush()?;
+        }
+
+        Ok(self.steps)
+    }
+}
+
+/// Reads all frames of a recording.
+///
+/// # Errors
+/// If the file can not be read or a frame does not decode as `T`.
+pub fn read_recording<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<Vec<T>> {
+    let path = path.as_ref();
+    let file =
+        File::open(path).with_context(|| format!("Could not open recording {}", path.display()))?;
+    let mut reader = BufReader::new(file);
+
+    let mut frames = vec![];
+    while !reader.fill_buf()?.is_empty() {
+        let frame = rmp_serde::from_read(&mut reader)
+            .with_context(|| format!("Broken frame {} in {}", frames.len(), path.display()))?;
+        frames.push(frame);
+    }
+
+    Ok(frames)
+}
+
+/// Lists the paths at which two decoded frames differ, like `[3].tail[0]: 4 vs 5`.
+#[must_use]
+pub fn frame_diff(a: &Value, b: &Value) -> Vec<String> {
+    let mut diffs = vec![];
+    value_diff(a, b, &mut String::new(), &mut diffs);
+    diffs
+}
+
+fn value_diff(a: &Value, b: &Value, path: &mut String, diffs: &mut Vec<String>) {
+    let len = path.len();
+    match (a, b) {
+        (Value::Array(a), Value::Array(b)) if a.len() == b.len() => {
+            for (idx, (a, b)) in a.iter().zip(b).enumerate() {
+                write!(path, "[{idx}]").expect("Writing to a String does not fail");
+                value_diff(a, b, path, diffs);
+                path.truncate(len);
+            }
+        }
+        (Value::Object(a), Value::Object(b)) if a.keys().eq(b.keys()) => {
+            for ((key, a), b) in a.iter().zip(b.values()) {
+                write!(path, ".{key}").expect("Writing to a String does not fail");
+                value_diff(a, b, path, diffs);
+                path.truncate(len);
+            }
+        }
+        (a, b) if a != b => {
+            let path = if path.is_empty() { "state" } else { path };
+            diffs.push(format!("{path}: {a} vs {b}"));
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn roundtrip() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("record.msgpack");
+
+        let mut rec = Recorder::create(&path).unwrap();
+        rec.record(&[(0, 0), (0, 1)]);
+        rec.record(&[(1, 1), (0, 1)]);
+        assert_eq!(rec.finish().unwrap(), 2);
+
+        let frames: Vec<[(i32, i32); 2]> = read_recording(&path).unwrap();
+        assert_eq!(frames, vec![[(0, 0), (0, 1)], [(1, 1), (0, 1)]]);
+
+        let generic: Vec<Value> = read_recording(&path).unwrap();
+        assert_eq!(generic[1], json!([[1, 1], [0, 1]]));
+    }
+
+    #[test]
+    fn disabled() {
+        let mut rec = Recorder::from_args(["--explain".to_owned()]).unwrap();
+        rec.record(&42);
+        assert_eq!(rec.finish().unwrap(), 0);
+    }
+
+    #[test]
+    fn diff() {
+        let a = json!([{"head": [1, 2], "tail": [0, 1]}, {"head": [0, 1], "tail": [0, 0]}]);
+        let b = json!([{"head": [1, 2], "tail": [0, 2]}, {"head": [0, 1], "tail": [0, 0]}]);
+
+        assert!(frame_diff(&a, &a).is_empty());
+        assert_eq!(frame_diff(&a, &b), vec!["[0].tail[1]: 1 vs 2"]);
+        assert_eq!(
+            frame_diff(&json!([1]), &json!([1, 2])),
+            vec!["state: [1] vs [1,2]"]
+        );
+    }
+}
@@ -0,0 +1,555 @@
+//! Which days exist for which year, found from the crates of all years in the workspace
+//! (`2022/`, `2023/`, ...), and the solutions linked into this binary with `#[aoc(...)]` or
+//! registered while it runs, like plugins.
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::parse::normalize;
+use crate::parts::Parts;
+use crate::solver::{label, parse_traced, Solver};
+use anyhow::{anyhow, Result};
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// Days that have a `src/bin/dayNN.rs` in the crate at `root`.
+#[must_use]
+pub fn implemented_days(root: &Path) -> Vec<u8> {
+    let mut days: Vec<u8> = fs::read_dir(root.join("src/bin"))
+        .into_iter()
+        .flatten()
+        .filter_map(|e| {
+            let name = e.ok()?.file_name().into_string().ok()?;
+            name.strip_prefix("day")?.strip_suffix(".rs")?.parse().ok()
+        })
+        .collect();
+    days.sort_unstable();
+    days
+}
+
+/// The workspace `dir` is in: the closest of it and the directories above it with a `Cargo.toml`
+/// that has a `[workspace]`.
+#[must_use]
+pub fn workspace_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|dir| {
+            fs::read_to_string(dir.join("Cargo.toml"))
+                .ok()
+                .and_then(|manifest| manifest.parse::<toml::Table>().ok())
+                .is_some_and(|manifest| manifest.contains_key("workspace"))
+        })
+        .map(Path::to_path_buf)
+}
+
+/// Package of a year in the workspace.
+#[must_use]
+pub fn package(year: u16) -> String {
+    format!("aoc-{year}")
+}
+
+/// The cargo feature that builds a day, `cargo build --no-default-features --features day2022_11`
+/// builds only that one of its year.
+#[must_use]
+pub fn day_feature(year: u16, day: u8) -> String {
+    format!("day{year}_{day:02}")
+}
+
+/// Binary of a day. All years share the target directory of the workspace, so the days of later
+/// years are prefixed with the year, only 2022 keeps the plain `dayNN`.
+#[must_use]
+pub fn bin_name(year: u16, day: u8) -> String {
+    if year == 2022 {
+        format!("day{day:02}")
+    } else {
+        format!("aoc{year}-day{day:02}")
+    }
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Registry {
+    /// Crate directory and implemented days of every year
+    years: BTreeMap<u16, (PathBuf, Vec<u8>)>,
+}
+
+impl Registry {
+    /// Looks for crates of years in the subdirectories of `root`, which are named after the year.
+    #[must_use]
+    pub fn discover(root: &Path) -> Self {
+        let years = fs::read_dir(root)
+            .into_iter()
+            .flatten()
+            .filter_map(|e| {
+                let e = e.ok()?;
+                let year = e.file_name().into_string().ok()?.parse().ok()?;
+                let dir = e.path();
+                dir.join("Cargo.toml")
+                    .exists()
+                    .then(|| (year, (dir.clone(), implemented_days(&dir))))
+            })
+            .collect();
+
+        Self { years }
+    }
+
+    pub fn years(&self) -> impl Iterator<Item = u16> + '_ {
+        self.years.keys().copied()
+    }
+
+    #[must_use]
+    pub fn dir(&self, year: u16) -> Option<&Path> {
+        self.years.get(&year).map(|(dir, _)| dir.as_path())
+    }
+
+    /// Implemented days of `year`, empty if there is no crate for it.
+    #[must_use]
+    pub fn days(&self, year: u16) -> &[u8] {
+        self.years.get(&year).map_or(&[], |(_, days)| days)
+    }
+
+    #[must_use]
+    pub fn contains(&self, year: u16, day: u8) -> bool {
+        self.days(year).contains(&day)
+    }
+
+    /// Every implemented `(year, day)`, in order.
+    pub fn all(&self) -> impl Iterator<Item = (u16, u8)> + '_ {
+        self.years
+            .iter()
+            .flat_map(|(&year, (_, days))| days.iter().map(move |&day| (year, day)))
+    }
+}
+
+type Solve = fn(&str, &[String]) -> Result<Vec<(String, Answer)>, AocError>;
+
+/// One part of an implementation only known at runtime, like a plugin, which solves the input
+/// without parsing it first.
+pub type RawPart = Arc<dyn Fn(&str) -> Result<Answer> + Send + Sync>;
+
+/// Name of the implementation registered without a `name`, see [`Solution::name`].
+pub const MAIN: &str = "main";
+
+/// Name of the deliberately simple implementation of a day, which is a lot slower but easy to
+/// trust.
+pub const REFERENCE: &str = "reference";
+
+/// A day registered with `#[aoc(year = .., day = ..)]`, to run it without knowing its types.
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    /// Tells apart implementations of the same day, [`MAIN`] for the one used by default
+    pub name: &'static str,
+    /// See [`Solver::EXAMPLE`]
+    pub example: Option<&'static str>,
+    functions: Functions,
+}
+
+enum Functions {
+    /// A [`Solver`] linked into this binary
+    Typed {
+        solve: Solve,
+        parse: fn(&str) -> Result<Box<dyn Any>, AocError>,
+        part1: fn(&dyn Any) -> Result<Answer, AocError>,
+        part2: fn(&dyn Any) -> Result<Answer, AocError>,
+        dump: fn(&dyn Any) -> Result<String>,
+    },
+    /// Parts added with [`register`], by their number
+    Raw(&'static [(u8, RawPart)]),
+}
+
+inventory::collect!(Solution);
+
+/// The implementations added with [`register`].
+static REGISTERED: RwLock<Vec<&'static Solution>> = RwLock::new(vec![]);
+
+impl Solution {
+    #[must_use]
+    pub const fn new<S: Solver>() -> Self {
+        Self {
+            year: S::YEAR,
+            day: S::DAY,
+            name: MAIN,
+            example: S::EXAMPLE,
+            functions: Functions::Typed {
+                solve: solve_any::<S>,
+                parse: parse_any::<S>,
+                part1: part1_any::<S>,
+                part2: part2_any::<S>,
+                dump: dump_any::<S>,
+            },
+        }
+    }
+
+    /// Another implementation of the day, `#[aoc(.., name = "..")]`.
+    #[must_use]
+    pub const fn named(self, name: &'static str) -> Self {
+        Self { name, ..self }
+    }
+
+    /// An implementation made of parts that solve the raw input, by their number, to [`register`]
+    /// at runtime. Like the ones linked in, it lives as long as the program. It has the example
+    /// of the [`MAIN`] implementation, if there is one.
+    #[must_use]
+    pub fn raw(year: u16, day: u8, name: &'static str, parts: Vec<(u8, RawPart)>) -> Self {
+        Self {
+            year,
+            day,
+            name,
+            example: solution(year, day).and_then(|main| main.example),
+            functions: Functions::Raw(parts.leak()),
+        }
+    }
+
+    /// Parses the input and solves the selected parts, like [`solver::solve`](crate::solver::solve).
+    ///
+    /// # Errors
+    /// If the input does not parse, a selected part does not exist or has no answer.
+    pub fn solve(
+        &self,
+        input: &str,
+        selection: &[String],
+    ) -> Result<Vec<(String, Answer)>, AocError> {
+        match &self.functions {
+            Functions::Typed { solve, .. } => solve(input, selection),
+            Functions::Raw(parts) => {
+                let mut named = Parts::<str>::new();
+                for (number, part) in *parts {
+                    let part = Arc::clone(part);
+                    named = named.part(format!("Part {number}"), move |input: &str| part(input));
+                }
+                named
+                    .run(&normalize(input), selection)
+                    .map_err(|e| AocError::UnknownPart(format!("{e:#}")))?
+                    .into_iter()
+                    .map(|r| Ok((r.name, r.answer.map_err(|e| AocError::solve(&e))?)))
+                    .collect()
+            }
+        }
+    }
+
+    /// Only parses, so the parts can be run (and timed) on their own.
+    ///
+    /// # Errors
+    /// If the input is malformed.
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Any>, AocError> {
+        match &self.functions {
+            Functions::Typed { parse, .. } => parse(input),
+            Functions::Raw(_) => Ok(Box::new(normalize(input).into_owned())),
+        }
+    }
+
+    /// # Errors
+    /// If there is no answer for this input.
+    ///
+    /// # Panics
+    /// If `input` was parsed by another day.
+    pub fn part1(&self, input: &dyn Any) -> Result<Answer, AocError> {
+        match &self.functions {
+            Functions::Typed { part1, .. } => part1(input),
+            Functions::Raw(parts) => self.raw_part(parts, 1, input),
+        }
+    }
+
+    /// # Errors
+    /// If there is no answer for this input.
+    ///
+    /// # Panics
+    /// If `input` was parsed by another day.
+    pub fn part2(&self, input: &dyn Any) -> Result<Answer, AocError> {
+        match &self.functions {
+            Functions::Typed { part2, .. } => part2(input),
+            Functions::Raw(parts) => self.raw_part(parts, 2, input),
+        }
+    }
+
+    /// The parsed input as JSON, see [`Solver::dump`].
+    ///
+    /// # Errors
+    /// If the day can not show its input.
+    ///
+    /// # Panics
+    /// If `input` was parsed by another day.
+    pub fn dump(&self, input: &dyn Any) -> Result<String> {
+        match &self.functions {
+            Functions::Typed { dump, .. } => dump(input),
+            Functions::Raw(_) => Err(anyhow!("{} does not parse its input", self.name)),
+        }
+    }
+
+    fn raw_part(
+        &self,
+        parts: &[(u8, RawPart)],
+        number: u8,
+        input: &dyn Any,
+    ) -> Result<Answer, AocError> {
+        let input: &String = input
+            .downcast_ref()
+            .unwrap_or_else(|| panic!("Not an input of {}", self.name));
+        let (_, part) = parts
+            .iter()
+            .find(|(n, _)| *n == number)
+            .ok_or_else(|| AocError::UnknownPart(format!("{} has no part {number}", self.name)))?;
+        part(input).map_err(|e| AocError::solve(&e))
+    }
+}
+
+/// Adds an implementation only known at runtime, like a plugin, to the [`implementations`] of its
+/// day.
+///
+/// # Panics
+/// If another thread panicked while registering.
+pub fn register(solution: Solution) {
+    REGISTERED
+        .write()
+        .expect("Registering panicked")
+        .push(Box::leak(Box::new(solution)));
+}
+
+fn solve_any<S: Solver>(
+    input: &str,
+    selection: &[String],
+) -> Result<Vec<(String, Answer)>, AocError> {
+    let input = normalize(input);
+    let parsed = parse_traced::<S>(&input).map_err(|e| AocError::parse(S::DAY, &input, &e))?;
+    S::parts()
+        .labeled(label::<S>())
+        .run(&parsed, selection)
+        .map_err(|e| AocError::UnknownPart(format!("{e:#}")))?
+        .into_iter()
+        .map(|r| Ok((r.name, r.answer.map_err(|e| AocError::solve(&e))?)))
+        .collect()
+}
+
+fn parse_any<S: Solver>(input: &str) -> Result<Box<dyn Any>, AocError> {
+    let input = normalize(input);
+    match parse_traced::<S>(&input) {
+        Ok(parsed) => Ok(Box::new(parsed)),
+        Err(e) => Err(AocError::parse(S::DAY, &input, &e)),
+    }
+}
+
+fn part1_any<S: Solver>(input: &dyn Any) -> Result<Answer, AocError> {
+    S::part1(downcast::<S>(input)).map_err(|e| AocError::solve(&e))
+}
+
+fn part2_any<S: Solver>(input: &dyn Any) -> Result<Answer, AocError> {
+    S::part2(downcast::<S>(input)).map_err(|e| AocError::solve(&e))
+}
+
+fn dump_any<S: Solver>(input: &dyn Any) -> Result<String> {
+    S::dump(downcast::<S>(input))
+}
+
+fn downcast<S: Solver>(input: &dyn Any) -> &S::Input {
+    input
+        .downcast_ref()
+        .unwrap_or_else(|| panic!("Not an input of {} day {}", S::YEAR, S::DAY))
+}
+
+/// Every registered day, ordered by year and day. Only the [`MAIN`] implementations.
+#[must_use]
+pub fn solutions() -> Vec<&'static Solution> {
+    let mut solutions: Vec<_> = inventory::iter::<Solution>()
+        .filter(|s| s.name == MAIN)
+        .collect();
+    solutions.sort_by_key(|s| (s.year, s.day));
+    solutions
+}
+
+/// The implementation of a day with that name, see [`implementations`].
+#[must_use]
+pub fn implementation(year: u16, day: u8, name: &str) -> Option<&'static Solution> {
+    implementations(year, day)
+        .into_iter()
+        .find(|s| s.name == name)
+}
+
+/// The [`MAIN`] implementation of a day.
+#[must_use]
+pub fn solution(year: u16, day: u8) -> Option<&'static Solution> {
+    inventory::iter::<Solution>().find(|s| s.year == year && s.day == day && s.name == MAIN)
+}
+
+/// All implementations of a day, the [`MAIN`] one first and the others by name, including those
+/// added with [`register`].
+///
+/// # Panics
+/// If another thread panicked while registering.
+#[must_use]
+pub fn implementations(year: u16, day: u8) -> Vec<&'static Solution> {
+    let registered = REGISTERED.read().expect("Registering panicked");
+    let mut found: Vec<_> = inventory::iter::<Solution>()
+        .chain(registered.iter().copied())
+        .filter(|s| s.year == year && s.day == day)
+        .collect();
+    found.sort_by_key(|s| (s.name != MAIN, s.name));
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discovers_years() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        for (year, days) in [
+            ("2022", &["day01.rs", "day03.rs", "stats.rs"][..]),
+            ("2023", &["day02.rs"]),
+        ] {
+            let bin = root.join(year).join("src/bin");
+            fs::create_dir_all(&bin).unwrap();
+            fs::write(root.join(year).join("Cargo.toml"), "").unwrap();
+            for day in days {
+                fs::write(bin.join(day), "").unwrap();
+            }
+        }
+        // Neither a year nor a crate
+        fs::create_dir_all(root.join("notes")).unwrap();
+        fs::create_dir_all(root.join("2021")).unwrap();
+
+        let registry = Registry::discover(root);
+
+        assert_eq!(registry.years().collect::<Vec<_>>(), vec![2022, 2023]);
+        assert_eq!(registry.days(2022), [1, 3]);
+        assert!(registry.contains(2023, 2));
+        assert!(!registry.contains(2021, 1));
+        assert_eq!(
+            registry.all().collect::<Vec<_>>(),
+            vec![(2022, 1), (2022, 3), (2023, 2)]
+        );
+        assert_eq!(registry.dir(2023), Some(root.join("2023").as_path()));
+    }
+
+    #[test]
+    fn finds_workspace_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let src = root.join("2022/src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(root.join("2022/Cargo.toml"), "[package]").unwrap();
+        assert_eq!(workspace_root(&src), None);
+
+        fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\"2022\"]").unwrap();
+        assert_eq!(workspace_root(&src).as_deref(), Some(root));
+        assert_eq!(workspace_root(root).as_deref(), Some(root));
+    }
+
+    #[crate::aoc(year = 1, day = 1)]
+    struct Registered;
+
+    impl Solver for Registered {
+        const YEAR: u16 = 1;
+        const DAY: u8 = 1;
+        const EXAMPLE: Option<&'static str> = Some("1 2 3");
+
+        type Input = Vec<i64>;
+
+        fn parse(input: &str) -> Result<Vec<i64>> {
+            Ok(input
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<_, _>>()?)
+        }
+
+        fn part1(input: &Vec<i64>) -> Result<Answer> {
+            Ok(input.iter().sum::<i64>().into())
+        }
+
+        fn part2(input: &Vec<i64>) -> Result<Answer> {
+            Ok(input.iter().product::<i64>().into())
+        }
+    }
+
+    /// Sums backwards, to have a second implementation
+    #[crate::aoc(year = 1, day = 1, name = "rev")]
+    struct Reversed;
+
+    impl Solver for Reversed {
+        const YEAR: u16 = 1;
+        const DAY: u8 = 1;
+
+        type Input = Vec<i64>;
+
+        fn parse(input: &str) -> Result<Vec<i64>> {
+            Registered::parse(input)
+        }
+
+        fn part1(input: &Vec<i64>) -> Result<Answer> {
+            Ok(input.iter().rev().sum::<i64>().into())
+        }
+
+        fn part2(input: &Vec<i64>) -> Result<Answer> {
+            Registered::part2(input)
+        }
+    }
+
+    #[test]
+    fn registers_implementations() {
+        let names: Vec<_> = implementations(1, 1).iter().map(|s| s.name).collect();
+        assert_eq!(names, [MAIN, "rev"]);
+        assert_eq!(solution(1, 1).unwrap().name, MAIN);
+        assert_eq!(
+            solutions()
+                .iter()
+                .filter(|s| (s.year, s.day) == (1, 1))
+                .count(),
+            1
+        );
+        assert_eq!(
+            implementations(1, 1)[1].solve("2 3 4", &[]).unwrap(),
+            solution(1, 1).unwrap().solve("2 3 4", &[]).unwrap()
+        );
+        assert!(implementations(1, 2).is_empty());
+    }
+
+    #[test]
+    fn registers_at_runtime() {
+        let lines: RawPart = Arc::new(|input| Ok(input.lines().count().into()));
+        register(Solution::raw(1, 3, "plugin:lines", vec![(2, lines)]));
+
+        let found = implementation(1, 3, "plugin:lines").unwrap();
+        assert!(solution(1, 3).is_none());
+        assert_eq!(found.example, None);
+        let example = Solution::raw(1, 1, "plugin:example", vec![]).example;
+        assert_eq!(example, Some("1 2 3"));
+        assert_eq!(
+            found.solve("a\r\nb\n", &[]).unwrap(),
+            [("Part 2".to_owned(), Answer::Int(2))]
+        );
+        let parsed = found.parse("a\nb\nc\n").unwrap();
+        assert_eq!(found.part2(parsed.as_ref()).unwrap(), Answer::Int(3));
+        assert!(matches!(
+            found.part1(parsed.as_ref()),
+            Err(AocError::UnknownPart(_))
+        ));
+        found.dump(parsed.as_ref()).unwrap_err();
+    }
+
+    #[test]
+    fn registers_solutions() {
+        assert!(solution(1, 2).is_none());
+        assert!(solutions().iter().any(|s| (s.year, s.day) == (1, 1)));
+
+        let solution = solution(1, 1).unwrap();
+        assert_eq!(solution.example, Some("1 2 3"));
+        assert_eq!(
+            solution.solve("2 3 4", &["2".to_owned()]).unwrap(),
+            vec![("Part 2".to_owned(), Answer::Int(24))]
+        );
+
+        let input = solution.parse("2 3 4").unwrap();
+        assert_eq!(solution.part1(input.as_ref()).unwrap(), Answer::Int(9));
+
+        assert!(matches!(
+            solution.parse("2 x"),
+            Err(AocError::ParseError { day: 1, .. })
+        ));
+        assert!(matches!(
+            solution.solve("2", &["3".to_owned()]),
+            Err(AocError::UnknownPart(_))
+        ));
+    }
+}
@@ -0,0 +1,367 @@
+//! `aoc new-day`: the files of a new day from a template, so they do not have to be copied from
+//! the previous day and fixed up by hand.
+
+use crate::registry::{bin_name, day_feature};
+use anyhow::{anyhow, Context, Result};
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `src/dayNN.rs`, with `__DAY__` for the padded and `__NUM__` for the plain number of the day.
+const MODULE: &str = r#"use crate::YEAR;
+use anyhow::{bail, ensure, Result};
+use aoc_core::answer::Answer;
+use aoc_core::aoc;
+use aoc_core::solver::Solver;
+
+pub type Input = Vec<String>;
+
+/// # Errors
+/// If the input is malformed.
+pub fn parse_input(input: &str) -> Result<Input> {
+    ensure!(!input.trim().is_empty(), "The input is empty");
+    Ok(input.lines().map(str::to_owned).collect())
+}
+
+/// # Errors
+/// If there is no answer for this input.
+pub fn part1(input: &Input) -> Result<usize> {
+    bail!("Not solved yet, the input has {} lines", input.len())
+}
+
+/// # Errors
+/// If there is no answer for this input.
+pub fn part2(input: &Input) -> Result<usize> {
+    bail!("Not solved yet, the input has {} lines", input.len())
+}
+
+/// The example of the puzzle text.
+pub const EXAMPLE: &str = include_str!("../examples/day__DAY__.txt");
+
+#[aoc(year = __YEAR__, day = __NUM__)]
+pub struct Day__DAY__;
+
+impl Solver for Day__DAY__ {
+    const YEAR: u16 = YEAR;
+    const DAY: u8 = __NUM__;
+    const EXAMPLE: Option<&'static str> = Some(EXAMPLE);
+
+    type Input = Input;
+
+    fn parse(input: &str) -> Result<Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Input) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore = "Fill in examples/day__DAY__.txt and the answers of the puzzle text"]
+    fn example() {
+        let input = parse_input(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), 0);
+        assert_eq!(part2(&input).unwrap(), 0);
+    }
+}
+"#;
+
+/// `src/bin/dayNN.rs`
+const BINARY: &str = r"#![warn(clippy::pedantic)]
+
+use anyhow::Result;
+use aoc__YEAR__::day__DAY__::Day__DAY__;
+use aoc_core::solver;
+
+fn main() -> Result<()> {
+    solver::run::<Day__DAY__>()
+}
+";
+
+fn fill(template: &str, year: u16, day: u8) -> String {
+    template
+        .replace("__YEAR__", &year.to_string())
+        .replace("__DAY__", &format!("{day:02}"))
+        .replace("__NUM__", &day.to_string())
+}
+
+/// The new files of a day, relative to the crate of its year: the module registered with
+/// `#[aoc]`, a binary running it and an empty example.
+#[must_use]
+pub fn files(year: u16, day: u8) -> Vec<(PathBuf, String)> {
+    vec![
+        (
+            format!("src/day{day:02}.rs").into(),
+            fill(MODULE, year, day),
+        ),
+        (
+            format!("src/bin/day{day:02}.rs").into(),
+            fill(BINARY, year, day),
+        ),
+        (format!("examples/day{day:02}.txt").into(), String::new()),
+    ]
+}
+
+/// `lib_rs` with `pub mod dayNN;` after the modules that sort before it, `None` if it is already
+/// there. Behind the feature of the day if the other days are.
+#[must_use]
+pub fn add_module(lib_rs: &str, year: u16, day: u8) -> Option<String> {
+    let module = format!("day{day:02}");
+    let declaration = format!("pub mod {module};");
+    let lines: Vec<&str> = lib_rs.lines().collect();
+    if lines.contains(&declaration.as_str()) {
+        return None;
+    }
+
+    let module_at = |i: usize| {
+        lines[i]
+            .strip_prefix("pub mod ")
+            .and_then(|m| m.strip_suffix(';'))
+    };
+    // After a module is always between two items, never between an attribute and its module
+    let at = if let Some(i) =
+        (0..lines.len()).rfind(|&i| module_at(i).is_some_and(|m| m < module.as_str()))
+    {
+        i + 1
+    } else {
+        let mut first = (0..lines.len())
+            .find(|&i| module_at(i).is_some())
+            .unwrap_or(lines.len());
+        while first > 0
+            && (lines[first - 1].starts_with("#[") || lines[first - 1].starts_with("//"))
+        {
+            first -= 1;
+        }
+        first
+    };
+
+    let mut lines: Vec<String> = lines.into_iter().map(str::to_owned).collect();
+    lines.insert(at, declaration);
+    if lib_rs.contains("#[cfg(feature = \"day") {
+        lines.insert(
+            at,
+            format!("#[cfg(feature = \"{}\")]", day_feature(year, day)),
+        );
+    }
+    Some(lines.join("\n") + "\n")
+}
+
+/// Whether the days of the crate are behind features, see [`day_feature`].
+fn has_day_features(cargo_toml: &str) -> bool {
+    cargo_toml.lines().any(|l| l.starts_with("all-days = ["))
+}
+
+/// `cargo_toml` with the feature of the day, also in `all-days`, `None` if the days have no
+/// features or it is already there.
+#[must_use]
+pub fn add_feature(cargo_toml: &str, year: u16, day: u8) -> Option<String> {
+    let feature = day_feature(year, day);
+    let mut lines: Vec<String> = cargo_toml.lines().map(str::to_owned).collect();
+    let all = lines.iter().position(|l| l.starts_with("all-days = ["))?;
+    if lines
+        .iter()
+        .any(|l| l.starts_with(&format!("{feature} = ")))
+    {
+        return None;
+    }
+
+    let mut end = all
+        + lines[all..]
+            .iter()
+            .position(|l| l.trim_end().ends_with(']'))?;
+    if end == all {
+        // All on one line
+        let line = lines[all].trim_end().trim_end_matches(']').trim_end();
+        let separator = if line.ends_with('[') { "" } else { ", " };
+        lines[all] = format!("{line}{separator}\"{feature}\"]");
+    } else {
+        lines.insert(end, format!("    \"{feature}\","));
+        end += 1;
+    }
+    let prefix = format!("day{year}_");
+    let after = lines
+        .iter()
+        .rposition(|l| l.starts_with(&prefix))
+        .map_or(end + 1, |i| i + 1);
+    lines.insert(after, format!("{feature} = []"));
+    Some(lines.join("\n") + "\n")
+}
+
+/// `cargo_toml` with a `[[bin]]` for the day, `None` if cargo finds the binaries by itself and
+/// the days have no features.
+#[must_use]
+pub fn add_bin(cargo_toml: &str, year: u16, day: u8) -> Option<String> {
+    let features = has_day_features(cargo_toml);
+    if !features && !cargo_toml.lines().any(|l| l.trim() == "autobins = false") {
+        return None;
+    }
+
+    let mut bin = format!(
+        "{}\n\n[[bin]]\nname = \"{}\"\npath = \"src/bin/day{day:02}.rs\"\n",
+        cargo_toml.trim_end(),
+        bin_name(year, day)
+    );
+    if features {
+        writeln!(bin, "required-features = [\"{}\"]", day_feature(year, day)).ok()?;
+    }
+    Some(bin)
+}
+
+/// Writes the [`files`] of a day into the crate at `dir` and declares the module and binary.
+/// Returns the created and changed files.
+///
+/// # Errors
+/// If a file of the day already exists, nothing is written then, or writing fails.
+pub fn new_day(dir: &Path, year: u16, day: u8) -> Result<Vec<PathBuf>> {
+    let files = files(year, day);
+    if let Some((path, _)) = files.iter().find(|(path, _)| dir.join(path).exists()) {
+        return Err(anyhow!(
+            "{} already exists, day {day} is there already",
+            dir.join(path).display()
+        ));
+    }
+
+    let mut written = vec![];
+    let mut write = |path: PathBuf, contents: &str| -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, contents)
+            .with_context(|| format!("Could not write {}", path.display()))?;
+        written.push(path);
+        Ok(())
+    };
+    for (path, contents) in &files {
+        write(dir.join(path), contents)?;
+    }
+
+    let lib = dir.join("src/lib.rs");
+    let lib_rs =
+        fs::read_to_string(&lib).with_context(|| format!("Could not read {}", lib.display()))?;
+    if let Some(lib_rs) = add_module(&lib_rs, year, day) {
+        write(lib, &lib_rs)?;
+    }
+    let manifest = dir.join("Cargo.toml");
+    let cargo_toml = fs::read_to_string(&manifest)
+        .with_context(|| format!("Could not read {}", manifest.display()))?;
+    let with_feature = add_feature(&cargo_toml, year, day);
+    let with_bin = add_bin(with_feature.as_deref().unwrap_or(&cargo_toml), year, day);
+    if let Some(cargo_toml) = with_bin.or(with_feature) {
+        write(manifest, &cargo_toml)?;
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn declares_module() {
+        let lib_rs = "pub const YEAR: u16 = 2022;
+
+pub mod day01;
+pub mod day12;
+// Needs an operating system
+#[cfg(unix)]
+pub mod perf;
+pub mod point;
+";
+        let added = add_module(lib_rs, 2022, 5).unwrap();
+        assert!(added.contains("pub mod day01;\npub mod day05;\npub mod day12;\n"));
+        assert_eq!(add_module(&added, 2022, 5), None);
+
+        let added = add_module(lib_rs, 2022, 13).unwrap();
+        assert!(added.contains("pub mod day12;\npub mod day13;\n// Needs"));
+        assert!(add_module("pub mod cancel;\n", 2023, 1)
+            .unwrap()
+            .ends_with("pub mod cancel;\npub mod day01;\n"));
+        // Not between the comment, the attribute and their module
+        assert_eq!(
+            add_module("//! Docs\n\n#[cfg(unix)]\npub mod perf;\n", 2022, 1).unwrap(),
+            "//! Docs\n\npub mod day01;\n#[cfg(unix)]\npub mod perf;\n"
+        );
+
+        let gated = "#[cfg(feature = \"day2022_01\")]\npub mod day01;\npub mod point;\n";
+        assert_eq!(
+            add_module(gated, 2022, 2).unwrap(),
+            "#[cfg(feature = \"day2022_01\")]\npub mod day01;\n\
+             #[cfg(feature = \"day2022_02\")]\npub mod day02;\npub mod point;\n"
+        );
+    }
+
+    #[test]
+    fn declares_feature() {
+        let cargo_toml = "[features]
+default = [\"all-days\"]
+all-days = [
+    \"day2022_01\",
+]
+day2022_01 = []
+heap = []
+";
+        assert_eq!(
+            add_feature(cargo_toml, 2022, 2).unwrap(),
+            "[features]
+default = [\"all-days\"]
+all-days = [
+    \"day2022_01\",
+    \"day2022_02\",
+]
+day2022_01 = []
+day2022_02 = []
+heap = []
+"
+        );
+        assert_eq!(
+            add_feature("all-days = []\n", 2023, 1).unwrap(),
+            "all-days = [\"day2023_01\"]\nday2023_01 = []\n"
+        );
+        assert_eq!(add_feature(cargo_toml, 2022, 1), None);
+        assert_eq!(add_feature("[package]\n", 2022, 1), None);
+    }
+
+    #[test]
+    fn declares_binary() {
+        assert_eq!(add_bin("[package]\nname = \"aoc-2022\"\n", 2022, 5), None);
+        assert_eq!(
+            add_bin("[package]\nautobins = false\n", 2023, 7).unwrap(),
+            "[package]\nautobins = false\n\n[[bin]]\nname = \"aoc2023-day07\"\npath = \"src/bin/day07.rs\"\n"
+        );
+        assert!(add_bin("all-days = []\n", 2022, 5)
+            .unwrap()
+            .ends_with("path = \"src/bin/day05.rs\"\nrequired-features = [\"day2022_05\"]\n"));
+    }
+
+    #[test]
+    fn writes_day() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "pub mod day01;\n").unwrap();
+        fs::write(dir.join("Cargo.toml"), "[package]\n").unwrap();
+
+        let written = new_day(dir, 2022, 2).unwrap();
+        let module = fs::read_to_string(dir.join("src/day02.rs")).unwrap();
+        let binary = fs::read_to_string(dir.join("src/bin/day02.rs")).unwrap();
+        let lib_rs = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+        let again = new_day(dir, 2022, 2);
+
+        assert_eq!(written.len(), 4);
+        assert!(module.contains("#[aoc(year = 2022, day = 2)]\npub struct Day02;"));
+        assert!(module.contains(r#"include_str!("../examples/day02.txt")"#));
+        assert!(binary.contains("use aoc2022::day02::Day02;"));
+        assert_eq!(lib_rs, "pub mod day01;\npub mod day02;\n");
+        assert!(again.unwrap_err().to_string().contains("already exists"));
+    }
+}
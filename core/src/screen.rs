@@ -0,0 +1,255 @@
+//! Monochrome pixel screens, and how densely they are drawn in the terminal, chosen with
+//! `--density`.
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::io::IsTerminal;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Monochrome pixel screen, e.g. for answers that are drawn instead of printed.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Screen {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+}
+
+impl Screen {
+    /// All pixels off.
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// # Panics
+    /// If the pixel is outside of the screen.
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is off screen"
+        );
+        self.pixels[y * self.width + x]
+    }
+
+    /// # Panics
+    /// If the pixel is outside of the screen.
+    pub fn set(&mut self, x: usize, y: usize, on: bool) {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is off screen"
+        );
+        self.pixels[y * self.width + x] = on;
+    }
+
+    /// One line per row, each ending in a newline.
+    #[must_use]
+    pub fn render(&self, on: char, off: char) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            s.extend(row.iter().map(|&p| if p { on } else { off }));
+            s.push('\n');
+        }
+        s
+    }
+
+    /// Drawn with `density`, large screens fit the terminal with the denser ones. Cells that stick
+    /// out over the edge are drawn as if the missing pixels were off.
+    #[must_use]
+    pub fn draw(&self, density: Density) -> String {
+        match density {
+            Density::Block => self.render('█', ' '),
+            Density::Half => self.cells(1, 2, |bits| [' ', '▀', '▄', '█'][usize::from(bits)]),
+            Density::Braille => self.cells(2, 4, |bits| {
+                if bits == 0 {
+                    ' '
+                } else {
+                    // All of U+2800 to U+28FF are braille patterns
+                    char::from_u32(0x2800 + u32::from(braille_dots(bits))).unwrap_or(' ')
+                }
+            }),
+        }
+    }
+
+    /// One `glyph` per cell of `width` by `height` pixels, which gets the pixels that are on as
+    /// bits, row by row.
+    fn cells(&self, width: usize, height: usize, glyph: impl Fn(u8) -> char) -> String {
+        let mut s = String::new();
+        for top in (0..self.height).step_by(height) {
+            for left in (0..self.width).step_by(width) {
+                let mut bits = 0;
+                for dy in 0..height {
+                    for dx in 0..width {
+                        let (x, y) = (left + dx, top + dy);
+                        if x < self.width && y < self.height && self.get(x, y) {
+                            bits |= 1 << (dy * width + dx);
+                        }
+                    }
+                }
+                s.push(glyph(bits));
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+
+/// The bits of a braille pattern, from the bits of [`Screen::cells`]. Dots 1 to 6 go down the
+/// left and then the right column, 7 and 8 are the bottom row.
+fn braille_dots(bits: u8) -> u8 {
+    const DOTS: [u8; 8] = [0x01, 0x08, 0x02, 0x10, 0x04, 0x20, 0x40, 0x80];
+    DOTS.iter()
+        .enumerate()
+        .filter(|&(bit, _)| bits & 1 << bit != 0)
+        .fold(0, |dots, (_, dot)| dots | dot)
+}
+
+/// How many pixels of a [`Screen`] one character shows.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum Density {
+    /// One, `█`
+    #[default]
+    Block,
+    /// Two above each other, with `▀`, `▄` and `█`
+    Half,
+    /// Two by four, with the braille patterns from U+2800
+    Braille,
+}
+
+impl Density {
+    pub const ALL: [Density; 3] = [Density::Block, Density::Half, Density::Braille];
+
+    /// `density` if stdout is a terminal, otherwise [`Density::Block`], so the output the runner
+    /// reads from the days stays the same.
+    #[must_use]
+    pub fn detect(density: Density) -> Self {
+        if std::io::stdout().is_terminal() {
+            density
+        } else {
+            Density::Block
+        }
+    }
+
+    /// Reads `--density <density>`, other arguments are ignored.
+    ///
+    /// # Errors
+    /// If the density is missing or unknown.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == "--density" {
+                let density = args.next().context("--density needs a name")?.parse()?;
+                return Ok(Self::detect(density));
+            }
+        }
+        Ok(Self::default())
+    }
+}
+
+impl Display for Density {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Density::Block => "block",
+            Density::Half => "half",
+            Density::Braille => "braille",
+        })
+    }
+}
+
+impl FromStr for Density {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Density::ALL
+            .into_iter()
+            .find(|density| density.to_string() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = Density::ALL.iter().map(ToString::to_string).collect();
+                anyhow!("Unknown density {s:?}, available are: {}", names.join(", "))
+            })
+    }
+}
+
+static DENSITY: OnceLock<Density> = OnceLock::new();
+
+/// Sets the density [`density`] returns. Does nothing if it is set already.
+pub fn init(density: Density) {
+    let _already = DENSITY.set(density);
+}
+
+/// [`init`] with the density given by `--density`.
+///
+/// # Errors
+/// If the density is missing or unknown.
+pub fn init_from_args(args: impl IntoIterator<Item = String>) -> Result<()> {
+    init(Density::from_args(args)?);
+    Ok(())
+}
+
+/// The density set with [`init`], [`Density::Block`] if it was not.
+#[must_use]
+pub fn density() -> Density {
+    DENSITY.get().copied().unwrap_or_default()
+}
+
+impl Display for Screen {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render('█', ' '))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render() {
+        let mut screen = Screen::new(3, 2);
+        screen.set(0, 0, true);
+        screen.set(2, 1, true);
+
+        assert!(screen.get(2, 1));
+        assert!(!screen.get(1, 1));
+        assert_eq!(screen.render('#', '.'), "#..\n..#\n");
+        assert_eq!(screen.to_string(), "█  \n  █\n");
+        assert_eq!(screen.draw(Density::Block), screen.to_string());
+    }
+
+    #[test]
+    fn dense() {
+        let mut screen = Screen::new(3, 5);
+        for (x, y) in [(0, 0), (1, 1), (0, 3), (1, 3), (2, 4)] {
+            screen.set(x, y, true);
+        }
+
+        assert_eq!(screen.draw(Density::Half), "▀▄ \n▄▄ \n  ▀\n");
+        assert_eq!(screen.draw(Density::Braille), "⣑ \n ⠁\n");
+        assert_eq!(Screen::new(0, 0).draw(Density::Braille), "");
+    }
+
+    #[test]
+    fn densities() {
+        let density = |args: &[&str]| Density::from_args(args.iter().map(|&a| a.to_owned()));
+        assert_eq!(density(&["--part", "1"]).unwrap(), Density::Block);
+        density(&["--density", "braille"]).unwrap();
+        let err = density(&["--density", "sixel"]).unwrap_err();
+        assert!(err.to_string().contains("block, half, braille"), "{err}");
+        density(&["--density"]).unwrap_err();
+    }
+}
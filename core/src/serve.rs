@@ -0,0 +1,588 @@
+//! `aoc serve`: solves the days registered with `#[aoc]` over HTTP, so e.g. a dashboard can use
+//! them without running the binaries.
+//!
+//! `POST /solve/<year>/<day>` with the puzzle input as body answers with
+//!
+//! ```json
+//! { "year": 2022, "day": 1, "parse_ms": 0.1,
+//!   "parts": [{ "part": "Part 1", "answer": "24000", "time_ms": 0.01 }, ...] }
+//! ```
+//!
+//! A part without an answer has an `error` and its [`AocError::kind`] instead. Everything else
+//! answers with an `error` and the matching status code, input that can not be parsed also with
+//! its `kind` and the `line` it broke in, if known. Inputs over [`MAX_INPUT`] are answered with 413,
+//! a day that panics with 500.
+//!
+//! Every request is solved by a worker process of its own, which can use no more memory and CPU
+//! time than its [`Limits`] and is killed after their wall clock time. A day stopped that way is
+//! answered with 503, so an input that makes it run away does not take the server down with it.
+//! Only so many requests are solved at once, the ones that come in while all of them are busy are
+//! answered with 503 right away.
+
+use crate::error::AocError;
+use crate::registry::solution;
+use crate::timing::{Phases, TimedPart};
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use std::any::Any;
+use std::io::{self, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+use tiny_http::{Header, Request, Response, Server};
+use wait_timeout::ChildExt;
+
+/// Bodies over this many bytes are not read, the biggest inputs are a few dozen KiB.
+pub const MAX_INPUT: u64 = 1 << 20;
+
+/// What is kept of the answer and of the logs of a worker, the rest is read and thrown away.
+const MAX_OUTPUT: u64 = 1 << 20;
+
+/// What solving one request may use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Limits {
+    /// Address space of the worker in bytes, only enforced on Unix
+    pub memory: u64,
+    /// CPU time of the worker, counted in whole seconds and only enforced on Unix
+    pub cpu: Duration,
+    /// Time until the worker is killed, also if it is not using the CPU
+    pub wall: Duration,
+}
+
+#[derive(Debug, Serialize)]
+struct Solved {
+    year: u16,
+    day: u8,
+    parse_ms: f64,
+    parts: Vec<SolvedPart>,
+}
+
+#[derive(Debug, Serialize)]
+struct SolvedPart {
+    part: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<&'static str>,
+    time_ms: f64,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Failed {
+    error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+}
+
+fn millis(time: Duration) -> f64 {
+    time.as_secs_f64() * 1000.0
+}
+
+fn solved_part(part: TimedPart) -> SolvedPart {
+    let (answer, error, kind) = match part.answer {
+        Ok(answer) => (Some(answer.to_string()), None, None),
+        Err(e) => (None, Some(e.to_string()), Some(e.kind())),
+    };
+    SolvedPart {
+        part: part.name,
+        answer,
+        error,
+        kind,
+        time_ms: millis(part.time),
+    }
+}
+
+fn json_error(status: u16, error: &Failed) -> (u16, String) {
+    (status, serde_json::to_string(error).unwrap_or_default())
+}
+
+fn error(status: u16, error: String) -> (u16, String) {
+    json_error(
+        status,
+        &Failed {
+            error,
+            ..Failed::default()
+        },
+    )
+}
+
+/// The message a panic was started with.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("no message")
+}
+
+/// The body, or the status code and JSON error if it can not be read or is over `limit` bytes.
+fn read_input(body: impl Read, limit: u64) -> Result<String, (u16, String)> {
+    let mut input = String::new();
+    match body.take(limit + 1).read_to_string(&mut input) {
+        Ok(read) if read as u64 > limit => Err(error(
+            413,
+            format!("The input is longer than {limit} bytes"),
+        )),
+        Ok(_) => Ok(input),
+        Err(e) => Err(error(400, format!("Could not read the input: {e}"))),
+    }
+}
+
+/// The status code and JSON body for one request, with the registered days solved by `solve`.
+#[must_use]
+pub fn respond(
+    method: &str,
+    url: &str,
+    body: &str,
+    solve: impl FnOnce(u16, u8, &str) -> (u16, String),
+) -> (u16, String) {
+    let path = url.split('?').next().unwrap_or_default();
+    let Some(day) = path.strip_prefix("/solve/") else {
+        return error(
+            404,
+            format!("Nothing at {path}, try POST /solve/<year>/<day>"),
+        );
+    };
+    let Some((Ok(year), Ok(day))) = day
+        .split_once('/')
+        .map(|(year, day)| (year.parse::<u16>(), day.parse::<u8>()))
+    else {
+        return error(404, format!("{day:?} is not <year>/<day>"));
+    };
+    if method != "POST" {
+        return error(405, format!("Use POST with the input, not {method}"));
+    }
+    if solution(year, day).is_none() {
+        return error(404, format!("{year} day {day} is not registered"));
+    }
+
+    solve(year, day, body)
+}
+
+/// Solves a registered day in this process, the status code and JSON body of [`respond`].
+#[must_use]
+pub fn solve(year: u16, day: u8, input: &str) -> (u16, String) {
+    let Some(solution) = solution(year, day) else {
+        return error(404, format!("{year} day {day} is not registered"));
+    };
+
+    // The days assume valid input and may panic on anything else, release builds abort instead
+    // and are answered by `in_worker`
+    let measured = panic::catch_unwind(AssertUnwindSafe(|| Phases::measure(solution, input)));
+    let phases = match measured {
+        Ok(Ok(phases)) => phases,
+        Err(payload) => {
+            return error(
+                500,
+                format!("{year} day {day} panicked: {}", panic_message(&*payload)),
+            );
+        }
+        Ok(Err(e)) => {
+            let line = match &e {
+                AocError::ParseError { line, .. } => *line,
+                _ => None,
+            };
+            let error = Failed {
+                error: e.to_string(),
+                kind: Some(e.kind()),
+                line,
+            };
+            return json_error(422, &error);
+        }
+    };
+    let solved = Solved {
+        year,
+        day,
+        parse_ms: millis(phases.parse),
+        parts: phases.parts.into_iter().map(solved_part).collect(),
+    };
+    match serde_json::to_string(&solved) {
+        Ok(json) => (200, json),
+        Err(e) => error(500, e.to_string()),
+    }
+}
+
+/// Whether the worker aborted, like a panic does with `panic = "abort"` but also running out of
+/// memory.
+#[cfg(unix)]
+fn aborted(status: ExitStatus) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal() == Some(libc::SIGABRT)
+}
+
+#[cfg(not(unix))]
+fn aborted(_: ExitStatus) -> bool {
+    false
+}
+
+/// The message of the panic that stopped the worker, which it printed to `stderr` like
+/// `thread 'main' panicked at src/day05.rs:158:9:\n<message>\nnote: run with ..`. A panic outside
+/// of [`solve`] exits with 101 where it unwinds.
+fn panic_in(status: ExitStatus, stderr: &str) -> Option<&str> {
+    if !aborted(status) && status.code() != Some(101) {
+        return None;
+    }
+    let (_, message) = stderr.split_once(" panicked at ")?;
+    let message = message.split_once('\n').map_or("", |(_, message)| message);
+    let end = message.find("\nnote: ").unwrap_or(message.len());
+    Some(message[..end].trim())
+}
+
+/// The first `limit` bytes of `pipe`, reading it to the end so whoever writes to it does not
+/// block.
+fn read_bounded(mut pipe: impl Read, limit: u64) -> io::Result<String> {
+    let mut output = Vec::new();
+    pipe.by_ref().take(limit).read_to_end(&mut output)?;
+    io::copy(&mut pipe, &mut io::sink())?;
+    Ok(String::from_utf8_lossy(&output).into_owned())
+}
+
+/// Runs `worker` with `input` on stdin, kills it after `wall` and takes the status code and JSON
+/// body from the first line and the rest of what it prints. What it logs goes to stderr.
+fn in_worker(mut worker: Command, wall: Duration, input: &str) -> (u16, String) {
+    let spawned = worker
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => return error(500, format!("Could not start a worker: {e}")),
+    };
+    let mut stdin = child.stdin.take().expect("Piped");
+    let input = input.to_owned();
+    // A worker that stops early does not read everything, which is not a problem of its own
+    let writer = thread::spawn(move || drop(stdin.write_all(input.as_bytes())));
+    let read = |pipe: Box<dyn Read + Send>| thread::spawn(move || read_bounded(pipe, MAX_OUTPUT));
+    let reader = read(Box::new(child.stdout.take().expect("Piped")));
+    let logs = read(Box::new(child.stderr.take().expect("Piped")));
+
+    let status = match child.wait_timeout(wall) {
+        Ok(Some(status)) => status,
+        Ok(None) => {
+            // Already gone if it exited just now
+            let _ = child.kill();
+            let _ = child.wait();
+            return error(503, format!("Stopped after {wall:?}"));
+        }
+        Err(e) => return error(500, format!("Could not wait for the worker: {e}")),
+    };
+    let _ = writer.join();
+    let output = reader.join().ok().and_then(Result::ok).unwrap_or_default();
+    let logs = logs.join().ok().and_then(Result::ok).unwrap_or_default();
+    eprint!("{logs}");
+
+    if let Some(message) = panic_in(status, &logs) {
+        return error(500, format!("The worker panicked: {message}"));
+    }
+    if !status.success() {
+        return error(
+            503,
+            format!("Stopped, probably for using more memory or CPU time than allowed ({status})"),
+        );
+    }
+    match output.split_once('\n').map(|(s, json)| (s.parse(), json)) {
+        Some((Ok(status), json)) => (status, json.to_owned()),
+        _ => error(500, format!("The worker answered {output:?}")),
+    }
+}
+
+/// Applies the memory and CPU time of `limits` to this process.
+#[cfg(unix)]
+fn limit(limits: &Limits) -> io::Result<()> {
+    let set = |resource, value: u64| {
+        let value = libc::rlim_t::try_from(value).unwrap_or(libc::RLIM_INFINITY);
+        let rlimit = libc::rlimit {
+            rlim_cur: value,
+            rlim_max: value,
+        };
+        // SAFETY: `rlimit` is a valid struct that lives through the call
+        if unsafe { libc::setrlimit(resource, &raw const rlimit) } == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    };
+    set(libc::RLIMIT_AS, limits.memory)?;
+    set(libc::RLIMIT_CPU, limits.cpu.as_secs().max(1))
+}
+
+/// Only the wall clock time is enforced elsewhere.
+#[cfg(not(unix))]
+#[allow(clippy::unnecessary_wraps)]
+fn limit(_: &Limits) -> io::Result<()> {
+    Ok(())
+}
+
+/// The worker of one request: limits this process, solves the day with the input on stdin and
+/// prints the status code and JSON body for [`serve`].
+///
+/// # Errors
+/// If the limits can not be applied or the answer not printed.
+pub fn work(year: u16, day: u8, limits: &Limits) -> Result<()> {
+    limit(limits).context("Could not limit the worker")?;
+    let (status, json) = match read_input(io::stdin(), MAX_INPUT) {
+        Ok(input) => solve(year, day, &input),
+        Err(response) => response,
+    };
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "{status}")?;
+    stdout.write_all(json.as_bytes())?;
+    Ok(())
+}
+
+/// How many requests are being solved, so there are never more than `max` at once.
+struct Slots {
+    busy: AtomicUsize,
+    max: usize,
+}
+
+/// One request being solved, its slot is free again when this is dropped.
+struct Slot<'a>(&'a Slots);
+
+impl Slots {
+    fn new(max: usize) -> Self {
+        Self {
+            busy: AtomicUsize::new(0),
+            max,
+        }
+    }
+
+    /// `None` if all slots are taken.
+    fn take(&self) -> Option<Slot<'_>> {
+        self.busy
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |busy| {
+                (busy < self.max).then_some(busy + 1)
+            })
+            .ok()
+            .map(|_| Slot(self))
+    }
+}
+
+impl Drop for Slot<'_> {
+    fn drop(&mut self) {
+        self.0.busy.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Reads the input of `request`, solves it with a `worker` and answers it.
+fn handle(
+    mut request: Request,
+    json: Header,
+    limits: &Limits,
+    worker: impl Fn(u16, u8) -> Command,
+) {
+    let (status, response) = match read_input(request.as_reader(), MAX_INPUT) {
+        Ok(body) => respond(
+            request.method().as_str(),
+            request.url(),
+            &body,
+            |year, day, input| in_worker(worker(year, day), limits.wall, input),
+        ),
+        Err(response) => response,
+    };
+    answer(request, json, status, response);
+}
+
+fn answer(request: Request, json: Header, status: u16, response: String) {
+    eprintln!("{} {} -> {status}", request.method(), request.url());
+
+    let response = Response::from_string(response)
+        .with_status_code(status)
+        .with_header(json);
+    if let Err(e) = request.respond(response) {
+        eprintln!("Could not respond: {e}");
+    }
+}
+
+/// Answers requests on `addr`, up to `workers` at once, until the process is stopped. Every
+/// request is solved by the process `worker` returns the command of, which has to call [`work`].
+///
+/// # Errors
+/// If `addr` can not be listened on.
+///
+/// # Panics
+/// Never, the `Content-Type` header is valid.
+pub fn serve(
+    addr: &str,
+    limits: &Limits,
+    workers: usize,
+    worker: impl Fn(u16, u8) -> Command + Sync,
+) -> Result<()> {
+    let server = Server::http(addr).map_err(|e| anyhow!("Could not listen on {addr}: {e}"))?;
+    let json =
+        Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).expect("Valid header");
+    let slots = Slots::new(workers);
+    eprintln!("Listening on http://{addr}, solving up to {workers} requests at once");
+
+    thread::scope(|scope| {
+        for request in server.incoming_requests() {
+            let Some(slot) = slots.take() else {
+                let (status, response) = error(503, format!("All {workers} workers are busy"));
+                answer(request, json.clone(), status, response);
+                continue;
+            };
+            let json = json.clone();
+            let worker = &worker;
+            scope.spawn(move || {
+                handle(request, json, limits, worker);
+                drop(slot);
+            });
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::time::Instant;
+
+    fn json(method: &str, url: &str, body: &str) -> (u16, Value) {
+        let (status, body) = respond(method, url, body, solve);
+        (status, serde_json::from_str(&body).unwrap())
+    }
+
+    #[test]
+    fn solves_registered_days() {
+        // Day 1 of year 1 is registered by the tests of `registry`
+        let (status, solved) = json("POST", "/solve/1/1?pretty", "2 3 4");
+        assert_eq!(status, 200);
+        assert_eq!(solved["day"], 1);
+        assert_eq!(solved["parts"][0]["answer"], "9");
+        assert_eq!(solved["parts"][1]["answer"], "24");
+        assert!(solved["parts"][1]["time_ms"].is_f64());
+
+        let (status, error) = json("POST", "/solve/1/1", "2 x");
+        assert_eq!(status, 422);
+        assert_eq!(error["kind"], "parse_error");
+        assert!(error["error"].as_str().unwrap().contains("invalid digit"));
+    }
+
+    /// Panics on any input that is not a number
+    #[crate::aoc(year = 1, day = 25)]
+    struct Unwrapping;
+
+    impl crate::solver::Solver for Unwrapping {
+        const YEAR: u16 = 1;
+        const DAY: u8 = 25;
+
+        type Input = String;
+
+        fn parse(input: &str) -> Result<String> {
+            Ok(input.to_owned())
+        }
+
+        fn part1(input: &String) -> Result<crate::answer::Answer> {
+            Ok(input.trim().parse::<i64>().unwrap().into())
+        }
+
+        fn part2(input: &String) -> Result<crate::answer::Answer> {
+            Self::part1(input)
+        }
+    }
+
+    #[test]
+    fn survives_panics() {
+        assert_eq!(json("POST", "/solve/1/25", "7").0, 200);
+        let (status, error) = json("POST", "/solve/1/25", "seven");
+        assert_eq!(status, 500);
+        assert!(error["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("1 day 25 panicked: called `Result::unwrap()`"));
+    }
+
+    #[test]
+    fn limits_inputs() {
+        assert_eq!(read_input(&b"1 2 3"[..], 5).unwrap(), "1 2 3");
+        let (status, error) = read_input(&b"1 2 3 4"[..], 5).unwrap_err();
+        assert_eq!(status, 413);
+        assert!(error.contains("longer than 5 bytes"));
+    }
+
+    #[test]
+    fn bounds_output() {
+        let mut pipe = &b"0123456789"[..];
+        assert_eq!(read_bounded(&mut pipe, 4).unwrap(), "0123");
+        assert!(pipe.is_empty());
+        assert_eq!(read_bounded(&b"01"[..], 4).unwrap(), "01");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn stops_workers() {
+        let sh = |script: &str| {
+            let mut command = Command::new("sh");
+            command.args(["-c", script]);
+            command
+        };
+        let wall = Duration::from_secs(5);
+
+        let (status, body) = in_worker(sh("cat >/dev/null; printf '200\\n{}'"), wall, "1 2");
+        assert_eq!((status, body.as_str()), (200, "{}"));
+
+        let start = Instant::now();
+        let (status, body) = in_worker(sh("sleep 10"), Duration::from_millis(100), "");
+        assert_eq!(status, 503);
+        assert!(body.contains("Stopped after 100ms"), "{body}");
+        assert!(start.elapsed() < wall);
+
+        // Logs past `MAX_OUTPUT` are thrown away without the worker blocking on them
+        let flood = "head -c 4000000 /dev/zero >&2; printf '200\\n{}'";
+        let (status, body) = in_worker(sh(flood), wall, "");
+        assert_eq!((status, body.as_str()), (200, "{}"));
+
+        let (status, body) = in_worker(sh("kill -XCPU $$"), wall, "");
+        assert_eq!(status, 503);
+        assert!(body.contains("more memory or CPU time"), "{body}");
+
+        let panicked = "printf \"thread 'main' panicked at src/a.rs:1:2:\\nboom\\nnote: ..\" >&2";
+        let (status, body) = in_worker(sh(&format!("{panicked}; kill -ABRT $$")), wall, "");
+        assert_eq!(status, 500);
+        assert!(body.contains("The worker panicked: boom"), "{body}");
+        let (status, body) = in_worker(sh(&format!("{panicked}; exit 101")), wall, "");
+        assert_eq!(status, 500);
+        assert!(body.contains("The worker panicked: boom"), "{body}");
+
+        // Running out of memory aborts without a panic
+        let (status, body) = in_worker(
+            sh("echo 'memory allocation failed' >&2; kill -ABRT $$"),
+            wall,
+            "",
+        );
+        assert_eq!(status, 503);
+        assert!(body.contains("more memory or CPU time"), "{body}");
+    }
+
+    #[test]
+    fn bounds_workers() {
+        let slots = Slots::new(2);
+        let first = slots.take().unwrap();
+        let second = slots.take().unwrap();
+        assert!(slots.take().is_none());
+
+        drop(first);
+        let third = slots.take().unwrap();
+        assert!(slots.take().is_none());
+        drop((second, third));
+        assert_eq!(slots.busy.load(Ordering::Acquire), 0);
+    }
+
+    #[test]
+    fn rejects_other_requests() {
+        assert_eq!(json("GET", "/solve/1/1", "").0, 405);
+        assert_eq!(json("POST", "/solve/1/2", "").0, 404);
+        assert_eq!(json("POST", "/solve/1", "").0, 404);
+        assert_eq!(json("POST", "/solve/1/x", "").0, 404);
+        assert_eq!(json("POST", "/", "").0, 404);
+    }
+}
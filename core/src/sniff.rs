@@ -0,0 +1,204 @@
+//! Which puzzle an input belongs to, recognized by how its lines look, for inputs with names that
+//! do not tell.
+
+/// Recognizes the inputs of one day.
+#[derive(Debug, Copy, Clone)]
+struct Fingerprint {
+    year: u16,
+    day: u8,
+    matches: fn(&str) -> bool,
+}
+
+/// Every line of `input` is like this.
+fn all_lines(input: &str, line: impl Fn(&str) -> bool) -> bool {
+    !input.trim().is_empty() && input.trim_end().lines().all(line)
+}
+
+fn is_number(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// `a-b,c-d`
+fn is_ranges(line: &str) -> bool {
+    line.split(',').count() == 2
+        && line.split(',').all(|range| {
+            range
+                .split_once('-')
+                .is_some_and(|(a, b)| is_number(a) && is_number(b))
+        })
+}
+
+/// A rectangle of lines, at least two of them.
+fn is_grid(input: &str, cell: impl Fn(u8) -> bool) -> bool {
+    let lines: Vec<_> = input.trim_end().lines().collect();
+    lines.len() > 1
+        && lines.iter().all(|line| line.len() == lines[0].len())
+        && lines.iter().all(|line| line.bytes().all(&cell))
+}
+
+/// By year and day, every input should match at most one of them.
+const FINGERPRINTS: &[Fingerprint] = &[
+    Fingerprint {
+        year: 2022,
+        day: 1,
+        matches: |input| {
+            input.contains("\n\n") && all_lines(input, |l| l.is_empty() || is_number(l))
+        },
+    },
+    Fingerprint {
+        year: 2022,
+        day: 2,
+        matches: |input| {
+            all_lines(input, |l| {
+                matches!(l.as_bytes(), [b'A'..=b'C', b' ', b'X'..=b'Z'])
+            })
+        },
+    },
+    Fingerprint {
+        year: 2022,
+        day: 3,
+        matches: |input| {
+            input.trim_end().lines().count() > 1
+                && all_lines(input, |l| {
+                    l.len() % 2 == 0 && l.bytes().all(|b| b.is_ascii_alphabetic())
+                })
+                && !is_grid(input, |b| b.is_ascii_alphabetic())
+        },
+    },
+    Fingerprint {
+        year: 2022,
+        day: 4,
+        matches: |input| all_lines(input, is_ranges),
+    },
+    Fingerprint {
+        year: 2022,
+        day: 5,
+        matches: |input| {
+            input
+                .lines()
+                .any(|l| l.starts_with("move ") && l.contains(" from ") && l.contains(" to "))
+        },
+    },
+    Fingerprint {
+        year: 2022,
+        day: 6,
+        matches: |input| {
+            input.trim_end().lines().count() == 1
+                && all_lines(input, |l| l.bytes().all(|b| b.is_ascii_lowercase()))
+        },
+    },
+    Fingerprint {
+        year: 2022,
+        day: 7,
+        matches: |input| input.starts_with("$ cd "),
+    },
+    Fingerprint {
+        year: 2022,
+        day: 8,
+        matches: |input| is_grid(input, |b| b.is_ascii_digit()),
+    },
+    Fingerprint {
+        year: 2022,
+        day: 9,
+        matches: |input| {
+            all_lines(input, |l| {
+                l.split_once(' ')
+                    .is_some_and(|(dir, n)| matches!(dir, "R" | "U" | "L" | "D") && is_number(n))
+            })
+        },
+    },
+    Fingerprint {
+        year: 2022,
+        day: 10,
+        matches: |input| all_lines(input, |l| l == "noop" || l.starts_with("addx ")),
+    },
+    Fingerprint {
+        year: 2022,
+        day: 11,
+        matches: |input| input.starts_with("Monkey 0:"),
+    },
+    Fingerprint {
+        year: 2022,
+        day: 12,
+        matches: |input| {
+            is_grid(input, |b| b.is_ascii_lowercase() || b == b'S' || b == b'E')
+                && input.matches('S').count() == 1
+                && input.matches('E').count() == 1
+        },
+    },
+    Fingerprint {
+        year: 2023,
+        day: 1,
+        matches: |input| {
+            all_lines(input, |l| {
+                l.bytes().all(|b| b.is_ascii_alphanumeric())
+                    && l.bytes().any(|b| b.is_ascii_digit())
+            }) && !is_grid(input, |b| b.is_ascii_digit())
+        },
+    },
+    Fingerprint {
+        year: 2023,
+        day: 2,
+        matches: |input| input.starts_with("Game 1: "),
+    },
+    Fingerprint {
+        year: 2023,
+        day: 5,
+        matches: |input| input.starts_with("seeds: "),
+    },
+    Fingerprint {
+        year: 2023,
+        day: 6,
+        matches: |input| input.starts_with("Time:") && input.contains("\nDistance:"),
+    },
+];
+
+/// The puzzles whose inputs look like `input`, as `(year, day)`. Usually one, none if it looks
+/// like nothing known.
+#[must_use]
+pub fn detect(input: &str) -> Vec<(u16, u8)> {
+    FINGERPRINTS
+        .iter()
+        .filter(|f| (f.matches)(input))
+        .map(|f| (f.year, f.day))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_days() {
+        let inputs = [
+            ((2022, 1), "1000\n2000\n\n3000\n"),
+            ((2022, 2), "A Y\nB X\nC Z\n"),
+            (
+                (2022, 3),
+                "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n",
+            ),
+            ((2022, 4), "2-4,6-8\n2-3,4-5\n"),
+            (
+                (2022, 5),
+                "    [D]\n[N] [C]\n 1   2\n\nmove 1 from 2 to 1\n",
+            ),
+            ((2022, 6), "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n"),
+            ((2022, 7), "$ cd /\n$ ls\ndir a\n"),
+            ((2022, 8), "30373\n25512\n65332\n"),
+            ((2022, 9), "R 4\nU 4\nL 3\n"),
+            ((2022, 10), "noop\naddx 3\naddx -5\n"),
+            ((2022, 11), "Monkey 0:\n  Starting items: 79, 98\n"),
+            ((2022, 12), "Sabqponm\nabcryxxl\naccszExk\n"),
+            ((2023, 1), "1abc2\npqr3stu8vwx\ntwo1nine\n"),
+            ((2023, 2), "Game 1: 3 blue, 4 red; 1 red\n"),
+            ((2023, 5), "seeds: 79 14 55 13\n\nseed-to-soil map:\n"),
+            ((2023, 6), "Time:      7  15   30\nDistance:  9  40  200\n"),
+        ];
+        for (day, input) in inputs {
+            assert_eq!(detect(input), [day], "{input}");
+        }
+
+        assert_eq!(detect(""), []);
+        assert_eq!(detect("Hello, World!\n"), []);
+    }
+}
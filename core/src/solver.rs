@@ -0,0 +1,273 @@
+use crate::answer::Answer;
+use crate::cancel;
+use crate::dump;
+use crate::fetch::InputSource;
+use crate::logging;
+use crate::parse;
+use crate::parts::{parts_from_args, Parts};
+use crate::screen::{self, Density};
+use crate::style::{self, Role, Style};
+use crate::verify;
+use anyhow::{anyhow, Result};
+use std::fmt::Write;
+use tracing::debug_span;
+
+/// One day of the puzzle, so its `main` does not have to be written again and the runner can
+/// treat all days the same.
+pub trait Solver: 'static {
+    const YEAR: u16;
+    const DAY: u8;
+    /// The example of the puzzle text, for `aoc run --example`.
+    const EXAMPLE: Option<&'static str> = None;
+
+    type Input;
+
+    /// # Errors
+    /// If the input is malformed.
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    /// # Errors
+    /// If there is no answer for this input.
+    fn part1(input: &Self::Input) -> Result<Answer>;
+
+    /// # Errors
+    /// If there is no answer for this input.
+    fn part2(input: &Self::Input) -> Result<Answer>;
+
+    /// Days with more than two parts can override this.
+    #[must_use]
+    fn parts() -> Parts<Self::Input> {
+        Parts::two(Self::part1, Self::part2)
+    }
+
+    /// The parsed input as JSON, for `--parse-only`. Days whose input can be serialized return
+    /// [`dump::json`] of it.
+    ///
+    /// # Errors
+    /// If the input can not be shown, which is the default.
+    fn dump(_input: &Self::Input) -> Result<String> {
+        Err(anyhow!(
+            "{} day {} can not show its parsed input",
+            Self::YEAR,
+            Self::DAY
+        ))
+    }
+}
+
+/// `2022-day05`, to tell apart what belongs to which day.
+pub(crate) fn label<S: Solver>() -> String {
+    format!("{}-day{:02}", S::YEAR, S::DAY)
+}
+
+/// [`Solver::parse`] in a `parse` span.
+pub(crate) fn parse_traced<S: Solver>(input: &str) -> Result<S::Input> {
+    debug_span!("parse", year = S::YEAR, day = S::DAY).in_scope(|| S::parse(input))
+}
+
+/// Parses the input and solves the parts, in order.
+///
+/// # Errors
+/// If the input does not parse or a selected part does not exist.
+pub fn solve<S: Solver>(input: &str, selection: &[String]) -> Result<Vec<(String, Answer)>> {
+    let input = parse_traced::<S>(&parse::normalize(input))?;
+    solve_parsed::<S>(&input, selection)
+}
+
+/// [`solve`] for an input that is parsed already.
+///
+/// # Errors
+/// If a selected part does not exist.
+pub fn solve_parsed<S: Solver>(
+    input: &S::Input,
+    selection: &[String],
+) -> Result<Vec<(String, Answer)>> {
+    S::parts()
+        .labeled(label::<S>())
+        .run(input, selection)?
+        .into_iter()
+        .map(|r| Ok((r.name, r.answer?)))
+        .collect()
+}
+
+/// Prints where in `input` the error is, if it knows, see [`parse::report`].
+///
+/// Returns the error, for `.map_err(|e| show_source(e, &input))?`.
+#[must_use]
+pub fn show_source(err: anyhow::Error, input: &str) -> anyhow::Error {
+    if let Some(report) = parse::report(&err, input) {
+        eprint!("{report}");
+    }
+    err
+}
+
+/// The `main` of a day: solves the parts selected with `--part` (all by default) for
+/// `input/dayNN.txt` (downloaded on the first run) and prints them as `Part 1: <answer>`. Logs
+/// to stderr up to `--log-level`, and colors the answers as asked with `--no-color` and `--theme`.
+/// With `--verify` they are compared with the known answers in `answers.toml` afterwards, with
+/// `--account <account>` the input and answers of that account are used instead. With
+/// `--parse-only` only the parsed input is printed, see [`Solver::dump`]. Parts that check a
+/// [`cancel::Cancel`] give up after `--timeout`.
+///
+/// # Errors
+/// If the input can not be read or parsed, a part fails, or an answer is wrong with `--verify`.
+pub fn run<S: Solver>() -> Result<()> {
+    run_with::<S>(|source| {
+        let input = source.read()?;
+        parse_traced::<S>(&input).map_err(|e| show_source(e, &input))
+    })
+}
+
+/// [`run`] with another way to get the parsed input from where it comes from, e.g. a cache.
+///
+/// # Errors
+/// If `parse` fails, a part fails, or an answer is wrong with `--verify`.
+pub fn run_with<S: Solver>(parse: impl FnOnce(&InputSource) -> Result<S::Input>) -> Result<()> {
+    logging::init_from_args(std::env::args().skip(1))?;
+    style::init_from_args(std::env::args().skip(1))?;
+    screen::init_from_args(std::env::args().skip(1))?;
+    cancel::init_from_args(std::env::args().skip(1))?;
+    let verify = verify::requested(std::env::args().skip(1))?;
+    let source = InputSource::from_args(S::YEAR, S::DAY, std::env::args().skip(1));
+    let input = parse(&source)?;
+    if dump::requested(std::env::args().skip(1)) {
+        return dump::print::<S>(&input);
+    }
+
+    let answers = solve_parsed::<S>(&input, &parts_from_args(std::env::args().skip(1)))?;
+    print!("{}", styled_answers(&answers));
+
+    if verify {
+        verify::check(&verify::answers_file(source.account()), S::DAY, &answers)?;
+    }
+    Ok(())
+}
+
+/// The end of the `main` of a day whose parts need more than the input, like a recorder, and so
+/// can not use [`run`]: solves the `parts` selected with `--part` (all by default) and prints them,
+/// and checks them with `--verify`, like [`run`].
+///
+/// # Errors
+/// If a selected part does not exist or fails, or an answer is wrong with `--verify`.
+pub fn run_parts<I: ?Sized>(year: u16, day: u8, parts: Parts<I>, input: &I) -> Result<()> {
+    let verify = verify::requested(std::env::args().skip(1))?;
+    let answers = parts
+        .labeled(format!("{year}-day{day:02}"))
+        .run(input, &parts_from_args(std::env::args().skip(1)))?
+        .into_iter()
+        .map(|r| Ok((r.name, r.answer?)))
+        .collect::<Result<Vec<_>>>()?;
+    print!("{}", styled_answers(&answers));
+
+    if verify {
+        let source = InputSource::from_args(year, day, std::env::args().skip(1));
+        verify::check(&verify::answers_file(source.account()), day, &answers)?;
+    }
+    Ok(())
+}
+
+/// One `Part 1: <answer>` line per part, pictures start on the line after the name. Always
+/// plain, this is what the runner reads back.
+#[must_use]
+pub fn format_answers(answers: &[(String, Answer)]) -> String {
+    paint_answers(answers, Style::plain(), Density::Block)
+}
+
+/// [`format_answers`] in the colors of the [`style::current`] style and the [`screen::density`],
+/// for the terminal.
+#[must_use]
+pub fn styled_answers(answers: &[(String, Answer)]) -> String {
+    paint_answers(answers, style::current(), screen::density())
+}
+
+fn paint_answers(answers: &[(String, Answer)], style: Style, density: Density) -> String {
+    let mut out = String::new();
+    for (name, answer) in answers {
+        let name = style.paint(Role::Part, name);
+        if answer.is_picture() {
+            let answer = answer.draw(density);
+            let picture = style.paint(Role::Picture, answer.trim_end_matches('\n'));
+            writeln!(out, "{name}:\n{picture}").unwrap();
+        } else {
+            let answer = style.paint(Role::Answer, &answer.to_string());
+            writeln!(out, "{name}: {answer}").unwrap();
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::screen::Screen;
+    use anyhow::anyhow;
+
+    struct Sum;
+
+    impl Solver for Sum {
+        const YEAR: u16 = 2022;
+        const DAY: u8 = 1;
+
+        type Input = Vec<i64>;
+
+        fn parse(input: &str) -> Result<Vec<i64>> {
+            Ok(input
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<_, _>>()?)
+        }
+
+        fn part1(input: &Vec<i64>) -> Result<Answer> {
+            Ok(input.iter().sum::<i64>().into())
+        }
+
+        fn part2(input: &Vec<i64>) -> Result<Answer> {
+            Ok(input
+                .iter()
+                .max()
+                .copied()
+                .ok_or_else(|| anyhow!("Empty"))?
+                .into())
+        }
+    }
+
+    #[test]
+    fn solves() {
+        assert_eq!(
+            solve::<Sum>("1 5 3", &[]).unwrap(),
+            vec![
+                ("Part 1".to_owned(), Answer::Int(9)),
+                ("Part 2".to_owned(), Answer::Int(5))
+            ]
+        );
+        assert_eq!(
+            solve::<Sum>("1 5 3", &["2".to_owned()]).unwrap(),
+            vec![("Part 2".to_owned(), Answer::Int(5))]
+        );
+
+        solve::<Sum>("", &[]).unwrap_err();
+        solve::<Sum>("x", &[]).unwrap_err();
+    }
+
+    #[test]
+    fn paints_answers() {
+        let answers = [
+            ("Part 1".to_owned(), Answer::Int(9)),
+            ("Part 2".to_owned(), Answer::from("#.\n.#")),
+        ];
+        assert_eq!(format_answers(&answers), "Part 1: 9\nPart 2:\n#.\n.#\n");
+        assert_eq!(
+            paint_answers(&answers, Style::colored(style::Theme::Mono), Density::Block),
+            "\x1b[1mPart 1\x1b[0m: \x1b[1m9\x1b[0m\n\x1b[1mPart 2\x1b[0m:\n#.\n.#\n"
+        );
+
+        let mut screen = Screen::new(2, 2);
+        screen.set(0, 0, true);
+        screen.set(1, 1, true);
+        let answers = [("Part 2".to_owned(), Answer::from(screen))];
+        assert_eq!(format_answers(&answers), "Part 2:\n█ \n █\n");
+        assert_eq!(
+            paint_answers(&answers, Style::plain(), Density::Braille),
+            "Part 2:\n⠑\n"
+        );
+    }
+}
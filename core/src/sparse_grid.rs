@@ -0,0 +1,155 @@
+use crate::point::{BoundingBox, Point2};
+use crate::screen::Screen;
+use std::collections::HashMap;
+
+/// Cells on an unbounded plane, only the occupied ones are stored.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point2, T>,
+    bounds: Option<BoundingBox<2>>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the previous value of the cell.
+    pub fn insert(&mut self, p: Point2, value: T) -> Option<T> {
+        match &mut self.bounds {
+            Some(bounds) => bounds.extend(p),
+            None => self.bounds = Some(BoundingBox::new(p, p)),
+        }
+        self.cells.insert(p, value)
+    }
+
+    /// The bounding box does not shrink when removing cells.
+    pub fn remove(&mut self, p: Point2) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    #[must_use]
+    pub fn get(&self, p: Point2) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point2) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    #[must_use]
+    pub fn contains(&self, p: Point2) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Box around every cell ever inserted, `None` if there were none.
+    #[must_use]
+    pub fn bounds(&self) -> Option<BoundingBox<2>> {
+        self.bounds
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.cells.iter().map(|(p, v)| (*p, v))
+    }
+
+    /// Occupied cells sharing an edge with `p`.
+    pub fn neighbors(&self, p: Point2) -> impl Iterator<Item = (Point2, &T)> {
+        p.neighbors()
+            .filter_map(|n| self.cells.get(&n).map(|v| (n, v)))
+    }
+
+    /// Occupied cells touching `p`, including diagonally.
+    pub fn neighbors_diagonal(&self, p: Point2) -> impl Iterator<Item = (Point2, &T)> {
+        p.neighbors_diagonal()
+            .filter_map(|n| self.cells.get(&n).map(|v| (n, v)))
+    }
+
+    /// Draws the bounding box, with the cells for which `lit` is true switched on. The top left
+    /// pixel is the minimum of the bounding box.
+    ///
+    /// # Panics
+    /// If the bounding box is too large to fit in memory.
+    #[must_use]
+    pub fn render(&self, lit: impl Fn(&T) -> bool) -> Screen {
+        let Some(bounds) = self.bounds else {
+            return Screen::new(0, 0);
+        };
+        let size = |axis: usize| {
+            usize::try_from(bounds.max[axis] - bounds.min[axis] + 1).expect("Grid too large")
+        };
+
+        let mut screen = Screen::new(size(0), size(1));
+        for (p, v) in self.iter() {
+            if lit(v) {
+                let rel = p - bounds.min;
+                let coord = |axis: usize| usize::try_from(rel[axis]).expect("Inside the bounds");
+                screen.set(coord(0), coord(1), true);
+            }
+        }
+        screen
+    }
+}
+
+impl<T> FromIterator<(Point2, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point2, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        for (p, v) in iter {
+            grid.insert(p, v);
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Point;
+
+    #[test]
+    fn grid() {
+        let mut grid: SparseGrid<char> = [(Point([-2, 1]), 'a'), (Point([3, -1]), 'b')]
+            .into_iter()
+            .collect();
+        assert_eq!(grid.insert(Point([0, 0]), 'c'), None);
+        assert_eq!(grid.insert(Point([0, 0]), 'd'), Some('c'));
+
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.get(Point([-2, 1])), Some(&'a'));
+        assert_eq!(
+            grid.bounds(),
+            Some(BoundingBox::new(Point([-2, -1]), Point([3, 1])))
+        );
+
+        assert_eq!(grid.neighbors(Point([-1, 1])).count(), 1);
+        assert_eq!(grid.neighbors_diagonal(Point([-1, 1])).count(), 2);
+
+        assert_eq!(
+            grid.render(|&c| c != 'b').render('#', '.'),
+            "......\n..#...\n#.....\n"
+        );
+
+        grid.remove(Point([3, -1]));
+        assert!(!grid.contains(Point([3, -1])));
+        assert_eq!(grid.render(|_| true).width(), 6);
+    }
+}
@@ -0,0 +1,241 @@
+//! `aoc stats`: one row of numbers per day, to see which solutions are worth optimizing, as a
+//! table or exported as CSV or JSON.
+
+use crate::answer::Answer;
+use crate::chart::Bar;
+use crate::timing::Phases;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Everything known about one day, times in milliseconds. Parse and part times are only known for
+/// days registered with `#[aoc]`, the others only have the total time of their binary.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DayStats {
+    pub year: u16,
+    pub day: u8,
+    pub parse_ms: Option<f64>,
+    pub part1_ms: Option<f64>,
+    pub part2_ms: Option<f64>,
+    pub total_ms: Option<f64>,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub input_bytes: Option<usize>,
+    pub input_lines: Option<usize>,
+    /// Not counting blank lines, of the module and the binary of the day
+    pub code_lines: usize,
+}
+
+impl DayStats {
+    /// The column names of [`DayStats::row`].
+    pub const COLUMNS: [&'static str; 11] = [
+        "year",
+        "day",
+        "parse_ms",
+        "part1_ms",
+        "part2_ms",
+        "total_ms",
+        "part1",
+        "part2",
+        "input_bytes",
+        "input_lines",
+        "code_lines",
+    ];
+
+    #[must_use]
+    pub fn new(year: u16, day: u8) -> Self {
+        Self {
+            year,
+            day,
+            ..Self::default()
+        }
+    }
+
+    /// Times and answers of the phases measured in this process.
+    pub fn set_phases(&mut self, phases: &Phases) {
+        self.parse_ms = Some(millis(phases.parse));
+        self.total_ms = Some(millis(phases.total()));
+        for (n, part) in phases.parts.iter().enumerate() {
+            let answer = part.answer.as_ref().ok().map(Answer::to_string);
+            match n {
+                0 => (self.part1_ms, self.part1) = (Some(millis(part.time)), answer),
+                1 => (self.part2_ms, self.part2) = (Some(millis(part.time)), answer),
+                _ => {}
+            }
+        }
+    }
+
+    /// The answers a day binary printed.
+    pub fn set_answers(&mut self, answers: &[(String, Answer)]) {
+        let find = |name| {
+            answers
+                .iter()
+                .find(|(part, _)| part == name)
+                .map(|(_, answer)| answer.to_string())
+        };
+        self.part1 = find("Part 1");
+        self.part2 = find("Part 2");
+    }
+
+    pub fn set_input(&mut self, input: &str) {
+        self.input_bytes = Some(input.len());
+        self.input_lines = Some(input.lines().count());
+    }
+
+    /// The values in the order of [`DayStats::COLUMNS`], empty if they are not known.
+    #[must_use]
+    pub fn row(&self) -> Vec<String> {
+        let ms = |ms: Option<f64>| ms.map(|ms| format!("{ms:.3}")).unwrap_or_default();
+        let count = |n: Option<usize>| n.map(|n| n.to_string()).unwrap_or_default();
+        vec![
+            self.year.to_string(),
+            self.day.to_string(),
+            ms(self.parse_ms),
+            ms(self.part1_ms),
+            ms(self.part2_ms),
+            ms(self.total_ms),
+            self.part1.clone().unwrap_or_default(),
+            self.part2.clone().unwrap_or_default(),
+            count(self.input_bytes),
+            count(self.input_lines),
+            self.code_lines.to_string(),
+        ]
+    }
+
+    /// The times of the phases stacked, or just the total time if they are not known.
+    #[must_use]
+    pub fn bar(&self) -> Bar {
+        let phases = [
+            ("parse", self.parse_ms),
+            ("part 1", self.part1_ms),
+            ("part 2", self.part2_ms),
+        ];
+        let mut segments: Vec<_> = phases
+            .into_iter()
+            .filter_map(|(phase, ms)| Some((phase.to_owned(), ms?)))
+            .collect();
+        if segments.is_empty() {
+            segments.extend(self.total_ms.map(|ms| ("total".to_owned(), ms)));
+        }
+        Bar {
+            label: self.day.to_string(),
+            segments,
+        }
+    }
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+/// Non-blank lines of `src/dayNN.rs` and `src/bin/dayNN.rs` in the directory of a year.
+#[must_use]
+pub fn code_lines(dir: &Path, day: u8) -> usize {
+    let name = format!("day{day:02}.rs");
+    [dir.join("src").join(&name), dir.join("src/bin").join(&name)]
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .map(|code| code.lines().filter(|l| !l.trim().is_empty()).count())
+        .sum()
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum Format {
+    #[default]
+    Table,
+    Csv,
+    Json,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Format::Table => "table",
+            Format::Csv => "csv",
+            Format::Json => "json",
+        })
+    }
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        [Format::Table, Format::Csv, Format::Json]
+            .into_iter()
+            .find(|format| format.to_string() == s)
+            .ok_or_else(|| anyhow!("Unknown format {s:?}, available are: table, csv, json"))
+    }
+}
+
+/// A header line and one line per day, values with commas, quotes or newlines (pictures) are
+/// quoted.
+#[must_use]
+pub fn to_csv(stats: &[DayStats]) -> String {
+    let line = |values: Vec<String>| {
+        let quoted: Vec<_> = values
+            .into_iter()
+            .map(|v| {
+                if v.contains([',', '"', '\n']) {
+                    format!("\"{}\"", v.replace('"', "\"\""))
+                } else {
+                    v
+                }
+            })
+            .collect();
+        quoted.join(",") + "\n"
+    };
+
+    let header = line(DayStats::COLUMNS.map(str::to_owned).to_vec());
+    header + &stats.iter().map(|s| line(s.row())).collect::<String>()
+}
+
+/// # Errors
+/// Never, all the values can be written as JSON.
+pub fn to_json(stats: &[DayStats]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(stats)? + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats() -> Vec<DayStats> {
+        let mut day = DayStats::new(2022, 10);
+        day.total_ms = Some(1.5);
+        day.set_answers(&[
+            ("Part 1".to_owned(), Answer::Int(13140)),
+            ("Part 2".to_owned(), Answer::from("#.\n.#")),
+        ]);
+        day.set_input("noop\naddx 3\n");
+        day.code_lines = 80;
+        vec![day]
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            to_csv(&stats()),
+            "year,day,parse_ms,part1_ms,part2_ms,total_ms,part1,part2,input_bytes,input_lines,\
+             code_lines\n2022,10,,,,1.500,13140,\"#.\n.#\",12,2,80\n"
+        );
+    }
+
+    #[test]
+    fn json() {
+        let json: serde_json::Value = serde_json::from_str(&to_json(&stats()).unwrap()).unwrap();
+        assert_eq!(json[0]["part1"], "13140");
+        assert_eq!(json[0]["parse_ms"], serde_json::Value::Null);
+        assert_eq!(json[0]["input_lines"], 2);
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!("csv".parse::<Format>().unwrap(), Format::Csv);
+        "xml".parse::<Format>().unwrap_err();
+    }
+}
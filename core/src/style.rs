@@ -0,0 +1,221 @@
+//! Colors for what the days and the runner print, in a theme chosen with `--theme`. Only used
+//! when stdout is a terminal, `NO_COLOR` is not set and `--no-color` is not given, so the output
+//! the runner reads from the days stays plain.
+
+use anyhow::{anyhow, Context, Result};
+use std::fmt;
+use std::io::IsTerminal;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// What a piece of output is, which decides its color.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Role {
+    /// `Part 1`
+    Part,
+    Answer,
+    /// A grid, like the letters on the CRT of 2022 day 10
+    Picture,
+    Time,
+    Error,
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum Theme {
+    #[default]
+    Default,
+    /// Everything green, pictures bright green on black like an old terminal
+    Crt,
+    /// Only bold and dim, for terminals with odd colors
+    Mono,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::Default, Theme::Crt, Theme::Mono];
+
+    /// The SGR parameters of `role`, `None` to leave it as it is.
+    fn sgr(self, role: Role) -> Option<&'static str> {
+        match (self, role) {
+            (Theme::Default, Role::Part) | (Theme::Crt, Role::Part | Role::Time) => Some("32"),
+            (Theme::Default, Role::Answer) => Some("1;93"),
+            (Theme::Default, Role::Picture) => Some("93"),
+            (Theme::Default, Role::Time) => Some("36"),
+            (Theme::Crt, Role::Answer) => Some("1;92"),
+            (Theme::Crt, Role::Picture) => Some("92;40"),
+            (Theme::Default | Theme::Crt, Role::Error) => Some("1;31"),
+            (Theme::Mono, Role::Part | Role::Answer | Role::Error) => Some("1"),
+            (Theme::Mono, Role::Time) => Some("2"),
+            (Theme::Mono, Role::Picture) => None,
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Theme::Default => "default",
+            Theme::Crt => "crt",
+            Theme::Mono => "mono",
+        })
+    }
+}
+
+impl FromStr for Theme {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Theme::ALL
+            .into_iter()
+            .find(|theme| theme.to_string() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = Theme::ALL.iter().map(ToString::to_string).collect();
+                anyhow!("Unknown theme {s:?}, available are: {}", names.join(", "))
+            })
+    }
+}
+
+/// How to print, `theme` is `None` without colors.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct Style {
+    theme: Option<Theme>,
+}
+
+static STYLE: OnceLock<Style> = OnceLock::new();
+
+impl Style {
+    /// No colors at all.
+    #[must_use]
+    pub fn plain() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn colored(theme: Theme) -> Self {
+        Self { theme: Some(theme) }
+    }
+
+    /// Colors in `theme` if stdout is a terminal, and neither `no_color` nor `NO_COLOR` is set.
+    #[must_use]
+    pub fn detect(no_color: bool, theme: Theme) -> Self {
+        let no_color = no_color || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        if no_color || !std::io::stdout().is_terminal() {
+            Self::plain()
+        } else {
+            Self::colored(theme)
+        }
+    }
+
+    /// Reads `--no-color` and `--theme <theme>`, other arguments are ignored.
+    ///
+    /// # Errors
+    /// If the theme is missing or unknown.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut no_color = false;
+        let mut theme = Theme::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--no-color" => no_color = true,
+                "--theme" => theme = args.next().context("--theme needs a name")?.parse()?,
+                _ => {}
+            }
+        }
+
+        Ok(Self::detect(no_color, theme))
+    }
+
+    /// `text` in the color of `role`. Every line is colored on its own, so backgrounds end with
+    /// the line.
+    #[must_use]
+    pub fn paint(&self, role: Role, text: &str) -> String {
+        let Some(sgr) = self.theme.and_then(|theme| theme.sgr(role)) else {
+            return text.to_owned();
+        };
+        text.split('\n')
+            .map(|line| {
+                if line.is_empty() {
+                    String::new()
+                } else {
+                    format!("\x1b[{sgr}m{line}\x1b[0m")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Sets the style [`paint`] uses. Does nothing if it is set already.
+pub fn init(style: Style) {
+    let _already = STYLE.set(style);
+}
+
+/// [`init`] with the style given by `--no-color` and `--theme`.
+///
+/// # Errors
+/// If the theme is missing or unknown.
+pub fn init_from_args(args: impl IntoIterator<Item = String>) -> Result<()> {
+    init(Style::from_args(args)?);
+    Ok(())
+}
+
+/// The style set with [`init`], plain if it was not.
+#[must_use]
+pub fn current() -> Style {
+    STYLE.get().copied().unwrap_or_default()
+}
+
+/// `text` in the color of `role` in the [`current`] style.
+#[must_use]
+pub fn paint(role: Role, text: &str) -> String {
+    current().paint(role, text)
+}
+
+/// How many columns `text` takes in the terminal, without the escape codes of [`paint`].
+#[must_use]
+pub fn width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Up to and including the `m` that ends the code
+            chars.by_ref().find(|&c| c == 'm');
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn style(args: &[&str]) -> Result<Style> {
+        Style::from_args(args.iter().map(|&a| a.to_owned()))
+    }
+
+    #[test]
+    fn paints() {
+        let crt = Style::colored(Theme::Crt);
+        assert_eq!(crt.paint(Role::Answer, "42"), "\x1b[1;92m42\x1b[0m");
+        assert_eq!(
+            crt.paint(Role::Picture, "#.\n.#\n"),
+            "\x1b[92;40m#.\x1b[0m\n\x1b[92;40m.#\x1b[0m\n"
+        );
+        assert_eq!(Style::colored(Theme::Mono).paint(Role::Picture, "#."), "#.");
+        assert_eq!(Style::plain().paint(Role::Error, "FAILED"), "FAILED");
+        assert_eq!(width(&crt.paint(Role::Time, "1.5ms")), 5);
+    }
+
+    #[test]
+    fn args() {
+        assert_eq!(
+            style(&["--no-color", "--theme", "crt"]).unwrap(),
+            Style::plain()
+        );
+        style(&["--theme", "mono", "--part", "1"]).unwrap();
+        let err = style(&["--theme", "neon"]).unwrap_err();
+        assert!(err.to_string().contains("default, crt, mono"), "{err}");
+        style(&["--theme"]).unwrap_err();
+    }
+}
@@ -0,0 +1,311 @@
+use crate::answer::Answer;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const SUBMISSIONS_FILE: &str = "submissions.json";
+
+/// The server does not take another answer for a minute after a wrong one, longer after several.
+const WRONG_ANSWER_TIMEOUT: Duration = Duration::from_mins(1);
+
+/// What the server said about an answer.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Answered too recently, nothing was checked
+    Wait(Duration),
+    /// The part is solved already, nothing was checked
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Reads the verdict from the HTML page the server responds with.
+    ///
+    /// # Errors
+    /// If the page contains none of the known responses.
+    pub fn parse(html: &str) -> Result<Self> {
+        if html.contains("That's the right answer") {
+            Ok(Self::Correct)
+        } else if html.contains("your answer is too high") {
+            Ok(Self::TooHigh)
+        } else if html.contains("your answer is too low") {
+            Ok(Self::TooLow)
+        } else if html.contains("That's not the right answer") {
+            Ok(Self::Wrong)
+        } else if html.contains("You gave an answer too recently") {
+            Ok(Self::Wait(parse_wait(html).unwrap_or(WRONG_ANSWER_TIMEOUT)))
+        } else if html.contains("Did you already complete it?") {
+            Ok(Self::AlreadySolved)
+        } else {
+            Err(anyhow!("Unknown response to the answer:\n{html}"))
+        }
+    }
+
+    /// Whether the server looked at the answer.
+    #[must_use]
+    pub fn checked(self) -> bool {
+        matches!(
+            self,
+            Self::Correct | Self::TooHigh | Self::TooLow | Self::Wrong
+        )
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "wrong, too high"),
+            Self::TooLow => write!(f, "wrong, too low"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::Wait(d) => write!(f, "too early, wait {}s", d.as_secs()),
+            Self::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// `You have 1m 5s left to wait.`
+fn parse_wait(html: &str) -> Option<Duration> {
+    let (before, _) = html.split_once(" left to wait")?;
+    let text = before.rsplit_once("You have ")?.1;
+
+    let mut secs = 0;
+    for token in text.split_whitespace() {
+        secs += if let Some(n) = token.strip_suffix('m') {
+            n.parse::<u64>().ok()? * 60
+        } else {
+            token.strip_suffix('s')?.parse().ok()?
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Attempt {
+    /// As it was sent
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Every answer given so far, by year, day and part, so none is sent twice and the server is not
+/// asked again before it is willing to answer.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Submissions {
+    pub attempts: BTreeMap<u16, BTreeMap<u8, BTreeMap<u8, Vec<Attempt>>>>,
+    /// Seconds since the epoch before which the server takes no answers
+    pub blocked_until: u64,
+}
+
+fn secs(t: SystemTime) -> u64 {
+    t.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+impl Submissions {
+    /// No submissions if the file does not exist yet.
+    ///
+    /// # Errors
+    /// If the file can not be read or parsed.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("Invalid submissions {}", path.display()))
+    }
+
+    /// # Errors
+    /// If the file can not be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+            .with_context(|| format!("Could not write {}", path.display()))
+    }
+
+    #[must_use]
+    pub fn attempts(&self, year: u16, day: u8, part: u8) -> &[Attempt] {
+        self.attempts
+            .get(&year)
+            .and_then(|days| days.get(&day))
+            .and_then(|parts| parts.get(&part))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Checks whether `answer` is worth sending: not known to be wrong and the server is not
+    /// blocking answers right now.
+    ///
+    /// # Errors
+    /// Explains why the answer should not be sent.
+    pub fn check(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &Answer,
+        now: SystemTime,
+    ) -> Result<()> {
+        for attempt in self.attempts(year, day, part) {
+            let Ok(known) = attempt.answer.parse::<Answer>();
+            let ruled_out = match (attempt.verdict, answer, &known) {
+                (Verdict::Correct, ..) => {
+                    return Err(anyhow!("Already solved with {}", attempt.answer));
+                }
+                (Verdict::TooHigh, Answer::Int(value), Answer::Int(known)) => value >= known,
+                (Verdict::TooLow, Answer::Int(value), Answer::Int(known)) => value <= known,
+                (verdict, ..) => verdict.checked() && known == *answer,
+            };
+            if ruled_out {
+                return Err(anyhow!(
+                    "{answer} is wrong, {} was {}",
+                    attempt.answer,
+                    attempt.verdict
+                ));
+            }
+        }
+
+        let wait = self.blocked_until.saturating_sub(secs(now));
+        if wait > 0 {
+            return Err(anyhow!("The server takes no answers for another {wait}s"));
+        }
+
+        Ok(())
+    }
+
+    /// Remembers what the server said and how long it will not take answers.
+    pub fn record(
+        &mut self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &Answer,
+        verdict: Verdict,
+        now: SystemTime,
+    ) {
+        let timeout = match verdict {
+            Verdict::Wait(d) => d,
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong => WRONG_ANSWER_TIMEOUT,
+            Verdict::Correct | Verdict::AlreadySolved => Duration::ZERO,
+        };
+        self.blocked_until = self.blocked_until.max(secs(now + timeout));
+
+        if verdict.checked() {
+            self.attempts
+                .entry(year)
+                .or_default()
+                .entry(day)
+                .or_default()
+                .entry(part)
+                .or_default()
+                .push(Attempt {
+                    answer: answer.to_string(),
+                    verdict,
+                });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verdicts() {
+        let page = |text: &str| format!("<main>\n<article><p>{text}</p></article>\n</main>");
+
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's the right answer!  You are one gold star closer."
+            ))
+            .unwrap(),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's not the right answer; your answer is too high.  If you're stuck, ..."
+            ))
+            .unwrap(),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::parse(&page("That's not the right answer.  If you're stuck, ...")).unwrap(),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 1m 5s left to wait."
+            ))
+            .unwrap(),
+            Verdict::Wait(Duration::from_secs(65))
+        );
+        assert_eq!(parse_wait("You have 1é left to wait."), None);
+        assert_eq!(
+            parse_wait("You have 12s left to wait."),
+            Some(Duration::from_secs(12))
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ))
+            .unwrap(),
+            Verdict::AlreadySolved
+        );
+        Verdict::parse(&page("Puzzle inputs differ by user.")).unwrap_err();
+    }
+
+    #[test]
+    fn rules_out_known_answers() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let later = now + Duration::from_hours(1);
+        let mut subs = Submissions::default();
+
+        subs.check(2022, 1, 1, &100.into(), now).unwrap();
+        subs.record(2022, 1, 1, &100.into(), Verdict::TooHigh, now);
+        subs.check(2022, 1, 1, &50.into(), now).unwrap_err();
+        subs.check(2022, 1, 1, &50.into(), later).unwrap();
+
+        subs.record(2022, 1, 1, &20.into(), Verdict::TooLow, later);
+        subs.record(2022, 1, 1, &"abc".into(), Verdict::Wrong, later);
+        let known = [100, 120, 20, 3].map(Answer::from);
+        for answer in known.iter().chain([&Answer::from("abc")]) {
+            subs.check(2022, 1, 1, answer, later + Duration::from_mins(1))
+                .unwrap_err();
+        }
+        subs.check(2022, 1, 1, &50.into(), later + Duration::from_mins(1))
+            .unwrap();
+        // Other parts are not affected
+        subs.check(2022, 1, 2, &100.into(), later + Duration::from_mins(1))
+            .unwrap();
+
+        subs.record(
+            2022,
+            1,
+            1,
+            &50.into(),
+            Verdict::Wait(Duration::from_secs(30)),
+            later,
+        );
+        assert_eq!(subs.attempts(2022, 1, 1).len(), 3);
+
+        subs.record(
+            2022,
+            1,
+            1,
+            &50.into(),
+            Verdict::Correct,
+            later + Duration::from_mins(1),
+        );
+        let err = subs
+            .check(2022, 1, 1, &51.into(), later + Duration::from_mins(1))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Already solved with 50");
+    }
+}
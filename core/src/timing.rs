@@ -0,0 +1,96 @@
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::heap::{self, HeapUsage};
+use crate::registry::Solution;
+use std::time::{Duration, Instant};
+
+/// How long a registered day took to parse its input and to solve each part, to see which of them
+/// is worth optimizing. Also how they used the heap, if [`heap::Counting`] is installed.
+#[derive(Debug)]
+pub struct Phases {
+    pub parse: Duration,
+    pub parse_heap: Option<HeapUsage>,
+    pub parts: Vec<TimedPart>,
+}
+
+#[derive(Debug)]
+pub struct TimedPart {
+    pub name: &'static str,
+    pub answer: Result<Answer, AocError>,
+    pub time: Duration,
+    pub heap: Option<HeapUsage>,
+}
+
+impl Phases {
+    /// Parses `input` once and solves both parts with it, timing each step on its own.
+    ///
+    /// # Errors
+    /// If the input does not parse, errors of the parts are in [`Phases::parts`].
+    pub fn measure(solution: &Solution, input: &str) -> Result<Self, AocError> {
+        let ((parsed, parse), parse_heap) = heap::measure(|| timed(|| solution.parse(input)));
+        let parsed = parsed?;
+
+        let part = |name, solve: &dyn Fn() -> Result<Answer, AocError>| {
+            let ((answer, time), heap) = heap::measure(|| timed(solve));
+            TimedPart {
+                name,
+                answer,
+                time,
+                heap,
+            }
+        };
+        let parts = vec![
+            part("Part 1", &|| solution.part1(&*parsed)),
+            part("Part 2", &|| solution.part2(&*parsed)),
+        ];
+
+        Ok(Self {
+            parse,
+            parse_heap,
+            parts,
+        })
+    }
+
+    /// Parsing and all parts together.
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.parse + self.parts.iter().map(|p| p.time).sum::<Duration>()
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::solution;
+
+    #[test]
+    fn measures_phases() {
+        // Registered by the tests of `registry`
+        let solution = solution(1, 1).unwrap();
+
+        let phases = Phases::measure(solution, "2 3 4").unwrap();
+        let answers: Vec<_> = phases
+            .parts
+            .iter()
+            .map(|p| (p.name, p.answer.clone().unwrap()))
+            .collect();
+        assert_eq!(
+            answers,
+            [("Part 1", Answer::Int(9)), ("Part 2", Answer::Int(24))]
+        );
+        assert!(phases.total() >= phases.parse);
+        // The tests of `core` count allocations
+        assert!(phases.parse_heap.unwrap().allocations > 0);
+
+        assert!(matches!(
+            Phases::measure(solution, "2 x"),
+            Err(AocError::ParseError { .. })
+        ));
+    }
+}
@@ -0,0 +1,258 @@
+use crate::answer::Answer;
+use anyhow::{anyhow, ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Next to `Cargo.toml` of every year.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// [`ANSWERS_FILE`] for the default account, `answers-<account>.toml` for the inputs of others.
+#[must_use]
+pub fn answers_file(account: Option<&str>) -> PathBuf {
+    match account {
+        Some(account) => PathBuf::from(format!("answers-{account}.toml")),
+        None => PathBuf::from(ANSWERS_FILE),
+    }
+}
+
+/// Known correct answers of one year, as `[dayNN]` tables from part name to answer.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers {
+    days: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Mismatch {
+    pub part: String,
+    pub expected: String,
+    /// `None` if the day printed no answer for the part
+    pub got: Option<String>,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let sep = |s: &str| if s.contains('\n') { "\n" } else { " " };
+        match &self.got {
+            Some(got) => write!(
+                f,
+                "{}: expected{}{}, got{}{}",
+                self.part,
+                sep(&self.expected),
+                self.expected,
+                sep(got),
+                got
+            ),
+            None => write!(f, "{}: no answer", self.part),
+        }
+    }
+}
+
+/// Outcome of comparing a day against its known answers.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Verification {
+    /// There are no known answers for the day
+    Unknown,
+    /// Number of parts that matched
+    Correct(usize),
+    Wrong(Vec<Mismatch>),
+}
+
+/// Pictures are printed with trailing spaces, which editors like to strip from the TOML.
+fn normalize(answer: &str) -> String {
+    answer
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn key(day: u8) -> String {
+    format!("day{day:02}")
+}
+
+impl Answers {
+    /// No answers if the file does not exist yet.
+    ///
+    /// # Errors
+    /// If the file can not be read or parsed.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("Invalid answers {}", path.display()))
+    }
+
+    /// # Errors
+    /// If the file can not be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, toml::to_string_pretty(self)?)
+            .with_context(|| format!("Could not write {}", path.display()))
+    }
+
+    #[must_use]
+    pub fn day(&self, day: u8) -> Option<&BTreeMap<String, String>> {
+        self.days.get(&key(day))
+    }
+
+    /// Sets the known answers of these parts, other parts of the day are kept.
+    pub fn set(&mut self, day: u8, answers: &[(String, Answer)]) {
+        self.days.entry(key(day)).or_default().extend(
+            answers
+                .iter()
+                .map(|(part, answer)| (part.clone(), normalize(&answer.to_string()))),
+        );
+    }
+
+    /// Compares what a day printed (see [`parse_answers`](crate::days::parse_answers)) with the
+    /// known answers. Parts without a known answer are not checked.
+    #[must_use]
+    pub fn verify(&self, day: u8, got: &[(String, Answer)]) -> Verification {
+        let Some(expected) = self.day(day) else {
+            return Verification::Unknown;
+        };
+
+        let mismatches: Vec<Mismatch> = expected
+            .iter()
+            .filter_map(|(part, expected)| {
+                let got = got
+                    .iter()
+                    .find(|(p, _)| p == part)
+                    .map(|(_, answer)| normalize(&answer.to_string()));
+                (got.as_ref() != Some(&normalize(expected))).then(|| Mismatch {
+                    part: part.clone(),
+                    expected: expected.clone(),
+                    got,
+                })
+            })
+            .collect();
+
+        if mismatches.is_empty() {
+            Verification::Correct(expected.len())
+        } else {
+            Verification::Wrong(mismatches)
+        }
+    }
+}
+
+/// Whether a day binary is run with `--verify`, to compare its answers with the known ones after
+/// solving. Other arguments are ignored.
+///
+/// # Errors
+/// If `--input` is given too, the known answers are those of the real input.
+pub fn requested(args: impl IntoIterator<Item = String>) -> Result<bool> {
+    let args: Vec<_> = args.into_iter().collect();
+    let verify = args.iter().any(|a| a == "--verify");
+    ensure!(
+        !verify || !args.iter().any(|a| a == "--input"),
+        "--verify compares with the answers of the real input, it can not be used with --input"
+    );
+    Ok(verify)
+}
+
+/// Compares the answers of a day with the known ones in `path`, for `--verify`. Says how many
+/// were correct on stderr, so stdout only has the answers.
+///
+/// # Errors
+/// If an answer is wrong or missing, showing what was expected and what it got, or there are no
+/// known answers for the day.
+pub fn check(path: &Path, day: u8, got: &[(String, Answer)]) -> Result<()> {
+    match Answers::load(path)?.verify(day, got) {
+        Verification::Unknown => Err(anyhow!(
+            "There are no known answers for day {day} in {}, store them with --bless",
+            path.display()
+        )),
+        Verification::Correct(n) => {
+            eprintln!("Day {day}: {n} correct");
+            Ok(())
+        }
+        Verification::Wrong(mismatches) => {
+            let mismatches: Vec<_> = mismatches.iter().map(|m| format!("  {m}")).collect();
+            Err(anyhow!(
+                "Day {day} gave wrong answers:\n{}",
+                mismatches.join("\n")
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(pairs: &[(&str, &str)]) -> Vec<(String, Answer)> {
+        pairs
+            .iter()
+            .map(|&(part, answer)| (part.to_owned(), answer.into()))
+            .collect()
+    }
+
+    #[test]
+    fn verifies() {
+        let mut known = Answers::default();
+        known.set(10, &answers(&[("Part 1", "13140"), ("Part 2", "## \n#  ")]));
+        assert_eq!(known.verify(1, &[]), Verification::Unknown);
+
+        assert_eq!(
+            known.verify(10, &answers(&[("Part 1", "13140"), ("Part 2", "##\n#")])),
+            Verification::Correct(2)
+        );
+        assert_eq!(
+            known.verify(10, &answers(&[("Part 1", "13141")])),
+            Verification::Wrong(vec![
+                Mismatch {
+                    part: "Part 1".into(),
+                    expected: "13140".into(),
+                    got: Some("13141".into()),
+                },
+                Mismatch {
+                    part: "Part 2".into(),
+                    expected: "##\n#".into(),
+                    got: None,
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn checks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("answers.toml");
+        let mut known = Answers::default();
+        known.set(8, &answers(&[("Part 1", "21"), ("Part 2", "8")]));
+        known.save(&path).unwrap();
+
+        check(&path, 8, &answers(&[("Part 1", "21"), ("Part 2", "8")])).unwrap();
+        let err = check(&path, 8, &answers(&[("Part 1", "21"), ("Part 2", "9")])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Day 8 gave wrong answers:\n  Part 2: expected 8, got 9"
+        );
+        check(&path, 9, &[]).unwrap_err();
+    }
+
+    #[test]
+    fn requests() {
+        let args = |args: &[&str]| requested(args.iter().map(|&a| a.to_owned()));
+        assert!(!args(&["--part", "1"]).unwrap());
+        assert!(args(&["--verify"]).unwrap());
+        args(&["--verify", "--input", "example.txt"]).unwrap_err();
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut known = Answers::default();
+        known.set(1, &answers(&[("Part 1", "24000"), ("Part 2", "45000")]));
+        known.set(10, &answers(&[("Part 2", "##\n#")]));
+
+        let text = toml::to_string_pretty(&known).unwrap();
+        assert!(text.contains("[day01]\n\"Part 1\" = \"24000\""), "{text}");
+        assert_eq!(toml::from_str::<Answers>(&text).unwrap(), known);
+    }
+}
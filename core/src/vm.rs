@@ -0,0 +1,169 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fmt::Debug;
+
+/// An instruction set for [`Machine`].
+pub trait Instruction: Copy + Debug {
+    type Registers: Default + Debug;
+
+    /// Decodes one line of the program.
+    ///
+    /// # Errors
+    /// If the line is no valid instruction.
+    fn decode(line: &str) -> Result<Self>;
+
+    /// How many cycles the instruction takes, its effect happens at the end of the last one.
+    fn cycles(&self) -> u8;
+
+    fn execute(&self, regs: &mut Self::Registers);
+}
+
+/// Gets to look at the registers during every cycle, before the cycle's instruction finishes.
+pub trait Peripheral<R> {
+    fn tick(&mut self, cycle: usize, regs: &R);
+}
+
+/// No peripherals attached.
+impl<R> Peripheral<R> for () {
+    fn tick(&mut self, _cycle: usize, _regs: &R) {}
+}
+
+/// # Errors
+/// If any line is no valid instruction.
+pub fn decode_program<I: Instruction>(src: &str) -> Result<Vec<I>> {
+    src.lines()
+        .enumerate()
+        .map(|(idx, line)| I::decode(line).with_context(|| format!("Line {}", idx + 1)))
+        .collect()
+}
+
+/// Runs a program one clock cycle at a time.
+#[derive(Debug, Serialize)]
+#[serde(bound = "I: Serialize, I::Registers: Serialize")]
+pub struct Machine<I: Instruction> {
+    pub regs: I::Registers,
+    /// Instruction in progress and how many cycles it still needs
+    waiting: Option<(u8, I)>,
+    ip: usize,
+    cycle: usize,
+    #[serde(skip)]
+    program: Vec<I>,
+}
+
+impl<I: Instruction> Machine<I> {
+    #[must_use]
+    pub fn new(program: Vec<I>) -> Self {
+        Self {
+            regs: I::Registers::default(),
+            waiting: None,
+            ip: 0,
+            cycle: 0,
+            program,
+        }
+    }
+
+    /// Number of completed cycles.
+    #[must_use]
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    /// Runs one cycle.
+    ///
+    /// # Errors
+    /// If the program has ended.
+    pub fn step(&mut self) -> Result<()> {
+        self.step_with(&mut ())
+    }
+
+    /// Runs one cycle, letting the peripheral see the registers during it.
+    ///
+    /// # Errors
+    /// If the program has ended.
+    pub fn step_with(&mut self, peripheral: &mut impl Peripheral<I::Registers>) -> Result<()> {
+        let (remaining, inst) = if let Some(waiting) = self.waiting.take() {
+            waiting
+        } else {
+            let inst = *self.program.get(self.ip).context("Fell of the program")?;
+            (inst.cycles(), inst)
+        };
+
+        peripheral.tick(self.cycle, &self.regs);
+        self.cycle += 1;
+
+        if remaining > 1 {
+            self.waiting = Some((remaining - 1, inst));
+        } else {
+            inst.execute(&mut self.regs);
+            self.ip += 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    enum Inst {
+        Inc,
+        Slow(i64),
+    }
+
+    impl Instruction for Inst {
+        type Registers = i64;
+
+        fn decode(line: &str) -> Result<Self> {
+            match line.split_once(' ') {
+                None if line == "inc" => Ok(Self::Inc),
+                Some(("slow", val)) => Ok(Self::Slow(val.parse()?)),
+                _ => Err(anyhow!("Invalid instruction {line:?}")),
+            }
+        }
+
+        fn cycles(&self) -> u8 {
+            match self {
+                Self::Inc => 1,
+                Self::Slow(_) => 3,
+            }
+        }
+
+        fn execute(&self, regs: &mut i64) {
+            match self {
+                Self::Inc => *regs += 1,
+                Self::Slow(val) => *regs += val,
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct Trace(Vec<(usize, i64)>);
+
+    impl Peripheral<i64> for Trace {
+        fn tick(&mut self, cycle: usize, regs: &i64) {
+            self.0.push((cycle, *regs));
+        }
+    }
+
+    #[test]
+    fn runs_program() {
+        let program = decode_program("inc\nslow 10\ninc").unwrap();
+        let mut m = Machine::<Inst>::new(program);
+        let mut trace = Trace::default();
+
+        while m.step_with(&mut trace).is_ok() {}
+
+        assert_eq!(m.regs, 12);
+        assert_eq!(m.cycle(), 5);
+        assert_eq!(trace.0, vec![(0, 0), (1, 1), (2, 1), (3, 1), (4, 11)]);
+    }
+
+    #[test]
+    fn decode_errors() {
+        let err = decode_program::<Inst>("inc\nfoo").unwrap_err();
+        assert_eq!(format!("{err:#}"), "Line 2: Invalid instruction \"foo\"");
+    }
+}
@@ -0,0 +1,147 @@
+//! For `aoc watch`: waits for the code or the input of a day to change, and tells how its answers
+//! changed since the last run.
+
+use crate::answer::Answer;
+use crate::style::{self, Role};
+use anyhow::{Context, Result};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher as _};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+/// Editors write a file in several steps, changes this close together are taken as one.
+const SETTLE: Duration = Duration::from_millis(200);
+
+/// Watches files and directories, ignoring changes to other files than the ones `relevant` picks.
+pub struct Watcher {
+    /// Stops watching when dropped
+    _inner: RecommendedWatcher,
+    events: Receiver<notify::Result<notify::Event>>,
+    relevant: Box<dyn Fn(&Path) -> bool>,
+}
+
+impl Watcher {
+    /// Directories are watched with everything in them.
+    ///
+    /// # Errors
+    /// If a path can not be watched, e.g. because it does not exist.
+    pub fn new(paths: &[PathBuf], relevant: impl Fn(&Path) -> bool + 'static) -> Result<Self> {
+        let (tx, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx)?;
+        for path in paths {
+            watcher
+                .watch(path, RecursiveMode::Recursive)
+                .with_context(|| format!("Could not watch {}", path.display()))?;
+        }
+
+        Ok(Self {
+            _inner: watcher,
+            events,
+            relevant: Box::new(relevant),
+        })
+    }
+
+    /// Blocks until a relevant file changed, and returns the ones that did.
+    ///
+    /// # Errors
+    /// If watching failed.
+    pub fn wait(&self) -> Result<Vec<PathBuf>> {
+        let mut changed = self.changed(self.events.recv()?)?;
+        loop {
+            match self.events.recv_timeout(SETTLE) {
+                Ok(event) => changed.extend(self.changed(event)?),
+                Err(_) if changed.is_empty() => changed = self.changed(self.events.recv()?)?,
+                Err(_) => break,
+            }
+        }
+
+        changed.sort();
+        changed.dedup();
+        Ok(changed)
+    }
+
+    /// The relevant files `event` changed, reading them does not count.
+    fn changed(&self, event: notify::Result<notify::Event>) -> Result<Vec<PathBuf>> {
+        let event = event?;
+        if matches!(event.kind, EventKind::Access(_)) {
+            return Ok(vec![]);
+        }
+        Ok(event
+            .paths
+            .into_iter()
+            .filter(|path| (self.relevant)(path))
+            .collect())
+    }
+}
+
+/// The answers of the new run, every one marked with how it differs from the old one.
+#[must_use]
+pub fn diff(old: &[(String, Answer)], new: &[(String, Answer)]) -> String {
+    let mut lines = vec![];
+    for (part, answer) in new {
+        let before = old.iter().find(|(p, _)| p == part).map(|(_, a)| a);
+        let sep = if answer.is_picture() { "\n" } else { " " };
+        let shown = style::paint(Role::Answer, &answer.to_string());
+        let change = match before {
+            Some(before) if before == answer => "(unchanged)".to_owned(),
+            Some(before) if before.is_picture() || answer.is_picture() => "(changed)".to_owned(),
+            Some(before) => format!("(was {before})"),
+            None => "(new)".to_owned(),
+        };
+        lines.push(format!(
+            "{}:{sep}{shown} {change}\n",
+            style::paint(Role::Part, part)
+        ));
+    }
+    for (part, _) in old {
+        if !new.iter().any(|(p, _)| p == part) {
+            lines.push(format!("{part}: {}\n", style::paint(Role::Error, "(gone)")));
+        }
+    }
+    lines.concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(pairs: &[(&str, i128)]) -> Vec<(String, Answer)> {
+        pairs
+            .iter()
+            .map(|&(part, n)| (part.to_owned(), Answer::Int(n)))
+            .collect()
+    }
+
+    #[test]
+    fn diffs() {
+        assert_eq!(diff(&[], &answers(&[("Part 1", 24)])), "Part 1: 24 (new)\n");
+        assert_eq!(
+            diff(
+                &answers(&[("Part 1", 24), ("Part 2", 45)]),
+                &answers(&[("Part 1", 24), ("Part 2", 46)])
+            ),
+            "Part 1: 24 (unchanged)\nPart 2: 46 (was 45)\n"
+        );
+        assert_eq!(
+            diff(
+                &answers(&[("Part 1", 24), ("Part 2", 45)]),
+                &answers(&[("Part 1", 24)])
+            ),
+            "Part 1: 24 (unchanged)\nPart 2: (gone)\n"
+        );
+    }
+
+    #[test]
+    fn sees_changes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        let watcher = Watcher::new(std::slice::from_ref(&dir), |path| {
+            path.extension().is_some_and(|e| e == "rs")
+        })
+        .unwrap();
+
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+        std::fs::write(dir.join("day01.rs"), "fn main() {}").unwrap();
+        assert_eq!(watcher.wait().unwrap(), [dir.join("day01.rs")]);
+    }
+}
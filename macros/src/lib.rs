@@ -0,0 +1,64 @@
+#![warn(clippy::pedantic)]
+
+//! `#[aoc(year = 2022, day = 11)]`, which registers a day with the runner of `aoc-core`.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, LitInt, LitStr};
+
+/// Registers the annotated `Solver` as the solution of a day, so it shows up in
+/// `aoc_core::registry::solutions()` without being listed anywhere by hand.
+///
+/// The year and day have to match the ones of the `Solver`, which is checked at compile time.
+/// Other implementations of the same day are registered with a `name`, as in
+/// `#[aoc(year = 2022, day = 3, name = "bits")]`.
+#[proc_macro_attribute]
+pub fn aoc(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut year: Option<u16> = None;
+    let mut day: Option<u8> = None;
+    let mut implementation: Option<LitStr> = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("year") {
+            year = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            Ok(())
+        } else if meta.path.is_ident("day") {
+            let lit = meta.value()?.parse::<LitInt>()?;
+            let value = lit.base10_parse()?;
+            if !(1..=25).contains(&value) {
+                return Err(syn::Error::new(lit.span(), "days go from 1 to 25"));
+            }
+            day = Some(value);
+            Ok(())
+        } else if meta.path.is_ident("name") {
+            implementation = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("expected `year`, `day` or `name`"))
+        }
+    });
+    parse_macro_input!(args with parser);
+
+    let input = parse_macro_input!(item as DeriveInput);
+    let (Some(year), Some(day)) = (year, day) else {
+        return syn::Error::new_spanned(&input.ident, "#[aoc] needs a `year` and a `day`")
+            .to_compile_error()
+            .into();
+    };
+
+    let name = &input.ident;
+    let named = implementation.map(|implementation| quote! { .named(#implementation) });
+    quote! {
+        #input
+
+        const _: () = assert!(
+            <#name as ::aoc_core::solver::Solver>::YEAR == #year
+                && <#name as ::aoc_core::solver::Solver>::DAY == #day,
+            "#[aoc] does not match the year and day of the Solver",
+        );
+
+        ::aoc_core::inventory::submit! {
+            ::aoc_core::registry::Solution::new::<#name>() #named
+        }
+    }
+    .into()
+}
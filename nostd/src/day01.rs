@@ -0,0 +1,50 @@
+//! Calorie counting.
+
+use crate::Error;
+
+/// The calories each elf carries, elves are separated by blank lines.
+pub fn totals(input: &str) -> impl Iterator<Item = Result<usize, Error>> + '_ {
+    input
+        .split("\n\n")
+        .filter(|elf| !elf.trim().is_empty())
+        .map(|elf| {
+            elf.lines()
+                .map(|line| line.parse::<usize>().map_err(|_| Error::NotANumber))
+                .sum()
+        })
+}
+
+pub fn part1(totals: impl IntoIterator<Item = usize>) -> Option<usize> {
+    totals.into_iter().max()
+}
+
+/// The three largest totals, kept as they come instead of sorting all of them.
+pub fn part2(totals: impl IntoIterator<Item = usize>) -> Option<usize> {
+    let mut top = [None; 3];
+    for total in totals {
+        if let Some(smallest) = top.iter_mut().min() {
+            if Some(total) > *smallest {
+                *smallest = Some(total);
+            }
+        }
+    }
+    top.into_iter().sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn example() {
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+        let elves: Vec<_> = totals(input).collect::<Result<_, _>>().unwrap();
+        assert_eq!(elves, [6000, 4000, 11000, 24000, 10000]);
+        assert_eq!(part1(elves.iter().copied()), Some(24_000));
+        assert_eq!(part2(elves.iter().copied()), Some(45_000));
+
+        assert_eq!(part2([1, 2]), None);
+        assert_eq!(totals("1\nx\n").next(), Some(Err(Error::NotANumber)));
+    }
+}
@@ -0,0 +1,171 @@
+//! Rock paper scissors.
+
+use crate::Error;
+use Outcome::{Draw, Lose, Win};
+use Rps::{Paper, Rock, Scissors};
+
+/// The second column of the strategy guide, which part 1 and 2 read differently.
+pub trait FromXyz: Sized {
+    /// `None` if the letter is not one of `X`, `Y` and `Z`.
+    fn from_xyz(letter: &str) -> Option<Self>;
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Outcome {
+    Win,
+    Draw,
+    Lose,
+}
+
+impl Outcome {
+    /// The outcome for the other player.
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        match self {
+            Win => Lose,
+            Draw => Draw,
+            Lose => Win,
+        }
+    }
+
+    #[must_use]
+    pub fn score(self) -> u32 {
+        match self {
+            Win => 6,
+            Draw => 3,
+            Lose => 0,
+        }
+    }
+}
+
+impl FromXyz for Outcome {
+    fn from_xyz(letter: &str) -> Option<Self> {
+        match letter {
+            "X" => Some(Lose),
+            "Y" => Some(Draw),
+            "Z" => Some(Win),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rps {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Rps {
+    pub const ALL: [Self; 3] = [Rock, Paper, Scissors];
+
+    #[must_use]
+    pub fn play(self, other: Self) -> Outcome {
+        match (self, other) {
+            (Rock, Scissors) | (Scissors, Paper) | (Paper, Rock) => Win,
+            (a, b) if a == b => Draw,
+            _ => Lose,
+        }
+    }
+
+    #[must_use]
+    pub fn score_game(self, other: Self) -> u32 {
+        self.score() + self.play(other).score()
+    }
+
+    #[must_use]
+    pub fn score(self) -> u32 {
+        match self {
+            Rock => 1,
+            Paper => 2,
+            Scissors => 3,
+        }
+    }
+
+    /// What to play against `self` to get `outcome`.
+    ///
+    /// # Panics
+    /// Never, every outcome is possible.
+    #[must_use]
+    pub fn choose_to_get_outcome(self, outcome: Outcome) -> Self {
+        // This might look like I was lazy... and that's because I was
+        for choice in Self::ALL {
+            if choice.play(self) == outcome {
+                return choice;
+            }
+        }
+
+        unreachable!("There should always be a way to get any outcome")
+    }
+
+    /// `None` if the letter is not one of `A`, `B` and `C`.
+    #[must_use]
+    pub fn from_abc(letter: &str) -> Option<Self> {
+        match letter {
+            "A" => Some(Rock),
+            "B" => Some(Paper),
+            "C" => Some(Scissors),
+            _ => None,
+        }
+    }
+}
+
+impl FromXyz for Rps {
+    fn from_xyz(letter: &str) -> Option<Self> {
+        match letter {
+            "X" => Some(Rock),
+            "Y" => Some(Paper),
+            "Z" => Some(Scissors),
+            _ => None,
+        }
+    }
+}
+
+/// The games of the guide one by one, errors point at the first byte of the broken letter.
+pub fn games<T: FromXyz>(input: &str) -> impl Iterator<Item = Result<(Rps, T), Error>> + '_ {
+    input.trim().lines().map(|line| {
+        let unexpected = |s: &str| Error::Unexpected(s.bytes().next().unwrap_or(b'\n'));
+        let (a, b) = line.split_once(' ').ok_or_else(|| unexpected(line))?;
+        Ok((
+            Rps::from_abc(a).ok_or_else(|| unexpected(a))?,
+            T::from_xyz(b).ok_or_else(|| unexpected(b))?,
+        ))
+    })
+}
+
+pub fn part1(games: impl IntoIterator<Item = (Rps, Rps)>) -> u32 {
+    games
+        .into_iter()
+        .map(|(elf, santa)| santa.score_game(elf))
+        .sum()
+}
+
+pub fn part2(games: impl IntoIterator<Item = (Rps, Outcome)>) -> u32 {
+    games
+        .into_iter()
+        .map(|(elf, outcome)| (elf, elf.choose_to_get_outcome(outcome)))
+        .map(|(elf, santa)| santa.score_game(elf))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "A Y\nB X\nC Z\n";
+
+    #[test]
+    fn example() {
+        let shapes = games::<Rps>(EXAMPLE).map(Result::unwrap);
+        assert_eq!(part1(shapes), 15);
+        let outcomes = games::<Outcome>(EXAMPLE).map(Result::unwrap);
+        assert_eq!(part2(outcomes), 12);
+
+        assert_eq!(
+            games::<Rps>("A Y\nD X\n").nth(1),
+            Some(Err(Error::Unexpected(b'D')))
+        );
+    }
+}
@@ -0,0 +1,132 @@
+//! Rucksack reorganization, with every compartment as a bit set of priorities.
+
+use crate::Error;
+use alloc::vec::Vec;
+
+/// `a..=z` are `1..=26`, `A..=Z` are `27..=52`.
+#[must_use]
+pub fn priority(item: u8) -> Option<u8> {
+    match item {
+        c @ b'a'..=b'z' => Some(c - b'a' + 1),
+        c @ b'A'..=b'Z' => Some(c - b'A' + 27),
+        _ => None,
+    }
+}
+
+/// The items as a bit set of their priorities, bit 0 for anything that is no item.
+#[must_use]
+pub fn priority_mask(items: &[u8]) -> u64 {
+    items
+        .iter()
+        .fold(0, |mask, &item| mask | 1 << priority(item).unwrap_or(0))
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Compartment(u64);
+
+impl Compartment {
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    #[must_use]
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// The priority of the only item in here.
+    ///
+    /// # Errors
+    /// If there is not exactly one.
+    pub fn single_item(self) -> Result<u32, Error> {
+        let ones = self.0.count_ones();
+        if ones == 1 {
+            Ok(self.0.trailing_zeros())
+        } else {
+            Err(Error::NotOneItem(ones))
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Backpack(pub Compartment, pub Compartment);
+
+impl Backpack {
+    /// A line of the input, `mask` turns each half into a bit set like [`priority_mask`].
+    ///
+    /// # Errors
+    /// If the line is not of an even number of letters.
+    pub fn from_line(line: &str, mask: impl Fn(&[u8]) -> u64) -> Result<Self, Error> {
+        if !line.len().is_multiple_of(2) {
+            return Err(Error::OddLength(line.len()));
+        }
+
+        let (l, r) = line.as_bytes().split_at(line.len() / 2);
+        let (l, r) = (mask(l), mask(r));
+        if (l | r) & 1 == 0 {
+            Ok(Self(Compartment(l), Compartment(r)))
+        } else {
+            let other = line.bytes().find(|&b| priority(b).is_none());
+            Err(Error::Unexpected(other.unwrap_or_default()))
+        }
+    }
+}
+
+/// # Errors
+/// If a line is not of an even number of letters.
+pub fn parse(input: &str) -> Result<Vec<Backpack>, Error> {
+    parse_with(input, priority_mask)
+}
+
+/// [`parse`] with another way to turn items into bit sets, like a vectorized one.
+///
+/// # Errors
+/// If a line is not of an even number of letters.
+pub fn parse_with(input: &str, mask: impl Fn(&[u8]) -> u64) -> Result<Vec<Backpack>, Error> {
+    input
+        .lines()
+        .map(|line| Backpack::from_line(line, &mask))
+        .collect()
+}
+
+/// # Errors
+/// If the compartments of a backpack do not have exactly one item in common.
+pub fn part1(bp: &[Backpack]) -> Result<u32, Error> {
+    bp.iter().map(|b| b.0.intersection(b.1).single_item()).sum()
+}
+
+/// # Errors
+/// If a group of three does not have exactly one item in common.
+pub fn part2(bp: &[Backpack]) -> Result<u32, Error> {
+    bp.chunks_exact(3)
+        .map(|group| {
+            group
+                .iter()
+                .map(|elf| elf.0.union(elf.1))
+                .fold(Compartment(!0), Compartment::intersection)
+                .single_item()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n\
+                     PmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n\
+                     ttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw\n";
+        let backpacks = parse(input).unwrap();
+        assert_eq!(part1(&backpacks), Ok(157));
+        assert_eq!(part2(&backpacks), Ok(70));
+
+        assert_eq!(parse("abc\n"), Err(Error::OddLength(3)));
+        assert_eq!(parse("ab1a\n"), Err(Error::Unexpected(b'1')));
+        assert_eq!(part1(&parse("abcd\n").unwrap()), Err(Error::NotOneItem(0)));
+    }
+}
@@ -0,0 +1,38 @@
+//! Tuning trouble.
+
+/// Whether no byte is there twice, without a set.
+#[must_use]
+pub fn is_uniq(bytes: &[u8]) -> bool {
+    // bytes.iter().all_unique() // is nice but uses a HashSet
+
+    bytes
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, &me)| bytes[idx + 1..].iter().map(move |&other| (me, other)))
+        .all(|(me, other)| me != other)
+}
+
+/// Where the first window of `n` distinct bytes starts.
+#[must_use]
+pub fn first_distinct(bytes: &[u8], n: usize) -> Option<usize> {
+    bytes.windows(n).position(is_uniq)
+}
+
+/// Where the first marker of `n` distinct bytes ends.
+#[must_use]
+pub fn pos_after_n_uniq(bytes: &[u8], n: usize) -> Option<usize> {
+    first_distinct(bytes, n).map(|pos| pos + n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markers() {
+        let input = b"mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        assert_eq!(pos_after_n_uniq(input, 4), Some(7));
+        assert_eq!(pos_after_n_uniq(input, 14), Some(19));
+        assert_eq!(pos_after_n_uniq(b"aaaa", 2), None);
+    }
+}
@@ -0,0 +1,40 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(clippy::pedantic)]
+
+//! The logic of the days of 2022 that need little memory and no operating system: days 1, 2 and 6
+//! and the bit sets of day 3. Without the default `std` feature this is `no_std` and only needs
+//! `alloc`, to run these days on a microcontroller. The days in `aoc-2022` solve with it.
+
+extern crate alloc;
+
+use core::fmt;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day06;
+
+/// What can go wrong, without formatting strings on the way.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Error {
+    /// A byte that does not belong there
+    Unexpected(u8),
+    NotANumber,
+    /// A line of day 3 with an odd number of items, this many
+    OddLength(usize),
+    /// Not exactly one item in common in day 3, but this many
+    NotOneItem(u32),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Unexpected(byte) => write!(f, "Unexpected {:?}", char::from(*byte)),
+            Error::NotANumber => f.write_str("Not a number"),
+            Error::OddLength(len) => write!(f, "Odd number of items: {len}"),
+            Error::NotOneItem(count) => write!(f, "Not exactly one but {count} items in common"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
@@ -0,0 +1,372 @@
+//! `aoc bench`: criterion benchmarks of the days, and their history.
+
+use crate::{columns, YEAR};
+use anyhow::{ensure, Context, Result};
+use aoc_core::bench::{
+    self, bench_group, bench_id, criterion_dir, mean_ms, BenchHistory, Change, Record, Run,
+    HISTORY_DB, NEW, PHASES,
+};
+use aoc_core::compress;
+use aoc_core::energy::Rapl;
+use aoc_core::fetch::{input_path, utc_date, INPUT_DIR};
+use aoc_core::registry::{implementations, package, solutions, Registry, Solution};
+use aoc_core::style::{self, Role};
+use clap::{Args, Subcommand};
+use std::hint::black_box;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+/// How long every day is solved over and over for its energy, RAPL counters only update every
+/// millisecond or so.
+const ENERGY_TIME: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct BenchArgs {
+    #[command(subcommand)]
+    command: Option<BenchCmd>,
+    /// Year of the days
+    #[arg(long, short, default_value_t = YEAR)]
+    year: u16,
+    /// Only this day
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+    /// Keeps the results under this name, to compare against later
+    #[arg(long, conflicts_with = "compare")]
+    save_baseline: Option<String>,
+    /// Shows how much faster or slower every part got since the baseline of this name
+    #[arg(long)]
+    compare: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BenchCmd {
+    /// Shows how the times of a day changed over the commits `aoc bench` ran on
+    History(BenchHistoryArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct BenchHistoryArgs {
+    /// Year of the day
+    #[arg(long, short, default_value_t = YEAR)]
+    year: u16,
+    /// The day, as `11` or `day11`
+    #[arg(value_parser = parse_day)]
+    day: u8,
+    /// The runs of another machine [default: this one]
+    #[arg(long)]
+    machine: Option<String>,
+}
+
+fn parse_day(s: &str) -> Result<u8> {
+    let day: u8 = s.strip_prefix("day").unwrap_or(s).parse()?;
+    ensure!((1..=25).contains(&day), "There is no day {day}");
+    Ok(day)
+}
+
+/// Runs `benches/days.rs` of the year, which needs a release build of everything and takes a while, and
+/// records the results in the bench history.
+pub fn bench(registry: &Registry, args: &BenchArgs) -> Result<()> {
+    if let Some(BenchCmd::History(args)) = &args.command {
+        return bench_history(args);
+    }
+
+    let year = args.year;
+    let dir = registry
+        .dir(year)
+        .with_context(|| format!("There is no crate for {year}"))?;
+    let mut command = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()));
+    command
+        .current_dir(dir)
+        .args(["bench", "--package"])
+        .arg(package(year))
+        .args(["--bench", "days", "--"]);
+    if let Some(day) = args.day {
+        command.arg(format!("^{}/", bench_group(year, day)));
+    }
+    if let Some(name) = &args.save_baseline {
+        command.args(["--save-baseline", name]);
+    }
+    if let Some(name) = &args.compare {
+        command.args(["--baseline", name]);
+    }
+    let status = command.status().context("Could not run cargo bench")?;
+    ensure!(status.success(), "cargo bench failed with {status}");
+
+    let root = crate::root();
+    let criterion = criterion_dir(&root);
+    let days: Vec<_> = solutions()
+        .into_iter()
+        .filter(|s| s.year == year && args.day.is_none_or(|day| day == s.day))
+        .collect();
+    let ids: Vec<_> = days
+        .iter()
+        .flat_map(|s| implementations(year, s.day))
+        .flat_map(|s| PHASES.map(|phase| (s.day, bench_id(phase, s.name))))
+        .collect();
+    let saved_as = args.save_baseline.as_deref().unwrap_or(NEW);
+    let results: Vec<_> = ids
+        .iter()
+        .filter_map(|(day, id)| {
+            let ms = mean_ms(&criterion, year, *day, id, saved_as).ok()?;
+            Some((*day, id.clone(), ms))
+        })
+        .collect();
+    let energy = if let Some(rapl) = Rapl::open() {
+        energy(&rapl, dir, &days)
+    } else {
+        eprintln!("No energy readings, they need read access to /sys/class/powercap");
+        vec![]
+    };
+    if !energy.is_empty() {
+        print!("{}", energy_table(&results, &energy));
+    }
+    match Run::current(&root) {
+        Ok(run) => {
+            let mut history = BenchHistory::open(&root.join(HISTORY_DB))?;
+            history.record(&run, year, &results)?;
+            history.record_energy(&run, year, &energy)?;
+        }
+        Err(e) => eprintln!("Not keeping the results in the history: {e:#}"),
+    }
+
+    if let Some(baseline) = &args.compare {
+        let changes = bench::compare(&criterion, year, &ids, baseline);
+        ensure!(
+            !changes.is_empty(),
+            "There are no results of {baseline:?} to compare with"
+        );
+        print!("{}", changes_table(&changes));
+    }
+    Ok(())
+}
+
+/// Joules one parse and both parts of every day use, on the input the benchmarks use. Criterion
+/// runs in another process, so the days are solved here once more while the counters run.
+fn energy(rapl: &Rapl, dir: &Path, days: &[&Solution]) -> Vec<(u8, f64)> {
+    days.iter()
+        .filter_map(|solution| {
+            let input = compress::read(&input_path(&dir.join(INPUT_DIR), solution.day))
+                .ok()
+                .or_else(|| solution.example.map(str::to_owned))?;
+            let solve = || -> Option<()> {
+                let parsed = solution.parse(&input).ok()?;
+                black_box(solution.part1(&*parsed).ok());
+                black_box(solution.part2(&*parsed).ok());
+                Some(())
+            };
+            solve()?;
+
+            let start = Instant::now();
+            let before = rapl.read()?;
+            let mut runs = 0_u32;
+            while runs == 0 || start.elapsed() < ENERGY_TIME {
+                solve();
+                runs += 1;
+            }
+            let after = rapl.read()?;
+            Some((solution.day, rapl.joules(&before, &after) / f64::from(runs)))
+        })
+        .collect()
+}
+
+fn joules(joules: f64) -> String {
+    match joules {
+        j if j >= 1.0 => format!("{j:.2}J"),
+        j if j >= 1e-3 => format!("{:.2}mJ", j * 1e3),
+        j => format!("{:.1}µJ", j * 1e6),
+    }
+}
+
+/// The time of the main implementation of every day next to the energy one solve of it used.
+fn energy_table(results: &[(u8, String, f64)], energy: &[(u8, f64)]) -> String {
+    let mut rows = vec![["Day", "Time", "Energy"].map(str::to_owned).to_vec()];
+    for &(day, energy) in energy {
+        let ms: f64 = results
+            .iter()
+            .filter(|(d, id, _)| *d == day && PHASES.contains(&id.as_str()))
+            .map(|(_, _, ms)| ms)
+            .sum();
+        rows.push(vec![day.to_string(), millis(ms), joules(energy)]);
+    }
+    columns(&rows)
+}
+
+fn bench_history(args: &BenchHistoryArgs) -> Result<()> {
+    let machine = args.machine.clone().unwrap_or_else(bench::machine);
+    let history = BenchHistory::open(&crate::root().join(HISTORY_DB))?;
+    let records = history.day(args.year, args.day, &machine)?;
+    ensure!(
+        !records.is_empty(),
+        "There are no results of {} day {} on {machine}, `aoc bench` records them",
+        args.year,
+        args.day
+    );
+    let energy = history.day_energy(args.year, args.day, &machine)?;
+    print!("{}", trend_table(&records, &energy));
+    Ok(())
+}
+
+fn millis(ms: f64) -> String {
+    format!("{:.1?}", Duration::from_secs_f64(ms / 1000.0))
+}
+
+/// Faster is shown as an answer, slower as an error, changes within 2% as they are.
+fn percent_change(percent: f64) -> String {
+    let shown = format!("{percent:+.1}%");
+    match percent {
+        p if p <= -2.0 => style::paint(Role::Answer, &shown),
+        p if p >= 2.0 => style::paint(Role::Error, &shown),
+        _ => shown,
+    }
+}
+
+fn changes_table(changes: &[Change]) -> String {
+    let mut rows = vec![["Day", "Bench", "Before", "After", "Change"]
+        .map(str::to_owned)
+        .to_vec()];
+    for change in changes {
+        rows.push(vec![
+            change.day.to_string(),
+            change.id.clone(),
+            millis(change.before_ms),
+            millis(change.after_ms),
+            percent_change(change.percent()),
+        ]);
+    }
+    columns(&rows)
+}
+
+/// One row per commit, with the total of the main implementation compared to the commit before,
+/// and the energy of a solve if any commit has one.
+fn trend_table(records: &[Record], energy: &[(String, f64)]) -> String {
+    let mut ids: Vec<&str> = vec![];
+    let mut commits: Vec<&str> = vec![];
+    for record in records {
+        if !ids.contains(&record.id.as_str()) {
+            ids.push(&record.id);
+        }
+        if !commits.contains(&record.run.commit.as_str()) {
+            commits.push(&record.run.commit);
+        }
+    }
+
+    let mut header = vec!["Date".to_owned(), "Commit".to_owned()];
+    header.extend(ids.iter().map(|&id| id.to_owned()));
+    header.extend(["Total".to_owned(), "Change".to_owned()]);
+    if !energy.is_empty() {
+        header.push("Energy".to_owned());
+    }
+    let mut rows = vec![header];
+    let mut before: Option<f64> = None;
+    for commit in commits {
+        let of_commit: Vec<_> = records.iter().filter(|r| r.run.commit == commit).collect();
+        let ms = |id: &str| of_commit.iter().find(|r| r.id == id).map(|r| r.mean_ms);
+        let time = of_commit
+            .iter()
+            .map(|r| r.run.time)
+            .max()
+            .unwrap_or_default();
+        let total: f64 = PHASES.iter().filter_map(|&phase| ms(phase)).sum();
+
+        let mut row = vec![
+            utc_date(UNIX_EPOCH + Duration::from_secs(time)),
+            commit.to_owned(),
+        ];
+        row.extend(ids.iter().map(|&id| ms(id).map(millis).unwrap_or_default()));
+        row.push(millis(total));
+        row.push(
+            before
+                .map(|before| percent_change((total - before) / before * 100.0))
+                .unwrap_or_default(),
+        );
+        if !energy.is_empty() {
+            row.push(
+                energy
+                    .iter()
+                    .rfind(|(c, _)| c == commit)
+                    .map(|&(_, j)| joules(j))
+                    .unwrap_or_default(),
+            );
+        }
+        rows.push(row);
+        before = Some(total);
+    }
+    columns(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bench_changes() {
+        let change = |id: &str, before_ms, after_ms| Change {
+            day: 5,
+            id: id.to_owned(),
+            before_ms,
+            after_ms,
+        };
+        let shown = changes_table(&[change("parse", 2.0, 0.5), change("part2", 1.0, 1.01)]);
+        let lines: Vec<_> = shown.lines().map(str::trim_end).collect();
+        assert_eq!(lines[1], "5    parse  2.0ms   500.0µs  -75.0%");
+        assert_eq!(lines[2], "5    part2  1.0ms   1.0ms    +1.0%");
+    }
+
+    #[test]
+    fn bench_energy() {
+        let results = [
+            (3, "parse".to_owned(), 0.5),
+            (3, "part1".to_owned(), 1.0),
+            (3, "part1/fast".to_owned(), 0.25),
+            (3, "part2".to_owned(), 1.0),
+        ];
+        let shown = energy_table(&results, &[(3, 0.0042)]);
+        let lines: Vec<_> = shown.lines().map(str::trim_end).collect();
+        assert_eq!(lines[0], "Day  Time   Energy");
+        assert_eq!(lines[1], "3    2.5ms  4.20mJ");
+        assert_eq!(joules(2.0), "2.00J");
+        assert_eq!(joules(0.000_05), "50.0µJ");
+    }
+
+    #[test]
+    fn bench_trend() {
+        let record = |commit: &str, time, id: &str, mean_ms| Record {
+            run: Run {
+                commit: commit.to_owned(),
+                machine: "laptop".to_owned(),
+                time,
+            },
+            id: id.to_owned(),
+            mean_ms,
+        };
+        let records = [
+            record("abc1234", 1_700_000_000, "parse", 1.0),
+            record("abc1234", 1_700_000_000, "part2", 9.0),
+            record("def5678", 1_710_000_000, "parse", 1.0),
+            record("def5678", 1_710_000_000, "part2", 4.0),
+        ];
+        let shown = trend_table(&records, &[]);
+        let lines: Vec<_> = shown.lines().map(str::trim_end).collect();
+        assert_eq!(
+            lines[0],
+            "Date        Commit   parse  part2  Total   Change"
+        );
+        assert_eq!(lines[1], "2023-11-14  abc1234  1.0ms  9.0ms  10.0ms");
+        assert_eq!(
+            lines[2],
+            "2024-03-09  def5678  1.0ms  4.0ms  5.0ms   -50.0%"
+        );
+
+        let shown = trend_table(&records, &[("def5678".to_owned(), 0.012)]);
+        let lines: Vec<_> = shown.lines().map(str::trim_end).collect();
+        assert!(lines[0].ends_with("Change  Energy"));
+        assert!(lines[1].ends_with("10.0ms"));
+        assert!(lines[2].ends_with("-50.0%  12.00mJ"));
+
+        assert_eq!(parse_day("day11").unwrap(), 11);
+        assert_eq!(parse_day("7").unwrap(), 7);
+        parse_day("day26").unwrap_err();
+    }
+}
@@ -0,0 +1,85 @@
+//! `aoc complexity`: times a day on generated inputs of growing size and estimates how its time
+//! grows, to catch an accidental O(n²).
+
+use crate::YEAR;
+use anyhow::{anyhow, ensure, Context, Result};
+use aoc_core::complexity::{measure, report};
+use aoc_core::registry::solution;
+use clap::Args;
+use std::collections::BTreeMap;
+use std::hint::black_box;
+
+#[derive(Debug, Args)]
+pub struct ComplexityArgs {
+    /// Year of the puzzle
+    #[arg(long, short, default_value_t = YEAR)]
+    year: u16,
+    /// Day of the puzzle, one with a generator in the `gen` module of its year
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// The input sizes, what they count depends on the day (see `gen`)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "1000,2000,4000,8000,16000"
+    )]
+    sizes: Vec<usize>,
+    /// Runs per size, the fastest counts
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+    runs: u32,
+    /// The same seed always gives the same inputs
+    #[arg(long, default_value_t = 1)]
+    seed: u64,
+}
+
+/// An input of the day from the generators of its year.
+fn generate(year: u16, day: u8, size: usize, seed: u64) -> Result<String> {
+    match year {
+        2022 => aoc2022::gen::generate(day, size, seed),
+        2023 => aoc2023::gen::generate(day, size, seed),
+        _ => Err(anyhow!("There are no generators for {year}")),
+    }
+}
+
+/// Solves every part of the day on each input, the inputs are generated before the clock starts.
+pub fn complexity(args: &ComplexityArgs) -> Result<()> {
+    let (year, day) = (args.year, args.day);
+    let solution = solution(year, day)
+        .with_context(|| format!("{year} day {day} is not registered with #[aoc]"))?;
+    ensure!(args.sizes.len() > 1, "Needs at least two sizes to compare");
+
+    let inputs = args
+        .sizes
+        .iter()
+        .map(|&size| Ok((size as u64, generate(year, day, size, args.seed)?)))
+        .collect::<Result<BTreeMap<_, _>>>()?;
+    for (size, input) in &inputs {
+        solution
+            .solve(input, &[])
+            .with_context(|| format!("{year} day {day} on the input of size {size}"))?;
+    }
+
+    let samples = measure(
+        inputs.keys().copied(),
+        args.runs as usize,
+        |size| inputs[&size].as_str(),
+        |input| {
+            black_box(solution.solve(input, &[])).ok();
+        },
+    );
+    print!("{}", report(&samples));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generators() {
+        assert!(generate(2022, 9, 10, 1).unwrap().lines().count() == 10);
+        assert!(generate(2023, 6, 10, 1).unwrap().starts_with("Time: 10\n"));
+        generate(2023, 1, 10, 1).unwrap_err();
+        generate(2015, 1, 10, 1).unwrap_err();
+    }
+}
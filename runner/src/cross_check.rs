@@ -0,0 +1,182 @@
+//! `aoc run --cross-check` and `--impl`: solves days with every implementation of them, or with one
+//! of them by name.
+
+use crate::run::input_source;
+use anyhow::{anyhow, Context, Result};
+use aoc_core::answer::Answer;
+use aoc_core::error::AocError;
+use aoc_core::registry::{implementations, Solution};
+use aoc_core::solver::styled_answers;
+use std::path::Path;
+
+/// Solves the days with all their implementations in this process, one after another.
+pub fn cross_check(
+    days: &[(u16, u8)],
+    input: Option<&Path>,
+    account: Option<&str>,
+    example: bool,
+    parts: &[String],
+) -> Result<()> {
+    let mut failed = vec![];
+    for &(year, day) in days {
+        let implementations = implementations(year, day);
+        let Some(main) = implementations.first() else {
+            failed.push(format!("{year} day {day} is not registered with #[aoc]"));
+            continue;
+        };
+        let answers = read(main, input, account, example).and_then(|input| {
+            agree(
+                implementations
+                    .iter()
+                    .map(|s| (s.name, s.solve(&input, parts))),
+            )
+        });
+        match answers {
+            Ok(answers) => {
+                let names: Vec<_> = implementations.iter().map(|s| s.name).collect();
+                if names.len() == 1 {
+                    println!("{year} day {day} has only one implementation");
+                } else {
+                    println!("{year} day {day}: {} agree", names.join(", "));
+                }
+                print!("{}", styled_answers(&answers));
+            }
+            Err(e) => failed.push(format!("{year} day {day}: {e:#}")),
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("{}", failed.join("\n")))
+    }
+}
+
+/// Solves the days with the implementation called `name` instead of the main one.
+///
+/// # Errors
+/// If a day has no such implementation, its input can not be read, or it fails.
+pub fn run_implementation(
+    days: &[(u16, u8)],
+    name: &str,
+    input: Option<&Path>,
+    account: Option<&str>,
+    example: bool,
+    parts: &[String],
+) -> Result<()> {
+    for &(year, day) in days {
+        let implementations = implementations(year, day);
+        let solution = implementations
+            .iter()
+            .find(|s| s.name == name)
+            .with_context(|| {
+                let names: Vec<_> = implementations.iter().map(|s| s.name).collect();
+                format!(
+                    "{year} day {day} has no implementation {name:?}, available are: {}",
+                    names.join(", ")
+                )
+            })?;
+        let input = read(solution, input, account, example)?;
+        let answers = solution
+            .solve(&input, parts)
+            .with_context(|| format!("{year} day {day} ({name})"))?;
+        print!("{}", styled_answers(&answers));
+    }
+
+    Ok(())
+}
+
+/// The input of the day of `solution`, or its example.
+fn read(
+    solution: &Solution,
+    input: Option<&Path>,
+    account: Option<&str>,
+    example: bool,
+) -> Result<String> {
+    let (year, day) = (solution.year, solution.day);
+    if example {
+        solution
+            .example
+            .map(str::to_owned)
+            .with_context(|| format!("{year} day {day} has no example"))
+    } else {
+        Ok(input_source(year, day, input, account).read()?)
+    }
+}
+
+/// The answers that all implementations came to.
+fn agree(
+    answers: impl IntoIterator<Item = (&'static str, Result<Vec<(String, Answer)>, AocError>)>,
+) -> Result<Vec<(String, Answer)>> {
+    let mut agreed: Option<(&str, Vec<(String, Answer)>)> = None;
+    for (name, answers) in answers {
+        let answers = answers.with_context(|| format!("{name} failed"))?;
+        let Some((first, expected)) = &agreed else {
+            agreed = Some((name, answers));
+            continue;
+        };
+        for ((part, expected), (_, answer)) in expected.iter().zip(&answers) {
+            if expected != answer {
+                return Err(anyhow!(
+                    "{part}: {first} says {expected} but {name} says {answer}"
+                ));
+            }
+        }
+    }
+
+    agreed
+        .map(|(_, answers)| answers)
+        .context("There are no implementations")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::registry::solution;
+
+    #[test]
+    #[cfg_attr(not(feature = "all-days"), ignore = "Needs every day")]
+    fn implementations_agree() {
+        let answers = |a: i64, b: i64| {
+            Ok(vec![
+                ("Part 1".to_owned(), Answer::from(a)),
+                ("Part 2".to_owned(), Answer::from(b)),
+            ])
+        };
+
+        assert_eq!(
+            agree([("main", answers(1, 2)), ("fast", answers(1, 2))]).unwrap(),
+            answers(1, 2).unwrap()
+        );
+        assert_eq!(
+            agree([("main", answers(1, 2)), ("fast", answers(1, 3))])
+                .unwrap_err()
+                .to_string(),
+            "Part 2: main says 2 but fast says 3"
+        );
+        let failed = Err(AocError::NoSolution("nope".to_owned()));
+        assert_eq!(
+            format!(
+                "{:#}",
+                agree([("main", answers(1, 2)), ("fast", failed)]).unwrap_err()
+            ),
+            "fast failed: There is no solution: nope"
+        );
+
+        // The implementations of day 3
+        let example = solution(2022, 3).unwrap().example.unwrap();
+        agree(
+            implementations(2022, 3)
+                .iter()
+                .map(|s| (s.name, s.solve(example, &[]))),
+        )
+        .unwrap();
+
+        run_implementation(&[(2022, 3)], "bits", None, None, true, &[]).unwrap();
+        let missing = run_implementation(&[(2022, 3)], "simd", None, None, true, &[]);
+        assert_eq!(
+            missing.unwrap_err().to_string(),
+            "2022 day 3 has no implementation \"simd\", available are: main, bits, reference"
+        );
+    }
+}
@@ -0,0 +1,129 @@
+//! `aoc run --determinism`: checks that the days print the same answers every time.
+
+use crate::THREADS_VAR;
+use anyhow::{anyhow, Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// The threads of the runs, in turn.
+const THREADS: [usize; 4] = [1, 2, 4, 8];
+
+/// Solves every day `runs` times with `aoc run` and compares what they print. Every run is a new
+/// process, so hash maps get new random seeds, and the number of rayon threads is varied between
+/// runs. Cached answers are not used, they would all be the same.
+///
+/// # Errors
+/// If a run fails, or the runs of a day differ.
+pub fn determinism(
+    days: &[(u16, u8)],
+    runs: u32,
+    parts: &[String],
+    input: Option<&Path>,
+    account: Option<&str>,
+    example: bool,
+) -> Result<()> {
+    let mut args = vec!["run".to_owned(), "--force".to_owned()];
+    for part in parts {
+        args.extend(["--part".to_owned(), part.clone()]);
+    }
+    if let Some(input) = input {
+        args.extend(["--input".to_owned(), input.display().to_string()]);
+    }
+    if let Some(account) = account {
+        args.extend(["--account".to_owned(), account.to_owned()]);
+    }
+    if example {
+        args.push("--example".to_owned());
+    }
+
+    let mut differing = vec![];
+    for &(year, day) in days {
+        let day_args = [
+            args.clone(),
+            vec![
+                "--year".to_owned(),
+                year.to_string(),
+                "--day".to_owned(),
+                day.to_string(),
+            ],
+        ]
+        .concat();
+        if !check_day(year, day, runs, &day_args)? {
+            differing.push(format!("{year} day {day}"));
+        }
+    }
+
+    if differing.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("Nondeterministic output: {}", differing.join(", ")))
+    }
+}
+
+/// Whether all runs of the day print the same, says where the first one that does not differs.
+fn check_day(year: u16, day: u8, runs: u32, args: &[String]) -> Result<bool> {
+    let expected = run(args, THREADS[0])?;
+
+    for (run_idx, threads) in (1..runs).zip(THREADS.into_iter().cycle().skip(1)) {
+        let output = run(args, threads)?;
+
+        if let Some((line, a, b)) = first_difference(&expected, &output) {
+            println!(
+                "{year} day {day}: run {run_idx} with {threads} threads differs in line {line}"
+            );
+            println!("  {a}");
+            println!("  {b}");
+            return Ok(false);
+        }
+    }
+
+    println!("{year} day {day}: {runs} identical runs");
+    Ok(true)
+}
+
+/// What `aoc` prints with `args`, in a new process.
+fn run(args: &[String], threads: usize) -> Result<String> {
+    let out = Command::new(std::env::current_exe()?)
+        .args(args)
+        // Over `--threads` and `AOC_THREADS` of this run, the days it starts get them too
+        .env(THREADS_VAR, threads.to_string())
+        .output()
+        .context("Could not run aoc")?;
+
+    if !out.status.success() {
+        return Err(anyhow!(
+            "aoc {} failed with {}:\n{}",
+            args.join(" "),
+            out.status,
+            String::from_utf8_lossy(&out.stderr)
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
+/// First line that differs, with its line number.
+fn first_difference<'a>(a: &'a str, b: &'a str) -> Option<(usize, &'a str, &'a str)> {
+    let mut a_lines = a.lines().chain(std::iter::repeat("<end of output>"));
+    let mut b_lines = b.lines().chain(std::iter::repeat("<end of output>"));
+
+    (1..=a.lines().count().max(b.lines().count())).find_map(|line| {
+        let (a, b) = (a_lines.next()?, b_lines.next()?);
+        (a != b).then_some((line, a, b))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn differences() {
+        assert_eq!(first_difference("1\n2\n", "1\n2\n"), None);
+        assert_eq!(first_difference("1\n2\n", "1\n3\n"), Some((2, "2", "3")));
+        assert_eq!(
+            first_difference("1\n", "1\n2\n"),
+            Some((2, "<end of output>", "2"))
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! `aoc doctor`: checks that everything is set up to run the days, and says how to fix what is not.
+
+use anyhow::{anyhow, Result};
+use aoc_core::fetch;
+use aoc_core::net::Client;
+use aoc_core::registry::{implemented_days, Registry};
+use clap::Args;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Args)]
+pub struct DoctorArgs {
+    /// Does not try the session cookie against the server
+    #[arg(long)]
+    offline: bool,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Status {
+    Ok,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct Check {
+    name: &'static str,
+    status: Status,
+    detail: String,
+    fix: Option<String>,
+}
+
+impl Check {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Ok,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn problem(
+        name: &'static str,
+        status: Status,
+        detail: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mark = match self.status {
+            Status::Ok => "ok  ",
+            Status::Warn => "warn",
+            Status::Fail => "FAIL",
+        };
+        writeln!(f, "[{mark}] {}: {}", self.name, self.detail)?;
+        if let Some(fix) = &self.fix {
+            writeln!(f, "       fix: {fix}")?;
+        }
+        Ok(())
+    }
+}
+
+fn layout(root: &Path) -> Check {
+    let missing: Vec<_> = ["Cargo.toml", "src/lib.rs", "src/bin"]
+        .into_iter()
+        .filter(|p| !root.join(p).exists())
+        .collect();
+
+    if missing.is_empty() {
+        Check::ok("layout", "crate found")
+    } else {
+        Check::problem(
+            "layout",
+            Status::Fail,
+            format!("missing {}", missing.join(", ")),
+            "every year needs its days in src/ and their binaries in src/bin/",
+        )
+    }
+}
+
+fn inputs(root: &Path, year: u16) -> Check {
+    let dir = root.join("input");
+    let missing: Vec<String> = implemented_days(root)
+        .into_iter()
+        .filter(|&day| !fetch::has_input(&fetch::input_path(&dir, day)))
+        .map(|day| day.to_string())
+        .collect();
+
+    if missing.is_empty() {
+        Check::ok("inputs", "every implemented day has an input")
+    } else {
+        Check::problem(
+            "inputs",
+            Status::Fail,
+            format!("no input for day {}", missing.join(", ")),
+            format!("aoc input fetch --year {year} --all"),
+        )
+    }
+}
+
+fn writable(root: &Path) -> Check {
+    let dir = root.join("input");
+    let probe = dir.join(".doctor");
+    let result = fs::create_dir_all(&dir).and_then(|()| fs::write(&probe, b""));
+    let _ = fs::remove_file(&probe);
+
+    match result {
+        Ok(()) => Check::ok("input directory", format!("{} is writable", dir.display())),
+        Err(e) => Check::problem(
+            "input directory",
+            Status::Fail,
+            format!("can not write to {}: {e}", dir.display()),
+            format!("check the permissions of {}", dir.display()),
+        ),
+    }
+}
+
+/// Parse caches whose input is gone or newer than the cache.
+fn stale_caches(root: &Path) -> Check {
+    let stale: Vec<PathBuf> = fs::read_dir(root.join("input"))
+        .into_iter()
+        .flatten()
+        .filter_map(|e| Some(e.ok()?.path()))
+        .filter(|p| p.extension().is_some_and(|e| e == "parsed"))
+        .filter(|cache| {
+            let modified = |p: &Path| fs::metadata(p).and_then(|m| m.modified()).ok();
+            match (modified(cache), modified(&cache.with_extension("txt"))) {
+                (Some(cache), Some(input)) => input > cache,
+                _ => true,
+            }
+        })
+        .collect();
+
+    if stale.is_empty() {
+        Check::ok("caches", "no stale parse caches")
+    } else {
+        let names: Vec<_> = stale.iter().map(|p| p.display().to_string()).collect();
+        Check::problem(
+            "caches",
+            Status::Warn,
+            format!("stale {}", names.join(", ")),
+            "they are rebuilt on the next run, or delete them with `rm input/*.parsed`",
+        )
+    }
+}
+
+fn session(offline: bool) -> Check {
+    let session = match fetch::session() {
+        Ok(session) if !session.is_empty() => session,
+        _ => {
+            return Check::problem(
+                "session",
+                Status::Warn,
+                "no session cookie, inputs can not be downloaded",
+                "copy the `session` cookie from adventofcode.com into AOC_SESSION, .session or ~/.config/aoc/session",
+            )
+        }
+    };
+
+    if offline {
+        return Check::ok("session", "cookie set, not checked (--offline)");
+    }
+
+    // Retrying would only make a rejected cookie take longer to report
+    match fetch::fetch_input(&Client::new(session).with_retries(0), 2022, 1) {
+        Ok(_) => Check::ok("session", "cookie accepted by adventofcode.com"),
+        Err(e) => Check::problem(
+            "session",
+            Status::Fail,
+            format!("cookie rejected: {e:#}"),
+            "log in again and copy the new `session` cookie, they expire after about a month",
+        ),
+    }
+}
+
+pub fn doctor(registry: &Registry, args: &DoctorArgs) -> Result<()> {
+    let mut checks = vec![];
+    if registry.years().next().is_none() {
+        let layout = Check::problem(
+            "layout",
+            Status::Fail,
+            "no crates of years found",
+            "run aoc from the directory of a year's crate, e.g. `cd 2022`",
+        );
+        print!("{layout}");
+        checks.push(layout);
+    }
+    for year in registry.years() {
+        let Some(root) = registry.dir(year) else {
+            continue;
+        };
+        println!("{year}:");
+        let year_checks = [
+            layout(root),
+            writable(root),
+            inputs(root, year),
+            stale_caches(root),
+        ];
+        for check in &year_checks {
+            print!("{check}");
+        }
+        checks.extend(year_checks);
+    }
+    let session = session(args.offline);
+    print!("{session}");
+    checks.push(session);
+
+    if checks.iter().any(|c| c.status == Status::Fail) {
+        Err(anyhow!("Found problems, see the fixes above"))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_a_crate() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("src/bin")).unwrap();
+        fs::create_dir_all(root.join("input")).unwrap();
+
+        assert_eq!(layout(root).status, Status::Fail);
+        fs::write(root.join("Cargo.toml"), "").unwrap();
+        fs::write(root.join("src/lib.rs"), "").unwrap();
+        assert_eq!(layout(root).status, Status::Ok);
+
+        for file in ["day01.rs", "day03.rs", "report.rs"] {
+            fs::write(root.join("src/bin").join(file), "").unwrap();
+        }
+        assert_eq!(implemented_days(root), vec![1, 3]);
+
+        fs::write(root.join("input/day01.txt"), "1\n").unwrap();
+        fs::write(root.join("input/day03.txt.gz"), "gz").unwrap();
+        fs::write(root.join("src/bin/day04.rs"), "").unwrap();
+        let check = inputs(root, 2022);
+        assert_eq!(check.status, Status::Fail);
+        assert_eq!(check.detail, "no input for day 4");
+
+        assert_eq!(writable(root).status, Status::Ok);
+
+        fs::write(root.join("input/day05.parsed"), "").unwrap();
+        assert_eq!(stale_caches(root).status, Status::Warn);
+    }
+}
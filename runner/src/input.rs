@@ -0,0 +1,459 @@
+//! `aoc input`: encrypts and decrypts the inputs, downloads them and looks at or makes up others.
+
+use crate::YEAR;
+use anyhow::{anyhow, Context, Result};
+use aoc2022::gen;
+use aoc2022::input_stats::{ParsedStats, Stats};
+use aoc2022::reference::Rng;
+use aoc2022::scramble::scramble;
+use aoc_core::compress;
+use aoc_core::crypt::{config_key_path, decrypt_file, encrypt_file, encrypted_path, Key};
+use aoc_core::fetch::{self, input_path, INPUT_DIR};
+use aoc_core::net::Client;
+use aoc_core::registry::{solution, Registry};
+use clap::{Args, Subcommand};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Subcommand)]
+pub enum InputCmd {
+    /// Writes input/dayNN.txt.enc next to every input of a year
+    Encrypt(InputArgs),
+    /// Writes input/dayNN.txt from input/dayNN.txt.enc, days do this themselves for missing inputs
+    Decrypt(InputArgs),
+    /// Generates a key into ~/.config/aoc/input-key
+    NewKey,
+    /// Downloads the inputs of a year that are not there yet, one every few seconds
+    Fetch(FetchArgs),
+    /// Shows the lines, blocks and tokens of an input and what the day parses it to, to spot
+    /// truncated or unexpected ones
+    Stats(StatsArgs),
+    /// Shows how two parsed inputs differ in structure, like those of two accounts
+    Diff(DiffArgs),
+    /// Prints an input of 2022 rewritten so it can be committed as a test fixture
+    Scramble(ScrambleArgs),
+    /// Prints a random but valid input of 2022 of any size, see `aoc2022::gen`
+    Gen(GenArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct InputArgs {
+    /// Year of the inputs
+    #[arg(long, short, default_value_t = YEAR)]
+    year: u16,
+    /// Only these days [default: all of them]
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    days: Vec<u8>,
+}
+
+#[derive(Debug, Args)]
+pub struct FetchArgs {
+    /// Year of the inputs
+    #[arg(long, short, default_value_t = YEAR)]
+    year: u16,
+    /// Every day that is unlocked
+    #[arg(long)]
+    all: bool,
+    /// Only these days
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=25))]
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    day: Vec<u8>,
+    /// Where to put them [default: input/ of the year]
+    #[arg(long)]
+    dir: Option<PathBuf>,
+}
+
+impl FetchArgs {
+    /// The days to download, those that are unlocked at `now` with `--all`.
+    fn days(&self, now: SystemTime) -> Vec<u8> {
+        if self.all {
+            fetch::unlocked_days(self.year, now)
+        } else {
+            self.day.clone()
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct StatsArgs {
+    /// Year of the day
+    #[arg(long, short, default_value_t = YEAR)]
+    year: u16,
+    /// A day, for its input in input/ of the year, or the path of an input
+    input: String,
+    /// Day that parses an input given by its path
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+}
+
+impl StatsArgs {
+    /// The day that parses the input, if it is known.
+    fn day(&self) -> Option<u8> {
+        self.day.or_else(|| self.input.parse().ok())
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct DiffArgs {
+    /// Year of the day
+    #[arg(long, short, default_value_t = YEAR)]
+    year: u16,
+    /// Day of the inputs, registered with `#[aoc]` to parse them
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Compares the inputs of two accounts, which are in input/ACCOUNT/
+    #[arg(long, alias = "profile")]
+    #[arg(required_unless_present = "paths", conflicts_with = "paths")]
+    account: Vec<String>,
+    /// Or the paths of two inputs
+    paths: Vec<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct ScrambleArgs {
+    /// Day of the input
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// The same seed scrambles the same input the same way [default: the current time]
+    #[arg(long)]
+    seed: Option<u64>,
+    /// The input [default: that of the day in input/ of 2022]
+    path: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct GenArgs {
+    /// Day of the input
+    #[arg(long, short)]
+    day: u8,
+    /// How many of what the day has a lot of there are
+    #[arg(long, default_value_t = 1000)]
+    size: usize,
+    /// The same seed always gives the same input [default: the current time]
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+pub fn input(registry: &Registry, cmd: &InputCmd) -> Result<()> {
+    match cmd {
+        InputCmd::Encrypt(args) => crypt(registry, args, true),
+        InputCmd::Decrypt(args) => crypt(registry, args, false),
+        InputCmd::NewKey => new_key(),
+        InputCmd::Fetch(args) => fetch_inputs(registry, args),
+        InputCmd::Stats(args) => {
+            let path = day_or_path(registry, args.year, &args.input)?;
+            print!("{}", read_stats(&path)?);
+            match args.day() {
+                Some(day) if solution(args.year, day).is_some() => {
+                    print!("\nParsed:\n{}", read_parsed(args.year, day, &path)?);
+                }
+                Some(day) => println!("\n{} day {day} is not registered with #[aoc]", args.year),
+                None => println!("\nPass --day to also show what the day parses it to"),
+            }
+            Ok(())
+        }
+        InputCmd::Diff(args) => {
+            let (a, b) = diff_paths(registry, args)?;
+            let parsed = |path| read_parsed(args.year, args.day, path);
+            let diffs = parsed(&a)?.diff(&parsed(&b)?);
+
+            println!("{} vs {}", a.display(), b.display());
+            if diffs.is_empty() {
+                println!("  no structural differences");
+            }
+            for diff in diffs {
+                println!("  {diff}");
+            }
+            Ok(())
+        }
+        InputCmd::Scramble(args) => {
+            let path = match &args.path {
+                Some(path) => path.clone(),
+                None => input_path(&year_dir(registry, YEAR)?.join(INPUT_DIR), args.day),
+            };
+            let input = compress::read(&path)?;
+            print!(
+                "{}",
+                scramble(args.day, &input, &mut Rng::new(seed(args.seed)?))
+            );
+            Ok(())
+        }
+        InputCmd::Gen(args) => {
+            print!("{}", gen::generate(args.day, args.size, seed(args.seed)?)?);
+            Ok(())
+        }
+    }
+}
+
+fn year_dir(registry: &Registry, year: u16) -> Result<&Path> {
+    registry
+        .dir(year)
+        .with_context(|| format!("There is no crate for {year}"))
+}
+
+/// A day number is looked up in input/ of the year, anything else is a path.
+fn day_or_path(registry: &Registry, year: u16, arg: &str) -> Result<PathBuf> {
+    match arg.parse::<u8>() {
+        Ok(day) => Ok(input_path(&year_dir(registry, year)?.join(INPUT_DIR), day)),
+        Err(_) => Ok(arg.into()),
+    }
+}
+
+fn read_stats(path: &Path) -> Result<Stats> {
+    Ok(Stats::from_input(&compress::read(path)?))
+}
+
+/// The [`ParsedStats`] of an input, parsed by the registered day.
+fn read_parsed(year: u16, day: u8, path: &Path) -> Result<ParsedStats> {
+    ParsedStats::of_day(year, day, &compress::read(path)?)
+        .with_context(|| format!("Could not parse {}", path.display()))
+}
+
+/// The inputs of the day of two accounts, or two paths.
+fn diff_paths(registry: &Registry, args: &DiffArgs) -> Result<(PathBuf, PathBuf)> {
+    match (args.account.as_slice(), args.paths.as_slice()) {
+        ([a, b], []) => {
+            let dir = year_dir(registry, args.year)?;
+            let path = |account| input_path(&dir.join(fetch::input_dir(Some(account))), args.day);
+            Ok((path(a), path(b)))
+        }
+        ([], [a, b]) => Ok((a.clone(), b.clone())),
+        _ => Err(anyhow!("Expected either two --account or two paths")),
+    }
+}
+
+/// `seed`, or the current time.
+fn seed(seed: Option<u64>) -> Result<u64> {
+    match seed {
+        Some(seed) => Ok(seed),
+        None => Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs()),
+    }
+}
+
+fn fetch_inputs(registry: &Registry, args: &FetchArgs) -> Result<()> {
+    let year = args.year;
+    let dir = match &args.dir {
+        Some(dir) => dir.clone(),
+        None => year_dir(registry, year)?.join(INPUT_DIR),
+    };
+    let client = Client::from_session()?;
+
+    let result = fetch::backfill(
+        &dir,
+        &args.days(SystemTime::now()),
+        |day| {
+            println!("Fetching {year} day {day}");
+            fetch::fetch_input(&client, year, day)
+        },
+        || std::thread::sleep(fetch::THROTTLE),
+    )?;
+    println!(
+        "Fetched {} inputs into {}, {} were already there",
+        result.fetched.len(),
+        dir.display(),
+        result.skipped.len()
+    );
+    Ok(())
+}
+
+/// Encrypts or decrypts the inputs there are. Encrypted inputs that are still up to date are kept,
+/// encrypting them again would change them.
+fn crypt(registry: &Registry, args: &InputArgs, encrypt: bool) -> Result<()> {
+    let key = Key::load()?.context(
+        "No key, set AOC_INPUT_KEY, write it to .input-key or create one with `aoc input new-key`",
+    )?;
+    let dir = year_dir(registry, args.year)?.join(INPUT_DIR);
+
+    let days = if args.days.is_empty() {
+        (1..=fetch::last_day(args.year)).collect()
+    } else {
+        args.days.clone()
+    };
+    for day in days {
+        let plain = input_path(&dir, day);
+        let encrypted = encrypted_path(&plain);
+        let (from, to) = if encrypt {
+            (&plain, &encrypted)
+        } else {
+            (&encrypted, &plain)
+        };
+        if !from.exists() {
+            if args.days.is_empty() {
+                continue;
+            }
+            return Err(anyhow!("There is no {}", from.display()));
+        }
+
+        if encrypt {
+            let current = fs::read(&encrypted).ok().and_then(|e| key.decrypt(&e).ok());
+            if current.is_some_and(|current| fs::read(&plain).is_ok_and(|p| p == current)) {
+                continue;
+            }
+            encrypt_file(&key, &plain, &encrypted)?;
+        } else {
+            decrypt_file(&key, &encrypted, &plain)?;
+        }
+        println!("Wrote {}", to.display());
+    }
+    Ok(())
+}
+
+fn new_key() -> Result<()> {
+    let path = config_key_path().context("Neither XDG_CONFIG_HOME nor HOME is set")?;
+    if path.exists() {
+        return Err(anyhow!(
+            "{} exists already, inputs encrypted with it could not be read with a new one",
+            path.display()
+        ));
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    write_key(&path, &Key::generate()?)
+        .with_context(|| format!("Could not write {}", path.display()))?;
+    println!("Wrote {}, keep a copy of it somewhere safe", path.display());
+    Ok(())
+}
+
+/// Writes `key` to the new file `path`, which only the user can read from the start and not just
+/// after a chmod.
+fn write_key(path: &Path, key: &Key) -> std::io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(path)?
+        .write_all((key.to_hex() + "\n").as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cli, Cmd};
+    use clap::Parser;
+
+    fn input_cmd(args: &[&str]) -> Result<InputCmd, clap::Error> {
+        let cli = Cli::try_parse_from(["aoc", "input"].iter().chain(args))?;
+        match cli.command {
+            Cmd::Input(cmd) => Ok(cmd),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn paths() {
+        let registry = Registry::discover(&crate::root());
+        let Ok(InputCmd::Stats(args)) = input_cmd(&["stats", "5"]) else {
+            panic!("Not stats");
+        };
+        assert!(day_or_path(&registry, args.year, &args.input)
+            .unwrap()
+            .ends_with("2022/input/day05.txt"));
+        assert_eq!(args.day(), Some(5));
+        assert_eq!(
+            day_or_path(&registry, YEAR, "foo.txt").unwrap(),
+            PathBuf::from("foo.txt")
+        );
+        let Ok(InputCmd::Stats(args)) = input_cmd(&["stats", "--day", "3", "foo.txt"]) else {
+            panic!("Not stats");
+        };
+        assert_eq!(args.day(), Some(3));
+
+        let args = [
+            "diff",
+            "--day",
+            "5",
+            "--profile",
+            "work",
+            "--account",
+            "home",
+        ];
+        let Ok(InputCmd::Diff(args)) = input_cmd(&args) else {
+            panic!("Not diff");
+        };
+        let (a, b) = diff_paths(&registry, &args).unwrap();
+        assert!(a.ends_with("2022/input/work/day05.txt"));
+        assert!(b.ends_with("2022/input/home/day05.txt"));
+        input_cmd(&["diff", "--day", "5"]).unwrap_err();
+        input_cmd(&["diff", "--day", "5", "--account", "work", "a.txt"]).unwrap_err();
+        input_cmd(&["diff", "a.txt", "b.txt"]).unwrap_err();
+        let Ok(InputCmd::Diff(args)) = input_cmd(&["diff", "--day", "5", "a.txt"]) else {
+            panic!("Not diff");
+        };
+        diff_paths(&registry, &args).unwrap_err();
+    }
+
+    #[test]
+    fn diffs_parsed_inputs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let (a, b) = (dir.join("a.txt"), dir.join("b.txt"));
+        fs::write(&a, "1000\n2000\n\n4000\n").unwrap();
+        fs::write(&b, "1000\n\n4000\n\n5000\n").unwrap();
+
+        let diffs = read_parsed(2022, 1, &a)
+            .unwrap()
+            .diff(&read_parsed(2022, 1, &b).unwrap());
+        assert!(diffs.contains(&"$: length 2 vs 3".to_owned()), "{diffs:?}");
+        read_parsed(2022, 24, &a).unwrap_err();
+    }
+
+    #[test]
+    fn writes_keys_only_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("input-key");
+        let key = Key::generate().unwrap();
+        write_key(&path, &key).unwrap();
+        assert_eq!(
+            Key::from_hex(&fs::read_to_string(&path).unwrap()).unwrap(),
+            key
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        write_key(&path, &Key::generate().unwrap()).unwrap_err();
+    }
+
+    #[test]
+    fn gen_arguments() {
+        let Ok(InputCmd::Gen(args)) =
+            input_cmd(&["gen", "--day", "9", "--size", "5", "--seed", "2"])
+        else {
+            panic!("Not gen");
+        };
+        assert_eq!((args.day, args.size, args.seed), (9, 5, Some(2)));
+        let Ok(InputCmd::Gen(args)) = input_cmd(&["gen", "--day", "9"]) else {
+            panic!("Not gen");
+        };
+        assert_eq!(args.size, 1000);
+        input_cmd(&["gen", "--size", "5"]).unwrap_err();
+    }
+
+    #[test]
+    fn fetch_arguments() {
+        let now = fetch::unlock_time(2023, 3);
+
+        let Ok(InputCmd::Fetch(args)) = input_cmd(&["fetch", "--year", "2023", "--all"]) else {
+            panic!("Not fetch");
+        };
+        assert_eq!(args.days(now), [1, 2, 3]);
+        assert_eq!(args.dir, None);
+        let Ok(InputCmd::Fetch(args)) = input_cmd(&["fetch", "--day", "7", "--dir", "x"]) else {
+            panic!("Not fetch");
+        };
+        assert_eq!((args.year, args.days(now)), (YEAR, vec![7]));
+        assert_eq!(args.dir, Some("x".into()));
+        input_cmd(&["fetch", "--year", "2022"]).unwrap_err();
+        input_cmd(&["fetch", "--all", "--day", "7"]).unwrap_err();
+    }
+}
@@ -0,0 +1,38 @@
+//! `aoc leaderboard`.
+
+use crate::YEAR;
+use anyhow::Result;
+use aoc_core::fetch;
+use aoc_core::history::format_duration;
+use aoc_core::leaderboard::{cache_path, load_or_fetch, Leaderboard};
+use aoc_core::net::Client;
+use clap::Args;
+use std::time::SystemTime;
+
+#[derive(Debug, Args)]
+pub struct LeaderboardArgs {
+    /// Id of the leaderboard, the number at the end of its URL
+    id: String,
+    /// Year of the leaderboard
+    #[arg(long, short, default_value_t = YEAR)]
+    year: u16,
+    /// Shows how long each member took for the parts of this day instead
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+}
+
+pub fn leaderboard(args: &LeaderboardArgs) -> Result<()> {
+    let (json, fetched) =
+        load_or_fetch(&cache_path(args.year, &args.id), SystemTime::now(), || {
+            fetch::fetch_leaderboard(&Client::from_session()?, args.year, &args.id)
+        })?;
+    let board = Leaderboard::parse(&json)?;
+
+    match args.day {
+        Some(day) => print!("{}", board.day_report(args.year, day)),
+        None => print!("{}", board.report()),
+    }
+    let age = fetched.elapsed().unwrap_or_default();
+    println!("\nFetched {} ago", format_duration(age));
+    Ok(())
+}
@@ -0,0 +1,269 @@
+#![warn(clippy::pedantic)]
+
+mod bench;
+mod complexity;
+mod cross_check;
+mod determinism;
+mod doctor;
+mod input;
+mod leaderboard;
+mod minimize;
+mod perf;
+mod puzzle;
+mod replay;
+mod report;
+mod run;
+mod serve;
+mod stats;
+mod status;
+mod submit;
+mod timings;
+mod watch;
+
+use anyhow::{anyhow, Context, Result};
+// Links the days of 2023, so they register with `#[aoc]`
+use aoc2023 as _;
+use aoc_core::plugins::{self, PLUGIN_DIR};
+use aoc_core::registry::{workspace_root, Registry};
+use aoc_core::scaffold::new_day;
+use aoc_core::style;
+use bench::BenchArgs;
+use clap::{Args, Parser, Subcommand};
+use complexity::ComplexityArgs;
+use doctor::DoctorArgs;
+use input::InputCmd;
+use leaderboard::LeaderboardArgs;
+use minimize::{MinimizeArgs, MinimizeCheckArgs};
+use puzzle::{FetchPuzzleArgs, OpenArgs};
+use replay::ReplayCmd;
+use report::ReportArgs;
+use run::RunArgs;
+use serve::{ServeArgs, ServeWorkerArgs};
+use stats::StatsArgs;
+use status::StatusArgs;
+use std::path::{Path, PathBuf};
+use submit::SubmitArgs;
+use watch::WatchArgs;
+
+/// The year of the days that commands without `--year` are about.
+const YEAR: u16 = aoc2022::YEAR;
+
+/// Runs the solutions of every year.
+///
+/// Days registered with `#[aoc]` are solved in this process, for everything else the binary of the
+/// day has to be built already.
+#[derive(Debug, Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    #[command(subcommand)]
+    command: Cmd,
+    /// Threads for the days that solve in parallel and for `run --all`, also for the days and
+    /// benchmarks it starts [default: `AOC_THREADS`, or one per CPU]
+    #[arg(long, global = true, value_name = "N")]
+    #[arg(value_parser = clap::value_parser!(u16).range(1..))]
+    threads: Option<u16>,
+}
+
+/// The environment variable for when there is no `--threads`.
+const THREADS_VAR: &str = "AOC_THREADS";
+
+/// `--threads`, or the value of [`THREADS_VAR`] if it is set.
+///
+/// # Errors
+/// If the variable is no number of threads.
+fn thread_count(arg: Option<u16>, var: Option<String>) -> Result<Option<u16>> {
+    if arg.is_some() {
+        return Ok(arg);
+    }
+    var.filter(|v| !v.trim().is_empty())
+        .map(|v| match v.trim().parse() {
+            Ok(n) if n > 0 => Ok(n),
+            _ => Err(anyhow!(
+                "{THREADS_VAR} should be a number of threads, not {v:?}"
+            )),
+        })
+        .transpose()
+}
+
+/// Sizes the global rayon pool, and that of every day and benchmark started from here, which read
+/// `RAYON_NUM_THREADS`. Has to run before anything uses rayon.
+///
+/// # Errors
+/// If the pool was already started.
+fn init_threads(threads: u16) -> Result<()> {
+    std::env::set_var("RAYON_NUM_THREADS", threads.to_string());
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(usize::from(threads))
+        .build_global()
+        .context("Could not size the thread pool")
+}
+
+#[derive(Debug, Subcommand)]
+enum Cmd {
+    /// Runs one day, or all of them
+    Run(RunArgs),
+    /// Submits the answer a day computes for one part
+    Submit(SubmitArgs),
+    /// Lists the implemented days of every year
+    List,
+    /// Shows a calendar of every year with the days that are implemented and have known answers
+    Status(StatusArgs),
+    /// Runs every day and shows its times, answers, input size and lines of code, or how long
+    /// solving the days took with `history`
+    Stats(StatsArgs),
+    /// Summarizes the input, answers and time of a day as markdown or JSON
+    Report(ReportArgs),
+    /// Shows or compares the recordings of simulations written with `aoc run -- --record FILE`
+    #[command(subcommand)]
+    Replay(ReplayCmd),
+    /// Benchmarks the days of this crate with criterion, saving or comparing against baselines
+    Bench(BenchArgs),
+    /// Times a day on generated inputs of growing size and estimates how its time grows
+    Complexity(ComplexityArgs),
+    /// Solves the days of this crate over HTTP with `POST /solve/<year>/<day>`, each request in a
+    /// worker process with limited memory and CPU time
+    Serve(ServeArgs),
+    #[command(hide = true)]
+    ServeWorker(ServeWorkerArgs),
+    /// Creates the module, binary and example of a day from a template and registers it
+    NewDay(NewDayArgs),
+    /// Downloads the text of a puzzle as markdown into puzzles/, to read it offline
+    FetchPuzzle(FetchPuzzleArgs),
+    /// Opens the puzzle of a day in the browser, and its input in the editor if asked to
+    Open(OpenArgs),
+    /// Shows the stars of the members of a private leaderboard, fetched at most every 15 minutes
+    Leaderboard(LeaderboardArgs),
+    /// Downloads the inputs, encrypts them so they can be committed, and looks at or makes up
+    /// others
+    #[command(subcommand)]
+    Input(InputCmd),
+    /// Shrinks an input a day of 2022 can not parse, or answers wrong, to a small one that still
+    /// fails the same way
+    Minimize(MinimizeArgs),
+    #[command(hide = true)]
+    MinimizeCheck(MinimizeCheckArgs),
+    /// Builds and runs a day again whenever its code or input changes, showing how the answers
+    /// changed
+    Watch(WatchArgs),
+    /// Checks that everything is set up to run the days, and says how to fix what is not
+    Doctor(DoctorArgs),
+}
+
+#[derive(Debug, Args)]
+struct NewDayArgs {
+    /// Year of the puzzle
+    #[arg(long, short, default_value_t = YEAR)]
+    year: u16,
+    /// Day of the puzzle
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+}
+
+/// Lines up the cells of the rows, the first row being the header. Colors do not count towards
+/// the width.
+fn columns(rows: &[Vec<String>]) -> String {
+    let mut widths = vec![0; rows.iter().map(Vec::len).max().unwrap_or_default()];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(style::width(cell));
+        }
+    }
+
+    let mut out = String::new();
+    for row in rows {
+        let line: Vec<_> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell}{}", " ".repeat(width - style::width(cell))))
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// The workspace the runner is started in, anywhere below its root, or else the one it was built
+/// in.
+fn root() -> PathBuf {
+    std::env::current_dir()
+        .ok()
+        .and_then(|dir| workspace_root(&dir))
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join(".."))
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    if let Some(threads) = thread_count(cli.threads, std::env::var(THREADS_VAR).ok())? {
+        init_threads(threads)?;
+    }
+    let root = root();
+    let registry = Registry::discover(&root);
+    // They can add implementations to any day, next to those linked in
+    match plugins::load_dir(root.join(PLUGIN_DIR)) {
+        Ok(solvers) => plugins::register(&solvers),
+        Err(e) => eprintln!("Could not load the plugins: {e:#}"),
+    }
+
+    match cli.command {
+        Cmd::Run(args) => run::run(&registry, &args),
+        Cmd::Submit(args) => submit::submit(&registry, &args),
+        Cmd::List => {
+            status::list(&registry);
+            Ok(())
+        }
+        Cmd::Status(args) => status::status(&registry, &args),
+        Cmd::Stats(args) => stats::stats(&registry, &args),
+        Cmd::Report(args) => report::report(&registry, &args),
+        Cmd::Replay(cmd) => replay::replay(&cmd),
+        Cmd::Bench(args) => bench::bench(&registry, &args),
+        Cmd::Complexity(args) => complexity::complexity(&args),
+        Cmd::Serve(args) => serve::serve(&args),
+        Cmd::ServeWorker(args) => serve::serve_worker(&args),
+        Cmd::NewDay(args) => {
+            let dir = registry
+                .dir(args.year)
+                .with_context(|| format!("There is no crate for {}", args.year))?;
+            for path in new_day(dir, args.year, args.day)? {
+                println!("Wrote {}", path.display());
+            }
+            Ok(())
+        }
+        Cmd::FetchPuzzle(args) => puzzle::fetch_puzzle(&args),
+        Cmd::Open(args) => puzzle::open(&registry, &args),
+        Cmd::Leaderboard(args) => leaderboard::leaderboard(&args),
+        Cmd::Input(cmd) => input::input(&registry, &cmd),
+        Cmd::Minimize(args) => minimize::minimize(&args),
+        Cmd::MinimizeCheck(args) => minimize::minimize_check(&args),
+        Cmd::Watch(args) => watch::watch(&registry, &args),
+        Cmd::Doctor(args) => doctor::doctor(&registry, &args),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn threads() {
+        let cli = Cli::try_parse_from(["aoc", "run", "--all", "--threads", "2"]).unwrap();
+        assert_eq!(cli.threads, Some(2));
+        Cli::try_parse_from(["aoc", "--threads", "0", "list"]).unwrap_err();
+
+        assert_eq!(
+            thread_count(Some(2), Some("8".to_owned())).unwrap(),
+            Some(2)
+        );
+        assert_eq!(thread_count(None, Some("8".to_owned())).unwrap(), Some(8));
+        assert_eq!(thread_count(None, Some(String::new())).unwrap(), None);
+        assert_eq!(thread_count(None, None).unwrap(), None);
+        thread_count(None, Some("0".to_owned())).unwrap_err();
+        thread_count(None, Some("many".to_owned())).unwrap_err();
+    }
+
+    #[test]
+    fn finds_days_from_the_workspace() {
+        let registry = Registry::discover(&root());
+        assert!(registry.contains(2022, 11));
+        assert!(registry.contains(2023, 6));
+    }
+}
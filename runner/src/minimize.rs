@@ -0,0 +1,167 @@
+//! `aoc minimize`: shrinks an input a day of 2022 fails on to a small one that still fails the same
+//! way, see `aoc2022::minimize`.
+//!
+//! Every candidate is checked by `aoc minimize-check` in a process of its own: the days may panic
+//! on the broken inputs, which aborts the process in release builds.
+
+use crate::YEAR;
+use anyhow::{ensure, Context, Result};
+use aoc2022::minimize::minimize_lines;
+use aoc_core::compress;
+use aoc_core::error::AocError;
+use aoc_core::fetch::InputSource;
+use aoc_core::registry::{implementation, solution, Solution, REFERENCE};
+use clap::Args;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Args)]
+pub struct MinimizeArgs {
+    /// Day of 2022 that fails on the input
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// The input it fails on
+    #[arg(long, short)]
+    input: PathBuf,
+    /// Keeps the answers disagreeing with the reference implementation of the day, instead of the
+    /// parse error
+    #[arg(long)]
+    wrong_answer: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct MinimizeCheckArgs {
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    #[arg(long)]
+    wrong_answer: bool,
+}
+
+/// Prints the smallest part of the input that still fails.
+///
+/// # Errors
+/// If the input can not be read, or does not fail to begin with.
+pub fn minimize(args: &MinimizeArgs) -> Result<()> {
+    let input = compress::read(&args.input)?;
+    let aoc = std::env::current_exe()?;
+    print!("{}", minimal(&aoc, args.day, &input, args.wrong_answer)?);
+    Ok(())
+}
+
+/// Checks the input on stdin for [`minimize`]: prints how it fails and exits with 1, or exits
+/// with 0 if it does not fail. A panic exits otherwise.
+///
+/// # Errors
+/// If the day does not exist or has no reference implementation.
+pub fn minimize_check(args: &MinimizeCheckArgs) -> Result<()> {
+    let (solution, reference) = implementations(args.day, args.wrong_answer)?;
+    let input = InputSource::Stdin.read()?;
+    if let Some(failure) = failure(solution, reference, &input) {
+        print!("{failure}");
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// The main implementation of the day, and its reference with `wrong_answer`.
+fn implementations(
+    day: u8,
+    wrong_answer: bool,
+) -> Result<(&'static Solution, Option<&'static Solution>)> {
+    let solution = solution(YEAR, day)
+        .with_context(|| format!("{YEAR} day {day} is not registered with #[aoc]"))?;
+    if !wrong_answer {
+        return Ok((solution, None));
+    }
+
+    let reference = implementation(YEAR, day, REFERENCE)
+        .with_context(|| format!("{YEAR} day {day} has no reference implementation"))?;
+    Ok((solution, Some(reference)))
+}
+
+/// How `input` fails: the end of its parse error, or nothing when it disagrees with the
+/// `reference`. Inputs either of them fails on do not count as disagreeing.
+fn failure(solution: &Solution, reference: Option<&Solution>, input: &str) -> Option<String> {
+    if let Some(reference) = reference {
+        let answers = solution.solve(input, &[]).ok()?;
+        let expected = reference.solve(input, &[]).ok()?;
+        return (answers != expected).then(String::new);
+    }
+
+    // The reason is the whole chain, like `Line 4: Invalid line: ..`, where the line moves as
+    // lines are removed, so only its end has to stay
+    match solution.parse(input) {
+        Err(AocError::ParseError { reason, .. }) => {
+            Some(reason.rsplit(": ").next().unwrap_or_default().to_owned())
+        }
+        _ => None,
+    }
+}
+
+fn minimal(aoc: &Path, day: u8, input: &str, wrong_answer: bool) -> Result<String> {
+    implementations(day, wrong_answer)?;
+    // The exit status and what it printed, the same for candidates that fail the same way
+    let check = |candidate: &str| -> Result<(Option<i32>, String)> {
+        let mut command = Command::new(aoc);
+        command.args(["minimize-check", "--day", &day.to_string()]);
+        if wrong_answer {
+            command.arg("--wrong-answer");
+        }
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Could not run {}", aoc.display()))?;
+        // One that panics early does not read everything, the status tells what happened
+        let _ = child
+            .stdin
+            .take()
+            .expect("Piped")
+            .write_all(candidate.as_bytes());
+        let out = child.wait_with_output()?;
+        Ok((
+            out.status.code(),
+            String::from_utf8_lossy(&out.stdout).into_owned(),
+        ))
+    };
+
+    let failed = check(input)?;
+    ensure!(
+        failed.0 != Some(0),
+        if wrong_answer {
+            "The answers agree with the reference, or one of them fails"
+        } else {
+            "The input parses without error"
+        }
+    );
+    Ok(minimize_lines(input, |candidate| {
+        check(candidate).is_ok_and(|result| result == failed)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cli, Cmd};
+    use clap::Parser;
+
+    #[test]
+    fn minimizes() {
+        let cli = Cli::try_parse_from(["aoc", "minimize", "--day", "10", "--input", "a.txt"]);
+        assert!(matches!(cli.unwrap().command, Cmd::Minimize(_)));
+        let cli = Cli::try_parse_from(["aoc", "minimize-check", "--day", "10", "--wrong-answer"]);
+        assert!(matches!(cli.unwrap().command, Cmd::MinimizeCheck(_)));
+
+        let (day10, _) = implementations(10, false).unwrap();
+        let broken = "noop\naddx 3\naddx -5\njump 2\nnoop\n";
+        assert!(failure(day10, None, broken).is_some_and(|reason| !reason.is_empty()));
+        assert_eq!(failure(day10, None, aoc2022::day10::EXAMPLE), None);
+
+        // Day 10 agrees with its reference, day 1 has none
+        let (day10, reference) = implementations(10, true).unwrap();
+        assert_eq!(failure(day10, reference, aoc2022::day10::EXAMPLE), None);
+        assert!(implementations(1, true).is_err());
+    }
+}
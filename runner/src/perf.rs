@@ -0,0 +1,126 @@
+//! `aoc run --perf`: hardware counters of the phases of the registered days.
+
+use crate::columns;
+use crate::run::input_source;
+use anyhow::{anyhow, Result};
+use aoc_core::perf::{Counts, PerfCounters, PhaseCounts};
+use aoc_core::registry::solution;
+use std::path::Path;
+
+/// Counts the phases of the registered days one after another, like `--timings`.
+///
+/// # Errors
+/// If the counters can not be opened, or a day fails to read or parse its input.
+pub fn perf(days: &[(u16, u8)], input: Option<&Path>, account: Option<&str>) -> Result<()> {
+    let mut counters = PerfCounters::open()?;
+    let mut counted = vec![];
+    let mut skipped = vec![];
+    let mut failed = vec![];
+    for &(year, day) in days {
+        let Some(solution) = solution(year, day) else {
+            skipped.push(format!("{year} day {day}"));
+            continue;
+        };
+        match input_source(year, day, input, account)
+            .read()
+            .map_err(anyhow::Error::from)
+            .and_then(|input| counters.phases(solution, &input))
+        {
+            Ok(counts) => counted.push((year, day, counts)),
+            Err(e) => failed.push(format!("{year} day {day}: {e:#}")),
+        }
+    }
+
+    print!("{}", perf_table(&counted));
+    if !skipped.is_empty() {
+        println!(
+            "Not registered with #[aoc], so not counted: {}",
+            skipped.join(", ")
+        );
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("{}", failed.join("\n")))
+    }
+}
+
+/// One line per phase of every day, `-` for counters the CPU does not have.
+fn perf_table(counted: &[(u16, u8, PhaseCounts)]) -> String {
+    let show = |v: Option<u64>| v.map_or_else(|| "-".to_owned(), |v| v.to_string());
+
+    let mut rows = vec![[
+        "Year",
+        "Day",
+        "Phase",
+        "Cycles",
+        "Instructions",
+        "Cache misses",
+        "Branch misses",
+    ]
+    .map(str::to_owned)
+    .to_vec()];
+    for (year, day, counts) in counted {
+        let phases: [(&str, &Counts); 3] = [
+            ("Parse", &counts.parse),
+            ("Part 1", &counts.part1),
+            ("Part 2", &counts.part2),
+        ];
+        for (phase, counts) in phases {
+            rows.push(vec![
+                year.to_string(),
+                day.to_string(),
+                phase.to_owned(),
+                show(counts.cycles),
+                show(counts.instructions),
+                show(counts.cache_misses),
+                show(counts.branch_misses),
+            ]);
+        }
+    }
+    columns(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cli, Cmd};
+    use clap::Parser;
+
+    #[test]
+    fn counts_table() {
+        let counts = |cycles| Counts {
+            cycles: Some(cycles),
+            instructions: Some(cycles * 2),
+            cache_misses: None,
+            branch_misses: Some(3),
+        };
+        let counted = [(
+            2022,
+            8,
+            PhaseCounts {
+                parse: counts(1000),
+                part1: counts(20),
+                part2: counts(300),
+            },
+        )];
+
+        assert_eq!(
+            perf_table(&counted),
+            "\
+Year  Day  Phase   Cycles  Instructions  Cache misses  Branch misses
+2022  8    Parse   1000    2000          -             3
+2022  8    Part 1  20      40            -             3
+2022  8    Part 2  300     600           -             3
+"
+        );
+
+        let parses = |args: &[&str]| Cli::try_parse_from([&["aoc", "run"], args].concat());
+        assert!(matches!(
+            parses(&["--day", "8", "--perf"]).unwrap().command,
+            Cmd::Run(_)
+        ));
+        parses(&["--day", "8", "--perf", "--timings"]).unwrap_err();
+    }
+}
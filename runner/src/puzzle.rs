@@ -0,0 +1,123 @@
+//! `aoc fetch-puzzle` and `aoc open`.
+
+use crate::YEAR;
+use anyhow::{Context, Result};
+use aoc_core::compress;
+use aoc_core::fetch::{self, input_path, INPUT_DIR};
+use aoc_core::net::Client;
+use aoc_core::puzzle::{puzzle_path, puzzle_url, to_markdown};
+use aoc_core::registry::Registry;
+use clap::Args;
+use std::fs;
+use std::process::Command;
+use std::time::SystemTime;
+
+#[derive(Debug, Args)]
+pub struct FetchPuzzleArgs {
+    /// Year of the puzzle
+    year: u16,
+    /// Day of the puzzle
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+}
+
+#[derive(Debug, Args)]
+pub struct OpenArgs {
+    /// Year of the puzzle [default: 2022]
+    #[arg(required_unless_present = "next")]
+    year: Option<u16>,
+    /// Day of the puzzle
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    #[arg(required_unless_present = "next", conflicts_with = "next")]
+    day: Option<u8>,
+    /// Opens the first unlocked day of the year that is not implemented yet
+    #[arg(long)]
+    next: bool,
+    /// Also opens the input of the day in $VISUAL or $EDITOR
+    #[arg(long)]
+    input: bool,
+}
+
+/// Downloads the puzzle again every time, as part 2 only shows up once part 1 is solved.
+pub fn fetch_puzzle(args: &FetchPuzzleArgs) -> Result<()> {
+    let FetchPuzzleArgs { year, day } = *args;
+    let html = fetch::fetch_puzzle(&Client::from_session()?, year, day)
+        .with_context(|| format!("Could not download {year} day {day}"))?;
+    let markdown = to_markdown(&html).with_context(|| format!("{year} day {day}"))?;
+
+    let path = puzzle_path(&crate::root(), year, day);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, markdown).with_context(|| format!("Could not write {}", path.display()))?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+pub fn open(registry: &Registry, args: &OpenArgs) -> Result<()> {
+    let year = args.year.unwrap_or(YEAR);
+    let day = match args.day {
+        Some(day) => day,
+        None => next_day(registry, year, SystemTime::now())?,
+    };
+
+    let url = puzzle_url(year, day);
+    println!("Opening {url}");
+    webbrowser::open(&url).with_context(|| format!("Could not open {url}"))?;
+
+    if args.input {
+        let dir = registry
+            .dir(year)
+            .with_context(|| format!("There is no crate for {year}"))?;
+        let path = input_path(&dir.join(INPUT_DIR), day);
+        let path = compress::find(&path)
+            .with_context(|| format!("There is no {}, `aoc run` downloads it", path.display()))?;
+        let editor = std::env::var("VISUAL")
+            .or_else(|_| std::env::var("EDITOR"))
+            .context("Set VISUAL or EDITOR to open the input")?;
+        // Like `code --wait`
+        let mut words = editor.split_whitespace();
+        Command::new(words.next().context("VISUAL or EDITOR is empty")?)
+            .args(words)
+            .arg(&path)
+            .status()
+            .with_context(|| format!("Could not run {editor}"))?;
+    }
+    Ok(())
+}
+
+/// The first day of `year` that is unlocked at `now` but not implemented.
+fn next_day(registry: &Registry, year: u16, now: SystemTime) -> Result<u8> {
+    fetch::unlocked_days(year, now)
+        .into_iter()
+        .find(|&day| !registry.contains(year, day))
+        .with_context(|| format!("Every unlocked day of {year} is implemented"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cli, Cmd};
+    use clap::Parser;
+
+    #[test]
+    fn finds_next_day() {
+        let registry = Registry::discover(&crate::root());
+        let now = SystemTime::now();
+        let first_missing = (1..=25).find(|&day| !registry.contains(YEAR, day));
+        assert_eq!(next_day(&registry, YEAR, now).ok(), first_missing);
+
+        let before = fetch::unlock_time(YEAR, 2);
+        assert_eq!(next_day(&registry, YEAR, before).ok(), None);
+    }
+
+    #[test]
+    fn parses_args() {
+        let cli = Cli::try_parse_from(["aoc", "fetch-puzzle", "2021", "7"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Cmd::FetchPuzzle(FetchPuzzleArgs { year: 2021, day: 7 })
+        ));
+        Cli::try_parse_from(["aoc", "fetch-puzzle", "2021", "26"]).unwrap_err();
+    }
+}
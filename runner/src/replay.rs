@@ -0,0 +1,79 @@
+//! `aoc replay`: looks at the recordings the simulations write with `--record`.
+
+use anyhow::{anyhow, Result};
+use aoc_core::record::{frame_diff, read_recording};
+use clap::{Args, Subcommand};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Subcommand)]
+pub enum ReplayCmd {
+    /// Prints the state of every step of a recording
+    Show(ShowArgs),
+    /// Shows the first step where two recordings differ, fails if they do
+    Diff(DiffArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ShowArgs {
+    recording: PathBuf,
+    /// The first step to show
+    #[arg(long, default_value_t = 0)]
+    from: usize,
+    /// How many steps to show [default: all of them]
+    #[arg(long)]
+    steps: Option<usize>,
+}
+
+#[derive(Debug, Args)]
+pub struct DiffArgs {
+    a: PathBuf,
+    b: PathBuf,
+}
+
+fn show(path: &Path, first: usize, steps: usize) -> Result<()> {
+    let frames: Vec<Value> = read_recording(path)?;
+
+    for (step, frame) in frames.iter().enumerate().skip(first).take(steps) {
+        println!("{step:>6}: {frame}");
+    }
+
+    Ok(())
+}
+
+fn diff(a_path: &Path, b_path: &Path) -> Result<()> {
+    let a: Vec<Value> = read_recording(a_path)?;
+    let b: Vec<Value> = read_recording(b_path)?;
+    let differ = || anyhow!("{} and {} differ", a_path.display(), b_path.display());
+
+    for (step, (a, b)) in a.iter().zip(&b).enumerate() {
+        let diffs = frame_diff(a, b);
+        if !diffs.is_empty() {
+            println!("First difference at step {step}:");
+            for diff in diffs {
+                println!("  {diff}");
+            }
+            return Err(differ());
+        }
+    }
+
+    if a.len() != b.len() {
+        println!(
+            "Recordings agree for {} steps, but have {} vs {} steps",
+            a.len().min(b.len()),
+            a.len(),
+            b.len()
+        );
+        return Err(differ());
+    }
+
+    println!("Recordings are identical ({} steps)", a.len());
+    Ok(())
+}
+
+pub fn replay(cmd: &ReplayCmd) -> Result<()> {
+    match cmd {
+        ReplayCmd::Show(args) => show(&args.recording, args.from, args.steps.unwrap_or(usize::MAX)),
+        ReplayCmd::Diff(args) => diff(&args.a, &args.b),
+    }
+}
@@ -0,0 +1,265 @@
+//! `aoc report`: a summary of the input, answers and time of one day.
+
+use crate::run::day_output;
+use crate::YEAR;
+use anyhow::{anyhow, Context, Result};
+use aoc2022::input_stats::{ParsedStats, Stats};
+use aoc_core::answer::Answer;
+use aoc_core::compress;
+use aoc_core::days::parse_answers;
+use aoc_core::fetch::{input_path, INPUT_DIR};
+use aoc_core::parse::normalize;
+use aoc_core::registry::Registry;
+use clap::Args;
+use serde::Serialize;
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Args)]
+pub struct ReportArgs {
+    /// Year of the puzzle
+    #[arg(long, short, default_value_t = YEAR)]
+    year: u16,
+    /// Day of the puzzle
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// How to write it: md or json
+    #[arg(long, default_value_t = Format::default())]
+    format: Format,
+    /// Solves the day this often, the fastest time counts
+    #[arg(long, default_value_t = 3)]
+    #[arg(value_parser = clap::value_parser!(u32).range(1..))]
+    runs: u32,
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+enum Format {
+    #[default]
+    Md,
+    Json,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Format::Md => "md",
+            Format::Json => "json",
+        })
+    }
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        [Format::Md, Format::Json]
+            .into_iter()
+            .find(|format| format.to_string() == s)
+            .ok_or_else(|| anyhow!("Unknown format {s:?}, available are: md, json"))
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Report {
+    year: u16,
+    day: u8,
+    input: Option<Stats>,
+    /// What the day parses the input to, if it is registered with `#[aoc]` and can show it
+    parsed: Option<ParsedStats>,
+    answers: Vec<(String, Answer)>,
+    /// Fastest wall clock time of the whole day, including reading and parsing, and starting its
+    /// binary if it is not registered with `#[aoc]`
+    time: Duration,
+    /// In `vis/` of the crate of the year
+    visualizations: Vec<PathBuf>,
+}
+
+impl Report {
+    /// Solves the day `runs` times, in this process if it is registered with `#[aoc]` and with
+    /// its binary otherwise.
+    fn generate(registry: &Registry, year: u16, day: u8, runs: u32) -> Result<Self> {
+        let dir = registry
+            .dir(year)
+            .with_context(|| format!("There is no crate for {year}"))?;
+        if !registry.contains(year, day) {
+            return Err(anyhow!(
+                "{year} day {day} is not implemented, see `aoc list`"
+            ));
+        }
+
+        let mut best: Option<(Duration, String)> = None;
+        for _ in 0..runs.max(1) {
+            let start = Instant::now();
+            let output = day_output(year, day, &[])?;
+            let time = start.elapsed();
+            if best.as_ref().is_none_or(|(best, _)| time < *best) {
+                best = Some((time, output));
+            }
+        }
+        let (time, output) = best.expect("Ran at least once");
+
+        let input = compress::read(&input_path(&dir.join(INPUT_DIR), day))
+            .ok()
+            .map(|input| normalize(&input).into_owned());
+        // Not every day can show what it parsed, see `Solver::dump`
+        let parsed = input
+            .as_ref()
+            .and_then(|input| ParsedStats::of_day(year, day, input).ok());
+        let input = input.map(|input| Stats::from_input(&input));
+
+        Ok(Self {
+            year,
+            day,
+            input,
+            parsed,
+            answers: parse_answers(&output),
+            time,
+            visualizations: visualizations(&dir.join("vis"), day),
+        })
+    }
+
+    fn markdown(&self) -> String {
+        let mut md = format!("# {} day {}\n\n", self.year, self.day);
+        let line = |md: &mut String, s: &str| {
+            writeln!(md, "{s}").expect("Writing to a String does not fail");
+        };
+
+        line(&mut md, "## Input\n");
+        match &self.input {
+            Some(stats) => line(&mut md, &format!("```\n{stats}```\n")),
+            None => line(&mut md, "Input not available.\n"),
+        }
+        if let Some(parsed) = &self.parsed {
+            line(&mut md, &format!("Parsed:\n\n```\n{parsed}```\n"));
+        }
+
+        line(&mut md, "## Answers\n");
+        for (part, answer) in &self.answers {
+            if answer.is_picture() {
+                line(&mut md, &format!("{part}:\n\n```\n{answer}\n```\n"));
+            } else {
+                line(&mut md, &format!("{part}: `{answer}`\n"));
+            }
+        }
+
+        line(&mut md, "## Timing\n");
+        line(&mut md, &format!("Whole run: {:.3?}\n", self.time));
+
+        if !self.visualizations.is_empty() {
+            line(&mut md, "## Visualizations\n");
+            for vis in &self.visualizations {
+                line(&mut md, &format!("![{0}]({0})\n", vis.display()));
+            }
+        }
+
+        md
+    }
+}
+
+/// The files in `dir` that start with `dayNN`, as `vis/<name>`.
+fn visualizations(dir: &Path, day: u8) -> Vec<PathBuf> {
+    let prefix = format!("day{day:02}");
+    let mut visualizations: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok()?.file_name().into_string().ok())
+        .filter(|name| name.starts_with(&prefix))
+        .map(|name| Path::new("vis").join(name))
+        .collect();
+    visualizations.sort();
+    visualizations
+}
+
+pub fn report(registry: &Registry, args: &ReportArgs) -> Result<()> {
+    let report = Report::generate(registry, args.year, args.day, args.runs)?;
+    match args.format {
+        Format::Md => print!("{}", report.markdown()),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown() {
+        let report = Report {
+            year: 2022,
+            day: 10,
+            input: None,
+            parsed: None,
+            answers: vec![
+                ("Part 1".into(), "13140".into()),
+                ("Part 2".into(), "##\n.#".into()),
+            ],
+            time: Duration::from_millis(2),
+            visualizations: vec![],
+        };
+
+        assert_eq!(
+            report.markdown(),
+            "# 2022 day 10
+
+## Input
+
+Input not available.
+
+## Answers
+
+Part 1: `13140`
+
+Part 2:
+
+```
+##
+.#
+```
+
+## Timing
+
+Whole run: 2.000ms
+
+"
+        );
+    }
+
+    #[test]
+    fn parsed_input() {
+        let input = "1000\n2000\n\n4000\n";
+        let report = Report {
+            year: 2022,
+            day: 1,
+            input: Some(Stats::from_input(input)),
+            parsed: Some(ParsedStats::from_json(&serde_json::json!([
+                [1000, 2000],
+                [4000]
+            ]))),
+            answers: vec![],
+            time: Duration::from_millis(2),
+            visualizations: vec![],
+        };
+
+        let md = report.markdown();
+        assert!(
+            md.contains("Parsed:\n\n```\n$      1 array, length 2\n$[]    2 array, length 1..=2\n"),
+            "{md}"
+        );
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(
+            json["parsed"]["$[][]"]["range"],
+            serde_json::json!([1000.0, 4000.0])
+        );
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        "html".parse::<Format>().unwrap_err();
+    }
+}
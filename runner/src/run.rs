@@ -0,0 +1,873 @@
+//! `aoc run`: solves one day, or all of them, and checks or times them.
+
+use crate::cross_check::{cross_check, run_implementation};
+use crate::determinism::determinism;
+use crate::perf::perf;
+use crate::timings::timings;
+use crate::{columns, YEAR};
+use anyhow::{anyhow, ensure, Context, Result};
+use aoc_core::answer::Answer;
+use aoc_core::answer_cache::AnswerCache;
+use aoc_core::cancel;
+use aoc_core::compress;
+use aoc_core::days::{day_binary, parse_answers};
+use aoc_core::error::AocError;
+use aoc_core::fetch::{self, input_path, InputSource};
+use aoc_core::logging;
+use aoc_core::registry::{solution, Registry};
+use aoc_core::screen::{self, Density};
+use aoc_core::sniff;
+use aoc_core::solver::{format_answers, styled_answers};
+use aoc_core::style::{self, Role, Style, Theme};
+use aoc_core::verify::{self, Answers, Verification};
+use clap::Args;
+use rayon::prelude::*;
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use tracing::level_filters::LevelFilter;
+
+#[derive(Debug, Args)]
+#[allow(clippy::struct_excessive_bools)] // They are independent flags
+pub struct RunArgs {
+    /// Year of the puzzle [default: 2022, or all years with --all]
+    #[arg(long, short)]
+    year: Option<u16>,
+    /// Day of the puzzle
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=25))]
+    #[arg(required_unless_present_any = ["all", "detect"], conflicts_with = "all")]
+    day: Option<u8>,
+    /// Runs every implemented day, those registered with `#[aoc]` in parallel, and shows their
+    /// answers in a table
+    #[arg(long)]
+    all: bool,
+    /// Only runs these parts, by name or number
+    #[arg(long, short)]
+    part: Vec<String>,
+    /// Reads the input from this file instead, `-` for stdin
+    #[arg(long, short, conflicts_with = "all")]
+    input: Option<PathBuf>,
+    /// Finds out from what the input file looks like which day it is for
+    #[arg(long, requires = "input", conflicts_with_all = ["day", "all"])]
+    detect: bool,
+    /// Solves the example of the puzzle text instead of the input, only for days registered with
+    /// `#[aoc]`
+    #[arg(long, conflicts_with_all = ["input", "verify", "bless", "timings", "perf", "animate"])]
+    example: bool,
+    /// Solves the days with every implementation registered with `#[aoc]` and checks that they
+    /// agree
+    #[arg(long, conflicts_with_all = ["verify", "bless", "timings", "perf", "animate"])]
+    cross_check: bool,
+    /// Solves the day with the implementation of this name instead of the main one, like `bits`
+    /// or a `plugin:<name>`
+    #[arg(long = "impl", value_name = "NAME", conflicts_with_all = ["all", "cross_check"])]
+    #[arg(conflicts_with_all = ["verify", "bless", "timings", "perf", "animate", "parse_only"])]
+    implementation: Option<String>,
+    /// Uses the inputs in input/ACCOUNT/ of the year instead, downloaded with the session cookie of
+    /// that account, and its known answers in answers-ACCOUNT.toml
+    #[arg(long, conflicts_with_all = ["input", "example"])]
+    account: Option<String>,
+    /// Compares the answers with the known ones in answers.toml of the year
+    #[arg(long, conflicts_with = "input")]
+    verify: bool,
+    /// Stores the answers in answers.toml of the year as the known ones
+    #[arg(long, conflicts_with_all = ["input", "verify"])]
+    bless: bool,
+    /// Times parsing and each part of the days registered with `#[aoc]`, instead of printing their
+    /// answers
+    #[arg(long, conflicts_with_all = ["part", "verify", "bless", "animate"])]
+    timings: bool,
+    /// Counts cycles, instructions, cache and branch misses of parsing and each part of the days
+    /// registered with `#[aoc]`, on Linux
+    #[arg(long, conflicts_with_all = ["part", "verify", "bless", "animate", "timings"])]
+    perf: bool,
+    /// Solves the days RUNS times in new processes, with new hash seeds and 1, 2, 4 or 8 rayon
+    /// threads, and checks that they always print the same
+    #[arg(long, value_name = "RUNS", num_args = 0..=1, default_missing_value = "8")]
+    #[arg(value_parser = clap::value_parser!(u32).range(2..))]
+    #[arg(conflicts_with_all = ["cross_check", "verify", "bless", "timings", "perf", "animate"])]
+    #[arg(conflicts_with_all = ["export", "checkpoint", "resume", "dot", "parse_only"])]
+    determinism: Option<u32>,
+    /// Shows the simulations of days that have one in the terminal, at FPS frames per second
+    #[arg(long, value_name = "FPS", num_args = 0..=1, default_missing_value = "10")]
+    #[arg(value_parser = clap::value_parser!(u32).range(1..))]
+    #[arg(conflicts_with_all = ["all", "verify", "bless"])]
+    animate: Option<u32>,
+    /// Writes the simulation of days 5 and 9 or the infi walk to FILE, a .gif or an animated .png,
+    /// at the frames per second of --animate
+    #[arg(long, value_name = "FILE")]
+    #[arg(conflicts_with_all = ["all", "example", "cross_check", "timings", "perf"])]
+    #[arg(conflicts_with_all = ["verify", "bless"])]
+    export: Option<PathBuf>,
+    /// The pixels of every character in the frames of --export
+    #[arg(long, value_name = "PIXELS", requires = "export")]
+    #[arg(value_parser = clap::value_parser!(u32).range(1..))]
+    scale: Option<u32>,
+    /// Saves the state of long simulations to FILE now and then, and continues from it if it is
+    /// already there
+    #[arg(long, value_name = "FILE")]
+    #[arg(conflicts_with_all = ["all", "example", "cross_check", "timings", "perf", "resume"])]
+    checkpoint: Option<PathBuf>,
+    /// Continues a simulation from the state saved with --checkpoint, after an interrupt
+    #[arg(long, value_name = "FILE")]
+    #[arg(conflicts_with_all = ["all", "example", "cross_check", "timings", "perf"])]
+    resume: Option<PathBuf>,
+    /// Writes what days 7 and 11 of 2022 and day 5 of 2023 parsed as a Graphviz graph to FILE
+    #[arg(long, value_name = "FILE")]
+    #[arg(conflicts_with_all = ["all", "example", "cross_check", "timings", "perf"])]
+    dot: Option<PathBuf>,
+    /// Gives up on the parts that take longer than this, like `30s`, `500ms` or `2m`, for the days
+    /// that check for it
+    #[arg(long, value_name = "DURATION", value_parser = cancel::parse_duration)]
+    timeout: Option<Duration>,
+    /// Logs what the days do to stderr, up to this level (off, error, warn, info, debug or trace)
+    #[arg(long, value_name = "LEVEL", default_value_t = logging::DEFAULT_LEVEL)]
+    log_level: LevelFilter,
+    /// Prints without colors, also done when `NO_COLOR` is set or the output is not a terminal
+    #[arg(long)]
+    no_color: bool,
+    /// The colors to print in: default, crt or mono
+    #[arg(long, default_value_t = Theme::default())]
+    theme: Theme,
+    /// How many pixels of a picture one character shows: block, half or braille, for pictures
+    /// wider than the terminal
+    #[arg(long, default_value_t = Density::default())]
+    density: Density,
+    /// Computes the answers again instead of taking the ones of the same input from ~/.cache/aoc
+    #[arg(long)]
+    force: bool,
+    /// Keeps the parsed input next to the input and reuses it while the input does not change,
+    /// for the days that support it
+    #[arg(long, conflicts_with = "example")]
+    parse_cache: bool,
+    /// Prints the parsed input of the day as JSON instead of solving it
+    #[arg(long, conflicts_with_all = ["all", "example", "cross_check", "timings", "perf"])]
+    #[arg(conflicts_with_all = ["verify", "bless", "animate", "export"])]
+    parse_only: bool,
+    /// Passed on to the day
+    #[arg(last = true)]
+    args: Vec<String>,
+}
+
+impl RunArgs {
+    /// Arguments for the day itself.
+    ///
+    /// # Errors
+    /// If the input file or the checkpoint to resume does not exist.
+    fn day_args(&self) -> Result<Vec<String>> {
+        let mut args = vec![];
+        for part in &self.part {
+            args.extend(["--part".to_owned(), part.clone()]);
+        }
+        if let Some(input) = &self.input {
+            // Other years run in their own directory
+            let input = if input == Path::new("-") {
+                input.clone()
+            } else {
+                input
+                    .canonicalize()
+                    .with_context(|| format!("Could not find {}", input.display()))?
+            };
+            args.extend(["--input".to_owned(), input.display().to_string()]);
+        }
+        if let Some(account) = &self.account {
+            args.extend(["--account".to_owned(), account.clone()]);
+        }
+        if let Some(fps) = self.animate {
+            args.extend(["--animate".to_owned(), fps.to_string()]);
+        }
+        if let Some(timeout) = self.timeout {
+            args.extend(["--timeout".to_owned(), format!("{}ms", timeout.as_millis())]);
+        }
+        if self.log_level != logging::DEFAULT_LEVEL {
+            args.extend(["--log-level".to_owned(), self.log_level.to_string()]);
+        }
+        if let Some(checkpoint) = &self.checkpoint {
+            let checkpoint = std::path::absolute(checkpoint)?;
+            args.extend(["--checkpoint".to_owned(), checkpoint.display().to_string()]);
+        }
+        if let Some(resume) = &self.resume {
+            let resume = resume
+                .canonicalize()
+                .with_context(|| format!("Could not find {}", resume.display()))?;
+            args.extend(["--resume".to_owned(), resume.display().to_string()]);
+        }
+        if let Some(export) = &self.export {
+            let export = std::path::absolute(export)?;
+            args.extend(["--export".to_owned(), export.display().to_string()]);
+        }
+        if let Some(scale) = self.scale {
+            args.extend(["--scale".to_owned(), scale.to_string()]);
+        }
+        if let Some(dot) = &self.dot {
+            let dot = std::path::absolute(dot)?;
+            args.extend(["--dot".to_owned(), dot.display().to_string()]);
+        }
+        if self.parse_cache {
+            args.push("--parse-cache".to_owned());
+        }
+        if self.parse_only {
+            args.push("--parse-only".to_owned());
+        }
+        args.extend(self.args.iter().cloned());
+        Ok(args)
+    }
+
+    /// Arguments for days that print to the terminal themselves.
+    fn style_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.no_color {
+            args.push("--no-color".to_owned());
+        }
+        if self.theme != Theme::default() {
+            args.extend(["--theme".to_owned(), self.theme.to_string()]);
+        }
+        if self.density != Density::default() {
+            args.extend(["--density".to_owned(), self.density.to_string()]);
+        }
+        args
+    }
+
+    /// Where answers are cached, `None` with `--force`.
+    fn cache(&self) -> Option<AnswerCache> {
+        (!self.force).then(AnswerCache::open).flatten()
+    }
+
+    /// The days to run, in order.
+    fn days(&self, registry: &Registry) -> Result<Vec<(u16, u8)>> {
+        if self.detect {
+            return self.detected(registry).map(|day| vec![day]);
+        }
+        if let Some(day) = self.day {
+            let year = self.year.unwrap_or(YEAR);
+            return if registry.contains(year, day) {
+                Ok(vec![(year, day)])
+            } else {
+                Err(anyhow!(
+                    "{year} day {day} is not implemented, see `aoc list`"
+                ))
+            };
+        }
+
+        let days: Vec<_> = registry
+            .all()
+            .filter(|&(year, _)| self.year.is_none() || self.year == Some(year))
+            .collect();
+        if days.is_empty() {
+            Err(anyhow!("No days found, see `aoc list`"))
+        } else {
+            Ok(days)
+        }
+    }
+
+    /// The day the `--input` file is for, with `--detect`.
+    ///
+    /// # Errors
+    /// If the input can not be read, or it does not look like one implemented day.
+    fn detected(&self, registry: &Registry) -> Result<(u16, u8)> {
+        let path = self.input.as_deref().context("--detect needs --input")?;
+        ensure!(
+            path != Path::new("-"),
+            "--detect needs a file, stdin can only be read once"
+        );
+        let input = InputSource::File(path.to_owned()).read()?;
+
+        let days: Vec<_> = sniff::detect(&input)
+            .into_iter()
+            .filter(|&(year, day)| {
+                self.year.is_none_or(|y| y == year) && registry.contains(year, day)
+            })
+            .collect();
+        match days[..] {
+            [(year, day)] => {
+                eprintln!("{} looks like {year} day {day}", path.display());
+                Ok((year, day))
+            }
+            [] => Err(anyhow!(
+                "{} does not look like the input of any implemented day",
+                path.display()
+            )),
+            _ => Err(anyhow!(
+                "{} could be the input of {days:?}, select one with --day",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// The binary of a day, for what only the day itself can do (like `--dot`). Plain runs of the
+/// registered days of every year are solved in this process instead.
+fn command(year: u16, day: u8, args: &[String]) -> Result<Command> {
+    let mut command = Command::new(day_binary(year, day)?);
+    command.args(args);
+    Ok(command)
+}
+
+/// Solves a day registered with `#[aoc]` in this process when nothing but parts (and the
+/// account) is selected, runs its binary with the arguments and `style_args` otherwise.
+fn run_day(year: u16, day: u8, args: &[String], style_args: &[String]) -> Result<()> {
+    if let (Some(solution), Some(parts)) = (solution(year, day), only_parts(args)) {
+        let account = account(args);
+        let input = InputSource::Default { year, day, account }.read()?;
+        let answers = solution
+            .solve(&input, &parts)
+            .with_context(|| format!("{year} day {day}"))?;
+        print!("{}", styled_answers(&answers));
+        return Ok(());
+    }
+
+    let status = command(year, day, &[args, style_args].concat())?
+        .status()
+        .with_context(|| format!("Could not run {year} day {day}"))?;
+    ensure!(status.success(), "{year} day {day} failed with {status}");
+    Ok(())
+}
+
+pub fn run(registry: &Registry, args: &RunArgs) -> Result<()> {
+    // For the days solved in here, the others get --log-level
+    logging::init(args.log_level);
+    style::init(Style::detect(args.no_color, args.theme));
+    screen::init(Density::detect(args.density));
+    cancel::init(args.timeout);
+    if args.verify || args.bless {
+        return verify(registry, args);
+    }
+
+    let days = args.days(registry)?;
+    if args.cross_check {
+        let (input, account) = (args.input.as_deref(), args.account.as_deref());
+        return cross_check(&days, input, account, args.example, &args.part);
+    }
+    if let Some(name) = &args.implementation {
+        let (input, account) = (args.input.as_deref(), args.account.as_deref());
+        return run_implementation(&days, name, input, account, args.example, &args.part);
+    }
+    if let Some(runs) = args.determinism {
+        let (input, account) = (args.input.as_deref(), args.account.as_deref());
+        return determinism(&days, runs, &args.part, input, account, args.example);
+    }
+    if args.example {
+        return examples(&days, &args.part, args.all);
+    }
+    if args.timings {
+        return timings(&days, args.input.as_deref(), args.account.as_deref());
+    }
+    if args.perf {
+        return perf(&days, args.input.as_deref(), args.account.as_deref());
+    }
+    let day_args = args.day_args()?;
+    if args.all {
+        return run_all(registry, &days, &day_args, args.cache().as_ref());
+    }
+
+    let style_args = args.style_args();
+    let mut failed = vec![];
+    for &(year, day) in &days {
+        if let Err(e) = run_day(year, day, &day_args, &style_args) {
+            failed.push(format!("{e:#}"));
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("{}", failed.join("\n")))
+    }
+}
+
+/// How one day went when running all of them.
+#[derive(Debug)]
+struct Outcome {
+    year: u16,
+    day: u8,
+    /// Wall clock time, including starting the binary
+    time: Duration,
+    answers: Result<Vec<(String, Answer)>>,
+}
+
+/// Runs the days and shows their answers in the order of `days`. Days solved in this process share
+/// the thread pool, the binaries of the others run one after another, as each of them starts a pool
+/// of its own.
+fn run_all(
+    registry: &Registry,
+    days: &[(u16, u8)],
+    args: &[String],
+    cache: Option<&AnswerCache>,
+) -> Result<()> {
+    let outcome = |&(year, day): &(u16, u8)| {
+        let start = Instant::now();
+        let answers = day_answers(registry, year, day, args, cache);
+        Outcome {
+            year,
+            day,
+            time: start.elapsed(),
+            answers,
+        }
+    };
+    let (pooled, binaries): (Vec<_>, Vec<_>) = days
+        .iter()
+        .partition(|&&(year, day)| in_process(year, day, args));
+    let mut outcomes: Vec<Outcome> = pooled.par_iter().map(outcome).collect();
+    outcomes.extend(binaries.iter().map(outcome));
+    outcomes.sort_by_key(|o| (o.year, o.day));
+    print!("{}", table(&outcomes));
+
+    let failed: Vec<_> = outcomes
+        .iter()
+        .filter_map(|o| o.answers.as_ref().err())
+        .map(|e| format!("{e:#}"))
+        .collect();
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("{}", failed.join("\n")))
+    }
+}
+
+/// Whether the day is solved in this process with `args`, instead of by its binary.
+fn in_process(year: u16, day: u8, args: &[String]) -> bool {
+    solution(year, day).is_some() && only_parts(args).is_some()
+}
+
+/// One line per day with a column for every part any day answered. Pictures do not fit into a
+/// table and are left out.
+fn table(outcomes: &[Outcome]) -> String {
+    let mut parts: Vec<&str> = vec![];
+    for (part, _) in outcomes
+        .iter()
+        .filter_map(|o| o.answers.as_ref().ok())
+        .flatten()
+    {
+        if !parts.contains(&part.as_str()) {
+            parts.push(part);
+        }
+    }
+
+    let mut rows = vec![["Year", "Day", "Time"]
+        .into_iter()
+        .chain(parts.iter().copied())
+        .map(str::to_owned)
+        .collect::<Vec<_>>()];
+    for o in outcomes {
+        let mut row = vec![
+            o.year.to_string(),
+            o.day.to_string(),
+            format!("{:.1?}", o.time),
+        ];
+        let Ok(answers) = &o.answers else {
+            row.push(style::paint(Role::Error, "FAILED"));
+            rows.push(row);
+            continue;
+        };
+        for &part in &parts {
+            row.push(match answers.iter().find(|(p, _)| p == part) {
+                Some((_, answer)) if answer.is_picture() => "(picture)".to_owned(),
+                Some((_, answer)) => answer.to_string(),
+                None => "-".to_owned(),
+            });
+        }
+        rows.push(row);
+    }
+
+    columns(&rows)
+}
+
+/// Solves the examples of the days in this process, without needing their inputs. A table like
+/// with `--all` for more than one day.
+fn examples(days: &[(u16, u8)], parts: &[String], all: bool) -> Result<()> {
+    let outcomes: Vec<Outcome> = days
+        .iter()
+        .map(|&(year, day)| {
+            let start = Instant::now();
+            let answers = example_answers(year, day, parts);
+            Outcome {
+                year,
+                day,
+                time: start.elapsed(),
+                answers,
+            }
+        })
+        .collect();
+
+    if all {
+        print!("{}", table(&outcomes));
+    }
+    let mut failed = vec![];
+    for outcome in outcomes {
+        match outcome.answers {
+            Ok(answers) if !all => print!("{}", styled_answers(&answers)),
+            Ok(_) => {}
+            Err(e) => failed.push(format!("{e:#}")),
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("{}", failed.join("\n")))
+    }
+}
+
+fn example_answers(year: u16, day: u8, parts: &[String]) -> Result<Vec<(String, Answer)>> {
+    let solution = solution(year, day)
+        .with_context(|| format!("{year} day {day} is not registered with #[aoc]"))?;
+    let example = solution
+        .example
+        .with_context(|| format!("{year} day {day} has no example"))?;
+    solution
+        .solve(example, parts)
+        .with_context(|| format!("{year} day {day}"))
+}
+
+/// The input given by `--input` and `--account`.
+pub fn input_source(
+    year: u16,
+    day: u8,
+    input: Option<&Path>,
+    account: Option<&str>,
+) -> InputSource {
+    match input {
+        Some(path) if path == Path::new("-") => InputSource::Stdin,
+        Some(path) => InputSource::File(path.to_owned()),
+        None => InputSource::Default {
+            year,
+            day,
+            account: account.map(str::to_owned),
+        },
+    }
+}
+
+/// The answers of the day, from the cache if it ran on the same input before. Only runs with
+/// nothing but parts (and the account) selected are cached, other arguments might change the
+/// answers.
+pub fn day_answers(
+    registry: &Registry,
+    year: u16,
+    day: u8,
+    args: &[String],
+    cache: Option<&AnswerCache>,
+) -> Result<Vec<(String, Answer)>> {
+    let cached = cache.zip(only_parts(args)).and_then(|(cache, parts)| {
+        let dir = registry.dir(year)?;
+        let dir = dir.join(fetch::input_dir(account(args).as_deref()));
+        let input = compress::read(&input_path(&dir, day)).ok()?;
+        Some((cache, parts, input))
+    });
+    if let Some((cache, parts, input)) = &cached {
+        if let Some(answers) = cache.get(year, day, input, parts) {
+            return Ok(answers);
+        }
+    }
+
+    let answers = parse_answers(&day_output(year, day, args)?);
+    if let Some((cache, parts, input)) = &cached {
+        if let Err(e) = cache.put(year, day, input, parts, &answers) {
+            eprintln!("Could not cache the answers of {year} day {day}: {e:#}");
+        }
+    }
+    Ok(answers)
+}
+
+/// Runs the day and returns what it printed. Days registered with `#[aoc]` in this binary are
+/// solved right here when nothing but parts (and the account) is selected, without starting their
+/// binary.
+pub fn day_output(year: u16, day: u8, args: &[String]) -> Result<String> {
+    if let (Some(solution), Some(parts)) = (solution(year, day), only_parts(args)) {
+        let account = account(args);
+        let input = InputSource::Default { year, day, account }.read()?;
+        return Ok(format_answers(&solution.solve(&input, &parts)?));
+    }
+
+    let out = command(year, day, args)?
+        .stderr(Stdio::inherit())
+        .output()
+        .with_context(|| format!("Could not run {year} day {day}"))?;
+    if out.status.success() {
+        Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+    } else {
+        Err(anyhow!("{year} day {day} failed with {}", out.status))
+    }
+}
+
+/// The parts selected with `--part`, `None` if there are any other arguments than those,
+/// `--account` and `--timeout` (set with [`cancel::init`] in here).
+fn only_parts(args: &[String]) -> Option<Vec<String>> {
+    args.chunks(2)
+        .filter(|pair| pair[0] != "--account" && pair[0] != "--timeout")
+        .map(|pair| match pair {
+            [flag, part] if flag == "--part" => Some(part.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The account selected with `--account`.
+fn account(args: &[String]) -> Option<String> {
+    args.windows(2)
+        .find(|pair| pair[0] == "--account")
+        .map(|pair| pair[1].clone())
+}
+
+/// Runs the days and compares their answers with the known ones, or stores them with `--bless`.
+/// Cached answers are not used, they would hide what a change to a day broke.
+fn verify(registry: &Registry, args: &RunArgs) -> Result<()> {
+    let days = args.days(registry)?;
+    let day_args = args.day_args()?;
+
+    let mut known: BTreeMap<u16, (PathBuf, Answers)> = BTreeMap::new();
+    let mut failed = vec![];
+    for &(year, day) in &days {
+        let (_, answers) = match known.entry(year) {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => {
+                let dir = registry
+                    .dir(year)
+                    .with_context(|| format!("There is no crate for {year}"))?;
+                let path = dir.join(verify::answers_file(args.account.as_deref()));
+                let answers = Answers::load(&path)?;
+                e.insert((path, answers))
+            }
+        };
+
+        if !args.bless && answers.day(day).is_none() {
+            println!("{year} day {day}: no known answers");
+            continue;
+        }
+
+        let got = match day_answers(registry, year, day, &day_args, None) {
+            Ok(answers) => answers,
+            Err(e) if matches!(e.downcast_ref(), Some(AocError::InputMissing { .. })) => {
+                println!("{year} day {day}: skipped, {e}");
+                continue;
+            }
+            Err(e) => {
+                failed.push(format!("{e:#}"));
+                continue;
+            }
+        };
+
+        if args.bless {
+            answers.set(day, &got);
+            println!("{year} day {day}: stored {} answers", got.len());
+            continue;
+        }
+        match answers.verify(day, &got) {
+            Verification::Unknown => unreachable!("Checked before running the day"),
+            Verification::Correct(n) => println!("{year} day {day}: {n} correct"),
+            Verification::Wrong(mismatches) => {
+                println!("{year} day {day}: {}", style::paint(Role::Error, "WRONG"));
+                for m in mismatches {
+                    println!("  {m}");
+                }
+                failed.push(format!("{year} day {day} gave wrong answers"));
+            }
+        }
+    }
+
+    if args.bless {
+        for (path, answers) in known.values() {
+            answers.save(path)?;
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("{}", failed.join("\n")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cli, Cmd};
+    use clap::{CommandFactory, Parser};
+
+    fn run_args(args: &[&str]) -> Result<RunArgs, clap::Error> {
+        let cli = Cli::try_parse_from(["aoc", "run"].iter().chain(args))?;
+        match cli.command {
+            Cmd::Run(args) => Ok(args),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn parses_args() {
+        Cli::command().debug_assert();
+
+        let args = run_args(&["--year", "2023", "--day", "5", "--part", "2", "--", "-v"]).unwrap();
+        assert_eq!((args.year, args.day), (Some(2023), Some(5)));
+        assert_eq!(args.day_args().unwrap(), ["--part", "2", "-v"]);
+
+        let args = run_args(&["--day", "1", "--input", "-"]).unwrap();
+        assert_eq!(args.day_args().unwrap(), ["--input", "-"]);
+        let args = run_args(&["--day", "10", "--density", "braille"]).unwrap();
+        assert_eq!(args.style_args(), ["--density", "braille"]);
+        run_args(&["--day", "10", "--density", "sixel"]).unwrap_err();
+        let args = run_args(&["--day", "7", "--parse-cache"]).unwrap();
+        assert_eq!(args.day_args().unwrap(), ["--parse-cache"]);
+        let args = run_args(&["--day", "11", "--parse-only"]).unwrap();
+        assert_eq!(args.day_args().unwrap(), ["--parse-only"]);
+        run_args(&["--all", "--parse-only"]).unwrap_err();
+        run_args(&["--all", "--input", "-"]).unwrap_err();
+        run_args(&["--all", "--verify"]).unwrap();
+        run_args(&["--day", "1", "--verify", "--bless"]).unwrap_err();
+        let args = run_args(&["--day", "3", "--impl", "bits", "--example"]).unwrap();
+        assert_eq!(args.implementation.as_deref(), Some("bits"));
+        run_args(&["--all", "--impl", "bits"]).unwrap_err();
+
+        assert!(run_args(&["--all"]).unwrap().all);
+        run_args(&[]).unwrap_err();
+        run_args(&["--day", "5", "--all"]).unwrap_err();
+        run_args(&["--day", "26"]).unwrap_err();
+
+        let args = run_args(&["--day", "9", "--animate"]).unwrap();
+        assert_eq!(args.day_args().unwrap(), ["--animate", "10"]);
+        let args = run_args(&["--day", "9", "--animate", "30", "--", "-v"]).unwrap();
+        assert_eq!(args.day_args().unwrap(), ["--animate", "30", "-v"]);
+        run_args(&["--day", "9", "--animate", "0"]).unwrap_err();
+        run_args(&["--all", "--verify", "--animate"]).unwrap_err();
+        run_args(&["--all", "--animate"]).unwrap_err();
+        assert!(run_args(&["--all", "--timings"]).unwrap().timings);
+        run_args(&["--day", "5", "--timings", "--part", "1"]).unwrap_err();
+
+        let args = run_args(&["--day", "11", "--checkpoint", "monkeys.json"]).unwrap();
+        let day_args = args.day_args().unwrap();
+        assert_eq!(day_args[0], "--checkpoint");
+        assert!(Path::new(&day_args[1]).is_absolute());
+        let args = run_args(&["--day", "11", "--resume", "missing.json"]).unwrap();
+        args.day_args().unwrap_err();
+        run_args(&["--all", "--checkpoint", "monkeys.json"]).unwrap_err();
+        run_args(&["--day", "11", "--example", "--resume", "monkeys.json"]).unwrap_err();
+        let args = run_args(&["--day", "9", "--export", "rope.gif", "--scale", "2"]).unwrap();
+        let day_args = args.day_args().unwrap();
+        assert_eq!(day_args[0], "--export");
+        assert!(Path::new(&day_args[1]).is_absolute());
+        assert!(day_args[1].ends_with("rope.gif"));
+        assert_eq!(day_args[2..], ["--scale", "2"]);
+        run_args(&["--day", "9", "--scale", "2"]).unwrap_err();
+        run_args(&["--day", "9", "--export", "rope.gif", "--scale", "0"]).unwrap_err();
+        run_args(&["--all", "--export", "rope.gif"]).unwrap_err();
+
+        let args = run_args(&["--day", "7", "--dot", "tree.dot"]).unwrap();
+        let day_args = args.day_args().unwrap();
+        assert_eq!(day_args[0], "--dot");
+        assert!(Path::new(&day_args[1]).is_absolute());
+
+        let args = run_args(&["--day", "11", "--log-level", "TRACE"]).unwrap();
+        assert_eq!(args.day_args().unwrap(), ["--log-level", "trace"]);
+        assert!(run_args(&["--day", "11"])
+            .unwrap()
+            .day_args()
+            .unwrap()
+            .is_empty());
+        run_args(&["--day", "11", "--log-level", "loud"]).unwrap_err();
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "all-days"), ignore = "Needs every day")]
+    fn solves_examples() {
+        assert_eq!(
+            example_answers(2022, 1, &[]).unwrap(),
+            [
+                ("Part 1".to_owned(), Answer::from(24_000)),
+                ("Part 2".to_owned(), Answer::from(45_000))
+            ]
+        );
+        for day in 2..=12 {
+            example_answers(2022, day, &[]).unwrap();
+        }
+        example_answers(2022, 25, &[]).unwrap_err();
+    }
+
+    #[test]
+    fn results_table() {
+        let outcome = |day, answers| Outcome {
+            year: 2022,
+            day,
+            time: Duration::from_micros(1500),
+            answers,
+        };
+        let outcomes = [
+            outcome(
+                1,
+                Ok(vec![
+                    ("Part 1".to_owned(), Answer::from(24_000)),
+                    ("Part 2".to_owned(), Answer::from(45_000)),
+                ]),
+            ),
+            outcome(
+                10,
+                Ok(vec![
+                    ("Part 1".to_owned(), Answer::from(13_140)),
+                    ("Part 2".to_owned(), Answer::from("##\n#.")),
+                ]),
+            ),
+            outcome(11, Err(anyhow!("2022 day 11 failed"))),
+            outcome(12, Ok(vec![("Part 2".to_owned(), Answer::from("CMZ"))])),
+        ];
+
+        assert_eq!(
+            table(&outcomes),
+            "\
+Year  Day  Time   Part 1  Part 2
+2022  1    1.5ms  24000   45000
+2022  10   1.5ms  13140   (picture)
+2022  11   1.5ms  FAILED
+2022  12   1.5ms  -       CMZ
+"
+        );
+    }
+
+    #[test]
+    fn days_run_their_binary() {
+        let Ok(command) = command(2023, 5, &["--dot".into(), "day05.dot".into()]) else {
+            // Only there after `cargo build --bins`
+            return;
+        };
+        assert!(command
+            .get_program()
+            .to_string_lossy()
+            .ends_with("aoc2023-day05"));
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            ["--dot", "day05.dot"]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "all-days"), ignore = "Needs every day")]
+    fn solves_registered_days_in_process() {
+        assert!(solution(YEAR, 1).is_some());
+        assert!(solution(2023, 1).is_some());
+        assert!(solution(2023, 3).is_none());
+
+        assert!(in_process(YEAR, 1, &[]));
+        assert!(!in_process(2023, 3, &[]));
+        assert!(!in_process(
+            YEAR,
+            1,
+            &["--dot".to_owned(), "tree.dot".to_owned()]
+        ));
+
+        let args = |args: &[&str]| args.iter().map(|&a| a.to_owned()).collect::<Vec<_>>();
+        assert_eq!(only_parts(&[]), Some(vec![]));
+        assert_eq!(
+            only_parts(&args(&["--part", "1", "--part", "2"])),
+            Some(args(&["1", "2"]))
+        );
+        assert_eq!(only_parts(&args(&["--part", "1", "-v"])), None);
+        assert_eq!(only_parts(&args(&["--part"])), None);
+
+        let work = args(&["--part", "1", "--account", "work"]);
+        assert_eq!(only_parts(&work), Some(args(&["1"])));
+        assert_eq!(account(&work).as_deref(), Some("work"));
+        assert_eq!(account(&args(&["--part", "1"])), None);
+        assert_eq!(
+            only_parts(&args(&["--timeout", "500ms", "--part", "2"])),
+            Some(args(&["2"]))
+        );
+    }
+}
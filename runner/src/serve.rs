@@ -0,0 +1,127 @@
+//! `aoc serve`: solves the registered days over HTTP, each request in a worker process of its own.
+
+use anyhow::Result;
+use aoc_core::serve::{self, Limits};
+use clap::Args;
+use std::num::NonZeroUsize;
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    addr: String,
+    /// Requests solved at once, more are answered with 503 [default: the number of CPUs]
+    #[arg(long, value_name = "N")]
+    workers: Option<NonZeroUsize>,
+    #[command(flatten)]
+    limits: LimitArgs,
+}
+
+/// What one request may use, the worker gets them passed on.
+#[derive(Debug, Clone, Args)]
+struct LimitArgs {
+    /// Memory a request may use, in MiB
+    #[arg(long, value_name = "MIB", default_value_t = 2048)]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    max_memory: u64,
+    /// CPU time a request may use, in seconds
+    #[arg(long, value_name = "SECONDS", default_value_t = 10)]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    max_cpu: u64,
+    /// Seconds after which a request is stopped, also if it does not use the CPU
+    #[arg(long, value_name = "SECONDS", default_value_t = 30)]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    timeout: u64,
+}
+
+impl LimitArgs {
+    fn limits(&self) -> Limits {
+        Limits {
+            memory: self.max_memory << 20,
+            cpu: Duration::from_secs(self.max_cpu),
+            wall: Duration::from_secs(self.timeout),
+        }
+    }
+
+    fn args(&self) -> [String; 6] {
+        [
+            "--max-memory".to_owned(),
+            self.max_memory.to_string(),
+            "--max-cpu".to_owned(),
+            self.max_cpu.to_string(),
+            "--timeout".to_owned(),
+            self.timeout.to_string(),
+        ]
+    }
+}
+
+/// The worker `aoc serve` starts for every request, not meant to be run by hand.
+#[derive(Debug, Args)]
+pub struct ServeWorkerArgs {
+    year: u16,
+    day: u8,
+    #[command(flatten)]
+    limits: LimitArgs,
+}
+
+/// # Errors
+/// If the address can not be listened on or this binary not be found to start the workers.
+pub fn serve(args: &ServeArgs) -> Result<()> {
+    let aoc = std::env::current_exe()?;
+    let workers = args
+        .workers
+        .or_else(|| thread::available_parallelism().ok())
+        .map_or(1, NonZeroUsize::get);
+    serve::serve(&args.addr, &args.limits.limits(), workers, |year, day| {
+        let mut worker = Command::new(&aoc);
+        worker
+            .args(["serve-worker", &year.to_string(), &day.to_string()])
+            .args(args.limits.args());
+        worker
+    })
+}
+
+/// Makes the workers panic before they solve anything, for the tests of how a panicking worker is
+/// answered.
+pub const PANIC_VAR: &str = "AOC_SERVE_WORKER_PANIC";
+
+/// # Errors
+/// If the worker can not be limited or its answer not printed.
+///
+/// # Panics
+/// If [`PANIC_VAR`] is set.
+pub fn serve_worker(args: &ServeWorkerArgs) -> Result<()> {
+    assert!(std::env::var_os(PANIC_VAR).is_none(), "{PANIC_VAR} is set");
+    serve::work(args.year, args.day, &args.limits.limits())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cli, Cmd};
+    use clap::Parser;
+
+    #[test]
+    fn passes_limits_to_workers() {
+        let cli = Cli::try_parse_from(["aoc", "serve", "--max-cpu", "2"]).unwrap();
+        let Cmd::Serve(args) = cli.command else {
+            panic!("Not serve: {:?}", cli.command);
+        };
+        assert_eq!(args.limits.limits().cpu, Duration::from_secs(2));
+        assert_eq!(args.limits.limits().memory, 2 << 30);
+
+        let worker = ["aoc", "serve-worker", "2023", "6"]
+            .map(str::to_owned)
+            .into_iter()
+            .chain(args.limits.args());
+        let cli = Cli::try_parse_from(worker).unwrap();
+        let Cmd::ServeWorker(worker) = cli.command else {
+            panic!("Not serve-worker: {:?}", cli.command);
+        };
+        assert_eq!((worker.year, worker.day), (2023, 6));
+        assert_eq!(worker.limits.limits(), args.limits.limits());
+    }
+}
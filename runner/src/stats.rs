@@ -0,0 +1,273 @@
+//! `aoc stats`: how long the days take, and how long solving them took.
+
+use crate::run::{day_answers, day_output};
+use crate::{columns, YEAR};
+use anyhow::{anyhow, Context, Result};
+use aoc_core::chart;
+use aoc_core::compress;
+use aoc_core::days::parse_answers;
+use aoc_core::fetch::{self, input_path, INPUT_DIR};
+use aoc_core::history::{History, HISTORY_FILE};
+use aoc_core::net::Client;
+use aoc_core::parse::normalize;
+use aoc_core::registry::{solution, Registry};
+use aoc_core::stats::{self, DayStats, Format};
+use aoc_core::style::{self, Role};
+use aoc_core::timing::Phases;
+use clap::{Args, Subcommand};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+#[derive(Debug, Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct StatsArgs {
+    #[command(subcommand)]
+    command: Option<StatsCmd>,
+    /// Only this year
+    #[arg(long, short)]
+    year: Option<u16>,
+    /// How to print them: table, csv or json
+    #[arg(long, default_value_t = Format::default())]
+    format: Format,
+    /// Also draws the times as a bar chart per year, `runtimes.svg` becomes `runtimes-2022.svg`
+    /// and so on (.svg or .png)
+    #[arg(long)]
+    chart: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+enum StatsCmd {
+    /// Shows the time to solve every day, streaks and a comparison of the years, from when each
+    /// part was first solved in history.json
+    History(HistoryArgs),
+}
+
+#[derive(Debug, Args)]
+struct HistoryArgs {
+    #[command(subcommand)]
+    command: Option<HistoryCmd>,
+}
+
+#[derive(Debug, Subcommand)]
+enum HistoryCmd {
+    /// Solves a day and notes every part it has an answer for as solved now, if it was not yet
+    Record(RecordArgs),
+    /// Takes when the parts were solved from a private leaderboard
+    Import(ImportArgs),
+}
+
+#[derive(Debug, Args)]
+struct RecordArgs {
+    /// Year of the puzzle
+    #[arg(long, short, default_value_t = YEAR)]
+    year: u16,
+    /// Day of the puzzle
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+}
+
+#[derive(Debug, Args)]
+struct ImportArgs {
+    /// Year of the leaderboard
+    #[arg(long, short, default_value_t = YEAR)]
+    year: u16,
+    /// Id of the leaderboard to fetch
+    #[arg(long, required_unless_present = "file", conflicts_with = "file")]
+    leaderboard: Option<String>,
+    /// The JSON of a leaderboard downloaded before instead
+    #[arg(long, requires = "member")]
+    file: Option<PathBuf>,
+    /// Id of the member whose stars to take [default: the owner of --leaderboard]
+    #[arg(long)]
+    member: Option<String>,
+}
+
+/// Days registered with `#[aoc]` are measured phase by phase in here, the others are timed as a
+/// whole by running their binary.
+pub fn stats(registry: &Registry, args: &StatsArgs) -> Result<()> {
+    if let Some(StatsCmd::History(args)) = &args.command {
+        return history(args);
+    }
+
+    let mut stats = vec![];
+    let mut failed = vec![];
+    for (year, day) in registry
+        .all()
+        .filter(|&(year, _)| args.year.is_none_or(|y| y == year))
+    {
+        let dir = registry
+            .dir(year)
+            .with_context(|| format!("There is no crate for {year}"))?;
+        let mut day_stats = DayStats::new(year, day);
+        day_stats.code_lines = stats::code_lines(dir, day);
+        let input = compress::read(&input_path(&dir.join(INPUT_DIR), day)).ok();
+        if let Some(input) = &input {
+            day_stats.set_input(input);
+        }
+
+        let measured = if let (Some(solution), Some(input)) = (solution(year, day), &input) {
+            Phases::measure(solution, &normalize(input))
+                .map(|phases| day_stats.set_phases(&phases))
+                .map_err(Into::into)
+        } else {
+            let start = Instant::now();
+            day_answers(registry, year, day, &[], None).map(|answers| {
+                day_stats.total_ms = Some(start.elapsed().as_secs_f64() * 1000.0);
+                day_stats.set_answers(&answers);
+            })
+        };
+        if let Err(e) = measured {
+            failed.push(format!("{year} day {day}: {e:#}"));
+        }
+        stats.push(day_stats);
+    }
+
+    match args.format {
+        Format::Table => print!("{}", stats_table(&stats)),
+        Format::Csv => print!("{}", stats::to_csv(&stats)),
+        Format::Json => print!("{}", stats::to_json(&stats)?),
+    }
+
+    if let Some(path) = &args.chart {
+        let mut years: Vec<_> = stats.iter().map(|s| s.year).collect();
+        years.dedup();
+        for year in years {
+            let bars: Vec<_> = stats
+                .iter()
+                .filter(|s| s.year == year && s.total_ms.is_some())
+                .map(DayStats::bar)
+                .collect();
+            let path = chart::year_path(path, year);
+            chart::runtimes(&path, &year.to_string(), &bars)
+                .with_context(|| format!("Could not draw {}", path.display()))?;
+            eprintln!("Wrote {}", path.display());
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("{}", failed.join("\n")))
+    }
+}
+
+fn history(args: &HistoryArgs) -> Result<()> {
+    let path = Path::new(HISTORY_FILE);
+    let mut history = History::load(path)?;
+
+    match &args.command {
+        None => {
+            print!("{}", history.report());
+            return Ok(());
+        }
+        Some(HistoryCmd::Record(args)) => record(&mut history, args.year, args.day)?,
+        Some(HistoryCmd::Import(args)) => {
+            let json = match (&args.leaderboard, &args.file) {
+                (Some(id), _) => fetch::fetch_leaderboard(&Client::from_session()?, args.year, id)?,
+                (None, Some(file)) => fs::read_to_string(file)
+                    .with_context(|| format!("Could not read {}", file.display()))?,
+                (None, None) => unreachable!("clap requires one of them"),
+            };
+            // The API does not say who is asking, but on an own board the owner is a good guess
+            let member = args
+                .member
+                .as_ref()
+                .or(args.leaderboard.as_ref())
+                .context("--member is needed")?;
+            let new = history.import_leaderboard(args.year, &json, member)?;
+            println!("Imported {new} star timestamps");
+        }
+    }
+
+    history.save(path)
+}
+
+/// Part numbers that got an answer, from `Part <n>: ...` lines.
+fn solved_parts(output: &str) -> Vec<u8> {
+    parse_answers(output)
+        .into_iter()
+        .filter_map(|(part, _)| part.strip_prefix("Part ")?.parse().ok())
+        .collect()
+}
+
+fn record(history: &mut History, year: u16, day: u8) -> Result<()> {
+    for part in solved_parts(&day_output(year, day, &[])?) {
+        if history.record(year, day, part, SystemTime::now()) {
+            println!("First solve of {year} day {day} part {part}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Pictures do not fit into a table and are left out.
+fn stats_table(stats: &[DayStats]) -> String {
+    let header = [
+        "Year", "Day", "Parse", "Part 1", "Part 2", "Total", "Answer 1", "Answer 2", "Bytes",
+        "Lines", "Code",
+    ];
+    let time = |ms: Option<f64>| {
+        ms.map(|ms| Duration::from_secs_f64(ms / 1000.0))
+            .map(|t| style::paint(Role::Time, &format!("{t:.1?}")))
+            .unwrap_or_default()
+    };
+    let answer = |answer: &Option<String>| match answer {
+        Some(answer) if answer.contains('\n') => "(picture)".to_owned(),
+        Some(answer) => answer.clone(),
+        None => String::new(),
+    };
+    let count = |n: Option<usize>| n.map(|n| n.to_string()).unwrap_or_default();
+
+    let mut rows = vec![header.map(str::to_owned).to_vec()];
+    for day in stats {
+        rows.push(vec![
+            day.year.to_string(),
+            day.day.to_string(),
+            time(day.parse_ms),
+            time(day.part1_ms),
+            time(day.part2_ms),
+            time(day.total_ms),
+            answer(&day.part1),
+            answer(&day.part2),
+            count(day.input_bytes),
+            count(day.input_lines),
+            day.code_lines.to_string(),
+        ]);
+    }
+    columns(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cli, Cmd};
+    use clap::Parser;
+
+    #[test]
+    fn parts() {
+        assert_eq!(solved_parts("Part 1: 24000\nPart 2: 45000\n"), vec![1, 2]);
+        assert_eq!(solved_parts("Part 1: 13140\nPart 2:\n##..\n"), vec![1, 2]);
+        assert_eq!(solved_parts("Part 1: 7\nPart 2:\n"), vec![1]);
+    }
+
+    #[test]
+    fn history_commands() {
+        let parse = |args: &[&str]| Cli::try_parse_from(["aoc", "stats"].iter().chain(args));
+
+        let Cmd::Stats(args) = parse(&["history", "record", "--day", "5"]).unwrap().command else {
+            panic!("Not stats");
+        };
+        assert!(matches!(
+            args.command,
+            Some(StatsCmd::History(HistoryArgs {
+                command: Some(HistoryCmd::Record(RecordArgs { year: YEAR, day: 5 }))
+            }))
+        ));
+        parse(&["history"]).unwrap();
+        parse(&["history", "import", "--leaderboard", "123"]).unwrap();
+        parse(&["history", "import", "--file", "board.json"]).unwrap_err();
+        parse(&["history", "import"]).unwrap_err();
+        parse(&["--year", "2022", "history"]).unwrap_err();
+    }
+}
@@ -0,0 +1,126 @@
+//! `aoc list` and `aoc status`.
+
+use crate::run::day_output;
+use crate::{columns, YEAR};
+use anyhow::{ensure, Context, Result};
+use aoc_core::fetch;
+use aoc_core::registry::Registry;
+use aoc_core::style::{self, Role};
+use aoc_core::verify::{self, Answers};
+use clap::Args;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Args)]
+pub struct StatusArgs {
+    /// Only this year
+    #[arg(long, short)]
+    year: Option<u16>,
+    /// Also runs every implemented day to show how long it takes
+    #[arg(long)]
+    time: bool,
+}
+
+pub fn list(registry: &Registry) {
+    for year in registry.years() {
+        let days: Vec<_> = registry.days(year).iter().map(u8::to_string).collect();
+        println!("{year}: {}", days.join(" "));
+    }
+}
+
+/// How far a day is.
+#[derive(Debug, Clone, PartialEq)]
+struct DayStatus {
+    day: u8,
+    implemented: bool,
+    /// Has known answers in answers.toml
+    verified: bool,
+    /// With `--time`, `Err` if it failed
+    time: Option<Result<Duration, ()>>,
+}
+
+pub fn status(registry: &Registry, args: &StatusArgs) -> Result<()> {
+    let years: Vec<_> = registry
+        .years()
+        .filter(|&year| args.year.is_none_or(|y| y == year))
+        .collect();
+    ensure!(
+        !years.is_empty(),
+        "There is no crate for {}",
+        args.year.unwrap_or(YEAR)
+    );
+
+    for year in years {
+        let dir = registry
+            .dir(year)
+            .with_context(|| format!("There is no crate for {year}"))?;
+        let answers = Answers::load(&dir.join(verify::answers_file(None)))?;
+        let days: Vec<_> = (1..=fetch::last_day(year))
+            .map(|day| {
+                let implemented = registry.contains(year, day);
+                let time = (args.time && implemented).then(|| {
+                    let start = Instant::now();
+                    day_output(year, day, &[])
+                        .map(|_| start.elapsed())
+                        .map_err(|e| eprintln!("{year} day {day}: {e:#}"))
+                });
+                DayStatus {
+                    day,
+                    implemented,
+                    verified: answers.day(day).is_some(),
+                    time,
+                }
+            })
+            .collect();
+
+        let implemented = days.iter().filter(|d| d.implemented).count();
+        let verified = days.iter().filter(|d| d.implemented && d.verified).count();
+        println!("{year}: {implemented} implemented, {verified} of them with known answers");
+        print!("{}", calendar(&days));
+    }
+    println!("* implemented with known answers, + implemented, . not yet");
+    Ok(())
+}
+
+/// The days five to a row, like the calendar on the site.
+fn calendar(days: &[DayStatus]) -> String {
+    let cells: Vec<_> = days
+        .iter()
+        .map(|d| {
+            let mark = match (d.implemented, d.verified) {
+                (true, true) => style::paint(Role::Answer, "*"),
+                (true, false) => style::paint(Role::Part, "+"),
+                (false, _) => ".".to_owned(),
+            };
+            let time = match d.time {
+                Some(Ok(time)) => style::paint(Role::Time, &format!("{time:.1?}")),
+                Some(Err(())) => style::paint(Role::Error, "failed"),
+                None => String::new(),
+            };
+            format!("{:>2} {mark} {time}", d.day)
+        })
+        .collect();
+    let rows: Vec<_> = cells.chunks(5).map(<[String]>::to_vec).collect();
+    columns(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calendar_grid() {
+        let days: Vec<_> = (1..=25)
+            .map(|day| DayStatus {
+                day,
+                implemented: day <= 3,
+                verified: day == 1,
+                time: (day == 2).then_some(Ok(Duration::from_millis(12))),
+            })
+            .collect();
+        let shown = calendar(&days);
+        let lines: Vec<_> = shown.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], " 1 *    2 + 12.0ms   3 +    4 .    5 .");
+        assert!(lines[4].starts_with("21 ."), "{shown}");
+    }
+}
@@ -0,0 +1,106 @@
+//! `aoc submit`.
+
+use crate::run::day_answers;
+use crate::YEAR;
+use anyhow::{anyhow, Context, Result};
+use aoc_core::answer::Answer;
+use aoc_core::answer_cache::AnswerCache;
+use aoc_core::fetch;
+use aoc_core::history::{History, HISTORY_FILE};
+use aoc_core::net::Client;
+use aoc_core::registry::Registry;
+use aoc_core::submit::{Submissions, Verdict, SUBMISSIONS_FILE};
+use clap::Args;
+use std::path::Path;
+use std::time::SystemTime;
+
+#[derive(Debug, Args)]
+pub struct SubmitArgs {
+    /// Year of the puzzle
+    #[arg(long, short, default_value_t = YEAR)]
+    year: u16,
+    /// Day of the puzzle
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Part to submit
+    #[arg(long, short, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    /// Only computes and checks the answer, without sending it
+    #[arg(long)]
+    dry_run: bool,
+    /// Computes the answer again instead of taking the one of the same input from ~/.cache/aoc
+    #[arg(long)]
+    force: bool,
+}
+
+/// Runs the day, or takes its cached answers, and returns the answer for the part.
+fn compute_answer(
+    registry: &Registry,
+    year: u16,
+    day: u8,
+    part: u8,
+    cache: Option<&AnswerCache>,
+) -> Result<Answer> {
+    let args = ["--part".into(), part.to_string()];
+    let answers = day_answers(registry, year, day, &args, cache)?;
+
+    let name = format!("Part {part}");
+    let answer = answers
+        .into_iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, answer)| answer)
+        .with_context(|| format!("{year} day {day} printed no answer for part {part}"))?;
+
+    if answer.is_picture() {
+        Err(anyhow!(
+            "Can not submit a picture, read it yourself:\n{answer}"
+        ))
+    } else {
+        Ok(answer)
+    }
+}
+
+pub fn submit(registry: &Registry, args: &SubmitArgs) -> Result<()> {
+    let SubmitArgs {
+        year, day, part, ..
+    } = *args;
+    let cache = (!args.force).then(AnswerCache::open).flatten();
+    let answer = compute_answer(registry, year, day, part, cache.as_ref())?;
+
+    let path = Path::new(SUBMISSIONS_FILE);
+    let mut submissions = Submissions::load(path)?;
+    submissions.check(year, day, part, &answer, SystemTime::now())?;
+    if args.dry_run {
+        println!("Would submit {answer} for {year} day {day} part {part}");
+        return Ok(());
+    }
+
+    let response = fetch::submit_answer(
+        &Client::from_session()?,
+        year,
+        day,
+        part,
+        &answer.to_string(),
+    )?;
+    let verdict = Verdict::parse(&response)?;
+    let now = SystemTime::now();
+    submissions.record(year, day, part, &answer, verdict, now);
+    submissions.save(path)?;
+
+    if verdict == Verdict::Correct {
+        let path = Path::new(HISTORY_FILE);
+        let mut history = History::load(path)?;
+        history.record(year, day, part, now);
+        history.save(path)?;
+    }
+
+    match verdict {
+        Verdict::Correct | Verdict::AlreadySolved => {
+            println!("{year} day {day} part {part}: {answer} is {verdict}");
+            Ok(())
+        }
+        _ => Err(anyhow!(
+            "{year} day {day} part {part}: {answer} is {verdict}"
+        )),
+    }
+}
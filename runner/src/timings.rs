@@ -0,0 +1,211 @@
+//! `aoc run --timings`: times the phases of the registered days.
+
+use crate::columns;
+use crate::run::input_source;
+use anyhow::{anyhow, Result};
+use aoc_core::heap::format_bytes;
+use aoc_core::registry::solution;
+use aoc_core::style::{self, Role};
+use aoc_core::timing::Phases;
+use std::path::Path;
+use std::time::Duration;
+
+/// Times the phases of the registered days one after another, so they do not slow each other down.
+pub fn timings(days: &[(u16, u8)], input: Option<&Path>, account: Option<&str>) -> Result<()> {
+    let mut timed = vec![];
+    let mut skipped = vec![];
+    let mut failed = vec![];
+    for &(year, day) in days {
+        let Some(solution) = solution(year, day) else {
+            skipped.push(format!("{year} day {day}"));
+            continue;
+        };
+        match input_source(year, day, input, account)
+            .read()
+            .and_then(|input| Phases::measure(solution, &input))
+        {
+            Ok(phases) => {
+                for part in &phases.parts {
+                    if let Err(e) = &part.answer {
+                        failed.push(format!("{year} day {day} {}: {e}", part.name));
+                    }
+                }
+                timed.push((year, day, phases));
+            }
+            Err(e) => failed.push(format!("{year} day {day}: {e}")),
+        }
+    }
+
+    print!("{}", timings_table(&timed));
+    if let Some(heap) = heap_table(&timed) {
+        print!("\n{heap}");
+    }
+    if !skipped.is_empty() {
+        println!(
+            "Not registered with #[aoc], so not timed: {}",
+            skipped.join(", ")
+        );
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("{}", failed.join("\n")))
+    }
+}
+
+/// One line per day and the totals of every year below its days.
+fn timings_table(timed: &[(u16, u8, Phases)]) -> String {
+    let time = |t: Duration| style::paint(Role::Time, &format!("{t:.1?}"));
+
+    let mut rows = vec![["Year", "Day", "Parse", "Part 1", "Part 2", "Total"]
+        .map(str::to_owned)
+        .to_vec()];
+    let mut totals = [Duration::ZERO; 4];
+    for (i, (year, day, phases)) in timed.iter().enumerate() {
+        let mut row = vec![year.to_string(), day.to_string(), time(phases.parse)];
+        for part in &phases.parts {
+            row.push(match part.answer {
+                Ok(_) => time(part.time),
+                Err(_) => format!(
+                    "{} {}",
+                    time(part.time),
+                    style::paint(Role::Error, "(failed)")
+                ),
+            });
+        }
+        row.push(time(phases.total()));
+        rows.push(row);
+
+        let durations = [phases.parse]
+            .into_iter()
+            .chain(phases.parts.iter().map(|p| p.time))
+            .chain([phases.total()]);
+        for (total, t) in totals.iter_mut().zip(durations) {
+            *total += t;
+        }
+        if timed.get(i + 1).is_none_or(|(next, ..)| next != year) {
+            let mut row = vec![year.to_string(), "all".to_owned()];
+            row.extend(totals.iter().map(|&t| time(t)));
+            rows.push(row);
+            totals.fill(Duration::ZERO);
+        }
+    }
+
+    columns(&rows)
+}
+
+/// One line per phase of every day, `None` if allocations were not counted.
+fn heap_table(timed: &[(u16, u8, Phases)]) -> Option<String> {
+    let mut rows = vec![["Year", "Day", "Phase", "Allocations", "Allocated", "Peak"]
+        .map(str::to_owned)
+        .to_vec()];
+    for (year, day, phases) in timed {
+        let usages = [("Parse", phases.parse_heap)]
+            .into_iter()
+            .chain(phases.parts.iter().map(|p| (p.name, p.heap)));
+        for (phase, usage) in usages {
+            let usage = usage?;
+            rows.push(vec![
+                year.to_string(),
+                day.to_string(),
+                phase.to_owned(),
+                usage.allocations.to_string(),
+                format_bytes(usage.bytes),
+                format_bytes(usage.peak as u64),
+            ]);
+        }
+    }
+
+    Some(columns(&rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::answer::Answer;
+    use aoc_core::error::AocError;
+    use aoc_core::heap::HeapUsage;
+    use aoc_core::timing::TimedPart;
+
+    #[test]
+    fn timings_with_totals() {
+        let ms = Duration::from_millis;
+        let phases = |parse, part2: Result<Answer, AocError>| Phases {
+            parse: ms(parse),
+            parse_heap: None,
+            parts: vec![
+                TimedPart {
+                    name: "Part 1",
+                    answer: Ok(Answer::Int(1)),
+                    time: ms(1),
+                    heap: None,
+                },
+                TimedPart {
+                    name: "Part 2",
+                    answer: part2,
+                    time: ms(2),
+                    heap: None,
+                },
+            ],
+        };
+        let timed = [
+            (2022, 1, phases(10, Ok(Answer::Int(2)))),
+            (
+                2022,
+                5,
+                phases(20, Err(AocError::NoSolution("Empty".to_owned()))),
+            ),
+            (2023, 1, phases(3, Ok(Answer::Int(2)))),
+        ];
+
+        assert_eq!(
+            timings_table(&timed),
+            "\
+Year  Day  Parse   Part 1  Part 2          Total
+2022  1    10.0ms  1.0ms   2.0ms           13.0ms
+2022  5    20.0ms  1.0ms   2.0ms (failed)  23.0ms
+2022  all  30.0ms  2.0ms   4.0ms           36.0ms
+2023  1    3.0ms   1.0ms   2.0ms           6.0ms
+2023  all  3.0ms   1.0ms   2.0ms           6.0ms
+"
+        );
+        assert_eq!(heap_table(&timed), None);
+    }
+
+    #[test]
+    fn heap_usage() {
+        let usage = |allocations, bytes| {
+            Some(HeapUsage {
+                allocations,
+                bytes,
+                peak: 1024,
+            })
+        };
+        let part = |name, heap| TimedPart {
+            name,
+            answer: Ok(Answer::Int(1)),
+            time: Duration::ZERO,
+            heap,
+        };
+        let timed = [(
+            2022,
+            7,
+            Phases {
+                parse: Duration::ZERO,
+                parse_heap: usage(1200, 80_000),
+                parts: vec![part("Part 1", usage(0, 0)), part("Part 2", usage(3, 2048))],
+            },
+        )];
+
+        assert_eq!(
+            heap_table(&timed).unwrap(),
+            "\
+Year  Day  Phase   Allocations  Allocated  Peak
+2022  7    Parse   1200         78.1 KiB   1.0 KiB
+2022  7    Part 1  0            0 B        1.0 KiB
+2022  7    Part 2  3            2.0 KiB    1.0 KiB
+"
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! `aoc watch`: runs a day again after every change.
+
+use anyhow::{Context, Result};
+use aoc_core::days::parse_answers;
+use aoc_core::fetch::INPUT_DIR;
+use aoc_core::registry::{bin_name, package, Registry};
+use aoc_core::style::{self, Role};
+use aoc_core::watch::{self, Watcher};
+use clap::Args;
+use std::process::{Command, Stdio};
+use std::time::Instant;
+
+#[derive(Debug, Args)]
+pub struct WatchArgs {
+    /// Year of the puzzle
+    year: u16,
+    /// Day of the puzzle
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Only runs these parts, by name or number
+    #[arg(long, short)]
+    part: Vec<String>,
+    /// Keeps the parsed input and only parses it again when it changes, for the days that
+    /// support it
+    #[arg(long)]
+    parse_cache: bool,
+}
+
+/// Builds the day if needed and runs it, arguments for the day come after this. For `aoc watch`,
+/// which has to build the day again after every change.
+fn cargo_run(registry: &Registry, year: u16, day: u8) -> Result<Command> {
+    let dir = registry
+        .dir(year)
+        .with_context(|| format!("There is no crate for {year}"))?;
+
+    let mut command = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()));
+    command
+        .current_dir(dir)
+        .args(["run", "--quiet", "--release", "--package"])
+        .arg(package(year))
+        .arg("--bin")
+        .arg(bin_name(year, day))
+        .arg("--");
+    Ok(command)
+}
+
+/// Runs the day, then again after every change to the sources of its year or of `core`, or to its
+/// input, until interrupted.
+pub fn watch(registry: &Registry, args: &WatchArgs) -> Result<()> {
+    let (year, day) = (args.year, args.day);
+    let dir = registry
+        .dir(year)
+        .with_context(|| format!("There is no crate for {year}"))?;
+    // The changed files come with absolute paths, they are shown relative to the workspace
+    let root = dir.join("..").canonicalize()?;
+    let paths: Vec<_> = [dir.join("src"), dir.join(INPUT_DIR), root.join("core/src")]
+        .into_iter()
+        .filter_map(|path| path.canonicalize().ok())
+        .collect();
+    // Also the compressed and encrypted input
+    let input = format!("day{day:02}.txt");
+    let watcher = Watcher::new(&paths, move |path| {
+        path.extension().is_some_and(|e| e == "rs")
+            || path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&input))
+    })?;
+
+    let mut day_args: Vec<_> = args
+        .part
+        .iter()
+        .flat_map(|part| ["--part".to_owned(), part.clone()])
+        .collect();
+    if args.parse_cache {
+        day_args.push("--parse-cache".to_owned());
+    }
+    let mut last = vec![];
+    loop {
+        let start = Instant::now();
+        let out = cargo_run(registry, year, day)?
+            .args(&day_args)
+            .stderr(Stdio::inherit())
+            .output()
+            .with_context(|| format!("Could not run {year} day {day}"))?;
+        let took = style::paint(Role::Time, &format!("{:.1?}", start.elapsed()));
+        if out.status.success() {
+            let answers = parse_answers(&String::from_utf8_lossy(&out.stdout));
+            print!("{}", watch::diff(&last, &answers));
+            println!("{year} day {day} took {took}");
+            last = answers;
+        } else {
+            let failed = style::paint(Role::Error, "failed");
+            println!("{year} day {day} {failed} with {} after {took}", out.status);
+        }
+
+        println!("Waiting for changes...");
+        let changed = watcher.wait()?;
+        let names: Vec<_> = changed
+            .iter()
+            .map(|p| p.strip_prefix(&root).unwrap_or(p).display().to_string())
+            .collect();
+        println!("\n{} changed", names.join(", "));
+    }
+}
@@ -0,0 +1,44 @@
+//! `aoc minimize` shrinks inputs also when the day panics on the candidates.
+
+use std::io::Write;
+use std::process::Command;
+
+fn minimize(day: &str, input: &str, args: &[&str]) -> std::process::Output {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(input.as_bytes()).unwrap();
+    Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["minimize", "--day", day, "--input"])
+        .arg(file.path())
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn shrinks_parse_errors() {
+    let out = minimize("10", "noop\naddx 3\naddx -5\njump 2\nnoop\n", &[]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "jump 2\n");
+
+    let broken = aoc2022::day11::EXAMPLE.replace("old + 6", "old ^ 6");
+    let out = minimize("11", &broken, &[]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "Monkey 1:\n  Starting items: 54, 65, 75, 74\n  Operation: new = old ^ 6\n"
+    );
+}
+
+#[test]
+fn needs_a_failing_input() {
+    let out = minimize("10", aoc2022::day10::EXAMPLE, &["--wrong-answer"]);
+    assert!(!out.status.success());
+}
@@ -0,0 +1,129 @@
+//! `aoc serve` keeps answering after a request had to be stopped or panicked.
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process::{Child, Command};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Kills the server also when an assertion fails.
+struct Server(Child);
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// The status code and body of `POST path`.
+fn post(addr: &str, path: &str, body: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "POST {path} HTTP/1.1\r\nHost: {addr}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let status = response.split(' ').nth(1).unwrap().parse().unwrap();
+    let body = response.split_once("\r\n\r\n").unwrap().1.to_owned();
+    (status, body)
+}
+
+/// Starts `aoc serve` with `args` on a free port and waits until it listens on the address it
+/// returns.
+fn start(args: &[&str], env: &[(&str, &str)]) -> (Server, String) {
+    let addr = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .to_string();
+    let server = Server(
+        Command::new(env!("CARGO_BIN_EXE_aoc"))
+            .args(["serve", "--addr", &addr])
+            .args(args)
+            .envs(env.iter().copied())
+            .spawn()
+            .unwrap(),
+    );
+    let start = Instant::now();
+    while TcpStream::connect(&addr).is_err() {
+        assert!(start.elapsed() < Duration::from_secs(10), "Not listening");
+        thread::sleep(Duration::from_millis(20));
+    }
+    (server, addr)
+}
+
+#[test]
+fn stops_runaway_requests() {
+    let (_server, addr) = start(&["--max-cpu", "1", "--timeout", "5"], &[]);
+
+    // Only holding for half the time wins, which day 6 takes forever to find in a race this long
+    let race = "Time: 4000000000\nDistance: 3999999999999999999\n";
+    let start = Instant::now();
+    let (status, body) = post(&addr, "/solve/2023/6", race);
+    assert_eq!(status, 503, "{body}");
+    assert!(body.contains("error"), "{body}");
+    assert!(start.elapsed() < Duration::from_secs(10));
+
+    let (status, body) = post(
+        &addr,
+        "/solve/2023/6",
+        "Time:      7  15   30\nDistance:  9  40  200\n",
+    );
+    assert_eq!(status, 200, "{body}");
+    assert!(body.contains(r#""answer":"71503""#), "{body}");
+}
+
+#[test]
+fn answers_panicking_workers() {
+    let (_server, addr) = start(&[], &[("AOC_SERVE_WORKER_PANIC", "1")]);
+
+    let (status, body) = post(&addr, "/solve/2023/6", aoc2023::day06::EXAMPLE);
+    assert_eq!(status, 500, "{body}");
+    assert!(
+        body.contains("The worker panicked: AOC_SERVE_WORKER_PANIC is set"),
+        "{body}"
+    );
+
+    // The server itself keeps going
+    let (status, body) = post(&addr, "/solve/2023/7", "");
+    assert_eq!(status, 404, "{body}");
+}
+
+#[test]
+fn answers_while_busy() {
+    let (_server, addr) = start(&["--workers", "2", "--max-cpu", "5", "--timeout", "5"], &[]);
+    let race = "Time: 4000000000\nDistance: 3999999999999999999\n";
+    let example = "Time:      7  15   30\nDistance:  9  40  200\n";
+    let runaway = || {
+        let addr = addr.clone();
+        let request = thread::spawn(move || post(&addr, "/solve/2023/6", race));
+        thread::sleep(Duration::from_millis(500));
+        request
+    };
+
+    // Another request does not have to wait for the one running away
+    let first = runaway();
+    let start = Instant::now();
+    let (status, body) = post(&addr, "/solve/2023/6", example);
+    assert_eq!(status, 200, "{body}");
+    assert!(start.elapsed() < Duration::from_secs(3));
+
+    // With both workers busy there is no one left
+    let second = runaway();
+    let (status, body) = post(&addr, "/solve/2023/6", example);
+    assert_eq!(status, 503, "{body}");
+    assert!(body.contains("All 2 workers are busy"), "{body}");
+
+    for request in [first, second] {
+        let (status, body) = request.join().unwrap();
+        assert_eq!(status, 503, "{body}");
+        assert!(body.contains("Stopped"), "{body}");
+    }
+    let (status, body) = post(&addr, "/solve/2023/6", example);
+    assert_eq!(status, 200, "{body}");
+}
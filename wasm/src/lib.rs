@@ -0,0 +1,105 @@
+#![warn(clippy::pedantic)]
+
+//! The days registered with `#[aoc]`, for solving puzzles in the browser with a pasted input.
+//!
+//! Build with `wasm-pack build --target web --out-dir www/pkg wasm` from the workspace root and
+//! serve `wasm/www/` with any static file server, browsers do not load modules from `file://`.
+
+use anyhow::{Context, Result};
+use aoc_core::registry::{solution, solutions};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+// Linking the years is what puts their days into the registry
+use aoc2022 as _;
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct PartAnswer {
+    pub part: String,
+    pub answer: String,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+}
+
+/// Every day that can be solved, in order.
+#[must_use]
+pub fn list() -> Vec<Day> {
+    solutions()
+        .into_iter()
+        .map(|s| Day {
+            year: s.year,
+            day: s.day,
+        })
+        .collect()
+}
+
+/// The answers of both parts. Does not go through [`aoc_core::parts::Parts::run`], timing it is
+/// not possible in a browser without help from JavaScript.
+///
+/// # Errors
+/// If the day does not exist, the input is malformed or a part has no answer.
+pub fn answers(year: u16, day: u8, input: &str) -> Result<Vec<PartAnswer>> {
+    let solution = solution(year, day).with_context(|| format!("{year} day {day} is not here"))?;
+    let input = solution.parse(input)?;
+
+    Ok(vec![
+        PartAnswer {
+            part: "Part 1".to_owned(),
+            answer: solution.part1(&*input).context("Part 1")?.to_string(),
+        },
+        PartAnswer {
+            part: "Part 2".to_owned(),
+            answer: solution.part2(&*input).context("Part 2")?.to_string(),
+        },
+    ])
+}
+
+/// `[{ year, day }]` of every day that can be solved.
+///
+/// # Errors
+/// Never, all days can be turned into JavaScript objects.
+#[wasm_bindgen]
+pub fn days() -> Result<JsValue, JsError> {
+    Ok(serde_wasm_bindgen::to_value(&list())?)
+}
+
+/// `[{ part, answer }]` for the given input.
+///
+/// # Errors
+/// As an `Error` with the whole chain of causes as message, see [`answers`].
+#[wasm_bindgen]
+pub fn solve(year: u16, day: u8, input: &str) -> Result<JsValue, JsError> {
+    let answers = answers(year, day, input).map_err(|e| JsError::new(&format!("{e:#}")))?;
+    Ok(serde_wasm_bindgen::to_value(&answers)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_registered_days() {
+        assert!(list().contains(&Day { year: 2022, day: 1 }));
+
+        assert_eq!(
+            answers(2022, 1, "1\n2\n\n4\n\n3\n").unwrap(),
+            [
+                PartAnswer {
+                    part: "Part 1".to_owned(),
+                    answer: "4".to_owned()
+                },
+                PartAnswer {
+                    part: "Part 2".to_owned(),
+                    answer: "10".to_owned()
+                },
+            ]
+        );
+
+        answers(2022, 1, "x\n").unwrap_err();
+        answers(1999, 1, "").unwrap_err();
+    }
+}